@@ -13,9 +13,15 @@ pub fn run(
     summary: String,
     context_refs: Vec<String>,
     files: Vec<String>,
+    sign: bool,
+    ssh_key: Option<String>,
+    ssh_agent: bool,
 ) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
+    let should_sign = config.resolve_sign(None, sign.then_some(true));
+    let ssh_key_path = config.resolve_ssh_key(None, ssh_key.as_deref());
 
     let op_type = match operation.to_lowercase().as_str() {
         "review" => OperationType::Review,
@@ -51,6 +57,14 @@ pub fn run(
     };
 
     let id = repo.create_agent_operation(agent_op)?;
+    if should_sign {
+        let key = if ssh_agent {
+            repo.signing_key_from_agent(None)?
+        } else {
+            repo.signing_key(ssh_key_path.as_deref().map(std::path::Path::new))?
+        };
+        repo.sign_object(&id, &key)?;
+    }
     println!("Logged agent operation {}", id.short());
     Ok(())
 }
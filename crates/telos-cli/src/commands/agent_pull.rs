@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::repository::Repository;
+
+/// Materialize outstanding work (unresolved bindings, uncovered `must`
+/// constraints), then atomically claim and return the oldest task. Always
+/// prints JSON, since this command is meant to be consumed by an agent
+/// rather than read by a person.
+pub fn run(agent: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    repo.agent_tasks.materialize(&repo.odb, repo.root())?;
+
+    match repo.agent_tasks.claim(&agent)? {
+        Some(task) => println!("{}", serde_json::to_string_pretty(&task)?),
+        None => println!("{}", serde_json::json!({ "task": null })),
+    }
+
+    Ok(())
+}
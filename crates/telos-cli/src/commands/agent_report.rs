@@ -0,0 +1,52 @@
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::env;
+use telos_core::hash::ObjectId;
+use telos_core::object::agent_operation::{AgentOperation, OperationResult, OperationType};
+use telos_store::repository::Repository;
+
+pub fn run(
+    task: u64,
+    agent: String,
+    session: String,
+    result: String,
+    summary: Option<String>,
+) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let open_tasks = repo.agent_tasks.list_open(None)?;
+    let Some(found) = open_tasks.into_iter().find(|t| t.task_id == task) else {
+        bail!("no open or claimed agent task with id {}", task);
+    };
+    let target_id = ObjectId::parse(&found.target_id)
+        .map_err(|e| anyhow::anyhow!("task target id '{}' is invalid: {}", found.target_id, e))?;
+
+    let op_result = match result.to_lowercase().as_str() {
+        "success" => OperationResult::Success,
+        "skipped" => OperationResult::Skipped,
+        "warning" => OperationResult::Warning(summary.clone().unwrap_or_else(|| found.description.clone())),
+        "failure" => OperationResult::Failure(summary.clone().unwrap_or_else(|| found.description.clone())),
+        other => OperationResult::Failure(format!("unrecognized result '{}'", other)),
+    };
+
+    let agent_op = AgentOperation {
+        agent_id: agent,
+        session_id: session,
+        timestamp: Utc::now(),
+        operation: OperationType::Custom("agent_task".into()),
+        result: op_result,
+        summary: summary.unwrap_or_else(|| found.description.clone()),
+        context_refs: vec![target_id],
+        files_touched: Vec::new(),
+        parent_op: None,
+        metadata: HashMap::new(),
+    };
+
+    let op_id = repo.create_agent_operation(agent_op)?;
+    repo.agent_tasks.complete(found.task_id)?;
+
+    println!("Closed task {} (agent operation {})", found.task_id, op_id.short());
+    Ok(())
+}
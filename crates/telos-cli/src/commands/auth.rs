@@ -0,0 +1,68 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use std::collections::BTreeSet;
+use std::env;
+use telos_core::serialize::canonical_serialize;
+use telos_store::auth::{CapabilityToken, SignedToken, Verb};
+use telos_store::repository::Repository;
+
+/// Mint a capability token granting `principal` the given `verbs`
+/// (optionally scoped to `impact` tags), signed by the repository's
+/// authority key, and write it to `output` as the `--token <file>` format.
+pub fn issue(
+    principal: String,
+    verbs: Vec<String>,
+    impact: Vec<String>,
+    expires_days: i64,
+    output: std::path::PathBuf,
+) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let verbs: BTreeSet<Verb> = verbs
+        .iter()
+        .map(|v| v.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|e: telos_store::error::StoreError| anyhow::anyhow!(e.to_string()))?;
+    if verbs.is_empty() {
+        anyhow::bail!("at least one --verb is required");
+    }
+
+    let token = CapabilityToken {
+        principal: principal.clone(),
+        verbs,
+        impacts: if impact.is_empty() { None } else { Some(impact) },
+        expires: Utc::now() + Duration::days(expires_days),
+    };
+
+    let key = repo.authority_key()?;
+    let bytes = canonical_serialize("capability_token", &token)?;
+    let signed = SignedToken {
+        signature: key.sign_bytes(&bytes)?,
+        authority_key: key.public_key_hex(),
+        token: token.clone(),
+    };
+    signed.save(&output)?;
+
+    println!(
+        "Issued token {} for '{}', expires {} -> {}",
+        token.id()?,
+        principal,
+        token.expires,
+        output.display()
+    );
+    Ok(())
+}
+
+/// Record `token_id` in `.telos/revoked_tokens.json` so `authorize` rejects
+/// it from now on.
+pub fn revoke(token_id: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let mut revoked = repo.revoked_tokens()?;
+    revoked.revoke(token_id.clone());
+    repo.save_revoked_tokens(&revoked)?;
+    println!("Revoked token {}", token_id);
+    Ok(())
+}
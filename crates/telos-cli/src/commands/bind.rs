@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::env;
 use telos_core::object::code_binding::{BindingResolution, BindingType, CodeBinding};
+use telos_store::fingerprint::compute_fingerprint;
 use telos_store::repository::Repository;
 
 pub fn run(
@@ -9,9 +10,15 @@ pub fn run(
     file: String,
     symbol: Option<String>,
     binding_type: String,
+    sign: bool,
+    ssh_key: Option<String>,
+    ssh_agent: bool,
 ) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
+    let should_sign = config.resolve_sign(None, sign.then_some(true));
+    let ssh_key_path = config.resolve_ssh_key(None, ssh_key.as_deref());
 
     // Resolve object_id
     let (oid, _obj) = repo
@@ -30,17 +37,27 @@ pub fn run(
         ),
     };
 
-    let binding = CodeBinding {
+    let mut binding = CodeBinding {
         path: file,
         symbol,
         span: None,
         binding_type: bt,
         resolution: BindingResolution::Unchecked,
         bound_object: oid.clone(),
+        fingerprint: None,
         metadata: HashMap::new(),
     };
+    binding.fingerprint = compute_fingerprint(repo.root(), &binding);
 
     let id = repo.create_code_binding(binding)?;
+    if should_sign {
+        let key = if ssh_agent {
+            repo.signing_key_from_agent(None)?
+        } else {
+            repo.signing_key(ssh_key_path.as_deref().map(std::path::Path::new))?
+        };
+        repo.sign_object(&id, &key)?;
+    }
     println!(
         "Created binding {} for object {}",
         id.short(),
@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use telos_store::changelog::{self, ChangelogEntry};
+use telos_store::repository::Repository;
+
+pub fn run(
+    stream: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    template: Option<PathBuf>,
+    json: bool,
+) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let until_id = match until.as_deref() {
+        Some(reference) => changelog::resolve_range_point(&repo, reference).context("cannot resolve --until")?,
+        None => {
+            let stream_ref = match &stream {
+                Some(name) => repo
+                    .refs
+                    .read_stream(name)
+                    .context(format!("stream '{}' not found", name))?,
+                None => repo.refs.current_stream()?,
+            };
+            stream_ref.tip.context("stream has no intents yet")?
+        }
+    };
+    let since_id = since
+        .as_deref()
+        .map(|reference| changelog::resolve_range_point(&repo, reference))
+        .transpose()
+        .context("cannot resolve --since")?;
+
+    let entries = changelog::build_changelog(&repo, &until_id, since_id.as_ref())?;
+
+    if json {
+        let value: Vec<_> = entries.iter().map(entry_to_json).collect();
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No change sets in range.");
+        return Ok(());
+    }
+
+    let template = template.map(fs::read_to_string).transpose()?;
+    for entry in &entries {
+        match &template {
+            Some(tpl) => println!("{}", render_template(tpl, entry)),
+            None => render_default(entry),
+        }
+    }
+    Ok(())
+}
+
+fn entry_to_json(entry: &ChangelogEntry) -> serde_json::Value {
+    serde_json::json!({
+        "id": entry.change_set_id.hex(),
+        "git_commit": entry.change_set.git_commit,
+        "timestamp": entry.change_set.timestamp,
+        "intents": entry.intents.iter().map(|(id, i)| serde_json::json!({"id": id.hex(), "object": i})).collect::<Vec<_>>(),
+        "decisions": entry.decisions.iter().map(|(id, d)| serde_json::json!({"id": id.hex(), "object": d})).collect::<Vec<_>>(),
+        "constraints": entry.constraints.iter().map(|(id, c)| serde_json::json!({"id": id.hex(), "object": c})).collect::<Vec<_>>(),
+    })
+}
+
+fn bullets_intents(entry: &ChangelogEntry) -> String {
+    entry
+        .intents
+        .iter()
+        .map(|(_, i)| format!("- {}", i.statement))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn bullets_decisions(entry: &ChangelogEntry) -> String {
+    entry
+        .decisions
+        .iter()
+        .map(|(_, d)| format!("- {}: {}", d.question, d.decision))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn bullets_constraints(entry: &ChangelogEntry) -> String {
+    entry
+        .constraints
+        .iter()
+        .map(|(_, c)| format!("- {}", c.statement))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_default(entry: &ChangelogEntry) {
+    println!(
+        "## {} ({})",
+        entry.change_set.git_commit,
+        entry.change_set.timestamp.format("%Y-%m-%d")
+    );
+    println!();
+    if !entry.intents.is_empty() {
+        println!("### Intents realized");
+        println!("{}", bullets_intents(entry));
+        println!();
+    }
+    if !entry.decisions.is_empty() {
+        println!("### Decisions recorded");
+        println!("{}", bullets_decisions(entry));
+        println!();
+    }
+    if !entry.constraints.is_empty() {
+        println!("### Constraints satisfied");
+        println!("{}", bullets_constraints(entry));
+        println!();
+    }
+}
+
+/// Substitute `{{git_commit}}`, `{{timestamp}}`, `{{intents}}`,
+/// `{{decisions}}`, and `{{constraints}}` placeholders in a user-supplied
+/// template with this entry's rendered sections — a plain find-and-replace
+/// rather than a templating engine, so teams can plug in their own
+/// release-note format without a new dependency.
+fn render_template(template: &str, entry: &ChangelogEntry) -> String {
+    template
+        .replace("{{git_commit}}", &entry.change_set.git_commit)
+        .replace("{{timestamp}}", &entry.change_set.timestamp.to_rfc3339())
+        .replace("{{intents}}", &bullets_intents(entry))
+        .replace("{{decisions}}", &bullets_decisions(entry))
+        .replace("{{constraints}}", &bullets_constraints(entry))
+}
@@ -1,32 +1,96 @@
 use anyhow::{Context, Result};
 use std::env;
 use telos_core::object::TelosObject;
+use telos_store::fingerprint::compute_fingerprint;
 use telos_store::repository::Repository;
 
-pub fn run(bindings: bool, all: bool) -> Result<()> {
+/// Resolution states `check --bindings` reports, in addition to plain
+/// existence: a binding whose file is still there but whose stored
+/// fingerprint no longer matches has DRIFTED, distinct from UNRESOLVED
+/// (target gone entirely).
+enum BindingCheck {
+    Resolved,
+    Drifted { old: String, new: String },
+    Unresolved,
+}
+
+pub fn run(bindings: bool, all: bool, json: bool) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
     if bindings || all {
         let all_objects = repo.odb.iter_all()?;
-        let mut ok_count = 0;
+        let mut resolved_count = 0;
+        let mut drifted_count = 0;
         let mut unresolved_count = 0;
+        let mut json_entries = Vec::new();
+
+        if !json {
+            println!("Checking bindings...");
+        }
+        for (id, obj) in &all_objects {
+            let TelosObject::CodeBinding(cb) = obj else {
+                continue;
+            };
+            let full_path = repo.root().join(&cb.path);
+            let check = if !full_path.exists() {
+                unresolved_count += 1;
+                BindingCheck::Unresolved
+            } else {
+                match (&cb.fingerprint, compute_fingerprint(repo.root(), cb)) {
+                    (Some(old), Some(new)) if *old != new => {
+                        drifted_count += 1;
+                        BindingCheck::Drifted {
+                            old: old.clone(),
+                            new,
+                        }
+                    }
+                    _ => {
+                        resolved_count += 1;
+                        BindingCheck::Resolved
+                    }
+                }
+            };
 
-        println!("Checking bindings...");
-        for (_id, obj) in &all_objects {
-            if let TelosObject::CodeBinding(cb) = obj {
-                let full_path = repo.root().join(&cb.path);
-                if full_path.exists() {
-                    ok_count += 1;
-                } else {
-                    println!("  UNRESOLVED  {}  (file not found)", cb.path);
-                    unresolved_count += 1;
+            if json {
+                let (state, old_fingerprint, new_fingerprint) = match &check {
+                    BindingCheck::Resolved => ("resolved", None, None),
+                    BindingCheck::Drifted { old, new } => {
+                        ("drifted", Some(old.clone()), Some(new.clone()))
+                    }
+                    BindingCheck::Unresolved => ("unresolved", None, None),
+                };
+                json_entries.push(serde_json::json!({
+                    "id": id.hex(),
+                    "path": cb.path,
+                    "state": state,
+                    "old_fingerprint": old_fingerprint,
+                    "new_fingerprint": new_fingerprint,
+                }));
+            } else {
+                match &check {
+                    BindingCheck::Resolved => {}
+                    BindingCheck::Drifted { .. } => {
+                        println!("  DRIFTED     {}  (fingerprint changed)", cb.path);
+                    }
+                    BindingCheck::Unresolved => {
+                        println!("  UNRESOLVED  {}  (file not found)", cb.path);
+                    }
                 }
             }
         }
-        println!("  OK          {} bindings resolved", ok_count);
-        if unresolved_count > 0 {
-            println!("  UNRESOLVED  {} bindings unresolved", unresolved_count);
+
+        if json {
+            let output = serde_json::json!({ "bindings": json_entries });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        } else {
+            println!("  OK          {} bindings resolved", resolved_count);
+            if drifted_count > 0 {
+                println!("  DRIFTED     {} bindings drifted", drifted_count);
+            }
+            if unresolved_count > 0 {
+                println!("  UNRESOLVED  {} bindings unresolved", unresolved_count);
+            }
         }
     }
 
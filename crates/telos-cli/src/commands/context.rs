@@ -3,18 +3,22 @@ use std::env;
 use telos_store::query;
 use telos_store::repository::Repository;
 
+#[tracing::instrument]
 pub fn run(impact: String, json: bool) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
-    // Find all intents matching the impact tag
-    let intents = query::query_intents(&repo.odb, Some(&impact), None)?;
+    // Find all intents matching the impact tag. Each intent below triggers
+    // its own `query_decisions` scan (N+1), so both are instrumented and
+    // nest as child spans here to make the fan-out visible in a trace.
+    let intents = query::query_intents(&repo.odb, &repo.indexes, Some(&impact), None, None)?;
+    let tasks = repo.agent_tasks.list_open(Some(&impact))?;
 
     if json {
         let mut entries = Vec::new();
         for (intent_id, intent) in &intents {
             let decisions =
-                query::query_decisions(&repo.odb, Some(intent_id), None)?;
+                query::query_decisions(&repo.odb, &repo.indexes, Some(intent_id), None, None)?;
             let decision_json: Vec<_> = decisions
                 .iter()
                 .map(|(did, dr)| {
@@ -33,12 +37,13 @@ pub fn run(impact: String, json: bool) -> Result<()> {
         let output = serde_json::json!({
             "impact": impact,
             "intents": entries,
+            "outstanding_tasks": tasks,
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
         return Ok(());
     }
 
-    if intents.is_empty() {
+    if intents.is_empty() && tasks.is_empty() {
         println!("No intents found for impact '{}'.", impact);
         return Ok(());
     }
@@ -78,7 +83,7 @@ pub fn run(impact: String, json: bool) -> Result<()> {
 
         // Show linked decisions
         let decisions =
-            query::query_decisions(&repo.odb, Some(intent_id), None)?;
+            query::query_decisions(&repo.odb, &repo.indexes, Some(intent_id), None, None)?;
         if !decisions.is_empty() {
             println!("  Decisions:");
             for (did, dr) in &decisions {
@@ -95,5 +100,14 @@ pub fn run(impact: String, json: bool) -> Result<()> {
         }
     }
 
+    if !tasks.is_empty() {
+        println!();
+        println!("Outstanding tasks:");
+        for task in &tasks {
+            println!("  task #{} ({:?})", task.task_id, task.kind);
+            println!("    {}", task.description);
+        }
+    }
+
     Ok(())
 }
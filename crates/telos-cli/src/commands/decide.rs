@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::collections::HashMap;
 use std::env;
 use telos_core::object::decision_record::{Alternative, DecisionRecord};
 use telos_core::object::intent::Author;
+use telos_core::object::TelosObject;
+use telos_store::auth::{SignedToken, Verb};
 use telos_store::repository::Repository;
 
 pub fn run(
@@ -12,9 +15,16 @@ pub fn run(
     rationale: Option<String>,
     alternatives_raw: Vec<String>,
     tags: Vec<String>,
+    sign: bool,
+    ssh_key: Option<String>,
+    ssh_agent: bool,
+    token: Option<String>,
 ) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
+    let should_sign = config.resolve_sign(None, sign.then_some(true));
+    let ssh_key_path = config.resolve_ssh_key(None, ssh_key.as_deref());
 
     // Resolve the intent ID (supports prefix)
     let (intent_oid, obj) = repo
@@ -22,8 +32,21 @@ pub fn run(
         .context(format!("intent '{}' not found", intent_id_str))?;
 
     // Verify it's actually an intent
-    if obj.type_tag() != "intent" {
+    let TelosObject::Intent(intent) = &obj else {
         anyhow::bail!("object {} is a {}, not an intent", intent_oid.short(), obj.type_tag());
+    };
+
+    let mut metadata = HashMap::new();
+    if config.auth_required() {
+        let token_path = token
+            .or_else(|| env::var("TELOS_AUTH_TOKEN").ok())
+            .context("--token (or TELOS_AUTH_TOKEN) is required: this repository requires capability-token authorization")?;
+        let signed = SignedToken::load(&token_path)?;
+        let token_id = repo.authorize(&signed, Verb::Decide, &intent.impacts)?;
+        metadata.insert(
+            "authorized_by".to_string(),
+            serde_json::json!({"principal": signed.token.principal, "token_id": token_id}),
+        );
     }
 
     // Parse alternative strings into Alternative structs
@@ -56,9 +79,18 @@ pub fn run(
         rationale,
         alternatives,
         tags,
+        metadata,
     };
 
     let id = repo.create_decision(record)?;
+    if should_sign {
+        let key = if ssh_agent {
+            repo.signing_key_from_agent(None)?
+        } else {
+            repo.signing_key(ssh_key_path.as_deref().map(std::path::Path::new))?
+        };
+        repo.sign_object(&id, &key)?;
+    }
     println!("Recorded decision {} for intent {}", id.short(), intent_oid.short());
     Ok(())
 }
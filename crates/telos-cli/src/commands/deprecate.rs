@@ -4,11 +4,13 @@ use std::env;
 use telos_core::object::constraint::ConstraintStatus;
 use telos_core::object::intent::Author;
 use telos_core::object::TelosObject;
+use telos_store::auth::{SignedToken, Verb};
 use telos_store::repository::Repository;
 
-pub fn run(constraint_id: String, reason: String) -> Result<()> {
+pub fn run(constraint_id: String, reason: String, token: Option<String>) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
 
     // Resolve constraint_id
     let (oid, obj) = repo
@@ -24,6 +26,16 @@ pub fn run(constraint_id: String, reason: String) -> Result<()> {
         ),
     };
 
+    let mut authorized_by = None;
+    if config.auth_required() {
+        let token_path = token
+            .or_else(|| env::var("TELOS_AUTH_TOKEN").ok())
+            .context("--token (or TELOS_AUTH_TOKEN) is required: this repository requires capability-token authorization")?;
+        let signed = SignedToken::load(&token_path)?;
+        let token_id = repo.authorize(&signed, Verb::Deprecate, &constraint.impacts)?;
+        authorized_by = Some(serde_json::json!({"principal": signed.token.principal, "token_id": token_id}));
+    }
+
     let author_name = env::var("TELOS_AUTHOR_NAME").unwrap_or_else(|_| "Unknown".into());
     let author_email = env::var("TELOS_AUTHOR_EMAIL").unwrap_or_else(|_| "unknown@unknown".into());
 
@@ -31,13 +43,24 @@ pub fn run(constraint_id: String, reason: String) -> Result<()> {
     let mut deprecated = constraint;
     deprecated.author = Author {
         name: author_name,
-        email: author_email,
+        email: author_email.clone(),
     };
     deprecated.timestamp = Utc::now();
     deprecated.status = ConstraintStatus::Deprecated;
-    deprecated.deprecation_reason = Some(reason);
+    deprecated.deprecation_reason = Some(reason.clone());
+    if let Some(authorized_by) = authorized_by {
+        deprecated.metadata.insert("authorized_by".to_string(), authorized_by);
+    }
 
     let new_id = repo.create_constraint(deprecated)?;
+    repo.record_status_change(
+        &oid,
+        new_id.clone(),
+        ConstraintStatus::Deprecated,
+        None,
+        Some(reason),
+        &author_email,
+    )?;
 
     println!(
         "Deprecated constraint {} -> {}",
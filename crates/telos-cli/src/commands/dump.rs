@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::path::PathBuf;
+use telos_store::dump::{self, DumpArchive};
+use telos_store::repository::Repository;
+
+pub fn run(output: PathBuf) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let archive = dump::dump(&repo.odb)?;
+    let count = archive.objects.len();
+    dump::write_to_file(&archive, &output)?;
+    println!("Wrote {} objects to {}", count, output.display());
+    Ok(())
+}
+
+pub fn restore(input: PathBuf) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let archive: DumpArchive = dump::read_from_file(&input)?;
+    let summary = dump::restore(archive, &repo.odb, &repo.indexes)?;
+    println!(
+        "Restored {} objects from {} and rebuilt indexes",
+        summary.objects_written,
+        input.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::path::PathBuf;
+use telos_store::export::{
+    write_agent_operations_parquet, write_object_tables_parquet, write_prov_json_for,
+    write_prov_jsonld_for, write_prov_turtle_for,
+};
+use telos_store::graph;
+use telos_store::repository::Repository;
+
+pub fn run(format: String, kind: String, output: PathBuf, stream: Option<String>) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    match format.as_str() {
+        "parquet" => match kind.as_str() {
+            "operations" => {
+                let rows = write_agent_operations_parquet(&repo.odb, &output)?;
+                println!("Wrote {} agent operation rows to {}", rows, output.display());
+            }
+            "objects" => {
+                let counts = write_object_tables_parquet(&repo.odb, &output)?;
+                println!(
+                    "Wrote {} intents, {} decision records, {} code bindings, {} behavior diffs, {} constraints, {} change sets to {}",
+                    counts.intents,
+                    counts.decision_records,
+                    counts.code_bindings,
+                    counts.behavior_diffs,
+                    counts.constraints,
+                    counts.change_sets,
+                    output.display()
+                );
+            }
+            other => anyhow::bail!("unknown export kind '{}' (expected: operations, objects)", other),
+        },
+        "prov-json" => {
+            let objects = prov_scope(&repo, stream.as_deref())?;
+            let count = write_prov_json_for(&repo.odb, objects, &output)?;
+            println!(
+                "Wrote {} provenance nodes (PROV-JSON) to {}",
+                count,
+                output.display()
+            );
+        }
+        "prov-jsonld" => {
+            let objects = prov_scope(&repo, stream.as_deref())?;
+            let count = write_prov_jsonld_for(&repo.odb, objects, &output)?;
+            println!(
+                "Wrote {} provenance nodes (PROV JSON-LD) to {}",
+                count,
+                output.display()
+            );
+        }
+        "prov-turtle" => {
+            let objects = prov_scope(&repo, stream.as_deref())?;
+            let count = write_prov_turtle_for(&repo.odb, objects, &output)?;
+            println!(
+                "Wrote {} provenance nodes (PROV Turtle) to {}",
+                count,
+                output.display()
+            );
+        }
+        other => anyhow::bail!(
+            "unsupported export format '{}' (expected: parquet, prov-json, prov-jsonld, prov-turtle)",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+/// Resolve the object set a `prov-json`/`prov-jsonld` export should cover:
+/// everything in the store, or — when `--stream` names one — just the
+/// objects reachable from that stream's current tip, via the same
+/// [`graph::reachable_from`] walk `telos graph --impact` uses.
+fn prov_scope(
+    repo: &Repository,
+    stream: Option<&str>,
+) -> Result<Vec<(telos_core::hash::ObjectId, telos_core::object::TelosObject)>> {
+    let Some(stream_name) = stream else {
+        return Ok(repo.odb.iter_all()?);
+    };
+    let stream_ref = repo.refs.read_stream(stream_name)?;
+    let Some(tip) = stream_ref.tip else {
+        return Ok(Vec::new());
+    };
+    Ok(graph::reachable_from(&repo.odb, &tip)?)
+}
@@ -0,0 +1,25 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::remote;
+use telos_store::repository::Repository;
+use telos_store::sync;
+
+pub fn run(remote_name: String, stream: Option<String>) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let (url, token) = repo
+        .remote(&remote_name)
+        .context(format!("remote '{}' not configured", remote_name))?;
+    let stream_name = match stream {
+        Some(s) => s,
+        None => repo.refs.read_head()?,
+    };
+
+    let backend = remote::open(&url, token);
+    let fetched = sync::fetch(&repo, &remote_name, backend.as_ref(), &stream_name)?;
+    println!(
+        "Fetched {} object(s) from '{}', updated refs/remotes/{}/{}",
+        fetched, remote_name, remote_name, stream_name
+    );
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::repository::Repository;
+
+pub fn run(pack: bool, prune: bool) -> Result<()> {
+    if !pack && !prune {
+        anyhow::bail!("gc requires --pack and/or --prune (no other gc strategies implemented yet)");
+    }
+
+    let cwd = env::current_dir()?;
+    let mut repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    if prune {
+        let mut roots = Vec::new();
+        for name in repo.refs.list_streams()? {
+            if let Some(tip) = repo.refs.read_stream(&name)?.tip {
+                roots.push(tip);
+            }
+        }
+
+        // `fetch` can leave objects on disk that aren't part of any local
+        // stream yet (pulled in but not merged). Without these, a
+        // `gc --prune` run between `fetch` and `pull`/`merge_stream` would
+        // call them unreferenced and delete them out from under the merge
+        // that still needs them — so every remote-tracking head, and every
+        // object a remote was last known to hold, counts as a root too.
+        for remote in repo.list_remotes()? {
+            for name in repo.refs.list_streams()? {
+                if let Some(head) = repo.refs.read_remote_head(&remote, &name)? {
+                    roots.push(head);
+                }
+            }
+            roots.extend(repo.refs.read_remote_objects(&remote)?);
+        }
+
+        let (collected, packed) = repo.odb.gc(&roots)?;
+        println!(
+            "Garbage-collected {} unreferenced object(s), packed {} loose object(s)",
+            collected, packed
+        );
+    } else {
+        let packed = repo.odb.pack_loose()?;
+        println!("Packed {} loose object(s)", packed);
+    }
+    Ok(())
+}
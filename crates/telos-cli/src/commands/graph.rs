@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::graph;
+use telos_store::repository::Repository;
+
+pub fn run(impact: Option<String>, json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let objects = match impact {
+        Some(ref seed) => {
+            let (seed_id, _) = repo
+                .read_object(seed)
+                .context(format!("object '{}' not found", seed))?;
+            graph::reachable_from(&repo.odb, &seed_id)?
+        }
+        None => repo.odb.iter_all()?,
+    };
+
+    let nodes = graph::build_nodes(&objects);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&nodes)?);
+    } else {
+        print!("{}", graph::to_dot(&nodes));
+    }
+    Ok(())
+}
@@ -1,10 +1,35 @@
 use anyhow::{Context, Result};
 use std::env;
+use std::fs;
 use telos_store::repository::Repository;
 
-pub fn run() -> Result<()> {
+pub fn run(encrypt: bool, signed: bool) -> Result<()> {
     let cwd = env::current_dir().context("failed to get current directory")?;
+
+    if encrypt {
+        let passphrase = env::var("TELOS_PASSPHRASE")
+            .context("--encrypt requires TELOS_PASSPHRASE to be set")?;
+        Repository::init_encrypted(&cwd, &passphrase).context("failed to initialize repository")?;
+        println!(
+            "Initialized encrypted Telos repository in {}",
+            cwd.join(".telos").display()
+        );
+        return Ok(());
+    }
+
     Repository::init(&cwd).context("failed to initialize repository")?;
     println!("Initialized empty Telos repository in {}", cwd.join(".telos").display());
+
+    if signed {
+        let repo = Repository::discover(&cwd).context("failed to open the repository just created")?;
+        repo.signing_key(None)
+            .context("failed to generate the repository signing key")?;
+        let config_path = cwd.join(".telos").join("config.toml");
+        let existing = fs::read_to_string(&config_path).unwrap_or_default();
+        fs::write(&config_path, format!("{existing}\n[sign]\ndefault = true\n"))
+            .context("failed to write .telos/config.toml")?;
+        println!("Generated a signing key and enabled signing by default");
+    }
+
     Ok(())
 }
@@ -2,12 +2,42 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::env;
+use telos_core::config::resolve_profile;
 use telos_core::object::intent::{Author, BehaviorClause, Intent};
+use telos_store::auth::{SignedToken, Verb};
 use telos_store::repository::Repository;
 
-pub fn run(statement: String, constraints: Vec<String>, impacts: Vec<String>, behaviors: Vec<String>) -> Result<()> {
+pub fn run(
+    statement: String,
+    constraints: Vec<String>,
+    impacts: Vec<String>,
+    behaviors: Vec<String>,
+    profile: Option<String>,
+    sign: bool,
+    ssh_key: Option<String>,
+    ssh_agent: bool,
+    token: Option<String>,
+) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository (or any parent)")?;
+    let config = repo.telos_config()?;
+    let profile = resolve_profile(profile.as_deref());
+    let resolved_author = config.resolve_author(profile.as_deref(), None, None);
+    let should_sign = config.resolve_sign(profile.as_deref(), sign.then_some(true));
+    let ssh_key_path = config.resolve_ssh_key(profile.as_deref(), ssh_key.as_deref());
+
+    let mut metadata = HashMap::new();
+    if config.auth_required() {
+        let token_path = token
+            .or_else(|| env::var("TELOS_AUTH_TOKEN").ok())
+            .context("--token (or TELOS_AUTH_TOKEN) is required: this repository requires capability-token authorization")?;
+        let signed = SignedToken::load(&token_path)?;
+        let token_id = repo.authorize(&signed, Verb::Intent, &impacts)?;
+        metadata.insert(
+            "authorized_by".to_string(),
+            serde_json::json!({"principal": signed.token.principal, "token_id": token_id}),
+        );
+    }
 
     // Get current stream tip as parent
     let current = repo.refs.current_stream()?;
@@ -34,8 +64,8 @@ pub fn run(statement: String, constraints: Vec<String>, impacts: Vec<String>, be
 
     let intent = Intent {
         author: Author {
-            name: env::var("TELOS_AUTHOR_NAME").unwrap_or_else(|_| "Anonymous".into()),
-            email: env::var("TELOS_AUTHOR_EMAIL").unwrap_or_else(|_| "anonymous@telos".into()),
+            name: resolved_author.name,
+            email: resolved_author.email,
         },
         timestamp: Utc::now(),
         statement,
@@ -44,10 +74,18 @@ pub fn run(statement: String, constraints: Vec<String>, impacts: Vec<String>, be
         parents,
         impacts,
         behavior_diff: None,
-        metadata: HashMap::new(),
+        metadata,
     };
 
     let id = repo.create_intent(intent)?;
+    if should_sign {
+        let key = if ssh_agent {
+            repo.signing_key_from_agent(None)?
+        } else {
+            repo.signing_key(ssh_key_path.as_deref().map(std::path::Path::new))?
+        };
+        repo.sign_object(&id, &key)?;
+    }
     let stream_name = repo.refs.read_head()?;
     println!("[{}] {}", stream_name, id.short());
     Ok(())
@@ -0,0 +1,35 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::provenance::ProvenanceGraph;
+use telos_store::repository::Repository;
+
+pub fn run(id: String, direction: String, json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let (oid, _obj) = repo
+        .read_object(&id)
+        .context(format!("object '{}' not found", id))?;
+
+    let graph = ProvenanceGraph::build(&repo.odb)?;
+    let reachable = match direction.as_str() {
+        "ancestors" => graph.ancestors(&oid),
+        "descendants" => graph.descendants(&oid),
+        other => anyhow::bail!("unknown direction '{}' (expected: ancestors, descendants)", other),
+    };
+
+    if json {
+        let entries: Vec<_> = reachable
+            .iter()
+            .map(|(rel, id)| serde_json::json!({"relation": format!("{:?}", rel), "id": id.hex()}))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    println!("{} of {}:", direction, oid.short());
+    for (rel, id) in reachable {
+        println!("  {:?}  {}", rel, id.short());
+    }
+    Ok(())
+}
@@ -1,11 +1,44 @@
 use anyhow::{Context, Result};
 use std::env;
+use telos_core::hash::ObjectId;
+use telos_core::object::Intent;
+use telos_store::query;
 use telos_store::repository::Repository;
 
-pub fn run(max_count: usize, json: bool) -> Result<()> {
+pub fn run(
+    max_count: usize,
+    json: bool,
+    topo_order: bool,
+    impact: Option<String>,
+    path: Option<String>,
+    symbol: Option<String>,
+) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
+    // An index predicate resolves its candidate set directly instead of
+    // walking the whole stream, the same tradeoff `query::constraints`
+    // already makes for `--file`/`--symbol`. Only one predicate is
+    // consulted at a time; the first one given wins.
+    let indexed = if let Some(tag) = impact.as_deref() {
+        Some(query::query_intents_by_impact(&repo.odb, &repo.indexes, tag)?)
+    } else if let Some(p) = path.as_deref() {
+        Some(query::query_intents_by_file(&repo.odb, &repo.indexes, p)?)
+    } else if let Some(s) = symbol.as_deref() {
+        Some(query::query_intents_by_symbol(&repo.odb, &repo.indexes, s)?)
+    } else {
+        None
+    };
+
+    if let Some(results) = indexed {
+        if results.is_empty() && !json {
+            println!("No matching intents found.");
+            return Ok(());
+        }
+        let entries = results.into_iter().take(max_count).map(Ok);
+        return print_log(&repo, entries, json);
+    }
+
     let current = repo.refs.current_stream()?;
     let tip = match current.tip {
         Some(tip) => tip,
@@ -19,25 +52,41 @@ pub fn run(max_count: usize, json: bool) -> Result<()> {
         }
     };
 
+    let entries: Box<dyn Iterator<Item = Result<(ObjectId, Intent)>>> = if topo_order {
+        let topo = repo
+            .walk_intents_topo(&tip)
+            .context("failed to walk intent DAG in topological order")?;
+        Box::new(topo.into_iter().map(Ok))
+    } else {
+        Box::new(
+            repo.walk_intents(&tip)
+                .map(|result| result.context("failed to read intent")),
+        )
+    };
+
+    print_log(&repo, entries.take(max_count), json)
+}
+
+fn print_log(
+    repo: &Repository,
+    entries: impl Iterator<Item = Result<(ObjectId, Intent)>>,
+    json: bool,
+) -> Result<()> {
     if json {
-        let mut entries = Vec::new();
-        for (count, result) in repo.walk_intents(&tip).enumerate() {
-            if count >= max_count {
-                break;
-            }
-            let (id, intent) = result.context("failed to read intent")?;
-            entries.push(serde_json::json!({
+        let mut out = Vec::new();
+        for result in entries {
+            let (id, intent) = result?;
+            let signature_status = repo.signature_status(&id)?;
+            out.push(serde_json::json!({
                 "id": id.hex(),
                 "object": intent,
+                "signature": signature_status.to_string(),
             }));
         }
-        println!("{}", serde_json::to_string_pretty(&entries)?);
+        println!("{}", serde_json::to_string_pretty(&out)?);
     } else {
-        for (count, result) in repo.walk_intents(&tip).enumerate() {
-            if count >= max_count {
-                break;
-            }
-            let (id, intent) = result.context("failed to read intent")?;
+        for (count, result) in entries.enumerate() {
+            let (id, intent) = result?;
 
             if count > 0 {
                 println!();
@@ -45,6 +94,7 @@ pub fn run(max_count: usize, json: bool) -> Result<()> {
             println!("intent {}", id.hex());
             println!("Author: {} <{}>", intent.author.name, intent.author.email);
             println!("Date:   {}", intent.timestamp.format("%Y-%m-%d %H:%M:%S %Z"));
+            println!("Signature: {}", repo.signature_status(&id)?);
             println!();
             println!("    {}", intent.statement);
 
@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use telos_store::backend::{object_backend_from_addr, ref_backend_from_addr};
+use telos_store::migrate::migrate;
+
+/// Split a backend address into the refs address and the objects address it
+/// implies: `file://<dir>` treats `<dir>` as a `.telos`-style directory with
+/// an `objects/` subdirectory, `sled://<dir>` keeps refs and objects in
+/// separate `.sled` databases under `<dir>` (two backends can't share one
+/// `sled::Db`), and `memory://` needs no splitting since both backends are
+/// independent in-process stores.
+fn split_addr(addr: &str) -> Result<(String, String)> {
+    if let Some(path) = addr.strip_prefix("file://") {
+        let path = path.trim_end_matches('/');
+        Ok((addr.to_string(), format!("file://{}/objects", path)))
+    } else if addr == "memory://" || addr.starts_with("memory://") {
+        Ok((addr.to_string(), addr.to_string()))
+    } else if let Some(path) = addr.strip_prefix("sled://") {
+        let path = path.trim_end_matches('/');
+        Ok((format!("sled://{}/refs", path), format!("sled://{}/objects", path)))
+    } else {
+        anyhow::bail!(
+            "unsupported backend address '{}' (supported schemes: file://, memory://, sled://)",
+            addr
+        )
+    }
+}
+
+pub fn run(from: String, to: String) -> Result<()> {
+    let (from_refs, from_objects) = split_addr(&from)?;
+    let (to_refs, to_objects) = split_addr(&to)?;
+
+    let src_refs = ref_backend_from_addr(&from_refs).context("opening source ref backend")?;
+    let src_objects =
+        object_backend_from_addr(&from_objects).context("opening source object backend")?;
+    let dst_refs = ref_backend_from_addr(&to_refs).context("opening destination ref backend")?;
+    let dst_objects =
+        object_backend_from_addr(&to_objects).context("opening destination object backend")?;
+
+    println!("Migrating from {} to {}...", from, to);
+    let counts = migrate(
+        src_objects.as_ref(),
+        dst_objects.as_ref(),
+        src_refs.as_ref(),
+        dst_refs.as_ref(),
+    )?;
+    println!("  objects copied:  {}", counts.objects_copied);
+    println!("  objects skipped: {} (already present)", counts.objects_skipped);
+    println!("  streams copied:  {}", counts.streams_copied);
+    println!("Done.");
+    Ok(())
+}
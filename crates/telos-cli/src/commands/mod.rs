@@ -0,0 +1,35 @@
+pub mod agent_log;
+pub mod agent_pull;
+pub mod agent_report;
+pub mod auth;
+pub mod bind;
+pub mod changelog;
+pub mod changeset;
+pub mod check;
+pub mod constraint;
+pub mod context;
+pub mod decide;
+pub mod deprecate;
+pub mod dump;
+pub mod export;
+pub mod fetch;
+pub mod gc;
+pub mod graph;
+pub mod init;
+pub mod intent;
+pub mod lineage;
+pub mod log;
+pub mod migrate;
+pub mod pull;
+pub mod push;
+pub mod query;
+pub mod reindex;
+pub mod remote;
+pub mod resolve;
+pub mod serve;
+pub mod show;
+pub mod stream;
+pub mod supersede;
+pub mod sync;
+pub mod verify;
+pub mod watch;
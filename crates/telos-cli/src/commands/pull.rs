@@ -0,0 +1,55 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::remote;
+use telos_store::repository::Repository;
+use telos_store::sync::{self, MergeOutcome};
+
+pub fn run(remote_name: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let (url, token) = repo
+        .remote(&remote_name)
+        .context(format!("remote '{}' not configured", remote_name))?;
+    let stream_name = repo.refs.read_head()?;
+
+    let backend = remote::open(&url, token);
+    let pulled = sync::fetch(&repo, &remote_name, backend.as_ref(), &stream_name)?;
+    println!("Pulled {} object(s) from '{}'", pulled, remote_name);
+
+    let conflicted = sync::sync_status_refs(&repo, backend.as_ref())?;
+    for base_id in &conflicted {
+        println!(
+            "Note: constraint {} had a concurrent status change reconciled; see .telos/refs/constraints/{}.json",
+            base_id.short(),
+            base_id.hex()
+        );
+    }
+
+    match sync::merge_stream(&repo, &remote_name, &stream_name)? {
+        MergeOutcome::AlreadyUpToDate => println!("Already up to date."),
+        MergeOutcome::FastForward(id) => {
+            println!("Fast-forwarded '{}' to {}", stream_name, id.hex())
+        }
+        MergeOutcome::Merged(id) => {
+            println!("Merged '{}' from '{}', new tip {}", stream_name, remote_name, id.hex())
+        }
+        MergeOutcome::Conflict(conflicts) => {
+            println!(
+                "Pull stopped: {} constraint conflict(s) must be resolved before merging.",
+                conflicts.len()
+            );
+            for c in &conflicts {
+                println!(
+                    "  \"{}\"\n    local:  {} -> {}\n    remote: {} -> {}",
+                    c.base_statement,
+                    c.local_superseded_copy.hex(),
+                    c.local_replacement.hex(),
+                    c.remote_superseded_copy.hex(),
+                    c.remote_replacement.hex(),
+                );
+            }
+            println!("Run `telos resolve <local-superseded-copy> <remote-superseded-copy> --keep local|remote` for each, then `telos pull` again.");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::remote;
+use telos_store::repository::Repository;
+use telos_store::sync;
+
+pub fn run(remote_name: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let (url, token) = repo
+        .remote(&remote_name)
+        .context(format!("remote '{}' not configured", remote_name))?;
+
+    let backend = remote::open(&url, token);
+    let uploaded = sync::push(&repo, backend.as_ref())?;
+    println!("Pushed {} object(s) to '{}'", uploaded, remote_name);
+
+    let conflicted = sync::sync_status_refs(&repo, backend.as_ref())?;
+    for base_id in &conflicted {
+        println!(
+            "Note: constraint {} had a concurrent status change reconciled; see .telos/refs/constraints/{}.json",
+            base_id.short(),
+            base_id.hex()
+        );
+    }
+
+    let stream = repo.refs.current_stream()?;
+    if let Some(tip) = stream.tip {
+        backend.set_stream_head(&stream.name, &tip)?;
+        println!("Updated '{}' on '{}' to {}", stream.name, remote_name, tip.hex());
+    }
+    Ok(())
+}
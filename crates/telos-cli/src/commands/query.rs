@@ -1,17 +1,25 @@
 use anyhow::{Context, Result};
 use std::env;
 use telos_core::hash::ObjectId;
+use telos_store::datalog;
 use telos_store::query;
 use telos_store::repository::Repository;
 
-pub fn intents(impact: Option<String>, constraint_contains: Option<String>, json: bool) -> Result<()> {
+pub fn intents(
+    impact: Option<String>,
+    constraint_contains: Option<String>,
+    text: Option<String>,
+    json: bool,
+) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
     let results = query::query_intents(
         &repo.odb,
+        &repo.indexes,
         impact.as_deref(),
         constraint_contains.as_deref(),
+        text.as_deref(),
     )?;
 
     if json {
@@ -55,7 +63,12 @@ pub fn intents(impact: Option<String>, constraint_contains: Option<String>, json
     Ok(())
 }
 
-pub fn decisions(intent: Option<String>, tag: Option<String>, json: bool) -> Result<()> {
+pub fn decisions(
+    intent: Option<String>,
+    tag: Option<String>,
+    text: Option<String>,
+    json: bool,
+) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
@@ -72,8 +85,10 @@ pub fn decisions(intent: Option<String>, tag: Option<String>, json: bool) -> Res
 
     let results = query::query_decisions(
         &repo.odb,
+        &repo.indexes,
         intent_id.as_ref(),
         tag.as_deref(),
+        text.as_deref(),
     )?;
 
     if json {
@@ -122,11 +137,18 @@ pub fn constraints(
     symbol: Option<String>,
     impact: Option<String>,
     status: String,
+    text: Option<String>,
+    include_history: bool,
     json: bool,
 ) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
+    if include_history {
+        let chains = query::query_constraints_history(&repo.odb, &repo.indexes, impact.as_deref())?;
+        return print_constraint_history(&chains, json);
+    }
+
     // If file or symbol is specified, use indexed code-aware queries
     if let Some(ref f) = file {
         let results = query::query_constraints_by_file(&repo.odb, &repo.indexes, f)?;
@@ -139,13 +161,62 @@ pub fn constraints(
 
     let results = query::query_constraints(
         &repo.odb,
+        &repo.indexes,
         impact.as_deref(),
         Some(status.as_str()),
+        text.as_deref(),
     )?;
 
     print_constraints(&results, json)
 }
 
+fn print_constraint_history(
+    chains: &[Vec<(ObjectId, telos_core::object::constraint::Constraint)>],
+    json: bool,
+) -> Result<()> {
+    if json {
+        let entries: Vec<_> = chains
+            .iter()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .map(|(id, c)| {
+                        serde_json::json!({
+                            "id": id.hex(),
+                            "object": c,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if chains.is_empty() {
+        println!("No matching constraints found.");
+        return Ok(());
+    }
+
+    for (i, chain) in chains.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        for (j, (id, c)) in chain.iter().enumerate() {
+            let arrow = if j == 0 { "  " } else { "-> " };
+            println!(
+                "{}constraint {} [{:?}]  {}",
+                arrow,
+                id.hex(),
+                c.status,
+                c.statement
+            );
+        }
+    }
+
+    Ok(())
+}
+
 fn print_constraints(
     results: &[(ObjectId, telos_core::object::constraint::Constraint)],
     json: bool,
@@ -188,6 +259,47 @@ fn print_constraints(
     Ok(())
 }
 
+pub fn run(program: String, json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let program = datalog::parse(&program).context("failed to parse datalog program")?;
+    let base = datalog::base_facts(&repo.odb)?;
+    let derived = datalog::evaluate(base, &program.rules);
+    let (columns, rows) = datalog::answer(&program.goal, &derived);
+
+    if json {
+        let entries: Vec<_> = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .zip(row.iter())
+                    .map(|(c, v)| (c.clone(), serde_json::Value::String(v.clone())))
+                    .collect::<serde_json::Map<_, _>>()
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if columns.is_empty() {
+        println!("{}", if rows.is_empty() { "false." } else { "true." });
+        return Ok(());
+    }
+
+    if rows.is_empty() {
+        println!("No matching rows.");
+        return Ok(());
+    }
+
+    println!("{}", columns.join("\t"));
+    for row in &rows {
+        println!("{}", row.join("\t"));
+    }
+    Ok(())
+}
+
 pub fn agent_ops(agent: Option<String>, session: Option<String>, json: bool) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
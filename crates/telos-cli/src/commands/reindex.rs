@@ -7,10 +7,13 @@ pub fn run() -> Result<()> {
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
 
     println!("Rebuilding indexes...");
-    let (impact_count, path_count, sym_count) = repo.indexes.rebuild_all(&repo.odb)?;
-    println!("  impact tags:  {} entries", impact_count);
-    println!("  code paths:   {} entries", path_count);
-    println!("  symbols:      {} entries", sym_count);
+    let counts = repo.indexes.rebuild_all(&repo.odb)?;
+    let have_filter_count = repo.indexes.rebuild_have_filter(&repo.odb)?;
+    println!("  impact tags:  {} entries", counts.impact);
+    println!("  code paths:   {} entries", counts.codepath);
+    println!("  symbols:      {} entries", counts.symbols);
+    println!("  text tokens:  {} entries", counts.text);
+    println!("  have filter:  {} objects", have_filter_count);
     println!("Done.");
     Ok(())
 }
@@ -0,0 +1,21 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::repository::Repository;
+
+pub fn add(name: String, url: String, token: Option<String>) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    repo.set_remote(&name, &url, token.as_deref())?;
+    println!("Added remote '{}' -> {}", name, url);
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    for name in repo.list_remotes()? {
+        let (url, _) = repo.remote(&name)?;
+        println!("{}\t{}", name, url);
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use telos_store::repository::Repository;
+use telos_store::sync::{self, ConstraintConflict, Keep};
+
+pub fn run(local_superseded_copy: String, remote_superseded_copy: String, keep: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let (local_id, _) = repo
+        .read_object(&local_superseded_copy)
+        .context(format!("object '{}' not found", local_superseded_copy))?;
+    let (remote_id, _) = repo
+        .read_object(&remote_superseded_copy)
+        .context(format!("object '{}' not found", remote_superseded_copy))?;
+    let keep = match keep.as_str() {
+        "local" => Keep::Local,
+        "remote" => Keep::Remote,
+        other => return Err(anyhow!("--keep must be 'local' or 'remote', got '{}'", other)),
+    };
+
+    let conflict = find_conflict(&repo, &local_id, &remote_id)?;
+    let deprecated_id = repo.resolve_constraint_conflict(&conflict, keep)?;
+    println!(
+        "Resolved conflict over \"{}\", kept {:?}; deprecated {}",
+        conflict.base_statement, keep, deprecated_id
+    );
+    Ok(())
+}
+
+/// Re-derive the [`ConstraintConflict`] the user is pointing at by walking
+/// every stream's remote-tracking tip, since conflicts aren't persisted
+/// themselves — only their resolution is (`ResolvedConflicts`).
+fn find_conflict(
+    repo: &Repository,
+    local_id: &telos_core::hash::ObjectId,
+    remote_id: &telos_core::hash::ObjectId,
+) -> Result<ConstraintConflict> {
+    for stream in repo.refs.list_streams()? {
+        for remote_name in repo.list_remotes()? {
+            if let sync::MergeOutcome::Conflict(conflicts) =
+                sync::merge_stream(repo, &remote_name, &stream)?
+            {
+                if let Some(conflict) = conflicts.into_iter().find(|c| {
+                    &c.local_superseded_copy == local_id && &c.remote_superseded_copy == remote_id
+                }) {
+                    return Ok(conflict);
+                }
+            }
+        }
+    }
+    Err(anyhow!(
+        "no pending conflict found between {} and {} — run `telos pull` to refresh",
+        local_id.short(),
+        remote_id.short()
+    ))
+}
@@ -0,0 +1,13 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::repository::Repository;
+use telos_store::serve;
+
+pub fn run(bind: String, token: Option<String>) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
+    let token = config.resolve_serve_token(token.as_deref());
+
+    serve::run(repo, &bind, token).context("telos serve failed")
+}
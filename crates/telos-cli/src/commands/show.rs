@@ -1,8 +1,19 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::env;
 use telos_core::object::TelosObject;
 use telos_store::repository::Repository;
 
+/// Print the principal and token id recorded by `Repository::authorize`,
+/// if this object's `metadata` carries one.
+fn print_authorized_by(metadata: &HashMap<String, serde_json::Value>) {
+    if let Some(info) = metadata.get("authorized_by") {
+        let principal = info.get("principal").and_then(|v| v.as_str()).unwrap_or("?");
+        let token_id = info.get("token_id").and_then(|v| v.as_str()).unwrap_or("?");
+        println!("Authorized by: {} (token {})", principal, token_id);
+    }
+}
+
 pub fn run(id: String, json: bool) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
@@ -10,11 +21,13 @@ pub fn run(id: String, json: bool) -> Result<()> {
     let (oid, obj) = repo
         .read_object(&id)
         .context(format!("object '{}' not found", id))?;
+    let signature_status = repo.signature_status(&oid)?;
 
     if json {
         let output = serde_json::json!({
             "id": oid.hex(),
             "object": obj,
+            "signature": signature_status.to_string(),
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
         return Ok(());
@@ -53,6 +66,7 @@ pub fn run(id: String, json: bool) -> Result<()> {
                 println!();
                 println!("Impacts: {}", intent.impacts.join(", "));
             }
+            print_authorized_by(&intent.metadata);
         }
         TelosObject::BehaviorDiff(diff) => {
             println!("behavior_diff {}", oid.hex());
@@ -104,6 +118,7 @@ pub fn run(id: String, json: bool) -> Result<()> {
                 println!();
                 println!("Tags: {}", dr.tags.join(", "));
             }
+            print_authorized_by(&dr.metadata);
         }
         TelosObject::Constraint(c) => {
             println!("constraint {}", oid.hex());
@@ -118,6 +133,7 @@ pub fn run(id: String, json: bool) -> Result<()> {
                 println!();
                 println!("Impacts: {}", c.impacts.join(", "));
             }
+            print_authorized_by(&c.metadata);
         }
         TelosObject::CodeBinding(cb) => {
             println!("code_binding {}", oid.hex());
@@ -174,5 +190,8 @@ pub fn run(id: String, json: bool) -> Result<()> {
         }
     }
 
+    println!();
+    println!("Signature: {}", signature_status);
+
     Ok(())
 }
@@ -3,6 +3,7 @@ use chrono::Utc;
 use std::env;
 use telos_core::object::intent_stream::IntentStreamRef;
 use telos_store::repository::Repository;
+use telos_store::stream_merge::{self, StreamMergeOutcome};
 
 pub fn create(name: String) -> Result<()> {
     let cwd = env::current_dir()?;
@@ -63,3 +64,45 @@ pub fn delete(name: String) -> Result<()> {
     println!("Deleted stream '{}'", name);
     Ok(())
 }
+
+pub fn merge(source: String, json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let outcome = stream_merge::merge_streams(&repo, &source).context("merge failed")?;
+
+    if json {
+        let value = match &outcome {
+            StreamMergeOutcome::AlreadyUpToDate => serde_json::json!({"outcome": "already_up_to_date"}),
+            StreamMergeOutcome::FastForward(id) => {
+                serde_json::json!({"outcome": "fast_forward", "tip": id.hex()})
+            }
+            StreamMergeOutcome::Merged(id) => {
+                serde_json::json!({"outcome": "merged", "tip": id.hex()})
+            }
+            StreamMergeOutcome::Conflict(conflicts) => {
+                serde_json::json!({"outcome": "conflict", "conflicts": conflicts})
+            }
+        };
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    match outcome {
+        StreamMergeOutcome::AlreadyUpToDate => println!("Already up to date."),
+        StreamMergeOutcome::FastForward(id) => {
+            println!("Fast-forwarded to {} (stream '{}')", id.short(), source)
+        }
+        StreamMergeOutcome::Merged(id) => {
+            println!("Merged stream '{}', new tip {}", source, id.short())
+        }
+        StreamMergeOutcome::Conflict(conflicts) => {
+            println!("Merge of '{}' aborted: {} conflict(s) found", source, conflicts.len());
+            for c in &conflicts {
+                println!("  - {}", c.description);
+            }
+            anyhow::bail!("unresolved conflicts; supersede or resolve before merging again");
+        }
+    }
+    Ok(())
+}
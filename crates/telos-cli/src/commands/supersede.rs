@@ -4,6 +4,7 @@ use std::env;
 use telos_core::object::constraint::{ConstraintSeverity, ConstraintStatus};
 use telos_core::object::intent::Author;
 use telos_core::object::TelosObject;
+use telos_store::auth::{SignedToken, Verb};
 use telos_store::repository::Repository;
 
 pub fn run(
@@ -11,9 +12,11 @@ pub fn run(
     statement: String,
     severity: String,
     reason: Option<String>,
+    token: Option<String>,
 ) -> Result<()> {
     let cwd = env::current_dir()?;
     let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
 
     // Resolve old_id to full ObjectId
     let (old_oid, old_obj) = repo
@@ -37,6 +40,16 @@ pub fn run(
         );
     }
 
+    let mut authorized_by = None;
+    if config.auth_required() {
+        let token_path = token
+            .or_else(|| env::var("TELOS_AUTH_TOKEN").ok())
+            .context("--token (or TELOS_AUTH_TOKEN) is required: this repository requires capability-token authorization")?;
+        let signed = SignedToken::load(&token_path)?;
+        let token_id = repo.authorize(&signed, Verb::Supersede, &old_constraint.impacts)?;
+        authorized_by = Some(serde_json::json!({"principal": signed.token.principal, "token_id": token_id}));
+    }
+
     let sev = match severity.to_lowercase().as_str() {
         "must" => ConstraintSeverity::Must,
         "should" => ConstraintSeverity::Should,
@@ -51,7 +64,7 @@ pub fn run(
     let mut new_constraint = old_constraint.clone();
     new_constraint.author = Author {
         name: author_name,
-        email: author_email,
+        email: author_email.clone(),
     };
     new_constraint.timestamp = Utc::now();
     new_constraint.statement = statement;
@@ -59,6 +72,9 @@ pub fn run(
     new_constraint.status = ConstraintStatus::Active;
     new_constraint.superseded_by = None;
     new_constraint.deprecation_reason = None;
+    if let Some(authorized_by) = authorized_by {
+        new_constraint.metadata.insert("authorized_by".to_string(), authorized_by);
+    }
 
     let new_id = repo.create_constraint(new_constraint)?;
 
@@ -70,7 +86,15 @@ pub fn run(
         superseded.deprecation_reason = Some(r);
     }
 
-    let superseded_id = repo.create_constraint(superseded)?;
+    let superseded_id = repo.create_constraint(superseded.clone())?;
+    repo.record_status_change(
+        &old_oid,
+        new_id.clone(),
+        ConstraintStatus::Superseded,
+        Some(new_id.clone()),
+        superseded.deprecation_reason.clone(),
+        &author_email,
+    )?;
 
     println!(
         "Superseded {} -> {} (superseded record: {})",
@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_store::remote;
+use telos_store::repository::Repository;
+use telos_store::sync::{self, MergeOutcome};
+
+/// `telos push <remote> && telos pull <remote>` in one step: upload every
+/// object the remote is missing, reconcile constraint status refs, then
+/// fetch and merge the remote's stream tip. Exists alongside `push`/`pull`
+/// for the common case of wanting both directions without typing the
+/// remote name twice.
+pub fn run(remote_name: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let (url, token) = repo
+        .remote(&remote_name)
+        .context(format!("remote '{}' not configured", remote_name))?;
+    let stream_name = repo.refs.read_head()?;
+
+    let backend = remote::open(&url, token);
+
+    let uploaded = sync::push(&repo, backend.as_ref())?;
+    println!("Pushed {} object(s) to '{}'", uploaded, remote_name);
+
+    let pulled = sync::fetch(&repo, &remote_name, backend.as_ref(), &stream_name)?;
+    println!("Pulled {} object(s) from '{}'", pulled, remote_name);
+
+    let conflicted = sync::sync_status_refs(&repo, backend.as_ref())?;
+    for base_id in &conflicted {
+        println!(
+            "Note: constraint {} had a concurrent status change reconciled; see .telos/refs/constraints/{}.json",
+            base_id.short(),
+            base_id.hex()
+        );
+    }
+
+    match sync::merge_stream(&repo, &remote_name, &stream_name)? {
+        MergeOutcome::AlreadyUpToDate => println!("Already up to date."),
+        MergeOutcome::FastForward(id) => {
+            println!("Fast-forwarded '{}' to {}", stream_name, id.hex())
+        }
+        MergeOutcome::Merged(id) => {
+            println!("Merged '{}' from '{}', new tip {}", stream_name, remote_name, id.hex())
+        }
+        MergeOutcome::Conflict(conflicts) => {
+            println!(
+                "Sync stopped: {} constraint conflict(s) must be resolved before merging.",
+                conflicts.len()
+            );
+            for c in &conflicts {
+                println!(
+                    "  \"{}\"\n    local:  {} -> {}\n    remote: {} -> {}",
+                    c.base_statement,
+                    c.local_superseded_copy.hex(),
+                    c.local_replacement.hex(),
+                    c.remote_superseded_copy.hex(),
+                    c.remote_replacement.hex(),
+                );
+            }
+            println!("Run `telos resolve <local-superseded-copy> <remote-superseded-copy> --keep local|remote` for each, then `telos sync` again.");
+        }
+    }
+
+    let stream = repo.refs.current_stream()?;
+    if let Some(tip) = stream.tip {
+        backend.set_stream_head(&stream.name, &tip)?;
+        println!("Updated '{}' on '{}' to {}", stream.name, remote_name, tip.hex());
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use std::env;
+use telos_core::config::resolve_profile;
+use telos_store::queue::VerificationWorker;
+use telos_store::repository::Repository;
+
+/// Show how many verification jobs are waiting to be claimed.
+pub fn status(json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let pending = repo.queue.pending_count()?;
+
+    if json {
+        println!("{}", serde_json::json!({ "pending": pending }));
+    } else {
+        println!("{} verification job(s) pending", pending);
+    }
+    Ok(())
+}
+
+/// Claim and process every currently pending verification job, one at a
+/// time, in the foreground.
+pub fn run_pending(profile: Option<String>) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let config = repo.telos_config()?;
+    let profile = resolve_profile(profile.as_deref());
+    let worker = VerificationWorker::with_config(&repo.queue, &repo.odb, &config, profile.as_deref());
+
+    let mut processed = 0;
+    while worker.run_once()? {
+        processed += 1;
+    }
+
+    println!("Processed {} verification job(s)", processed);
+    Ok(())
+}
+
+/// Check an object's cryptographic signature against
+/// `.telos/allowed_signers`.
+pub fn signature(hash: String, json: bool) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let (oid, _) = repo
+        .read_object(&hash)
+        .context(format!("object '{}' not found", hash))?;
+    let status = repo.signature_status(&oid)?;
+    let sig = repo.signatures.get(&oid)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "id": oid.hex(),
+                "status": status.to_string(),
+                "fingerprint": sig.as_ref().map(|s| &s.fingerprint),
+            })
+        );
+        return Ok(());
+    }
+
+    println!("{}: {}", oid.hex(), status);
+    if let Some(sig) = sig {
+        println!("Signer fingerprint: {}", sig.fingerprint);
+    }
+    Ok(())
+}
+
+/// Trust a signer's fingerprint in `.telos/allowed_signers`.
+pub fn trust(fingerprint: String, public_key: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let mut allowed = repo.allowed_signers()?;
+    allowed.trust(fingerprint.clone(), public_key);
+    repo.save_allowed_signers(&allowed)?;
+    println!("Trusted signer {}", fingerprint);
+    Ok(())
+}
+
+/// Remove a signer's fingerprint from `.telos/allowed_signers`.
+pub fn untrust(fingerprint: String) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+
+    let mut allowed = repo.allowed_signers()?;
+    allowed.revoke(&fingerprint);
+    repo.save_allowed_signers(&allowed)?;
+    println!("Untrusted signer {}", fingerprint);
+    Ok(())
+}
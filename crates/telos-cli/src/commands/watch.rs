@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::thread;
+use std::time::Duration;
+use telos_store::config_reload::ConfigWatcher;
+use telos_store::repository::Repository;
+use telos_store::watch::{ScopeFilter, WatchEvent, Watcher};
+
+/// How often to re-check the stream ref and scan for new objects when no
+/// filesystem-notification backend is wired in. A plain poll loop is the
+/// whole implementation for now; it's cheap enough at this interval that a
+/// real inotify/FSEvents watcher would only buy lower latency, not
+/// correctness.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+pub fn run(
+    stream: Option<String>,
+    impact: Option<String>,
+    kind: Option<String>,
+    listen: Option<String>,
+) -> Result<()> {
+    let cwd = env::current_dir()?;
+    let repo = Repository::discover(&cwd).context("not a Telos repository")?;
+    let stream_name = match stream {
+        Some(s) => s,
+        None => repo.refs.read_head()?,
+    };
+
+    let scope = ScopeFilter {
+        impact,
+        kind: kind
+            .map(|k| k.parse())
+            .transpose()
+            .map_err(|e: telos_store::error::StoreError| anyhow::anyhow!(e.to_string()))?,
+    };
+
+    let mut watcher = Watcher::new(&repo, stream_name.clone())?;
+    eprintln!("Watching stream '{}'...", stream_name);
+
+    let (_config, mut config_watcher) = ConfigWatcher::open(repo.root().join(".telos"))
+        .context("failed to load .telos/config.toml")?;
+
+    match listen {
+        Some(socket_path) => run_with_listener(&repo, &stream_name, &mut watcher, &scope, &mut config_watcher, &socket_path),
+        None => run_stdout(&repo, &mut watcher, &scope, &mut config_watcher),
+    }
+}
+
+/// Re-check `.telos/config.toml` once per poll iteration. A changed author
+/// identity or signing policy takes effect on the next object this process
+/// writes; a parse error is reported without interrupting the watch.
+fn poll_config(config_watcher: &mut ConfigWatcher) {
+    match config_watcher.poll() {
+        Ok(Some(sections)) if !sections.is_empty() => {
+            eprintln!("config.toml reloaded ({} changed)", sections.join(", "));
+        }
+        Ok(Some(_)) | Ok(None) => {}
+        Err(e) => eprintln!("config.toml changed but failed to parse, keeping previous settings: {}", e),
+    }
+}
+
+fn emit_stdout(event: &WatchEvent) -> Result<()> {
+    println!("{}", serde_json::to_string(event)?);
+    std::io::stdout().flush()?;
+    Ok(())
+}
+
+fn run_stdout(repo: &Repository, watcher: &mut Watcher, scope: &ScopeFilter, config_watcher: &mut ConfigWatcher) -> Result<()> {
+    loop {
+        for event in watcher.poll(repo, scope)? {
+            emit_stdout(&event)?;
+        }
+        poll_config(config_watcher);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn write_event(client: &mut UnixStream, event: &WatchEvent) -> std::io::Result<()> {
+    let line = serde_json::to_string(event).map_err(std::io::Error::other)?;
+    client.write_all(line.as_bytes())?;
+    client.write_all(b"\n")?;
+    client.flush()
+}
+
+/// Serve events over a Unix socket in addition to stdout: each connection
+/// first gets a full backlog replay (everything matching `scope`, not just
+/// what's happened since this watcher started), then live events going
+/// forward — a subscribe-with-snapshot handshake so a consumer doesn't have
+/// to race startup against the first real commit.
+fn run_with_listener(
+    repo: &Repository,
+    stream_name: &str,
+    watcher: &mut Watcher,
+    scope: &ScopeFilter,
+    config_watcher: &mut ConfigWatcher,
+    socket_path: &str,
+) -> Result<()> {
+    let _ = fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("failed to bind --listen socket '{}'", socket_path))?;
+    listener.set_nonblocking(true)?;
+    eprintln!("Listening for subscribers on {}", socket_path);
+
+    let mut clients: Vec<UnixStream> = Vec::new();
+
+    loop {
+        match listener.accept() {
+            Ok((mut client, _addr)) => {
+                let (_, backlog) = Watcher::backlog(repo, stream_name, scope)?;
+                if backlog.iter().all(|event| write_event(&mut client, event).is_ok()) {
+                    clients.push(client);
+                }
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let events = watcher.poll(repo, scope)?;
+        if !events.is_empty() {
+            for event in &events {
+                emit_stdout(event)?;
+            }
+            let mut alive = Vec::with_capacity(clients.len());
+            for mut client in clients.drain(..) {
+                if events.iter().all(|event| write_event(&mut client, event).is_ok()) {
+                    alive.push(client);
+                }
+            }
+            clients = alive;
+        }
+
+        poll_config(config_watcher);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -9,6 +9,10 @@ struct Cli {
     #[arg(long, global = true)]
     json: bool,
 
+    /// Named `.telos/config.toml` profile to apply (env: TELOS_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -16,7 +20,17 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize a new Telos repository
-    Init,
+    Init {
+        /// Encrypt object bytes at rest (passphrase via TELOS_PASSPHRASE)
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Generate a repository signing key and set [sign].default = true
+        /// in .telos/config.toml, so intent, decide, constraint, bind and
+        /// agent-log sign by default
+        #[arg(long)]
+        signed: bool,
+    },
 
     /// Create a new intent (analogous to git commit)
     Intent {
@@ -35,6 +49,25 @@ enum Commands {
         /// Behavior clauses (repeatable, format: "GIVEN x|WHEN y|THEN z")
         #[arg(long)]
         behavior: Vec<String>,
+
+        /// Sign the created object (env: TELOS_SIGN, config: [sign].default)
+        #[arg(long)]
+        sign: bool,
+
+        /// OpenSSH ssh-ed25519 private key to sign with, instead of the
+        /// repository's own generated key (env: TELOS_SIGN_SSH_KEY)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Sign using a key already loaded in a running ssh-agent
+        /// (via SSH_AUTH_SOCK) instead of `--ssh-key`/the repository's key
+        #[arg(long)]
+        ssh_agent: bool,
+
+        /// Capability token authorizing this action, required if the
+        /// repository has `[auth] required = true` (env: TELOS_AUTH_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
     },
 
     /// Manage intent streams (analogous to git branch)
@@ -48,6 +81,26 @@ enum Commands {
         /// Maximum number of entries
         #[arg(short = 'n', long, default_value = "20")]
         max_count: usize,
+
+        /// Order entries in reverse-topological order (every intent before
+        /// its ancestors) instead of plain BFS order
+        #[arg(long)]
+        topo_order: bool,
+
+        /// Only show intents tagged with this impact area, resolved via the
+        /// impact index instead of a full walk
+        #[arg(long)]
+        impact: Option<String>,
+
+        /// Only show intents bound to this file path, resolved via the
+        /// code-path index
+        #[arg(long)]
+        path: Option<String>,
+
+        /// Only show intents bound to this symbol, resolved via the symbol
+        /// index
+        #[arg(long)]
+        symbol: Option<String>,
     },
 
     /// Show details of any object by ID or prefix
@@ -81,6 +134,25 @@ enum Commands {
         /// Tags (repeatable)
         #[arg(long)]
         tag: Vec<String>,
+
+        /// Sign the created object (env: TELOS_SIGN, config: [sign].default)
+        #[arg(long)]
+        sign: bool,
+
+        /// OpenSSH ssh-ed25519 private key to sign with, instead of the
+        /// repository's own generated key (env: TELOS_SIGN_SSH_KEY)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Sign using a key already loaded in a running ssh-agent
+        /// (via SSH_AUTH_SOCK) instead of `--ssh-key`/the repository's key
+        #[arg(long)]
+        ssh_agent: bool,
+
+        /// Capability token authorizing this action, required if the
+        /// repository has `[auth] required = true` (env: TELOS_AUTH_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
     },
 
     /// Query objects in the repository
@@ -113,6 +185,20 @@ enum Commands {
         /// Scope file paths (repeatable)
         #[arg(long)]
         scope: Vec<String>,
+
+        /// Sign the created object (env: TELOS_SIGN, config: [sign].default)
+        #[arg(long)]
+        sign: bool,
+
+        /// OpenSSH ssh-ed25519 private key to sign with, instead of the
+        /// repository's own generated key (env: TELOS_SIGN_SSH_KEY)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Sign using a key already loaded in a running ssh-agent
+        /// (via SSH_AUTH_SOCK) instead of `--ssh-key`/the repository's key
+        #[arg(long)]
+        ssh_agent: bool,
     },
 
     /// Supersede an existing constraint
@@ -131,6 +217,11 @@ enum Commands {
         /// Reason for superseding
         #[arg(long)]
         reason: Option<String>,
+
+        /// Capability token authorizing this action, required if the
+        /// repository has `[auth] required = true` (env: TELOS_AUTH_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
     },
 
     /// Deprecate a constraint
@@ -141,6 +232,11 @@ enum Commands {
         /// Reason for deprecation
         #[arg(long)]
         reason: String,
+
+        /// Capability token authorizing this action, required if the
+        /// repository has `[auth] required = true` (env: TELOS_AUTH_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
     },
 
     /// Create a code binding
@@ -159,6 +255,20 @@ enum Commands {
         /// Binding type (file, function, module, api, type)
         #[arg(long, default_value = "file")]
         r#type: String,
+
+        /// Sign the created object (env: TELOS_SIGN, config: [sign].default)
+        #[arg(long)]
+        sign: bool,
+
+        /// OpenSSH ssh-ed25519 private key to sign with, instead of the
+        /// repository's own generated key (env: TELOS_SIGN_SSH_KEY)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Sign using a key already loaded in a running ssh-agent
+        /// (via SSH_AUTH_SOCK) instead of `--ssh-key`/the repository's key
+        #[arg(long)]
+        ssh_agent: bool,
     },
 
     /// Validate bindings and constraints against code
@@ -197,10 +307,249 @@ enum Commands {
         /// Files touched (repeatable)
         #[arg(long)]
         file: Vec<String>,
+
+        /// Sign the created object (env: TELOS_SIGN, config: [sign].default)
+        #[arg(long)]
+        sign: bool,
+
+        /// OpenSSH ssh-ed25519 private key to sign with, instead of the
+        /// repository's own generated key (env: TELOS_SIGN_SSH_KEY)
+        #[arg(long)]
+        ssh_key: Option<String>,
+
+        /// Sign using a key already loaded in a running ssh-agent
+        /// (via SSH_AUTH_SOCK) instead of `--ssh-key`/the repository's key
+        #[arg(long)]
+        ssh_agent: bool,
+    },
+
+    /// Materialize outstanding agent work (unresolved bindings, uncovered
+    /// `must` constraints) and atomically claim the oldest task
+    AgentPull {
+        /// Agent identifier the claimed task is leased to
+        #[arg(long)]
+        agent: String,
+    },
+
+    /// Close an agent task claimed via `agent-pull`, recording a linked
+    /// agent_operation
+    AgentReport {
+        /// Task id (from `agent-pull`'s output)
+        #[arg(long)]
+        task: u64,
+
+        /// Agent identifier
+        #[arg(long)]
+        agent: String,
+
+        /// Session identifier
+        #[arg(long)]
+        session: String,
+
+        /// Outcome: success, warning, failure, or skipped
+        #[arg(long)]
+        result: String,
+
+        /// Summary of the outcome (defaults to the task's description)
+        #[arg(long)]
+        summary: Option<String>,
     },
 
     /// Rebuild all indexes
     Reindex,
+
+    /// Move a repository's objects and stream refs to a different storage backend
+    Migrate {
+        /// Source backend address (file://, memory://, or sled://)
+        #[arg(long)]
+        from: String,
+
+        /// Destination backend address (file://, memory://, or sled://)
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Garbage-collect the object database
+    Gc {
+        /// Consolidate loose objects into compressed packfiles
+        #[arg(long)]
+        pack: bool,
+
+        /// Before packing, delete loose objects unreachable from any
+        /// stream's current tip, remote-tracking head or object a remote
+        /// is known to hold
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Export the intent/constraint/impact graph as Graphviz DOT
+    Graph {
+        /// Restrict output to the subgraph reachable from this object
+        /// (id or prefix), following parent/cross-reference links
+        #[arg(long)]
+        impact: Option<String>,
+    },
+
+    /// Manage remotes
+    Remote {
+        #[command(subcommand)]
+        action: RemoteAction,
+    },
+
+    /// Push missing objects (and the current stream's tip) to a remote
+    Push {
+        /// Remote name (see `telos remote add`)
+        remote: String,
+    },
+
+    /// Fetch missing objects and the remote's stream tip, without merging
+    Fetch {
+        /// Remote name (see `telos remote add`)
+        remote: String,
+
+        /// Stream to fetch (default: current)
+        #[arg(long)]
+        stream: Option<String>,
+    },
+
+    /// Fetch, then merge the remote's stream tip into the current stream
+    Pull {
+        /// Remote name (see `telos remote add`)
+        remote: String,
+    },
+
+    /// Push, then fetch and merge: `push` and `pull` in one step
+    Sync {
+        /// Remote name (see `telos remote add`)
+        remote: String,
+    },
+
+    /// Resolve a constraint conflict surfaced by `pull`
+    Resolve {
+        /// Base constraint statement's superseded-copy id on the local side
+        local_superseded_copy: String,
+
+        /// Base constraint statement's superseded-copy id on the remote side
+        remote_superseded_copy: String,
+
+        /// Which branch's replacement to keep ("local" or "remote")
+        #[arg(long)]
+        keep: String,
+    },
+
+    /// Serve the read paths (query/context/show) and a /changes long-poll
+    /// route over HTTP, for clients that want a persistent endpoint instead
+    /// of forking the binary per call
+    Serve {
+        /// Address to bind, e.g. "127.0.0.1:7878"
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        bind: String,
+
+        /// Bearer token write routes (currently just POST /agent-log)
+        /// require; falls back to [serve].token / TELOS_SERVE_TOKEN
+        #[arg(long)]
+        token: Option<String>,
+    },
+
+    /// Follow a stream, printing newly committed objects as NDJSON
+    Watch {
+        /// Stream to follow (default: current)
+        #[arg(long)]
+        stream: Option<String>,
+
+        /// Only report objects touching this impact tag
+        #[arg(long)]
+        impact: Option<String>,
+
+        /// Only report objects of this kind ("intent", "decision", or "constraint")
+        #[arg(long)]
+        kind: Option<String>,
+
+        /// Also serve events over this Unix socket; each new connection gets
+        /// a backlog replay followed by live events
+        #[arg(long)]
+        listen: Option<String>,
+    },
+
+    /// Export stored objects to a columnar or provenance interchange format
+    Export {
+        /// Output format ("parquet", "prov-json", "prov-jsonld", or "prov-turtle")
+        #[arg(long, default_value = "parquet")]
+        format: String,
+
+        /// What to export ("operations" or "objects", parquet only)
+        #[arg(long, default_value = "operations")]
+        kind: String,
+
+        /// Output path: a file for "operations"/prov-json/prov-jsonld/prov-turtle,
+        /// a directory for "objects"
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+
+        /// Restrict a prov-json/prov-jsonld/prov-turtle export to the objects
+        /// reachable from this stream's current tip, instead of the whole store
+        #[arg(long)]
+        stream: Option<String>,
+    },
+
+    /// Manage the behavior-diff verification job queue
+    Verify {
+        #[command(subcommand)]
+        action: VerifyAction,
+    },
+
+    /// Issue and revoke capability tokens for `intent`/`decide`/`supersede`/`deprecate`
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+
+    /// Dump every object to a self-describing, versioned archive file
+    Dump {
+        /// Output archive path
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+    },
+
+    /// Restore objects from a dump archive and rebuild indexes
+    Restore {
+        /// Archive path produced by `telos dump`
+        #[arg(short, long)]
+        input: std::path::PathBuf,
+    },
+
+    /// Walk the provenance lineage graph from an object
+    Lineage {
+        /// Object ID (full or prefix, minimum 4 chars)
+        id: String,
+
+        /// Direction to walk: ancestors or descendants
+        #[arg(long, default_value = "ancestors")]
+        direction: String,
+    },
+
+    /// Render release notes from the ChangeSets in a stream range
+    Changelog {
+        /// Stream to read (defaults to the current stream)
+        #[arg(long)]
+        stream: Option<String>,
+
+        /// Range start: an IntentStreamSnapshot id or intent id, exclusive.
+        /// Omit to cover the whole history up to `--until`
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Range end: an IntentStreamSnapshot id or intent id. Defaults to
+        /// the stream's current tip
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Path to a template file with `{{git_commit}}`, `{{timestamp}}`,
+        /// `{{intents}}`, `{{decisions}}`, and `{{constraints}}`
+        /// placeholders. Defaults to a built-in Markdown rendering
+        #[arg(long)]
+        template: Option<std::path::PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -214,6 +563,10 @@ enum QueryAction {
         /// Filter by constraint substring (case-insensitive)
         #[arg(long)]
         constraint_contains: Option<String>,
+
+        /// Full-text search over statement/constraints, via the text index
+        #[arg(long)]
+        text: Option<String>,
     },
     /// Query decision records with optional filters
     Decisions {
@@ -224,6 +577,10 @@ enum QueryAction {
         /// Filter by tag
         #[arg(long)]
         tag: Option<String>,
+
+        /// Full-text search over question/decision/rationale/tags, via the text index
+        #[arg(long)]
+        text: Option<String>,
     },
     /// Query constraints
     Constraints {
@@ -242,6 +599,16 @@ enum QueryAction {
         /// Filter by status (active, superseded, deprecated)
         #[arg(long, default_value = "active")]
         status: String,
+
+        /// Full-text search over the constraint statement, via the text index
+        #[arg(long)]
+        text: Option<String>,
+
+        /// Show each matching constraint's full supersession chain
+        /// (oldest to newest) instead of just the active tip. Ignores
+        /// `--status`; `--file`/`--symbol` aren't supported with this flag.
+        #[arg(long)]
+        include_history: bool,
     },
     /// Query agent operations
     AgentOps {
@@ -253,6 +620,88 @@ enum QueryAction {
         #[arg(long)]
         session: Option<String>,
     },
+    /// Run a Datalog program (rules plus a trailing `?-` goal) over the
+    /// object graph, e.g. `telos query run 'ancestor(X, Y) :- parent(X, Y).
+    /// ancestor(X, Y) :- parent(X, Z), ancestor(Z, Y). ?- ancestor(X, "<id>").'`
+    Run {
+        /// The program source: newline- or `.`-separated rules, ending in
+        /// exactly one `?- goal(...).` line
+        program: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum VerifyAction {
+    /// Show how many verification jobs are pending
+    Status,
+    /// Claim and process every pending verification job in the foreground
+    Run,
+    /// Check an object's cryptographic signature against
+    /// `.telos/allowed_signers`
+    Signature {
+        /// Object ID (full or prefix) to check
+        hash: String,
+    },
+    /// Trust a signer's fingerprint in `.telos/allowed_signers`
+    Trust {
+        /// Fingerprint (as printed by `telos verify signature`)
+        fingerprint: String,
+        /// The signer's full Ed25519 public key, hex-encoded
+        public_key: String,
+    },
+    /// Remove a signer's fingerprint from `.telos/allowed_signers`
+    Untrust {
+        /// Fingerprint to remove
+        fingerprint: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Mint a capability token, signed by the repository's authority key
+    Issue {
+        /// Principal name the token is granted to
+        #[arg(long)]
+        principal: String,
+
+        /// Verb to grant (repeatable): intent, decide, supersede, deprecate
+        #[arg(long = "verb")]
+        verbs: Vec<String>,
+
+        /// Restrict the grant to these impact tags (repeatable); omit for an unscoped grant
+        #[arg(long)]
+        impact: Vec<String>,
+
+        /// Expiry, in days from now
+        #[arg(long, default_value = "30")]
+        expires_days: i64,
+
+        /// Output path for the signed token
+        #[arg(short, long)]
+        output: std::path::PathBuf,
+    },
+    /// Revoke a previously issued token by id
+    Revoke {
+        /// Token id, as printed by `telos auth issue` or a `show`/`query`
+        /// result's "authorized_by" field
+        token_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum RemoteAction {
+    /// Add (or update) a remote
+    Add {
+        /// Remote name
+        name: String,
+        /// Remote base URL
+        url: String,
+        /// Bearer token for authenticated remotes
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// List configured remotes
+    List,
 }
 
 #[derive(Subcommand)]
@@ -274,26 +723,54 @@ enum StreamAction {
         /// Stream name to delete
         name: String,
     },
+    /// Merge another stream into the current one
+    Merge {
+        /// Stream to merge into the current one
+        source: String,
+    },
 }
 
 fn main() {
+    let _telemetry = telos_telemetry::init_from_env("telos-cli");
+
     let cli = Cli::parse();
 
     let result = match cli.command {
-        Commands::Init => commands::init::run(),
+        Commands::Init { encrypt, signed } => commands::init::run(encrypt, signed),
         Commands::Intent {
             statement,
             constraint,
             impact,
             behavior,
-        } => commands::intent::run(statement, constraint, impact, behavior),
+            sign,
+            ssh_key,
+            ssh_agent,
+            token,
+        } => commands::intent::run(
+            statement,
+            constraint,
+            impact,
+            behavior,
+            cli.profile.clone(),
+            sign,
+            ssh_key,
+            ssh_agent,
+            token,
+        ),
         Commands::Stream { action } => match action {
             StreamAction::Create { name } => commands::stream::create(name),
             StreamAction::List => commands::stream::list(),
             StreamAction::Switch { name } => commands::stream::switch(name),
             StreamAction::Delete { name } => commands::stream::delete(name),
+            StreamAction::Merge { source } => commands::stream::merge(source, cli.json),
         },
-        Commands::Log { max_count } => commands::log::run(max_count, cli.json),
+        Commands::Log {
+            max_count,
+            topo_order,
+            impact,
+            path,
+            symbol,
+        } => commands::log::run(max_count, cli.json, topo_order, impact, path, symbol),
         Commands::Show { id } => commands::show::run(id, cli.json),
         Commands::Decide {
             intent,
@@ -302,24 +779,43 @@ fn main() {
             rationale,
             alternative,
             tag,
-        } => commands::decide::run(intent, question, decision, rationale, alternative, tag),
+            sign,
+            ssh_key,
+            ssh_agent,
+            token,
+        } => commands::decide::run(
+            intent, question, decision, rationale, alternative, tag, sign, ssh_key, ssh_agent,
+            token,
+        ),
         Commands::Query { action } => match action {
             QueryAction::Intents {
                 impact,
                 constraint_contains,
-            } => commands::query::intents(impact, constraint_contains, cli.json),
-            QueryAction::Decisions { intent, tag } => {
-                commands::query::decisions(intent, tag, cli.json)
+                text,
+            } => commands::query::intents(impact, constraint_contains, text, cli.json),
+            QueryAction::Decisions { intent, tag, text } => {
+                commands::query::decisions(intent, tag, text, cli.json)
             }
             QueryAction::Constraints {
                 file,
                 symbol,
                 impact,
                 status,
-            } => commands::query::constraints(file, symbol, impact, status, cli.json),
+                text,
+                include_history,
+            } => commands::query::constraints(
+                file,
+                symbol,
+                impact,
+                status,
+                text,
+                include_history,
+                cli.json,
+            ),
             QueryAction::AgentOps { agent, session } => {
                 commands::query::agent_ops(agent, session, cli.json)
             }
+            QueryAction::Run { program } => commands::query::run(program, cli.json),
         },
         Commands::Context { impact } => commands::context::run(impact, cli.json),
         Commands::Constraint {
@@ -327,21 +823,28 @@ fn main() {
             severity,
             impact,
             scope,
-        } => commands::constraint::run(statement, severity, impact, scope),
+            sign,
+            ssh_key,
+            ssh_agent,
+        } => commands::constraint::run(statement, severity, impact, scope, sign, ssh_key, ssh_agent),
         Commands::Supersede {
             id,
             statement,
             severity,
             reason,
-        } => commands::supersede::run(id, statement, severity, reason),
-        Commands::Deprecate { id, reason } => commands::deprecate::run(id, reason),
+            token,
+        } => commands::supersede::run(id, statement, severity, reason, token),
+        Commands::Deprecate { id, reason, token } => commands::deprecate::run(id, reason, token),
         Commands::Bind {
             id,
             file,
             symbol,
             r#type,
-        } => commands::bind::run(id, file, symbol, r#type),
-        Commands::Check { bindings, all } => commands::check::run(bindings, all),
+            sign,
+            ssh_key,
+            ssh_agent,
+        } => commands::bind::run(id, file, symbol, r#type, sign, ssh_key, ssh_agent),
+        Commands::Check { bindings, all } => commands::check::run(bindings, all, cli.json),
         Commands::AgentLog {
             agent,
             session,
@@ -349,8 +852,75 @@ fn main() {
             summary,
             context_ref,
             file,
-        } => commands::agent_log::run(agent, session, operation, summary, context_ref, file),
+            sign,
+            ssh_key,
+            ssh_agent,
+        } => commands::agent_log::run(
+            agent, session, operation, summary, context_ref, file, sign, ssh_key, ssh_agent,
+        ),
+        Commands::AgentPull { agent } => commands::agent_pull::run(agent),
+        Commands::AgentReport {
+            task,
+            agent,
+            session,
+            result,
+            summary,
+        } => commands::agent_report::run(task, agent, session, result, summary),
         Commands::Reindex => commands::reindex::run(),
+        Commands::Migrate { from, to } => commands::migrate::run(from, to),
+        Commands::Gc { pack, prune } => commands::gc::run(pack, prune),
+        Commands::Graph { impact } => commands::graph::run(impact, cli.json),
+        Commands::Remote { action } => match action {
+            RemoteAction::Add { name, url, token } => commands::remote::add(name, url, token),
+            RemoteAction::List => commands::remote::list(),
+        },
+        Commands::Push { remote } => commands::push::run(remote),
+        Commands::Fetch { remote, stream } => commands::fetch::run(remote, stream),
+        Commands::Pull { remote } => commands::pull::run(remote),
+        Commands::Sync { remote } => commands::sync::run(remote),
+        Commands::Resolve {
+            local_superseded_copy,
+            remote_superseded_copy,
+            keep,
+        } => commands::resolve::run(local_superseded_copy, remote_superseded_copy, keep),
+        Commands::Serve { bind, token } => commands::serve::run(bind, token),
+        Commands::Watch {
+            stream,
+            impact,
+            kind,
+            listen,
+        } => commands::watch::run(stream, impact, kind, listen),
+        Commands::Export {
+            format,
+            kind,
+            output,
+            stream,
+        } => commands::export::run(format, kind, output, stream),
+        Commands::Verify { action } => match action {
+            VerifyAction::Status => commands::verify::status(cli.json),
+            VerifyAction::Run => commands::verify::run_pending(cli.profile.clone()),
+            VerifyAction::Signature { hash } => commands::verify::signature(hash, cli.json),
+            VerifyAction::Trust { fingerprint, public_key } => {
+                commands::verify::trust(fingerprint, public_key)
+            }
+            VerifyAction::Untrust { fingerprint } => commands::verify::untrust(fingerprint),
+        },
+        Commands::Auth { action } => match action {
+            AuthAction::Issue {
+                principal,
+                verbs,
+                impact,
+                expires_days,
+                output,
+            } => commands::auth::issue(principal, verbs, impact, expires_days, output),
+            AuthAction::Revoke { token_id } => commands::auth::revoke(token_id),
+        },
+        Commands::Dump { output } => commands::dump::run(output),
+        Commands::Restore { input } => commands::dump::restore(input),
+        Commands::Lineage { id, direction } => commands::lineage::run(id, direction, cli.json),
+        Commands::Changelog { stream, since, until, template } => {
+            commands::changelog::run(stream, since, until, template, cli.json)
+        }
     };
 
     if let Err(e) = result {
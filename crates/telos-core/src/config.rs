@@ -0,0 +1,399 @@
+//! Layered repository configuration read from `.telos/config.toml`.
+//!
+//! Team defaults live in top-level `[author]`/`[codex]` tables; named
+//! `[profile.<name>]` sections override either for a particular environment
+//! (e.g. `[profile.ci]`), selected with `--profile`/`TELOS_PROFILE` (in the
+//! spirit of Wrangler's per-environment manifest sections). Every resolved
+//! setting follows the same precedence: CLI flag > env var > selected
+//! profile > top-level config > built-in default, so a team can commit
+//! shared defaults while a contributor still overrides locally.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid config.toml: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AuthorConfig {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct CodexConfig {
+    pub binary: Option<String>,
+    pub timeout_secs: Option<u64>,
+    pub args: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SignConfig {
+    /// Whether `intent`/`decide`/`constraint` sign their object by default.
+    pub default: Option<bool>,
+    /// Path to an OpenSSH `ssh-ed25519` private key to sign with, instead
+    /// of the repo's own generated key under `.telos/keys`.
+    pub ssh_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct AuthConfig {
+    /// Whether `decide`/`supersede`/`deprecate`/`intent` require a
+    /// `--token`/`TELOS_AUTH_TOKEN` capability token to run. Deliberately
+    /// not resolvable from a `[profile.<name>]` override or an env var
+    /// like the other settings here — it's a repo-wide security policy,
+    /// not something a contributor's local environment should be able to
+    /// silently relax.
+    pub required: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ServeConfig {
+    /// Bearer token `telos serve`'s write routes require. Like
+    /// `[auth].required`, deliberately not resolvable from a
+    /// `[profile.<name>]` override — the same server process serves every
+    /// profile's requests.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ProfileConfig {
+    #[serde(default)]
+    pub author: AuthorConfig,
+    #[serde(default)]
+    pub codex: CodexConfig,
+    #[serde(default)]
+    pub sign: SignConfig,
+}
+
+/// Parsed `.telos/config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct TelosConfig {
+    #[serde(default)]
+    pub author: AuthorConfig,
+    #[serde(default)]
+    pub codex: CodexConfig,
+    #[serde(default)]
+    pub sign: SignConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub serve: ServeConfig,
+    #[serde(default, rename = "profile")]
+    pub profiles: HashMap<String, ProfileConfig>,
+}
+
+const DEFAULT_AUTHOR_NAME: &str = "Anonymous";
+const DEFAULT_AUTHOR_EMAIL: &str = "anonymous@telos";
+const DEFAULT_CODEX_BINARY: &str = "codex";
+const DEFAULT_CODEX_TIMEOUT_SECS: u64 = 120;
+
+/// Author identity resolved down the precedence chain, ready to stamp onto
+/// an `Intent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+/// Codex invocation settings resolved down the precedence chain, ready to
+/// build a `CodexRunner` from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedCodex {
+    pub binary: String,
+    pub timeout_secs: u64,
+    pub args: Vec<String>,
+}
+
+impl TelosConfig {
+    /// Load `config.toml` from a repository's `.telos/` directory. A
+    /// missing file resolves to an all-default config, since the file
+    /// itself is optional.
+    pub fn load(telos_dir: &Path) -> Result<Self, ConfigError> {
+        let path = telos_dir.join("config.toml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn profile(&self, name: Option<&str>) -> Option<&ProfileConfig> {
+        name.and_then(|n| self.profiles.get(n))
+    }
+
+    /// Resolve author identity: `cli_name`/`cli_email` > `TELOS_AUTHOR_NAME`
+    /// / `TELOS_AUTHOR_EMAIL` env vars > `[profile.<name>].author` >
+    /// top-level `[author]` > built-in default. Name and email resolve
+    /// independently, so a profile can set just one of them.
+    pub fn resolve_author(
+        &self,
+        profile: Option<&str>,
+        cli_name: Option<&str>,
+        cli_email: Option<&str>,
+    ) -> ResolvedAuthor {
+        let profile = self.profile(profile);
+        let name = cli_name
+            .map(str::to_string)
+            .or_else(|| std::env::var("TELOS_AUTHOR_NAME").ok())
+            .or_else(|| profile.and_then(|p| p.author.name.clone()))
+            .or_else(|| self.author.name.clone())
+            .unwrap_or_else(|| DEFAULT_AUTHOR_NAME.to_string());
+        let email = cli_email
+            .map(str::to_string)
+            .or_else(|| std::env::var("TELOS_AUTHOR_EMAIL").ok())
+            .or_else(|| profile.and_then(|p| p.author.email.clone()))
+            .or_else(|| self.author.email.clone())
+            .unwrap_or_else(|| DEFAULT_AUTHOR_EMAIL.to_string());
+        ResolvedAuthor { name, email }
+    }
+
+    /// Resolve codex invocation settings: `TELOS_CODEX_BINARY` /
+    /// `TELOS_CODEX_TIMEOUT_SECS` env vars > `[profile.<name>].codex` >
+    /// top-level `[codex]` > built-in default. There is no per-invocation
+    /// CLI flag for these yet, so that tier is skipped.
+    pub fn resolve_codex(&self, profile: Option<&str>) -> ResolvedCodex {
+        let profile = self.profile(profile);
+        let binary = std::env::var("TELOS_CODEX_BINARY")
+            .ok()
+            .or_else(|| profile.and_then(|p| p.codex.binary.clone()))
+            .or_else(|| self.codex.binary.clone())
+            .unwrap_or_else(|| DEFAULT_CODEX_BINARY.to_string());
+        let timeout_secs = std::env::var("TELOS_CODEX_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .or_else(|| profile.and_then(|p| p.codex.timeout_secs))
+            .or(self.codex.timeout_secs)
+            .unwrap_or(DEFAULT_CODEX_TIMEOUT_SECS);
+        let args = profile
+            .and_then(|p| p.codex.args.clone())
+            .or_else(|| self.codex.args.clone())
+            .unwrap_or_default();
+        ResolvedCodex {
+            binary,
+            timeout_secs,
+            args,
+        }
+    }
+
+    /// Resolve whether objects are signed by default: `cli_flag` (only if
+    /// explicitly passed) > `TELOS_SIGN` env var > `[profile.<name>].sign` >
+    /// top-level `[sign]` > `false`.
+    pub fn resolve_sign(&self, profile: Option<&str>, cli_flag: Option<bool>) -> bool {
+        let profile = self.profile(profile);
+        cli_flag
+            .or_else(|| std::env::var("TELOS_SIGN").ok().map(|v| v == "1" || v == "true"))
+            .or_else(|| profile.and_then(|p| p.sign.default))
+            .or(self.sign.default)
+            .unwrap_or(false)
+    }
+
+    /// Resolve the OpenSSH key path to sign with, if any: `cli_path` >
+    /// `TELOS_SIGN_SSH_KEY` env var > `[profile.<name>].sign.ssh_key` >
+    /// top-level `[sign].ssh_key` > none (fall back to the repo's own key).
+    pub fn resolve_ssh_key(&self, profile: Option<&str>, cli_path: Option<&str>) -> Option<String> {
+        let profile = self.profile(profile);
+        cli_path
+            .map(str::to_string)
+            .or_else(|| std::env::var("TELOS_SIGN_SSH_KEY").ok())
+            .or_else(|| profile.and_then(|p| p.sign.ssh_key.clone()))
+            .or_else(|| self.sign.ssh_key.clone())
+    }
+
+    /// Whether capability-token authorization is required for
+    /// `intent`/`decide`/`supersede`/`deprecate`. Off by default, so
+    /// existing repos keep working unchanged until an administrator opts
+    /// in via `[auth] required = true`.
+    pub fn auth_required(&self) -> bool {
+        self.auth.required.unwrap_or(false)
+    }
+
+    /// Resolve the bearer token `telos serve`'s write routes require:
+    /// `cli_token` > `TELOS_SERVE_TOKEN` env var > top-level `[serve].token`
+    /// > none (no token configured disables write routes entirely, so a
+    /// server can never be started in a state that silently accepts
+    /// unauthenticated mutations).
+    pub fn resolve_serve_token(&self, cli_token: Option<&str>) -> Option<String> {
+        cli_token
+            .map(str::to_string)
+            .or_else(|| std::env::var("TELOS_SERVE_TOKEN").ok())
+            .or_else(|| self.serve.token.clone())
+    }
+}
+
+/// Resolve which profile is active: `--profile` flag > `TELOS_PROFILE` env
+/// var > none (top-level config only).
+pub fn resolve_profile(cli_profile: Option<&str>) -> Option<String> {
+    cli_profile
+        .map(str::to_string)
+        .or_else(|| std::env::var("TELOS_PROFILE").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_when_nothing_set() {
+        let config = TelosConfig::default();
+        let author = config.resolve_author(None, None, None);
+        assert_eq!(author.name, DEFAULT_AUTHOR_NAME);
+        assert_eq!(author.email, DEFAULT_AUTHOR_EMAIL);
+
+        let codex = config.resolve_codex(None);
+        assert_eq!(codex.binary, DEFAULT_CODEX_BINARY);
+        assert_eq!(codex.timeout_secs, DEFAULT_CODEX_TIMEOUT_SECS);
+        assert!(codex.args.is_empty());
+    }
+
+    #[test]
+    fn top_level_config_overrides_default() {
+        let mut config = TelosConfig::default();
+        config.author.name = Some("Team Default".into());
+        config.codex.timeout_secs = Some(60);
+
+        assert_eq!(config.resolve_author(None, None, None).name, "Team Default");
+        assert_eq!(config.resolve_codex(None).timeout_secs, 60);
+    }
+
+    #[test]
+    fn profile_overrides_top_level_config() {
+        let mut config = TelosConfig::default();
+        config.author.name = Some("Team Default".into());
+        config.profiles.insert(
+            "ci".into(),
+            ProfileConfig {
+                author: AuthorConfig {
+                    name: Some("CI Bot".into()),
+                    email: None,
+                },
+                codex: CodexConfig::default(),
+                sign: SignConfig::default(),
+            },
+        );
+
+        assert_eq!(config.resolve_author(Some("ci"), None, None).name, "CI Bot");
+        // unselected profile falls back to top-level
+        assert_eq!(config.resolve_author(None, None, None).name, "Team Default");
+        // unknown profile name falls back to top-level rather than erroring
+        assert_eq!(config.resolve_author(Some("nope"), None, None).name, "Team Default");
+    }
+
+    #[test]
+    fn cli_override_wins_over_everything() {
+        let mut config = TelosConfig::default();
+        config.author.name = Some("Team Default".into());
+        config.profiles.insert(
+            "ci".into(),
+            ProfileConfig {
+                author: AuthorConfig {
+                    name: Some("CI Bot".into()),
+                    email: None,
+                },
+                codex: CodexConfig::default(),
+                sign: SignConfig::default(),
+            },
+        );
+
+        assert_eq!(
+            config.resolve_author(Some("ci"), Some("Local Override"), None).name,
+            "Local Override"
+        );
+    }
+
+    #[test]
+    fn parses_config_toml() {
+        let toml = r#"
+            [author]
+            name = "Team Default"
+            email = "team@example.com"
+
+            [codex]
+            binary = "codex"
+            timeout_secs = 90
+            args = ["--no-color"]
+
+            [profile.ci]
+            codex.timeout_secs = 300
+        "#;
+        let config: TelosConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.author.name.as_deref(), Some("Team Default"));
+        assert_eq!(config.resolve_codex(None).timeout_secs, 90);
+        assert_eq!(config.resolve_codex(Some("ci")).timeout_secs, 300);
+        assert_eq!(config.resolve_codex(Some("ci")).args, vec!["--no-color"]);
+    }
+
+    #[test]
+    fn resolve_sign_defaults_to_false() {
+        let config = TelosConfig::default();
+        assert!(!config.resolve_sign(None, None));
+    }
+
+    #[test]
+    fn resolve_sign_precedence() {
+        let mut config = TelosConfig::default();
+        config.sign.default = Some(true);
+        assert!(config.resolve_sign(None, None));
+        assert!(!config.resolve_sign(None, Some(false)));
+
+        config.profiles.insert(
+            "ci".into(),
+            ProfileConfig {
+                author: AuthorConfig::default(),
+                codex: CodexConfig::default(),
+                sign: SignConfig {
+                    default: Some(false),
+                    ssh_key: None,
+                },
+            },
+        );
+        assert!(!config.resolve_sign(Some("ci"), None));
+        assert!(config.resolve_sign(Some("ci"), Some(true)));
+    }
+
+    #[test]
+    fn resolve_ssh_key_precedence() {
+        let mut config = TelosConfig::default();
+        config.sign.ssh_key = Some("/team/id_ed25519".into());
+        assert_eq!(
+            config.resolve_ssh_key(None, None).as_deref(),
+            Some("/team/id_ed25519")
+        );
+        assert_eq!(
+            config.resolve_ssh_key(None, Some("/local/id_ed25519")).as_deref(),
+            Some("/local/id_ed25519")
+        );
+    }
+
+    #[test]
+    fn auth_required_defaults_to_false() {
+        assert!(!TelosConfig::default().auth_required());
+    }
+
+    #[test]
+    fn auth_required_can_be_enabled() {
+        let mut config = TelosConfig::default();
+        config.auth.required = Some(true);
+        assert!(config.auth_required());
+    }
+
+    #[test]
+    fn resolve_serve_token_defaults_to_none() {
+        assert_eq!(TelosConfig::default().resolve_serve_token(None), None);
+    }
+
+    #[test]
+    fn resolve_serve_token_precedence() {
+        let mut config = TelosConfig::default();
+        config.serve.token = Some("config-token".into());
+        assert_eq!(config.resolve_serve_token(None).as_deref(), Some("config-token"));
+        assert_eq!(config.resolve_serve_token(Some("cli-token")).as_deref(), Some("cli-token"));
+    }
+}
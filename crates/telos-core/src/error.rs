@@ -10,4 +10,7 @@ pub enum CoreError {
 
     #[error("unknown object type tag: {0}")]
     UnknownTypeTag(String),
+
+    #[error("canonicalization error: {0}")]
+    Canonicalization(String),
 }
@@ -1,50 +1,293 @@
-use sha2::{Digest, Sha256};
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256, Sha512};
 use std::fmt;
 
-/// A SHA-256 content address, displayed and stored as 64 hex chars.
-#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+/// The hash function an [`ObjectId`] was (or should be) computed with.
+///
+/// Codes are the [multicodec](https://github.com/multiformats/multicodec)
+/// hash-function table entries Telos supports, so they round-trip through
+/// the multihash varint header unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashAlgo {
+    /// The default since Telos's first release; every pre-multihash id is
+    /// implicitly this.
+    Sha256,
+    Sha512,
+    /// Not cryptographically necessary for content-addressing, but
+    /// meaningfully faster on large objects than either SHA variant.
+    Blake3,
+}
+
+impl HashAlgo {
+    const CODE_SHA256: u64 = 0x12;
+    const CODE_SHA512: u64 = 0x13;
+    const CODE_BLAKE3: u64 = 0x1e;
+
+    fn code(self) -> u64 {
+        match self {
+            HashAlgo::Sha256 => Self::CODE_SHA256,
+            HashAlgo::Sha512 => Self::CODE_SHA512,
+            HashAlgo::Blake3 => Self::CODE_BLAKE3,
+        }
+    }
+
+    fn from_code(code: u64) -> Option<Self> {
+        match code {
+            Self::CODE_SHA256 => Some(HashAlgo::Sha256),
+            Self::CODE_SHA512 => Some(HashAlgo::Sha512),
+            Self::CODE_BLAKE3 => Some(HashAlgo::Blake3),
+            _ => None,
+        }
+    }
+
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgo::Sha256 => Sha256::digest(data).to_vec(),
+            HashAlgo::Sha512 => Sha512::digest(data).to_vec(),
+            HashAlgo::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+
+    /// The name used in `.telos/config.json`'s `hash_algo` field.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HashAlgo::Sha256 => "sha256",
+            HashAlgo::Sha512 => "sha512",
+            HashAlgo::Blake3 => "blake3",
+        }
+    }
+
+    /// Parse a `.telos/config.json` `hash_algo` value. Unknown names fall
+    /// back to `None` so callers can decide whether to error or default.
+    pub fn parse_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(HashAlgo::Sha256),
+            "sha512" => Some(HashAlgo::Sha512),
+            "blake3" => Some(HashAlgo::Blake3),
+            _ => None,
+        }
+    }
+}
+
+impl Default for HashAlgo {
+    fn default() -> Self {
+        HashAlgo::Sha256
+    }
+}
+
+/// Encode `value` as an unsigned LEB128 varint, per the multihash/multicodec
+/// spec (same encoding protobuf and dag-cbor use for varints).
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Decode an unsigned LEB128 varint from the front of `bytes`, returning
+/// the value and how many bytes it consumed.
+fn read_varint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    for (i, &byte) in bytes.iter().enumerate() {
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+    None
+}
+
+/// RFC 4648 base32 (lowercase, unpadded) — the alphabet behind the `b`
+/// multibase prefix, chosen (per the request) over base58 since it needs
+/// no big-integer division to encode/decode.
+const BASE32_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8 + 4) / 5);
+    let mut bits: u32 = 0;
+    let mut bit_count = 0u32;
+    for &byte in bytes {
+        bits = (bits << 8) | u32::from(byte);
+        bit_count += 8;
+        while bit_count >= 5 {
+            bit_count -= 5;
+            let idx = (bits >> bit_count) & 0x1f;
+            out.push(BASE32_ALPHABET[idx as usize] as char);
+        }
+    }
+    if bit_count > 0 {
+        let idx = (bits << (5 - bit_count)) & 0x1f;
+        out.push(BASE32_ALPHABET[idx as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 5 / 8);
+    let mut bits: u32 = 0;
+    let mut bit_count = 0u32;
+    for c in s.chars() {
+        let idx = BASE32_ALPHABET.iter().position(|&b| b as char == c)? as u32;
+        bits = (bits << 5) | idx;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// The multibase prefix for lowercase, unpadded base32 — the one encoding
+/// [`ObjectId`] uses, but kept as a named constant so `parse` and
+/// `hash_with` can't drift apart.
+const MULTIBASE_PREFIX: char = 'b';
+
+/// Decode a multihash-rendered string (after its multibase prefix has been
+/// stripped) into `(algo, digest bytes)`.
+fn decode_multihash(bytes: &[u8]) -> Option<(HashAlgo, Vec<u8>)> {
+    let (code, code_len) = read_varint(bytes)?;
+    let algo = HashAlgo::from_code(code)?;
+    let (len, len_len) = read_varint(&bytes[code_len..])?;
+    let start = code_len + len_len;
+    if bytes.len() != start + len as usize {
+        return None;
+    }
+    Some((algo, bytes[start..].to_vec()))
+}
+
+/// Decode an [`ObjectId`]'s canonical string form into `(algo, digest
+/// bytes)`, accepting both the multibase multihash encoding and the legacy
+/// bare 64-char hex SHA-256 digest.
+fn decode_repr(repr: &str) -> Option<(HashAlgo, Vec<u8>)> {
+    // `'b'` (the multibase prefix) is itself a valid hex digit, so a legacy
+    // 64-char-hex id that happens to start with it would otherwise get
+    // misrouted into the multibase branch and fail there instead of ever
+    // reaching the legacy-hex check below. Only take the multibase
+    // decoding if it actually succeeds; anything else falls through.
+    if let Some(rest) = repr.strip_prefix(MULTIBASE_PREFIX) {
+        if let Some(decoded) = base32_decode(rest).and_then(|bytes| decode_multihash(&bytes)) {
+            return Some(decoded);
+        }
+    }
+    if repr.len() == 64 && repr.chars().all(|c| c.is_ascii_hexdigit()) {
+        return hex::decode(repr).ok().map(|digest| (HashAlgo::Sha256, digest));
+    }
+    None
+}
+
+/// A self-describing content address.
+///
+/// Ids created with [`Self::hash_with`] are encoded as a
+/// [multihash](https://github.com/multiformats/multihash) —
+/// `varint(hash-code) || varint(digest-length) || digest` — rendered as a
+/// `b`-prefixed (multibase) lowercase base32 string, so the algorithm
+/// travels with the id instead of being hardcoded store-wide. Migrating to
+/// a new default algorithm (see [`HashAlgo`]) is then just a config change:
+/// old and new ids coexist and both `parse` and compare correctly.
+///
+/// Ids from before this format existed are a bare 64-char hex SHA-256
+/// digest with no multihash header; `parse` still accepts them, treating
+/// the algorithm as implicit SHA-256, so existing stores keep working
+/// unmigrated.
+///
+/// `short`/`fan_out`/`hex` all slice the canonical `repr` string directly
+/// (not the decoded digest) — exactly like the pre-multihash `ObjectId`
+/// did — so on-disk fan-out layout and pack indexing, which are keyed off
+/// these same slices, don't need to know or care whether a given id is
+/// multihash-encoded or legacy bare hex.
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct ObjectId(String);
 
 impl ObjectId {
-    /// Create an ObjectId by hashing raw bytes.
+    /// Create an ObjectId by hashing raw bytes with `algo`.
+    pub fn hash_with(algo: HashAlgo, data: &[u8]) -> Self {
+        let digest = algo.digest(data);
+        let mut multihash = Vec::with_capacity(digest.len() + 2);
+        write_varint(algo.code(), &mut multihash);
+        write_varint(digest.len() as u64, &mut multihash);
+        multihash.extend_from_slice(&digest);
+        Self(format!("{MULTIBASE_PREFIX}{}", base32_encode(&multihash)))
+    }
+
+    /// Create an ObjectId by hashing raw bytes with the default algorithm
+    /// ([`HashAlgo::Sha256`]). Most call sites that don't manage a
+    /// repo-configured algorithm (e.g. tests, one-off tooling) want this.
     pub fn hash(data: &[u8]) -> Self {
-        let digest = Sha256::digest(data);
-        Self(hex::encode(digest))
+        Self::hash_with(HashAlgo::default(), data)
     }
 
-    /// Parse a full 64-char hex string into an ObjectId.
-    pub fn parse(hex_str: &str) -> Result<Self, crate::error::CoreError> {
-        let hex_str = hex_str.trim();
-        if hex_str.len() != 64 || !hex_str.chars().all(|c| c.is_ascii_hexdigit()) {
-            return Err(crate::error::CoreError::InvalidObjectId(
-                hex_str.to_string(),
-            ));
-        }
-        Ok(Self(hex_str.to_lowercase()))
+    /// Parse a multihash-encoded id, or a legacy bare 64-char hex SHA-256
+    /// digest (treated as implicit SHA-256 for backward compatibility).
+    pub fn parse(s: &str) -> Result<Self, crate::error::CoreError> {
+        let trimmed = s.trim();
+        decode_repr(trimmed)
+            .ok_or_else(|| crate::error::CoreError::InvalidObjectId(trimmed.to_string()))?;
+        // `decode_repr` having succeeded already tells us `trimmed` is
+        // either a valid (necessarily lowercase — `base32_decode` is
+        // case-sensitive) multibase string or a legacy hex digest;
+        // lowercasing is a no-op for the former and canonicalizes the
+        // latter, so there's no need to special-case which branch matched.
+        Ok(Self(trimmed.to_lowercase()))
+    }
+
+    /// The algorithm this id was hashed with — [`HashAlgo::Sha256`] for
+    /// legacy bare-hex ids, which predate the multihash header.
+    pub fn algo(&self) -> HashAlgo {
+        decode_repr(&self.0).map(|(algo, _)| algo).unwrap_or_default()
     }
 
-    /// The full 64-char hex representation.
+    /// The full canonical representation: a `b`-prefixed multihash string
+    /// for ids made with [`Self::hash_with`], or the bare hex digest for
+    /// ids predating that format.
     pub fn hex(&self) -> &str {
         &self.0
     }
 
-    /// First 8 chars, used for display.
+    /// First 8 chars of the canonical representation, used for display.
     pub fn short(&self) -> &str {
         &self.0[..8]
     }
 
-    /// First 2 hex chars — used as fan-out directory name.
+    /// First 2 / remaining chars of the canonical representation — used as
+    /// a fan-out directory name.
     pub fn fan_out(&self) -> (&str, &str) {
-        (&self.0[..2], &self.0[2..])
+        self.0.split_at(2)
     }
 
-    /// Check if this ObjectId starts with the given prefix.
+    /// Check if this ObjectId's canonical representation starts with the
+    /// given prefix.
     pub fn starts_with(&self, prefix: &str) -> bool {
         self.0.starts_with(prefix)
     }
 }
 
+impl Serialize for ObjectId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        ObjectId::parse(&s).map_err(D::Error::custom)
+    }
+}
+
 impl fmt::Debug for ObjectId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ObjectId({})", self.short())
@@ -66,7 +309,6 @@ mod tests {
         let a = ObjectId::hash(b"hello world");
         let b = ObjectId::hash(b"hello world");
         assert_eq!(a, b);
-        assert_eq!(a.hex().len(), 64);
     }
 
     #[test]
@@ -77,10 +319,56 @@ mod tests {
     }
 
     #[test]
-    fn parse_valid() {
+    fn hash_defaults_to_sha256() {
         let id = ObjectId::hash(b"test");
+        assert_eq!(id.algo(), HashAlgo::Sha256);
+    }
+
+    #[test]
+    fn hash_with_different_algos_differs() {
+        let sha256 = ObjectId::hash_with(HashAlgo::Sha256, b"test");
+        let sha512 = ObjectId::hash_with(HashAlgo::Sha512, b"test");
+        let blake3 = ObjectId::hash_with(HashAlgo::Blake3, b"test");
+        assert_ne!(sha256, sha512);
+        assert_ne!(sha256, blake3);
+        assert_ne!(sha512, blake3);
+        assert_eq!(sha512.algo(), HashAlgo::Sha512);
+        assert_eq!(blake3.algo(), HashAlgo::Blake3);
+    }
+
+    #[test]
+    fn multihash_round_trips_through_parse() {
+        let id = ObjectId::hash_with(HashAlgo::Blake3, b"round trip me");
         let parsed = ObjectId::parse(id.hex()).unwrap();
         assert_eq!(id, parsed);
+        assert_eq!(parsed.algo(), HashAlgo::Blake3);
+    }
+
+    #[test]
+    fn legacy_hex_parses_as_implicit_sha256() {
+        let legacy = hex::encode(Sha256::digest(b"test"));
+        let parsed = ObjectId::parse(&legacy).unwrap();
+        assert_eq!(parsed.algo(), HashAlgo::Sha256);
+        assert_eq!(parsed.hex(), legacy);
+        // Fresh hashes always get the multihash-wrapped form, even for the
+        // default algorithm, so the two no longer print identically — only
+        // `parse` treats bare hex as an alternate, older spelling of the
+        // same algorithm.
+        assert_ne!(parsed.hex(), ObjectId::hash(b"test").hex());
+    }
+
+    #[test]
+    fn legacy_hex_starting_with_the_multibase_prefix_still_parses() {
+        // 'b' is both the multibase prefix and a valid hex digit, so a
+        // legacy id that happens to start with it used to get misrouted
+        // into the multibase branch, fail there, and never fall through to
+        // the legacy-hex check.
+        let legacy = hex::encode(Sha256::digest(b"16"));
+        assert!(legacy.starts_with('b'), "test fixture must start with 'b': {legacy}");
+
+        let parsed = ObjectId::parse(&legacy).unwrap();
+        assert_eq!(parsed.algo(), HashAlgo::Sha256);
+        assert_eq!(parsed.hex(), legacy);
     }
 
     #[test]
@@ -94,12 +382,16 @@ mod tests {
         assert!(ObjectId::parse(&bad).is_err());
     }
 
+    #[test]
+    fn parse_invalid_multibase_payload() {
+        assert!(ObjectId::parse("bnotvalidbase32???").is_err());
+    }
+
     #[test]
     fn fan_out_split() {
         let id = ObjectId::hash(b"test");
         let (dir, file) = id.fan_out();
         assert_eq!(dir.len(), 2);
-        assert_eq!(file.len(), 62);
         assert_eq!(format!("{}{}", dir, file), id.hex());
     }
 
@@ -110,4 +402,13 @@ mod tests {
         assert_eq!(display.len(), 8);
         assert_eq!(display, id.short());
     }
+
+    #[test]
+    fn serde_round_trip_is_transparent_string() {
+        let id = ObjectId::hash_with(HashAlgo::Blake3, b"serde");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"{}\"", id.hex()));
+        let back: ObjectId = serde_json::from_str(&json).unwrap();
+        assert_eq!(id, back);
+    }
 }
@@ -0,0 +1,5 @@
+pub mod config;
+pub mod error;
+pub mod hash;
+pub mod object;
+pub mod serialize;
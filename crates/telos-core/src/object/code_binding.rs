@@ -37,6 +37,13 @@ pub struct CodeBinding {
     pub resolution: BindingResolution,
     /// The Telos object this binding belongs to.
     pub bound_object: ObjectId,
+    /// Content fingerprint (hex SHA-256) of the bound target at bind time —
+    /// the file bytes for a `file` binding, or just the `span` lines when
+    /// one is known. `None` for bindings created before this field existed,
+    /// or when the target didn't exist yet at bind time. See
+    /// `telos_store::fingerprint` for how it's computed and compared.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
 }
@@ -2,6 +2,7 @@ use crate::hash::ObjectId;
 use crate::object::intent::Author;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// An alternative that was considered but not chosen.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -24,4 +25,6 @@ pub struct DecisionRecord {
     pub alternatives: Vec<Alternative>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
 }
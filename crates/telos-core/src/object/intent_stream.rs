@@ -38,3 +38,191 @@ pub struct StreamConflict {
     pub conflicting_intents: Vec<ObjectId>,
     pub description: String,
 }
+
+/// Logical timestamp for totally ordering concurrent [`StreamOp`]s across
+/// peers that edited a stream offline: ties on `counter` break on
+/// `author_email`, so any two peers replaying the same operation set agree
+/// on the same order without coordinating clocks.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LamportClock {
+    pub counter: u64,
+    pub author_email: String,
+}
+
+/// A single mutation to a stream, as recorded in a peer's local operation
+/// log. `depends_on` names the operations (by [`StreamOp::content_id`])
+/// this one was applied on top of; an operation is held pending during
+/// [`IntentStreamSnapshot::merge`] until all of its dependencies have
+/// already been applied.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StreamOp {
+    pub clock: LamportClock,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<ObjectId>,
+    pub kind: StreamOpKind,
+}
+
+/// The mutations a [`StreamOp`] can carry — everything [`IntentStreamSnapshot`]
+/// actually holds state for.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StreamOpKind {
+    SetTip(ObjectId),
+    SetDescription(Option<String>),
+    Rebase { parent_stream: Option<String> },
+}
+
+impl StreamOp {
+    /// Content id of this operation, used both as the value other operations
+    /// name in `depends_on` and as the dedup key while replaying.
+    pub fn content_id(&self) -> ObjectId {
+        let bytes = serde_json::to_vec(self).expect("StreamOp always serializes");
+        ObjectId::hash(&bytes)
+    }
+}
+
+impl IntentStreamSnapshot {
+    /// Bayou-style convergent merge: given the union of both peers'
+    /// operation logs for this stream, replay every causally-ready
+    /// operation in the total order `(lamport_counter, author_email)` and
+    /// return the resulting snapshot.
+    ///
+    /// There is no stored history to roll back to here — `IntentStreamSnapshot`
+    /// is a point-in-time pointer, not a log — so the "last common committed
+    /// prefix" is modeled as whichever of `self`/`other` is older (ties broken
+    /// by content id), and every operation in `ops` is then replayed on top of
+    /// it in canonical order. Because the order and the starting point are
+    /// both deterministic functions of `ops` and the two snapshots, any peer
+    /// that replays the same operation set reaches byte-identical canonical
+    /// bytes, regardless of which side called `merge`.
+    ///
+    /// Operations whose `depends_on` ids never appear in `ops` are left
+    /// pending (not applied) rather than erroring — they are presumed to
+    /// arrive in a later merge once their dependency is known.
+    pub fn merge(&self, other: &IntentStreamSnapshot, ops: &[StreamOp]) -> IntentStreamSnapshot {
+        let mut working = if self.created_at != other.created_at {
+            if self.created_at < other.created_at { self.clone() } else { other.clone() }
+        } else if self.tip.hex() <= other.tip.hex() {
+            self.clone()
+        } else {
+            other.clone()
+        };
+
+        let mut pending: Vec<&StreamOp> = ops.iter().collect();
+        let mut applied: std::collections::HashSet<ObjectId> = std::collections::HashSet::new();
+
+        loop {
+            let mut ready: Vec<&StreamOp> = pending
+                .iter()
+                .copied()
+                .filter(|op| op.depends_on.iter().all(|dep| applied.contains(dep)))
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            ready.sort_by(|a, b| a.clock.cmp(&b.clock));
+
+            let mut ready_ids = std::collections::HashSet::new();
+            for op in ready {
+                working.apply(op);
+                let id = op.content_id();
+                applied.insert(id.clone());
+                ready_ids.insert(id);
+            }
+            pending.retain(|op| !ready_ids.contains(&op.content_id()));
+        }
+
+        working
+    }
+
+    fn apply(&mut self, op: &StreamOp) {
+        match &op.kind {
+            StreamOpKind::SetTip(id) => self.tip = id.clone(),
+            StreamOpKind::SetDescription(desc) => self.description = desc.clone(),
+            StreamOpKind::Rebase { parent_stream } => self.parent_stream = parent_stream.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(name: &str, tip: &str, created_secs: i64) -> IntentStreamSnapshot {
+        IntentStreamSnapshot {
+            name: name.to_string(),
+            tip: ObjectId::hash(tip.as_bytes()),
+            created_at: DateTime::from_timestamp(created_secs, 0).unwrap(),
+            description: None,
+            parent_stream: None,
+        }
+    }
+
+    fn clock(counter: u64, author_email: &str) -> LamportClock {
+        LamportClock { counter, author_email: author_email.to_string() }
+    }
+
+    #[test]
+    fn replays_ops_in_lamport_order_regardless_of_arrival_order() {
+        let base = snapshot("main", "base", 0);
+        let tip_a = ObjectId::hash(b"tip-a");
+        let tip_b = ObjectId::hash(b"tip-b");
+
+        let op_a = StreamOp {
+            clock: clock(1, "alice@example.com"),
+            depends_on: vec![],
+            kind: StreamOpKind::SetTip(tip_a.clone()),
+        };
+        let op_b = StreamOp {
+            clock: clock(2, "bob@example.com"),
+            depends_on: vec![],
+            kind: StreamOpKind::SetTip(tip_b.clone()),
+        };
+
+        let forward = base.merge(&base, &[op_a.clone(), op_b.clone()]);
+        let reversed = base.merge(&base, &[op_b, op_a]);
+
+        assert_eq!(forward, reversed);
+        assert_eq!(forward.tip, tip_b);
+    }
+
+    #[test]
+    fn merge_is_symmetric_for_the_same_operation_set() {
+        let a = snapshot("main", "a-tip", 0);
+        let b = snapshot("main", "b-tip", 5);
+        let ops = vec![StreamOp {
+            clock: clock(1, "alice@example.com"),
+            depends_on: vec![],
+            kind: StreamOpKind::SetDescription(Some("merged".into())),
+        }];
+
+        let merged_from_a = a.merge(&b, &ops);
+        let merged_from_b = b.merge(&a, &ops);
+        assert_eq!(merged_from_a, merged_from_b);
+        assert_eq!(merged_from_a.description, Some("merged".to_string()));
+    }
+
+    #[test]
+    fn pending_op_is_held_until_its_dependency_is_present() {
+        let base = snapshot("main", "base", 0);
+        let root_tip = ObjectId::hash(b"root-tip");
+        let root_op = StreamOp {
+            clock: clock(1, "alice@example.com"),
+            depends_on: vec![],
+            kind: StreamOpKind::SetTip(root_tip.clone()),
+        };
+        let dependent_tip = ObjectId::hash(b"dependent-tip");
+        let dependent_op = StreamOp {
+            clock: clock(2, "alice@example.com"),
+            depends_on: vec![root_op.content_id()],
+            kind: StreamOpKind::SetTip(dependent_tip.clone()),
+        };
+
+        // Dependency missing: the dependent op stays pending, so the tip is unchanged.
+        let partial = base.merge(&base, &[dependent_op.clone()]);
+        assert_eq!(partial.tip, base.tip);
+
+        // Both present: root applies first, then the dependent op.
+        let full = base.merge(&base, &[dependent_op, root_op]);
+        assert_eq!(full.tip, dependent_tip);
+    }
+}
@@ -9,7 +9,7 @@ pub mod intent_stream;
 
 use crate::error::CoreError;
 use crate::hash::ObjectId;
-use crate::serialize::{canonical_serialize, content_hash};
+use crate::serialize::{canonical_serialize, canonical_serialize_with, content_hash_with, ContentFormat};
 use serde::{Deserialize, Serialize};
 
 pub use agent_operation::AgentOperation;
@@ -19,7 +19,7 @@ pub use code_binding::CodeBinding;
 pub use constraint::Constraint;
 pub use decision_record::DecisionRecord;
 pub use intent::Intent;
-pub use intent_stream::IntentStreamSnapshot;
+pub use intent_stream::{IntentStreamSnapshot, LamportClock, StreamOp, StreamOpKind};
 
 /// Type tags used in content-addressable hashing.
 const TAG_INTENT: &str = "intent";
@@ -68,17 +68,65 @@ impl TelosObject {
         }
     }
 
-    /// Compute the content-address (SHA-256) for this object.
+    /// The ObjectIds this object directly references (parents plus any
+    /// cross-links such as `DecisionRecord::intent_id` or
+    /// `CodeBinding::bound_object`). Used to walk the object graph for
+    /// purposes other than the intent-parent chain, e.g. repo-to-repo sync.
+    pub fn links(&self) -> Vec<ObjectId> {
+        match self {
+            Self::Intent(o) => {
+                let mut links = o.parents.clone();
+                links.extend(o.behavior_diff.clone());
+                links
+            }
+            Self::BehaviorDiff(_) => Vec::new(),
+            Self::IntentStreamSnapshot(_) => Vec::new(),
+            Self::DecisionRecord(o) => vec![o.intent_id.clone()],
+            Self::Constraint(o) => {
+                let mut links = vec![o.source_intent.clone()];
+                links.extend(o.superseded_by.clone());
+                links.extend(o.scope.iter().cloned());
+                links
+            }
+            Self::CodeBinding(o) => vec![o.bound_object.clone()],
+            Self::AgentOperation(o) => {
+                let mut links = o.context_refs.clone();
+                links.extend(o.parent_op.clone());
+                links
+            }
+            Self::ChangeSet(o) => {
+                let mut links = o.parents.clone();
+                links.extend(o.intents.iter().cloned());
+                links.extend(o.constraints.iter().cloned());
+                links.extend(o.decisions.iter().cloned());
+                links.extend(o.code_bindings.iter().cloned());
+                links.extend(o.agent_operations.iter().cloned());
+                links
+            }
+        }
+    }
+
+    /// Compute the content-address for this object using the default hash
+    /// algorithm ([`crate::hash::HashAlgo::Sha256`]). See
+    /// [`Self::content_id_with`] to dispatch on a repo-configured algorithm.
     pub fn content_id(&self) -> Result<ObjectId, CoreError> {
+        self.content_id_with(crate::hash::HashAlgo::default())
+    }
+
+    /// Compute the content-address for this object, hashing the same
+    /// `type_tag\0sorted_json` pre-image [`Self::content_id`] does — so the
+    /// type tag stays mixed into the pre-image and domain separation is
+    /// unaffected — but digesting it with `algo` instead of the default.
+    pub fn content_id_with(&self, algo: crate::hash::HashAlgo) -> Result<ObjectId, CoreError> {
         match self {
-            Self::Intent(o) => content_hash(TAG_INTENT, o),
-            Self::BehaviorDiff(o) => content_hash(TAG_BEHAVIOR_DIFF, o),
-            Self::IntentStreamSnapshot(o) => content_hash(TAG_STREAM_SNAPSHOT, o),
-            Self::DecisionRecord(o) => content_hash(TAG_DECISION_RECORD, o),
-            Self::Constraint(o) => content_hash(TAG_CONSTRAINT, o),
-            Self::CodeBinding(o) => content_hash(TAG_CODE_BINDING, o),
-            Self::AgentOperation(o) => content_hash(TAG_AGENT_OPERATION, o),
-            Self::ChangeSet(o) => content_hash(TAG_CHANGE_SET, o),
+            Self::Intent(o) => content_hash_with(algo, TAG_INTENT, o),
+            Self::BehaviorDiff(o) => content_hash_with(algo, TAG_BEHAVIOR_DIFF, o),
+            Self::IntentStreamSnapshot(o) => content_hash_with(algo, TAG_STREAM_SNAPSHOT, o),
+            Self::DecisionRecord(o) => content_hash_with(algo, TAG_DECISION_RECORD, o),
+            Self::Constraint(o) => content_hash_with(algo, TAG_CONSTRAINT, o),
+            Self::CodeBinding(o) => content_hash_with(algo, TAG_CODE_BINDING, o),
+            Self::AgentOperation(o) => content_hash_with(algo, TAG_AGENT_OPERATION, o),
+            Self::ChangeSet(o) => content_hash_with(algo, TAG_CHANGE_SET, o),
         }
     }
 
@@ -96,6 +144,24 @@ impl TelosObject {
         }
     }
 
+    /// Serialize to canonical bytes using `format` to choose the
+    /// canonicalization instead of always taking the legacy one
+    /// [`Self::canonical_bytes`] uses.
+    pub fn canonical_bytes_with(&self, format: ContentFormat) -> Result<Vec<u8>, CoreError> {
+        match self {
+            Self::Intent(o) => canonical_serialize_with(format, TAG_INTENT, o),
+            Self::BehaviorDiff(o) => canonical_serialize_with(format, TAG_BEHAVIOR_DIFF, o),
+            Self::IntentStreamSnapshot(o) => {
+                canonical_serialize_with(format, TAG_STREAM_SNAPSHOT, o)
+            }
+            Self::DecisionRecord(o) => canonical_serialize_with(format, TAG_DECISION_RECORD, o),
+            Self::Constraint(o) => canonical_serialize_with(format, TAG_CONSTRAINT, o),
+            Self::CodeBinding(o) => canonical_serialize_with(format, TAG_CODE_BINDING, o),
+            Self::AgentOperation(o) => canonical_serialize_with(format, TAG_AGENT_OPERATION, o),
+            Self::ChangeSet(o) => canonical_serialize_with(format, TAG_CHANGE_SET, o),
+        }
+    }
+
     /// Deserialize from canonical bytes (`type_tag\0json`).
     pub fn from_canonical_bytes(data: &[u8]) -> Result<Self, CoreError> {
         let null_pos = data
@@ -175,6 +241,16 @@ mod tests {
         assert_eq!(id1, id2);
     }
 
+    #[test]
+    fn canonical_bytes_with_dispatches_on_content_format_and_still_round_trips() {
+        let obj = TelosObject::Intent(sample_intent());
+        let legacy = obj.canonical_bytes_with(ContentFormat::Legacy).unwrap();
+        let jcs = obj.canonical_bytes_with(ContentFormat::Jcs).unwrap();
+        assert_eq!(legacy, obj.canonical_bytes().unwrap());
+        assert_ne!(legacy, jcs);
+        assert_eq!(TelosObject::from_canonical_bytes(&jcs).unwrap(), obj);
+    }
+
     #[test]
     fn type_tag_correct() {
         assert_eq!(
@@ -183,6 +259,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn links_cover_cross_references() {
+        let intent_id = ObjectId::hash(b"dummy");
+        let dr = DecisionRecord {
+            intent_id: intent_id.clone(),
+            author: Author {
+                name: "Bob".into(),
+                email: "bob@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            question: "Which auth method?".into(),
+            decision: "Use JWT".into(),
+            rationale: None,
+            alternatives: vec![],
+            tags: vec![],
+            metadata: HashMap::new(),
+        };
+        assert_eq!(TelosObject::DecisionRecord(dr).links(), vec![intent_id]);
+
+        let bound_id = ObjectId::hash(b"constraint1");
+        let cb = code_binding::CodeBinding {
+            path: "src/auth/mod.rs".into(),
+            symbol: None,
+            span: None,
+            binding_type: code_binding::BindingType::File,
+            resolution: code_binding::BindingResolution::Resolved,
+            bound_object: bound_id.clone(),
+            fingerprint: None,
+            metadata: HashMap::new(),
+        };
+        assert_eq!(TelosObject::CodeBinding(cb).links(), vec![bound_id]);
+    }
+
     #[test]
     fn round_trip_decision_record() {
         let dr = DecisionRecord {
@@ -200,6 +309,7 @@ mod tests {
                 rejection_reason: "Requires server state".into(),
             }],
             tags: vec!["auth".into()],
+            metadata: HashMap::new(),
         };
         let obj = TelosObject::DecisionRecord(dr.clone());
         let bytes = obj.canonical_bytes().unwrap();
@@ -248,6 +358,7 @@ mod tests {
             binding_type: code_binding::BindingType::Function,
             resolution: code_binding::BindingResolution::Resolved,
             bound_object: ObjectId::hash(b"constraint1"),
+            fingerprint: None,
             metadata: HashMap::new(),
         };
         let obj = TelosObject::CodeBinding(cb.clone());
@@ -1,7 +1,43 @@
 use crate::error::CoreError;
-use crate::hash::ObjectId;
+use crate::hash::{HashAlgo, ObjectId};
 use serde::Serialize;
 
+/// Which canonical-bytes encoding a repository's new writes use.
+///
+/// Both encodings produce valid JSON after the `type_tag\0` prefix, so
+/// [`crate::object::TelosObject::from_canonical_bytes`] reads either back
+/// without needing to know which one wrote it — this only decides what
+/// *new* writes hash and store. Changing it mid-repo-lifetime is safe for
+/// the same reason [`HashAlgo`] is: existing objects keep the bytes (and
+/// therefore the id) they were written with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ContentFormat {
+    /// [`canonical_serialize`]'s UTF-8 key sort — the format every Telos
+    /// repository has used since its first release.
+    #[default]
+    Legacy,
+    /// [`canonical_serialize_jcs`]'s RFC 8785 JSON Canonicalization, for
+    /// interop with other JCS-aware tooling.
+    Jcs,
+}
+
+impl ContentFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContentFormat::Legacy => "legacy",
+            ContentFormat::Jcs => "jcs",
+        }
+    }
+
+    pub fn parse_name(name: &str) -> Option<Self> {
+        match name {
+            "legacy" => Some(ContentFormat::Legacy),
+            "jcs" => Some(ContentFormat::Jcs),
+            _ => None,
+        }
+    }
+}
+
 /// Produce a canonical byte representation: `type_tag\0sorted_json`.
 ///
 /// The JSON keys are sorted to ensure deterministic output regardless
@@ -16,10 +52,37 @@ pub fn canonical_serialize(type_tag: &str, value: &impl Serialize) -> Result<Vec
     Ok(buf)
 }
 
-/// Compute the ObjectId for a typed, serializable value.
+/// Compute the ObjectId for a typed, serializable value using the default
+/// algorithm ([`HashAlgo::Sha256`]). Most callers that don't track a
+/// repo-configured algorithm want this; see [`content_hash_with`].
 pub fn content_hash(type_tag: &str, value: &impl Serialize) -> Result<ObjectId, CoreError> {
+    content_hash_with(HashAlgo::default(), type_tag, value)
+}
+
+/// Compute the ObjectId for a typed, serializable value, hashing the same
+/// `type_tag\0sorted_json` pre-image [`content_hash`] does but with a
+/// chosen [`HashAlgo`] — so a repo can migrate its default digest
+/// algorithm without changing how the pre-image (and therefore the type's
+/// domain separation) is built, only which function digests it.
+pub fn content_hash_with(
+    algo: HashAlgo,
+    type_tag: &str,
+    value: &impl Serialize,
+) -> Result<ObjectId, CoreError> {
     let bytes = canonical_serialize(type_tag, value)?;
-    Ok(ObjectId::hash(&bytes))
+    Ok(ObjectId::hash_with(algo, &bytes))
+}
+
+/// Produce canonical bytes in whichever [`ContentFormat`] `format` names.
+pub fn canonical_serialize_with(
+    format: ContentFormat,
+    type_tag: &str,
+    value: &impl Serialize,
+) -> Result<Vec<u8>, CoreError> {
+    match format {
+        ContentFormat::Legacy => canonical_serialize(type_tag, value),
+        ContentFormat::Jcs => canonical_serialize_jcs(type_tag, value),
+    }
 }
 
 /// Recursively sort all object keys in a JSON value.
@@ -41,6 +104,162 @@ fn sort_value(v: serde_json::Value) -> serde_json::Value {
     }
 }
 
+/// Produce canonical bytes using proper RFC 8785 JSON Canonicalization
+/// (JCS) instead of [`canonical_serialize`]'s UTF-8 key sort: `type_tag\0jcs_bytes`.
+///
+/// `canonical_serialize` routes keys through a `BTreeMap<String, _>`, which
+/// orders by Rust's UTF-8 byte comparison and leaves numbers formatted
+/// however `serde_json` prints them — not the same ordering or number
+/// format most JCS-aware interop tooling expects, so two systems can hash
+/// the "same" value to different ids. This function implements JCS
+/// properly: object members are sorted by property name using UTF-16
+/// code-unit order (not UTF-8 byte order — these differ above U+FFFF),
+/// numbers are printed per the ECMAScript `Number::toString` shortest
+/// round-trip algorithm, and strings use `serde_json`'s own minimal
+/// escaping (which already only escapes `"`, `\`, and control characters,
+/// never `/`, matching JCS).
+///
+/// This is a distinct, additively-introduced hash format: changing what
+/// `canonical_serialize`/`content_hash` produce for existing objects would
+/// change every `ObjectId` in every repository that's ever used this
+/// crate, with no way to distinguish old from new on read. Callers that
+/// want JCS-compatible hashes opt in explicitly by calling this function
+/// (or [`content_hash_jcs`]) instead, or by selecting
+/// [`ContentFormat::Jcs`] through [`canonical_serialize_with`] (which
+/// `TelosObject::canonical_bytes_with` uses).
+pub fn canonical_serialize_jcs(type_tag: &str, value: &impl Serialize) -> Result<Vec<u8>, CoreError> {
+    let json_value = serde_json::to_value(value)?;
+    let mut jcs = String::new();
+    write_jcs(&json_value, &mut jcs)?;
+    let mut buf = Vec::with_capacity(type_tag.len() + 1 + jcs.len());
+    buf.extend_from_slice(type_tag.as_bytes());
+    buf.push(0);
+    buf.extend_from_slice(jcs.as_bytes());
+    Ok(buf)
+}
+
+/// JCS-hashed equivalent of [`content_hash`]. See [`canonical_serialize_jcs`].
+pub fn content_hash_jcs(type_tag: &str, value: &impl Serialize) -> Result<ObjectId, CoreError> {
+    let bytes = canonical_serialize_jcs(type_tag, value)?;
+    Ok(ObjectId::hash(&bytes))
+}
+
+/// Recursively write `v` in JCS form into `out`.
+fn write_jcs(v: &serde_json::Value, out: &mut String) -> Result<(), CoreError> {
+    match v {
+        serde_json::Value::Null => out.push_str("null"),
+        serde_json::Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        serde_json::Value::Number(n) => out.push_str(&jcs_number(n)?),
+        serde_json::Value::String(s) => out.push_str(&serde_json::to_string(s)?),
+        serde_json::Value::Array(arr) => {
+            out.push('[');
+            for (i, item) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_jcs(item, out)?;
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(map) => {
+            out.push('{');
+            // RFC 8785 sec 3.2.3: object members sorted by UTF-16 code-unit
+            // order, not UTF-8 byte order (they diverge above U+FFFF, where
+            // a UTF-16 surrogate pair's high surrogate 0xD800-0xDBFF sorts
+            // below BMP characters in 0xE000-0xFFFF despite encoding a
+            // higher code point in UTF-8).
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort_by(|a, b| a.encode_utf16().cmp(b.encode_utf16()));
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::to_string(key)?);
+                out.push(':');
+                write_jcs(map.get(key.as_str()).expect("key came from this map"), out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Format a JSON number per the ECMAScript `Number::toString` algorithm
+/// (ECMA-262 7.1.12.1), as JCS section 3.2.2.3 requires.
+fn jcs_number(n: &serde_json::Number) -> Result<String, CoreError> {
+    // Integers that fit losslessly are printed exactly; this also avoids
+    // precision loss for large i64/u64 values that `as_f64` would incur.
+    if let Some(i) = n.as_i64() {
+        return Ok(i.to_string());
+    }
+    if let Some(u) = n.as_u64() {
+        return Ok(u.to_string());
+    }
+    let f = n
+        .as_f64()
+        .ok_or_else(|| CoreError::Canonicalization(format!("number '{}' has no f64 representation", n)))?;
+    if !f.is_finite() {
+        return Err(CoreError::Canonicalization(
+            "JCS cannot encode NaN or Infinity".into(),
+        ));
+    }
+    Ok(es_number_to_string(f))
+}
+
+/// ECMAScript `Number::toString` for finite, non-integer-fast-path floats.
+///
+/// Rust's `{:e}` formatting already produces the shortest decimal digit
+/// string that round-trips back to `f` (same guarantee `Number::toString`
+/// requires) — it's just in `d.ddd​e±N` scientific form. We re-derive `s`
+/// (the significant digits) and `n` (s × 10^(n−k) = f, per the spec's own
+/// variable names) from that, then apply the spec's notation rules, which
+/// pick fixed vs. exponential notation based on the exponent's magnitude.
+fn es_number_to_string(f: f64) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    let negative = f.is_sign_negative();
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exp_str) = sci.split_once('e').expect("`{:e}` always contains 'e'");
+    let exp: i64 = exp_str.parse().expect("exponent is always a valid integer");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i64;
+    let n = exp + 1;
+
+    let mut s = String::new();
+    if negative {
+        s.push('-');
+    }
+
+    if (1..=21).contains(&n) {
+        if k <= n {
+            s.push_str(&digits);
+            s.push_str(&"0".repeat((n - k) as usize));
+        } else {
+            s.push_str(&digits[..n as usize]);
+            s.push('.');
+            s.push_str(&digits[n as usize..]);
+        }
+    } else if n > -6 && n <= 0 {
+        s.push_str("0.");
+        s.push_str(&"0".repeat((-n) as usize));
+        s.push_str(&digits);
+    } else {
+        s.push_str(&digits[..1]);
+        if k > 1 {
+            s.push('.');
+            s.push_str(&digits[1..]);
+        }
+        s.push('e');
+        let e = n - 1;
+        if e >= 0 {
+            s.push('+');
+        }
+        s.push_str(&e.to_string());
+    }
+    s
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +330,90 @@ mod tests {
             alpha: 42,
         };
         let h = content_hash("sample", &s).unwrap();
-        assert_eq!(h.hex().len(), 64);
+        assert_eq!(h.algo(), crate::hash::HashAlgo::Sha256);
+    }
+
+    #[test]
+    fn jcs_sorts_by_utf16_code_unit_not_utf8_byte_order() {
+        let bytes = canonical_serialize_jcs(
+            "test",
+            &serde_json::json!({
+                "b": 2,
+                "a": 1,
+                "\u{00e9}": "accented",
+                "1": "one",
+                "\u{fb01}": "ligature",
+                "\u{1f600}": "astral"
+            }),
+        )
+        .unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        let json_part = &text["test\0".len()..];
+        // U+1F600 is the surrogate pair D83D DE00 in UTF-16, so it sorts
+        // before U+FB01; in UTF-8 bytes (F0.. vs EF..) the order is reversed.
+        assert_eq!(
+            json_part,
+            "{\"1\":\"one\",\"a\":1,\"b\":2,\"\u{00e9}\":\"accented\",\"\u{1f600}\":\"astral\",\"\u{fb01}\":\"ligature\"}"
+        );
+    }
+
+    #[test]
+    fn jcs_number_formatting_matches_ecmascript_number_to_string() {
+        let cases: &[(f64, &str)] = &[
+            (1e300, "1e+300"),
+            (1e-300, "1e-300"),
+            (0.1, "0.1"),
+            (123.456, "123.456"),
+            (1e21, "1e+21"),
+            (1e20, "100000000000000000000"),
+            (1e-6, "0.000001"),
+            (1e-7, "1e-7"),
+            (100.0, "100"),
+            (-0.0, "0"),
+            (1234567890123456.0, "1234567890123456"),
+            (-123.456, "-123.456"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(&es_number_to_string(*input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn jcs_integers_stay_exact_for_large_values() {
+        let bytes = canonical_serialize_jcs("test", &serde_json::json!({"n": u64::MAX})).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.ends_with(&format!("{{\"n\":{}}}", u64::MAX)));
+    }
+
+    #[test]
+    fn canonical_serialize_with_dispatches_on_content_format() {
+        let s = Sample {
+            zebra: "z".into(),
+            alpha: 1,
+        };
+        let legacy = canonical_serialize_with(ContentFormat::Legacy, "sample", &s).unwrap();
+        let jcs = canonical_serialize_with(ContentFormat::Jcs, "sample", &s).unwrap();
+        assert_eq!(legacy, canonical_serialize("sample", &s).unwrap());
+        assert_eq!(jcs, canonical_serialize_jcs("sample", &s).unwrap());
+        assert_ne!(legacy, jcs);
+    }
+
+    #[test]
+    fn content_format_name_round_trips() {
+        for format in [ContentFormat::Legacy, ContentFormat::Jcs] {
+            assert_eq!(ContentFormat::parse_name(format.as_str()), Some(format));
+        }
+        assert_eq!(ContentFormat::parse_name("bogus"), None);
+    }
+
+    #[test]
+    fn jcs_and_legacy_canonicalization_produce_different_hashes() {
+        let s = Sample {
+            zebra: "z".into(),
+            alpha: 1,
+        };
+        let legacy = content_hash("sample", &s).unwrap();
+        let jcs = content_hash_jcs("sample", &s).unwrap();
+        assert_ne!(legacy, jcs);
     }
 }
@@ -1,11 +1,23 @@
 use anyhow::Result;
-use std::process::Command;
-use std::time::Instant;
+use metrics::{counter, histogram};
+use std::io::BufRead;
+use std::io::BufReader;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Grace period after SIGTERM before escalating to SIGKILL.
+const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 #[allow(dead_code)]
 pub struct CodexRunner {
     pub binary: String,
     pub timeout_secs: u64,
+    /// Extra arguments appended after the built-in `-q --prompt <prompt>`,
+    /// e.g. from `.telos/config.toml`'s `[codex].args`.
+    pub extra_args: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -14,6 +26,9 @@ pub struct CodexResponse {
     pub output: String,
     pub exit_code: i32,
     pub duration_ms: u64,
+    /// Set when `timeout_secs` elapsed before the child exited on its own;
+    /// `output` is then whatever stdout had been captured up to that point.
+    pub timed_out: bool,
 }
 
 impl Default for CodexRunner {
@@ -21,37 +36,145 @@ impl Default for CodexRunner {
         Self {
             binary: "codex".into(),
             timeout_secs: 120,
+            extra_args: Vec::new(),
         }
     }
 }
 
 impl CodexRunner {
+    /// Build a runner from a resolved `.telos/config.toml` `[codex]`
+    /// section instead of the hardcoded `Default` values, so a repository
+    /// (or profile) can point at a different binary, timeout, or pass
+    /// extra CLI args.
+    pub fn from_config(config: &telos_core::config::ResolvedCodex) -> Self {
+        Self {
+            binary: config.binary.clone(),
+            timeout_secs: config.timeout_secs,
+            extra_args: config.args.clone(),
+        }
+    }
+
     pub fn run(&self, prompt: &str) -> Result<CodexResponse> {
+        self.run_streaming(prompt, |_line| {})
+    }
+
+    /// Run `prompt` through the codex agent, invoking `on_line` with each
+    /// line of stdout as the child emits it so long-running intent
+    /// generation can surface progress.
+    ///
+    /// Honors `timeout_secs`: if the deadline elapses before the child
+    /// exits, it is sent SIGTERM, given a short grace period, then SIGKILL,
+    /// and whatever output was captured so far is returned with `timed_out`
+    /// set rather than erroring.
+    #[tracing::instrument(skip(self, on_line), fields(prompt.len = prompt.len()))]
+    pub fn run_streaming(&self, prompt: &str, mut on_line: impl FnMut(&str)) -> Result<CodexResponse> {
         let start = Instant::now();
+        let deadline = Duration::from_secs(self.timeout_secs);
 
-        let output = Command::new(&self.binary)
+        let mut child = Command::new(&self.binary)
             .args(["-q", "--prompt", prompt])
-            .output()?;
+            .args(&self.extra_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+
+        // Reader threads so a full stderr pipe can't block stdout (or us)
+        // while the child is still writing, and vice versa.
+        let (stdout_tx, stdout_rx) = mpsc::channel::<String>();
+        let stdout_thread = thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(std::result::Result::ok) {
+                if stdout_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let (stderr_tx, stderr_rx) = mpsc::channel::<String>();
+        let stderr_thread = thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(std::result::Result::ok) {
+                if stderr_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut stdout_lines = Vec::new();
+        let mut stderr_lines = Vec::new();
+        let mut timed_out = false;
+
+        let exit_status = loop {
+            for line in stdout_rx.try_iter() {
+                on_line(&line);
+                stdout_lines.push(line);
+            }
+            stderr_lines.extend(stderr_rx.try_iter());
+
+            match child.try_wait()? {
+                Some(status) => break status,
+                None => {
+                    if start.elapsed() >= deadline {
+                        timed_out = true;
+                        Self::terminate(&mut child);
+                        break child.wait()?;
+                    }
+                    thread::sleep(POLL_INTERVAL);
+                }
+            }
+        };
+
+        // Drain anything the reader threads buffered after the child exited.
+        for line in stdout_rx.try_iter() {
+            on_line(&line);
+            stdout_lines.push(line);
+        }
+        stderr_lines.extend(stderr_rx.try_iter());
+        let _ = stdout_thread.join();
+        let _ = stderr_thread.join();
 
         let duration_ms = start.elapsed().as_millis() as u64;
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let exit_code = exit_status.code().unwrap_or(-1);
+        histogram!("telos.codex_runner.duration_ms").record(duration_ms as f64);
 
-        if !output.status.success() {
+        if !timed_out && !exit_status.success() {
+            counter!("telos.codex_runner.runs", "outcome" => "failure", "exit_code" => exit_code.to_string())
+                .increment(1);
             anyhow::bail!(
                 "codex exited with {}: {}",
-                output.status.code().unwrap_or(-1),
-                stderr
+                exit_code,
+                stderr_lines.join("\n")
             );
         }
 
+        counter!("telos.codex_runner.runs", "outcome" => if timed_out { "timed_out" } else { "success" }, "exit_code" => exit_code.to_string())
+            .increment(1);
+
         Ok(CodexResponse {
-            output: stdout,
-            exit_code: output.status.code().unwrap_or(0),
+            output: stdout_lines.join("\n"),
+            exit_code,
             duration_ms,
+            timed_out,
         })
     }
 
+    /// Send SIGTERM, wait out a brief grace period for the child to exit on
+    /// its own, then SIGKILL if it's still alive.
+    fn terminate(child: &mut Child) {
+        unsafe {
+            libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+        }
+        let grace_deadline = Instant::now() + TERMINATE_GRACE;
+        while Instant::now() < grace_deadline {
+            if matches!(child.try_wait(), Ok(Some(_))) {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        let _ = child.kill(); // SIGKILL
+    }
+
     /// Check if the codex binary is available.
     pub fn is_available(&self) -> bool {
         Command::new(&self.binary)
@@ -0,0 +1,80 @@
+//! Columnar (Apache Arrow / Parquet) export of trial results for analytics.
+//!
+//! `telos-experiment run` accumulates `TrialResult`s in JSON result files.
+//! This flattens them into an Arrow `RecordBatch` and writes Parquet so the
+//! results can be loaded into DataFrame or SQL tooling alongside the
+//! operations export from `telos-store`.
+
+use crate::runner::TrialResult;
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, StringArray, UInt64Array, UInt8Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+fn trial_results_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("scenario_name", DataType::Utf8, false),
+        Field::new("condition", DataType::Utf8, false),
+        Field::new("trial_number", DataType::UInt64, false),
+        Field::new("duration_ms", DataType::UInt64, false),
+        Field::new("caught_issue", DataType::Boolean, true),
+        Field::new("recommended_rejection", DataType::Boolean, true),
+        Field::new("cited_constraint", DataType::Boolean, true),
+        Field::new("reasoning_quality", DataType::UInt8, true),
+        Field::new("agreement_kappa", DataType::Float64, true),
+    ])
+}
+
+fn trial_results_to_batch(results: &[TrialResult]) -> anyhow::Result<RecordBatch> {
+    let scenario_names: StringArray = results.iter().map(|r| Some(r.scenario_name.as_str())).collect();
+    let conditions: StringArray = results.iter().map(|r| Some(r.condition.as_str())).collect();
+    let trial_numbers: UInt64Array = results.iter().map(|r| Some(r.trial_number as u64)).collect();
+    let durations: UInt64Array = results.iter().map(|r| Some(r.duration_ms)).collect();
+    let caught_issue: BooleanArray = results.iter().map(|r| r.score.as_ref().map(|s| s.caught_issue)).collect();
+    let recommended_rejection: BooleanArray = results
+        .iter()
+        .map(|r| r.score.as_ref().map(|s| s.recommended_rejection))
+        .collect();
+    let cited_constraint: BooleanArray = results
+        .iter()
+        .map(|r| r.score.as_ref().map(|s| s.cited_constraint))
+        .collect();
+    let reasoning_quality: UInt8Array = results
+        .iter()
+        .map(|r| r.score.as_ref().map(|s| s.reasoning_quality))
+        .collect();
+    let agreement_kappa: Float64Array = results.iter().map(|r| r.agreement_kappa).collect();
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(scenario_names),
+        Arc::new(conditions),
+        Arc::new(trial_numbers),
+        Arc::new(durations),
+        Arc::new(caught_issue),
+        Arc::new(recommended_rejection),
+        Arc::new(cited_constraint),
+        Arc::new(reasoning_quality),
+        Arc::new(agreement_kappa),
+    ];
+
+    Ok(RecordBatch::try_new(Arc::new(trial_results_schema()), columns)?)
+}
+
+/// Write `results` to a Parquet file at `path`, one row group per call
+/// since result sets are already bounded in memory by `telos-experiment run`.
+pub fn write_trial_results_parquet(results: &[TrialResult], path: &Path) -> anyhow::Result<usize> {
+    let schema = Arc::new(trial_results_schema());
+    let file = File::create(path)?;
+    let props = WriterProperties::builder().build();
+    let mut writer = ArrowWriter::try_new(file, schema, Some(props))?;
+
+    let batch = trial_results_to_batch(results)?;
+    writer.write(&batch)?;
+    writer.close()?;
+
+    Ok(results.len())
+}
@@ -0,0 +1,8 @@
+//! Library surface for the experiment framework.
+//!
+//! The `telos-experiment` binary owns its own module tree for running
+//! scenarios and scoring results; `codex` is additionally exposed here so
+//! other crates (e.g. the verification queue in `telos-store`) can drive
+//! the same `CodexRunner` without depending on the binary.
+
+pub mod codex;
@@ -1,8 +1,10 @@
 mod codex;
+mod export;
 mod report;
 mod runner;
 mod scenario;
 mod scorer;
+mod workload;
 
 use clap::{Parser, Subcommand};
 use scenario::ScenarioFile;
@@ -30,13 +32,32 @@ enum Commands {
         #[arg(long)]
         scenario: Option<String>,
 
-        /// Conditions to test (repeatable: git_only, constraints_md, telos)
+        /// Conditions to test (repeatable; defaults to every condition the
+        /// scenario defines under `[context]`)
         #[arg(long)]
         condition: Vec<String>,
 
+        /// Number of independent judge passes per trial, reduced by
+        /// majority vote / median (reports Fleiss' kappa agreement)
+        #[arg(long, default_value = "1")]
+        judge_runs: usize,
+
         /// Directory containing scenario TOML files
         #[arg(long, default_value = "crates/telos-experiment/scenarios")]
         scenarios_dir: PathBuf,
+
+        /// Write this run's report as a single JSON document to this path
+        #[arg(long)]
+        json_report: Option<PathBuf>,
+
+        /// Append this run's report as one JSONL line to this history file
+        #[arg(long)]
+        jsonl_history: Option<PathBuf>,
+
+        /// Upload this run's report as JSON to a results server (env:
+        /// TELOS_RESULTS_TOKEN for the bearer token)
+        #[arg(long)]
+        upload_endpoint: Option<String>,
     },
 
     /// List available scenarios
@@ -56,9 +77,45 @@ enum Commands {
         #[arg(long, default_value = ".telos-experiment/results/latest.json")]
         results: PathBuf,
     },
+
+    /// Run a workload file: batches many scenarios into one parameterized
+    /// run, with a concurrency limit across scenario/condition/trial cells
+    Workload {
+        /// Path to the workload TOML file
+        path: PathBuf,
+
+        /// Write this run's report as a single JSON document to this path
+        #[arg(long)]
+        json_report: Option<PathBuf>,
+
+        /// Append this run's report as one JSONL line to this history file
+        #[arg(long)]
+        jsonl_history: Option<PathBuf>,
+
+        /// Upload this run's report as JSON to a results server (env:
+        /// TELOS_RESULTS_TOKEN for the bearer token)
+        #[arg(long)]
+        upload_endpoint: Option<String>,
+    },
+
+    /// Export trial results to a columnar format for analytics
+    Export {
+        /// Output format (currently only "parquet")
+        #[arg(long, default_value = "parquet")]
+        format: String,
+
+        /// Results file path
+        #[arg(long, default_value = ".telos-experiment/results/latest.json")]
+        results: PathBuf,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: PathBuf,
+    },
 }
 
 fn main() {
+    let _telemetry = telos_telemetry::init_from_env("telos-experiment");
     let cli = Cli::parse();
 
     let result = match cli.command {
@@ -66,10 +123,34 @@ fn main() {
             repeats,
             scenario: scenario_filter,
             condition,
+            judge_runs,
+            scenarios_dir,
+            json_report,
+            jsonl_history,
+            upload_endpoint,
+        } => run_experiments(
+            repeats,
+            scenario_filter,
+            condition,
+            judge_runs,
             scenarios_dir,
-        } => run_experiments(repeats, scenario_filter, condition, scenarios_dir),
+            json_report,
+            jsonl_history,
+            upload_endpoint,
+        ),
         Commands::List { scenarios_dir } => list_scenarios(scenarios_dir),
         Commands::Report { json, results } => show_report(json, results),
+        Commands::Workload {
+            path,
+            json_report,
+            jsonl_history,
+            upload_endpoint,
+        } => run_workload(path, json_report, jsonl_history, upload_endpoint),
+        Commands::Export {
+            format,
+            results,
+            output,
+        } => export_results(format, results, output),
     };
 
     if let Err(e) = result {
@@ -78,11 +159,16 @@ fn main() {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_experiments(
     repeats: usize,
     scenario_filter: Option<String>,
     conditions: Vec<String>,
+    judge_runs: usize,
     scenarios_dir: PathBuf,
+    json_report: Option<PathBuf>,
+    jsonl_history: Option<PathBuf>,
+    upload_endpoint: Option<String>,
 ) -> anyhow::Result<()> {
     let codex_runner = codex::CodexRunner::default();
     if !codex_runner.is_available() {
@@ -96,26 +182,30 @@ fn run_experiments(
         anyhow::bail!("No scenarios found in {}", scenarios_dir.display());
     }
 
-    let active_conditions: Vec<&str> = if conditions.is_empty() {
-        runner::CONDITIONS.to_vec()
-    } else {
-        conditions.iter().map(|s| s.as_str()).collect()
-    };
-
     eprintln!(
-        "Running {} scenarios x {} conditions x {} repeats",
+        "Running {} scenarios x {} repeats",
         scenarios.len(),
-        active_conditions.len(),
         repeats
     );
 
-    let runner = runner::ExperimentRunner::new(repeats);
+    let runner = runner::ExperimentRunner::new(repeats, judge_runs);
     let mut all_results = Vec::new();
 
     for scenario in &scenarios {
+        // Conditions are data-driven: each scenario declares its own
+        // `[context]` entries, so with no `--condition` filter we run
+        // every condition that scenario defines rather than a fixed trio.
+        let active_conditions: Vec<String> = if conditions.is_empty() {
+            scenario.context.condition_names()
+        } else {
+            conditions.clone()
+        };
+
         eprintln!(
-            "\nScenario: {} ({})",
-            scenario.scenario.name, scenario.scenario.category
+            "\nScenario: {} ({}) — conditions: {}",
+            scenario.scenario.name,
+            scenario.scenario.category,
+            active_conditions.join(", ")
         );
         let results = runner.run_scenario(scenario, &active_conditions)?;
         all_results.extend(results);
@@ -138,9 +228,104 @@ fn run_experiments(
     let reports = report::aggregate(&all_results, "all");
     report::print_table(&reports);
 
+    let run = report::RunReport {
+        header: report::RunHeader {
+            git_commit: current_git_commit(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            model: codex_runner.binary.clone(),
+            conditions: if conditions.is_empty() {
+                scenarios
+                    .iter()
+                    .flat_map(|s| s.context.condition_names())
+                    .collect()
+            } else {
+                conditions
+            },
+        },
+        scenarios: reports,
+    };
+    export_run_report(&run, json_report, jsonl_history, upload_endpoint)?;
+
+    Ok(())
+}
+
+/// Write/append/upload `run` per whichever of the three export flags were
+/// passed; a no-op if none were, shared by `run` and `workload`.
+fn export_run_report(
+    run: &report::RunReport,
+    json_report: Option<PathBuf>,
+    jsonl_history: Option<PathBuf>,
+    upload_endpoint: Option<String>,
+) -> anyhow::Result<()> {
+    if let Some(path) = &json_report {
+        report::write_json(run, path)?;
+        eprintln!("Wrote JSON report to {}", path.display());
+    }
+    if let Some(path) = &jsonl_history {
+        report::append_jsonl(run, path)?;
+        eprintln!("Appended run to {}", path.display());
+    }
+    if let Some(endpoint) = &upload_endpoint {
+        let token = std::env::var("TELOS_RESULTS_TOKEN").ok();
+        report::upload(run, endpoint, token.as_deref())?;
+        eprintln!("Uploaded run report to {}", endpoint);
+    }
+    Ok(())
+}
+
+fn run_workload(
+    path: PathBuf,
+    json_report: Option<PathBuf>,
+    jsonl_history: Option<PathBuf>,
+    upload_endpoint: Option<String>,
+) -> anyhow::Result<()> {
+    let workload = workload::WorkloadFile::load(&path)?;
+    eprintln!(
+        "Running workload '{}' (concurrency: {})",
+        workload.workload.name, workload.concurrency
+    );
+
+    let results = workload.run(&path)?;
+
+    let results_dir = PathBuf::from(".telos-experiment/results");
+    std::fs::create_dir_all(&results_dir)?;
+    let results_json = serde_json::to_string_pretty(&results)?;
+    let latest_path = results_dir.join("latest.json");
+    std::fs::write(&latest_path, &results_json)?;
+    eprintln!("Results saved to {}", latest_path.display());
+
+    let reports = report::aggregate(&results, &workload.workload.name);
+    report::print_table(&reports);
+
+    let run = report::RunReport {
+        header: report::RunHeader {
+            git_commit: current_git_commit(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            model: codex::CodexRunner::default().binary,
+            conditions: workload.conditions.clone(),
+        },
+        scenarios: reports,
+    };
+    export_run_report(&run, json_report, jsonl_history, upload_endpoint)?;
+
     Ok(())
 }
 
+/// Best-effort resolution of the current git commit, so a JSON/JSONL report
+/// can be tracked against the tree it was run from even if this isn't a git
+/// checkout (e.g. an extracted archive) — in which case this returns `None`
+/// rather than failing the run.
+fn current_git_commit() -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok().map(|s| s.trim().to_string())
+}
+
 fn list_scenarios(scenarios_dir: PathBuf) -> anyhow::Result<()> {
     let scenarios = load_scenarios(&scenarios_dir, None)?;
     println!(
@@ -172,6 +357,19 @@ fn show_report(json: bool, results_path: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn export_results(format: String, results_path: PathBuf, output: PathBuf) -> anyhow::Result<()> {
+    if format != "parquet" {
+        anyhow::bail!("unsupported export format '{}' (expected: parquet)", format);
+    }
+
+    let data = std::fs::read_to_string(&results_path)?;
+    let results: Vec<runner::TrialResult> = serde_json::from_str(&data)?;
+
+    let rows = export::write_trial_results_parquet(&results, &output)?;
+    eprintln!("Wrote {} trial rows to {}", rows, output.display());
+    Ok(())
+}
+
 fn load_scenarios(dir: &PathBuf, filter: Option<&str>) -> anyhow::Result<Vec<ScenarioFile>> {
     let mut scenarios = Vec::new();
     if !dir.exists() {
@@ -1,6 +1,117 @@
 use crate::runner::TrialResult;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+/// z-score for a 95% confidence interval / significance threshold.
+const Z_95: f64 = 1.96;
+
+/// A rate (x successes out of n trials) together with its Wilson 95%
+/// confidence interval, so a gap between conditions can be judged against
+/// its noise rather than read as a bare percentage.
+#[derive(Debug, Serialize)]
+pub struct RateWithCi {
+    pub x: usize,
+    pub n: usize,
+    pub rate: f64,
+    pub ci95: [f64; 2],
+}
+
+impl RateWithCi {
+    fn new(x: usize, n: usize) -> Self {
+        if n == 0 {
+            return Self {
+                x,
+                n,
+                rate: 0.0,
+                ci95: [0.0, 0.0],
+            };
+        }
+        let n_f = n as f64;
+        let phat = x as f64 / n_f;
+        let z2 = Z_95 * Z_95;
+        let center = (phat + z2 / (2.0 * n_f)) / (1.0 + z2 / n_f);
+        let half_width = (Z_95 / (1.0 + z2 / n_f))
+            * (phat * (1.0 - phat) / n_f + z2 / (4.0 * n_f * n_f)).sqrt();
+        Self {
+            x,
+            n,
+            rate: phat,
+            ci95: [(center - half_width).max(0.0), (center + half_width).min(1.0)],
+        }
+    }
+}
+
+/// Two-proportion z-test comparing `a` against `b` on the same metric.
+/// Returns `None` when either side has zero trials (undefined).
+pub fn two_proportion_z(a: &RateWithCi, b: &RateWithCi) -> Option<f64> {
+    if a.n == 0 || b.n == 0 {
+        return None;
+    }
+    let n1 = a.n as f64;
+    let n2 = b.n as f64;
+    let pooled = (a.x + b.x) as f64 / (n1 + n2);
+    let denom = (pooled * (1.0 - pooled) * (1.0 / n1 + 1.0 / n2)).sqrt();
+    if denom == 0.0 {
+        return None;
+    }
+    Some((a.rate - b.rate) / denom)
+}
+
+/// Identifies one experiment run so results can be tracked and charted
+/// across commits, mirroring a workload-benchmark flow where each run's
+/// metrics land in a central dashboard.
+#[derive(Debug, Serialize)]
+pub struct RunHeader {
+    /// Git commit the experiment was run against, if resolvable.
+    pub git_commit: Option<String>,
+    /// RFC 3339 timestamp of when the run was recorded.
+    pub timestamp: String,
+    /// Identifier for the model/agent under test (the codex binary name).
+    pub model: String,
+    pub conditions: Vec<String>,
+}
+
+/// One full run's report, ready to be serialized to JSON/JSONL or uploaded.
+#[derive(Debug, Serialize)]
+pub struct RunReport {
+    pub header: RunHeader,
+    pub scenarios: Vec<ScenarioReport>,
+}
+
+/// Write `run` as a single pretty-printed JSON document at `path`,
+/// overwriting any existing file.
+pub fn write_json(run: &RunReport, path: &Path) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(run)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// Append `run` as one compact JSON line to the JSONL history file at
+/// `path`, creating it if it doesn't exist yet, so each run adds a row to
+/// the history without disturbing prior ones.
+pub fn append_jsonl(run: &RunReport, path: &Path) -> anyhow::Result<()> {
+    let line = serde_json::to_string(run)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+/// POST `run` as JSON to `endpoint`, with an optional bearer token, for an
+/// opt-in upload to a central results dashboard.
+pub fn upload(run: &RunReport, endpoint: &str, token: Option<&str>) -> anyhow::Result<()> {
+    let client = reqwest::blocking::Client::new();
+    let mut builder = client.post(endpoint).json(run);
+    if let Some(token) = token {
+        builder = builder.bearer_auth(token);
+    }
+    builder.send()?.error_for_status()?;
+    Ok(())
+}
 
 #[derive(Debug, Serialize)]
 pub struct ScenarioReport {
@@ -12,11 +123,15 @@ pub struct ScenarioReport {
 #[derive(Debug, Serialize)]
 pub struct ConditionStats {
     pub trials: usize,
-    pub caught_issue_rate: f64,
-    pub rejection_rate: f64,
-    pub cited_constraint_rate: f64,
+    pub caught_issue: RateWithCi,
+    pub rejection: RateWithCi,
+    pub cited_constraint: RateWithCi,
     pub avg_reasoning_quality: f64,
     pub avg_duration_ms: f64,
+    /// Mean Fleiss' kappa across trials that ran a judge ensemble, so a
+    /// scenario/condition with flaky judging is visible and can be
+    /// re-run or flagged. `None` when no trial reported agreement.
+    pub avg_agreement_kappa: Option<f64>,
 }
 
 pub fn aggregate(results: &[TrialResult], category: &str) -> Vec<ScenarioReport> {
@@ -46,42 +161,47 @@ pub fn aggregate(results: &[TrialResult], category: &str) -> Vec<ScenarioReport>
                 .iter()
                 .filter_map(|t| t.score.as_ref())
                 .collect();
-            let s = scored.len() as f64;
+            let s = scored.len();
+            let s_f = s as f64;
+
+            let kappas: Vec<f64> = cond_trials
+                .iter()
+                .filter_map(|t| t.agreement_kappa)
+                .collect();
+            let avg_agreement_kappa = if kappas.is_empty() {
+                None
+            } else {
+                Some(kappas.iter().sum::<f64>() / kappas.len() as f64)
+            };
 
             conditions.insert(
                 cond,
                 ConditionStats {
                     trials: n,
-                    caught_issue_rate: if s > 0.0 {
-                        scored.iter().filter(|sc| sc.caught_issue).count() as f64 / s
-                    } else {
-                        0.0
-                    },
-                    rejection_rate: if s > 0.0 {
-                        scored
-                            .iter()
-                            .filter(|sc| sc.recommended_rejection)
-                            .count() as f64
-                            / s
-                    } else {
-                        0.0
-                    },
-                    cited_constraint_rate: if s > 0.0 {
-                        scored.iter().filter(|sc| sc.cited_constraint).count() as f64 / s
-                    } else {
-                        0.0
-                    },
-                    avg_reasoning_quality: if s > 0.0 {
+                    caught_issue: RateWithCi::new(
+                        scored.iter().filter(|sc| sc.caught_issue).count(),
+                        s,
+                    ),
+                    rejection: RateWithCi::new(
+                        scored.iter().filter(|sc| sc.recommended_rejection).count(),
+                        s,
+                    ),
+                    cited_constraint: RateWithCi::new(
+                        scored.iter().filter(|sc| sc.cited_constraint).count(),
+                        s,
+                    ),
+                    avg_reasoning_quality: if s_f > 0.0 {
                         scored
                             .iter()
                             .map(|sc| sc.reasoning_quality as f64)
                             .sum::<f64>()
-                            / s
+                            / s_f
                     } else {
                         0.0
                     },
                     avg_duration_ms: cond_trials.iter().map(|t| t.duration_ms as f64).sum::<f64>()
                         / n as f64,
+                    avg_agreement_kappa,
                 },
             );
         }
@@ -102,41 +222,81 @@ pub fn print_table(reports: &[ScenarioReport]) {
             "\n=== {} ({}) ===",
             report.scenario_name, report.category
         );
-        println!(
-            "{:<20} {:>10} {:>10} {:>10}",
-            "Metric", "Git-only", "CONST.md", "Telos"
-        );
 
-        let git = report.conditions.get("git_only");
-        let cmd = report.conditions.get("constraints_md");
-        let telos = report.conditions.get("telos");
+        // Conditions are data-driven per scenario, so the column set is
+        // whatever that scenario actually ran, not a fixed trio.
+        let mut condition_names: Vec<&String> = report.conditions.keys().collect();
+        condition_names.sort();
 
-        let fmt = |stats: Option<&ConditionStats>, f: fn(&ConditionStats) -> f64| -> String {
+        print!("{:<20}", "Metric");
+        for name in &condition_names {
+            print!(" {:>22}", name);
+        }
+        println!();
+
+        let fmt = |stats: Option<&ConditionStats>, f: fn(&ConditionStats) -> &RateWithCi| -> String {
             stats
-                .map(|s| format!("{:.0}%", f(s) * 100.0))
+                .map(|s| {
+                    let r = f(s);
+                    format!(
+                        "{:>5.0}% [{:.0},{:.0}]",
+                        r.rate * 100.0,
+                        r.ci95[0] * 100.0,
+                        r.ci95[1] * 100.0
+                    )
+                })
                 .unwrap_or_else(|| "—".into())
         };
 
-        println!(
-            "{:<20} {:>10} {:>10} {:>10}",
-            "Caught issue",
-            fmt(git, |s| s.caught_issue_rate),
-            fmt(cmd, |s| s.caught_issue_rate),
-            fmt(telos, |s| s.caught_issue_rate)
-        );
-        println!(
-            "{:<20} {:>10} {:>10} {:>10}",
-            "Rejected",
-            fmt(git, |s| s.rejection_rate),
-            fmt(cmd, |s| s.rejection_rate),
-            fmt(telos, |s| s.rejection_rate)
-        );
-        println!(
-            "{:<20} {:>10} {:>10} {:>10}",
-            "Cited constraint",
-            fmt(git, |s| s.cited_constraint_rate),
-            fmt(cmd, |s| s.cited_constraint_rate),
-            fmt(telos, |s| s.cited_constraint_rate)
-        );
+        let print_row = |label: &str, f: fn(&ConditionStats) -> &RateWithCi| {
+            print!("{:<20}", label);
+            for name in &condition_names {
+                print!(" {:>22}", fmt(report.conditions.get(*name), f));
+            }
+            println!();
+        };
+
+        print_row("Caught issue", |s| &s.caught_issue);
+        print_row("Rejected", |s| &s.rejection);
+        print_row("Cited constraint", |s| &s.cited_constraint);
+
+        print!("{:<20}", "Agreement (kappa)");
+        for name in &condition_names {
+            let cell = report
+                .conditions
+                .get(*name)
+                .and_then(|s| s.avg_agreement_kappa)
+                .map(|k| format!("{:.2}", k))
+                .unwrap_or_else(|| "—".into());
+            print!(" {:>22}", cell);
+        }
+        println!();
+
+        // If this scenario ran the classic two-condition comparison,
+        // call out where "telos" is a statistically significant
+        // improvement over the "git_only" baseline (z > 1.96, p < .05).
+        if let (Some(telos), Some(git_only)) = (
+            report.conditions.get("telos"),
+            report.conditions.get("git_only"),
+        ) {
+            let metrics: [(&str, fn(&ConditionStats) -> &RateWithCi); 3] = [
+                ("Caught issue", |s| &s.caught_issue),
+                ("Rejected", |s| &s.rejection),
+                ("Cited constraint", |s| &s.cited_constraint),
+            ];
+            let mut significant = Vec::new();
+            for (label, f) in metrics {
+                if let Some(z) = two_proportion_z(f(telos), f(git_only)) {
+                    if z > Z_95 {
+                        significant.push(format!("{} (z={:.2})", label, z));
+                    }
+                }
+            }
+            if significant.is_empty() {
+                println!("Telos vs git_only: no statistically significant improvement (p < .05)");
+            } else {
+                println!("Telos vs git_only, significant improvement: {}", significant.join(", "));
+            }
+        }
     }
 }
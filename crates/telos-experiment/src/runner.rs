@@ -3,8 +3,6 @@ use crate::scenario::ScenarioFile;
 use crate::scorer::{JudgeScorer, Score};
 use serde::{Deserialize, Serialize};
 
-pub const CONDITIONS: [&str; 3] = ["git_only", "constraints_md", "telos"];
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrialResult {
     pub scenario_name: String,
@@ -12,6 +10,9 @@ pub struct TrialResult {
     pub trial_number: usize,
     pub llm_response: String,
     pub score: Option<Score>,
+    /// Fleiss' kappa from the judge ensemble that produced `score`, if
+    /// `judge_runs > 1`. `None` when scoring failed or only one judge ran.
+    pub agreement_kappa: Option<f64>,
     pub duration_ms: u64,
 }
 
@@ -19,50 +20,73 @@ pub struct ExperimentRunner {
     codex: CodexRunner,
     scorer: JudgeScorer,
     pub repeats: usize,
+    /// Number of independent judge passes per trial, reduced by majority
+    /// vote / median. 1 disables the ensemble (a single judge call).
+    pub judge_runs: usize,
 }
 
 impl ExperimentRunner {
-    pub fn new(repeats: usize) -> Self {
+    pub fn new(repeats: usize, judge_runs: usize) -> Self {
         let codex = CodexRunner::default();
         let scorer = JudgeScorer::new(CodexRunner::default());
         Self {
             codex,
             scorer,
             repeats,
+            judge_runs,
         }
     }
 
     pub fn run_scenario(
         &self,
         scenario: &ScenarioFile,
-        conditions: &[&str],
+        conditions: &[String],
     ) -> anyhow::Result<Vec<TrialResult>> {
         let mut results = Vec::new();
 
-        for &condition in conditions {
-            let prompt = scenario.render_prompt(condition);
-
+        for condition in conditions {
             for trial in 1..=self.repeats {
                 eprintln!(
                     "  [{}/{}] {} / {} ...",
                     trial, self.repeats, scenario.scenario.name, condition
                 );
-
-                let response = self.codex.run(&prompt)?;
-
-                let score = self.scorer.score(scenario, &response.output).ok();
-
-                results.push(TrialResult {
-                    scenario_name: scenario.scenario.name.clone(),
-                    condition: condition.into(),
-                    trial_number: trial,
-                    llm_response: response.output,
-                    score,
-                    duration_ms: response.duration_ms,
-                });
+                results.push(self.run_trial(scenario, condition, trial)?);
             }
         }
 
         Ok(results)
     }
+
+    /// Run a single (scenario, condition, trial) cell: render the prompt,
+    /// invoke codex once, and score the response. Factored out of
+    /// [`Self::run_scenario`] so callers that parallelize across cells (e.g.
+    /// [`crate::workload::WorkloadFile::run`]) can drive one cell per task.
+    pub fn run_trial(
+        &self,
+        scenario: &ScenarioFile,
+        condition: &str,
+        trial_number: usize,
+    ) -> anyhow::Result<TrialResult> {
+        let prompt = scenario.render_prompt(condition)?;
+        let response = self.codex.run(&prompt)?;
+
+        let ensemble = self
+            .scorer
+            .score_ensemble(scenario, &response.output, self.judge_runs)
+            .ok();
+        let score = ensemble.as_ref().map(|e| e.score.clone());
+        let agreement_kappa = ensemble
+            .filter(|_| self.judge_runs > 1)
+            .map(|e| e.agreement_kappa);
+
+        Ok(TrialResult {
+            scenario_name: scenario.scenario.name.clone(),
+            condition: condition.to_string(),
+            trial_number,
+            llm_response: response.output,
+            score,
+            agreement_kappa,
+            duration_ms: response.duration_ms,
+        })
+    }
 }
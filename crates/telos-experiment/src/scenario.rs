@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,11 +24,26 @@ pub struct DiffConfig {
     pub commit_message: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ContextConfig {
-    pub git_only: String,
-    pub constraints_md: String,
-    pub telos_json: String,
+/// Condition name -> rendered context text for that condition, e.g.
+/// `git_only`, `constraints_md`, `telos`, or any other name a scenario
+/// wants to test. Arbitrary keys, so scenarios aren't limited to the
+/// original three hardcoded conditions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(transparent)]
+pub struct ContextConfig(pub HashMap<String, String>);
+
+impl ContextConfig {
+    pub fn get(&self, condition: &str) -> Option<&str> {
+        self.0.get(condition).map(String::as_str)
+    }
+
+    /// Condition names this scenario defines context for, sorted for
+    /// deterministic iteration order.
+    pub fn condition_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.0.keys().cloned().collect();
+        names.sort();
+        names
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,18 +65,28 @@ impl ScenarioFile {
     }
 
     /// Render the prompt template with the given condition's context.
-    pub fn render_prompt(&self, condition: &str) -> String {
-        let context = match condition {
-            "git_only" => &self.context.git_only,
-            "constraints_md" => &self.context.constraints_md,
-            "telos" => &self.context.telos_json,
-            _ => "",
+    /// `"none"` always renders with empty context (the no-context control);
+    /// any other condition must be defined in `[context]` or this errors,
+    /// so a typo'd `--condition` fails loudly instead of silently running
+    /// with blank context.
+    pub fn render_prompt(&self, condition: &str) -> anyhow::Result<String> {
+        let context = if condition == "none" {
+            ""
+        } else {
+            self.context.get(condition).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "scenario '{}' has no context defined for condition '{}'",
+                    self.scenario.name,
+                    condition
+                )
+            })?
         };
-        self.prompt
+        Ok(self
+            .prompt
             .template
             .replace("{{commit_message}}", &self.diff.commit_message)
             .replace("{{diff}}", &self.diff.content)
-            .replace("{{context}}", context)
+            .replace("{{context}}", context))
     }
 }
 
@@ -84,7 +110,7 @@ commit_message = "Update thing"
 [context]
 git_only = "git log output"
 constraints_md = "- Must do X"
-telos_json = '{"constraints": []}'
+telos = '{"constraints": []}'
 
 [prompt]
 template = "Review: {{commit_message}}\n{{diff}}\n{{context}}"
@@ -116,11 +142,11 @@ key_findings = ["finding1"]
                 content: "- old\n+ new".into(),
                 commit_message: "fix stuff".into(),
             },
-            context: ContextConfig {
-                git_only: "GIT CONTEXT".into(),
-                constraints_md: "MD CONTEXT".into(),
-                telos_json: "TELOS CONTEXT".into(),
-            },
+            context: ContextConfig(HashMap::from([
+                ("git_only".to_string(), "GIT CONTEXT".to_string()),
+                ("constraints_md".to_string(), "MD CONTEXT".to_string()),
+                ("telos".to_string(), "TELOS CONTEXT".to_string()),
+            ])),
             prompt: PromptConfig {
                 template: "Msg: {{commit_message}}\nDiff: {{diff}}\nCtx: {{context}}".into(),
             },
@@ -130,7 +156,7 @@ key_findings = ["finding1"]
             },
         };
 
-        let git_prompt = scenario.render_prompt("git_only");
+        let git_prompt = scenario.render_prompt("git_only").unwrap();
         assert!(git_prompt.contains("GIT CONTEXT"));
         assert!(git_prompt.contains("fix stuff"));
         assert!(git_prompt.contains("- old\n+ new"));
@@ -138,12 +164,63 @@ key_findings = ["finding1"]
         assert!(!git_prompt.contains("{{diff}}"));
         assert!(!git_prompt.contains("{{context}}"));
 
-        let md_prompt = scenario.render_prompt("constraints_md");
+        let md_prompt = scenario.render_prompt("constraints_md").unwrap();
         assert!(md_prompt.contains("MD CONTEXT"));
         assert!(md_prompt.contains("fix stuff"));
 
-        let telos_prompt = scenario.render_prompt("telos");
+        let telos_prompt = scenario.render_prompt("telos").unwrap();
         assert!(telos_prompt.contains("TELOS CONTEXT"));
         assert!(telos_prompt.contains("fix stuff"));
     }
+
+    #[test]
+    fn render_prompt_none_condition_is_always_empty() {
+        let scenario = ScenarioFile {
+            scenario: ScenarioMeta {
+                name: "test".into(),
+                category: "true_positive".into(),
+                description: "desc".into(),
+            },
+            diff: DiffConfig {
+                content: "- old\n+ new".into(),
+                commit_message: "fix stuff".into(),
+            },
+            context: ContextConfig::default(),
+            prompt: PromptConfig {
+                template: "Ctx: [{{context}}]".into(),
+            },
+            expected: ExpectedConfig {
+                should_reject: true,
+                key_findings: vec![],
+            },
+        };
+
+        let prompt = scenario.render_prompt("none").unwrap();
+        assert_eq!(prompt, "Ctx: []");
+    }
+
+    #[test]
+    fn render_prompt_unknown_condition_errors() {
+        let scenario = ScenarioFile {
+            scenario: ScenarioMeta {
+                name: "test".into(),
+                category: "true_positive".into(),
+                description: "desc".into(),
+            },
+            diff: DiffConfig {
+                content: String::new(),
+                commit_message: String::new(),
+            },
+            context: ContextConfig::default(),
+            prompt: PromptConfig {
+                template: "{{context}}".into(),
+            },
+            expected: ExpectedConfig {
+                should_reject: false,
+                key_findings: vec![],
+            },
+        };
+
+        assert!(scenario.render_prompt("typo_condition").is_err());
+    }
 }
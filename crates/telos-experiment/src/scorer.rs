@@ -68,4 +68,118 @@ Score as JSON (no other text):
         let score: Score = serde_json::from_str(json_str)?;
         Ok(score)
     }
+
+    /// Run the judge `raters` times and reduce the individual `Score`s into
+    /// one: majority vote on each boolean, median on `reasoning_quality`.
+    /// Also reports Fleiss' kappa over the three boolean votes so a flaky,
+    /// low-agreement scenario is visible rather than hidden behind a single
+    /// noisy pass.
+    pub fn score_ensemble(
+        &self,
+        scenario: &ScenarioFile,
+        llm_response: &str,
+        raters: usize,
+    ) -> anyhow::Result<EnsembleScore> {
+        let raters = raters.max(1);
+        let scores: Vec<Score> = (0..raters)
+            .map(|_| self.score(scenario, llm_response))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let explanations: Vec<&str> = scores.iter().map(|s| s.judge_explanation.as_str()).collect();
+        let score = Score {
+            caught_issue: majority_vote(scores.iter().map(|s| s.caught_issue)),
+            recommended_rejection: majority_vote(scores.iter().map(|s| s.recommended_rejection)),
+            cited_constraint: majority_vote(scores.iter().map(|s| s.cited_constraint)),
+            reasoning_quality: median_u8(scores.iter().map(|s| s.reasoning_quality)),
+            judge_explanation: explanations.join(" | "),
+        };
+
+        let agreement_kappa = fleiss_kappa(&[
+            binary_counts(scores.iter().map(|s| s.caught_issue)),
+            binary_counts(scores.iter().map(|s| s.recommended_rejection)),
+            binary_counts(scores.iter().map(|s| s.cited_constraint)),
+        ]);
+
+        Ok(EnsembleScore {
+            score,
+            raters,
+            agreement_kappa,
+        })
+    }
+}
+
+/// Aggregated result of running the judge multiple times over one response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleScore {
+    pub score: Score,
+    pub raters: usize,
+    /// Fleiss' kappa over the boolean votes, 1.0 meaning perfect agreement.
+    pub agreement_kappa: f64,
+}
+
+fn majority_vote(votes: impl Iterator<Item = bool>) -> bool {
+    let votes: Vec<bool> = votes.collect();
+    let true_count = votes.iter().filter(|v| **v).count();
+    true_count * 2 >= votes.len()
+}
+
+fn median_u8(values: impl Iterator<Item = u8>) -> u8 {
+    let mut values: Vec<u8> = values.collect();
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        ((values[mid - 1] as u16 + values[mid] as u16 + 1) / 2) as u8
+    }
+}
+
+/// `[true_count, false_count]` for one item's votes, for Fleiss' kappa.
+fn binary_counts(votes: impl Iterator<Item = bool>) -> [usize; 2] {
+    let mut counts = [0usize; 2];
+    for v in votes {
+        counts[if v { 0 } else { 1 }] += 1;
+    }
+    counts
+}
+
+/// Fleiss' kappa over a fixed set of items (here: the three boolean
+/// judge-score categories), each rated by the same `n` raters into the same
+/// two categories (true/false).
+fn fleiss_kappa(items: &[[usize; 2]]) -> f64 {
+    let n = items[0].iter().sum::<usize>();
+    if n < 2 {
+        return 1.0;
+    }
+    let num_items = items.len() as f64;
+    let denom_per_item = (n * (n - 1)) as f64;
+
+    let p_bar = items
+        .iter()
+        .map(|counts| {
+            let sum_sq: usize = counts.iter().map(|c| c * c).sum();
+            (sum_sq as f64 - n as f64) / denom_per_item
+        })
+        .sum::<f64>()
+        / num_items;
+
+    let total_ratings = num_items * n as f64;
+    let mut category_totals = [0usize; 2];
+    for counts in items {
+        category_totals[0] += counts[0];
+        category_totals[1] += counts[1];
+    }
+    let p_e_bar: f64 = category_totals
+        .iter()
+        .map(|&total| {
+            let p_j = total as f64 / total_ratings;
+            p_j * p_j
+        })
+        .sum();
+
+    if (1.0 - p_e_bar).abs() < f64::EPSILON {
+        1.0
+    } else {
+        (p_bar - p_e_bar) / (1.0 - p_e_bar)
+    }
 }
@@ -0,0 +1,185 @@
+//! Workload files batch many scenarios into a single parameterized run, so
+//! a large eval suite doesn't need one `telos-experiment run --scenario`
+//! invocation per scenario. A workload names a set of scenario paths (or
+//! directories, scanned for `*.toml`), which conditions to run, how many
+//! trials per condition, and judge settings, then drives the whole matrix
+//! with up to `concurrency` cells in flight at once.
+
+use crate::runner::{ExperimentRunner, TrialResult};
+use crate::scenario::ScenarioFile;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadFile {
+    pub workload: WorkloadMeta,
+
+    /// Scenario file paths or directories (every `*.toml` inside is
+    /// included), resolved relative to this workload file's own directory.
+    pub scenarios: Vec<String>,
+
+    /// Conditions to run; empty means every condition each scenario
+    /// defines under `[context]`.
+    #[serde(default)]
+    pub conditions: Vec<String>,
+
+    #[serde(default = "default_repeats")]
+    pub repeats: usize,
+
+    #[serde(default)]
+    pub judge: JudgeSettings,
+
+    /// Maximum number of (scenario, condition, trial) cells run at once.
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadMeta {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JudgeSettings {
+    /// Number of independent judge passes per trial (ensemble size); 1
+    /// disables the ensemble.
+    #[serde(default = "default_judge_runs")]
+    pub runs: usize,
+}
+
+impl Default for JudgeSettings {
+    fn default() -> Self {
+        Self {
+            runs: default_judge_runs(),
+        }
+    }
+}
+
+fn default_repeats() -> usize {
+    5
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn default_judge_runs() -> usize {
+    1
+}
+
+/// One (scenario, condition, trial) unit of work.
+struct Cell<'a> {
+    scenario: &'a ScenarioFile,
+    condition: String,
+    trial_number: usize,
+}
+
+impl WorkloadFile {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Resolve `scenarios` into loaded `ScenarioFile`s, relative to
+    /// `workload_path`'s directory.
+    pub fn load_scenarios(&self, workload_path: &Path) -> anyhow::Result<Vec<ScenarioFile>> {
+        let base = workload_path.parent().unwrap_or_else(|| Path::new("."));
+        let mut paths: Vec<PathBuf> = Vec::new();
+
+        for entry in &self.scenarios {
+            let resolved = base.join(entry);
+            if resolved.is_dir() {
+                for file in std::fs::read_dir(&resolved)? {
+                    let path = file?.path();
+                    if path.extension().map(|e| e == "toml").unwrap_or(false) {
+                        paths.push(path);
+                    }
+                }
+            } else {
+                paths.push(resolved);
+            }
+        }
+        paths.sort();
+        paths.dedup();
+
+        paths.iter().map(|p| ScenarioFile::load(p)).collect()
+    }
+
+    /// Run the full scenario x condition x trial matrix, with up to
+    /// `concurrency` cells running at once, and return every `TrialResult`
+    /// (in a stable scenario/condition/trial order, regardless of which
+    /// worker finished first).
+    pub fn run(&self, workload_path: &Path) -> anyhow::Result<Vec<TrialResult>> {
+        let scenarios = self.load_scenarios(workload_path)?;
+        if scenarios.is_empty() {
+            anyhow::bail!(
+                "workload '{}' matched no scenario files",
+                self.workload.name
+            );
+        }
+
+        let mut cells = Vec::new();
+        for scenario in &scenarios {
+            let conditions = if self.conditions.is_empty() {
+                scenario.context.condition_names()
+            } else {
+                self.conditions.clone()
+            };
+            for condition in conditions {
+                for trial in 1..=self.repeats {
+                    cells.push(Cell {
+                        scenario,
+                        condition: condition.clone(),
+                        trial_number: trial,
+                    });
+                }
+            }
+        }
+        let total = cells.len();
+
+        let runner = ExperimentRunner::new(self.repeats, self.judge.runs);
+        let queue = Mutex::new(cells.into_iter().enumerate().collect::<Vec<_>>());
+        let results = Mutex::new(Vec::with_capacity(total));
+        let worker_count = self.concurrency.max(1);
+
+        std::thread::scope(|scope| -> anyhow::Result<()> {
+            let mut handles = Vec::new();
+            for _ in 0..worker_count {
+                let queue = &queue;
+                let results = &results;
+                let runner = &runner;
+                handles.push(scope.spawn(move || -> anyhow::Result<()> {
+                    loop {
+                        let next = queue.lock().unwrap().pop();
+                        let Some((index, cell)) = next else {
+                            break;
+                        };
+                        eprintln!(
+                            "  [{}/{}] {} / {} (trial {}) ...",
+                            index + 1,
+                            total,
+                            cell.scenario.scenario.name,
+                            cell.condition,
+                            cell.trial_number
+                        );
+                        let result =
+                            runner.run_trial(cell.scenario, &cell.condition, cell.trial_number)?;
+                        results.lock().unwrap().push((index, result));
+                    }
+                    Ok(())
+                }));
+            }
+            for handle in handles {
+                handle.join().expect("workload worker thread panicked")?;
+            }
+            Ok(())
+        })?;
+
+        let mut indexed = results.into_inner().unwrap();
+        indexed.sort_by_key(|(index, _)| *index);
+        Ok(indexed.into_iter().map(|(_, result)| result).collect())
+    }
+}
@@ -0,0 +1,414 @@
+//! Persistent agent task queue.
+//!
+//! Modeled directly on [`crate::queue::VerificationQueue`]: tasks live in a
+//! `tasks` sled tree keyed by a monotonic task id (tracked in a `meta` tree
+//! counter) under `.telos/agent_queue/`. [`AgentTaskQueue::materialize`] scans
+//! the object database for work an agent should pick up — unresolved code
+//! bindings and `must`-severity constraints with no code binding covering
+//! them — and enqueues one [`AgentTask`] per target, skipping targets that
+//! already have an open or claimed task so repeated calls (e.g. every
+//! `agent-pull`) don't pile up duplicates. [`AgentTaskQueue::claim`] hands out
+//! the oldest eligible task via compare-and-swap on its state (`Open` ->
+//! `Claimed`), the same lease-with-reclaim scheme `VerificationQueue` uses so
+//! a crashed agent's task returns to the queue instead of being stuck.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use telos_core::hash::ObjectId;
+use telos_core::object::code_binding::BindingResolution;
+use telos_core::object::constraint::{ConstraintSeverity, ConstraintStatus};
+use telos_core::object::TelosObject;
+
+/// How long a `Claimed` task may go without a `complete()` call before it's
+/// considered abandoned and eligible for another agent to reclaim.
+const LEASE_DURATION: Duration = Duration::from_secs(1800);
+
+fn sled_err(e: sled::Error) -> StoreError {
+    StoreError::IndexError(e.to_string())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Open,
+    Claimed,
+    Done,
+}
+
+/// What generated a task.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    /// A `CodeBinding` whose file no longer exists on disk.
+    UnresolvedBinding,
+    /// A `must`-severity constraint with no code binding covering it.
+    UncoveredConstraint,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTask {
+    pub task_id: u64,
+    pub kind: TaskKind,
+    /// Hex id of the binding or constraint this task was generated from.
+    pub target_id: String,
+    /// Impact tags copied from the target, so `context --impact` can surface
+    /// this task alongside the intents/decisions for the same impact.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub impacts: Vec<String>,
+    pub description: String,
+    pub state: TaskState,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<u64>,
+}
+
+pub struct AgentTaskQueue {
+    dir: PathBuf,
+    db: sled::Db,
+}
+
+impl AgentTaskQueue {
+    pub fn new(queue_dir: impl Into<PathBuf>) -> Self {
+        let dir = queue_dir.into();
+        let db = sled::open(dir.join("kv")).expect("failed to open agent queue kv store");
+        Self { dir, db }
+    }
+
+    pub fn ensure_dir(&self) -> Result<(), StoreError> {
+        std::fs::create_dir_all(&self.dir)?;
+        Ok(())
+    }
+
+    fn tasks_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("tasks").map_err(sled_err)
+    }
+
+    fn meta_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("meta").map_err(sled_err)
+    }
+
+    fn next_task_id(&self) -> Result<u64, StoreError> {
+        let meta = self.meta_tree()?;
+        let next = meta
+            .update_and_fetch(b"next_task_id", |old| {
+                let id = old
+                    .map(|v| u64::from_be_bytes(v.try_into().unwrap_or([0; 8])))
+                    .unwrap_or(0)
+                    + 1;
+                Some(id.to_be_bytes().to_vec())
+            })
+            .map_err(sled_err)?
+            .expect("update_and_fetch always returns Some here");
+        Ok(u64::from_be_bytes(next.as_ref().try_into().unwrap()))
+    }
+
+    /// Target ids already backed by an open or claimed task, so
+    /// `materialize` doesn't enqueue duplicates for the same target.
+    fn live_target_ids(&self) -> Result<std::collections::HashSet<String>, StoreError> {
+        let tasks = self.tasks_tree()?;
+        let mut ids = std::collections::HashSet::new();
+        for entry in tasks.iter() {
+            let (_, value) = entry.map_err(sled_err)?;
+            let task: AgentTask = serde_json::from_slice(&value)?;
+            if task.state != TaskState::Done {
+                ids.insert(task.target_id);
+            }
+        }
+        Ok(ids)
+    }
+
+    fn enqueue(
+        &self,
+        kind: TaskKind,
+        target_id: &ObjectId,
+        impacts: Vec<String>,
+        description: String,
+    ) -> Result<u64, StoreError> {
+        self.ensure_dir()?;
+        let task_id = self.next_task_id()?;
+        let task = AgentTask {
+            task_id,
+            kind,
+            target_id: target_id.hex().to_string(),
+            impacts,
+            description,
+            state: TaskState::Open,
+            claimed_by: None,
+            lease_expires_at: None,
+        };
+        let tasks = self.tasks_tree()?;
+        tasks
+            .insert(task_id.to_be_bytes(), serde_json::to_vec(&task)?)
+            .map_err(sled_err)?;
+        Ok(task_id)
+    }
+
+    /// Scan the object database for outstanding work — unresolved code
+    /// bindings and uncovered `must` constraints — and enqueue a task for
+    /// every target that doesn't already have one open or claimed. Returns
+    /// the number of tasks newly created.
+    pub fn materialize(&self, odb: &ObjectDatabase, root: &std::path::Path) -> Result<usize, StoreError> {
+        let all_objects = odb.iter_all()?;
+        let live = self.live_target_ids()?;
+
+        let mut covered_constraints = std::collections::HashSet::new();
+        for (_id, obj) in &all_objects {
+            if let TelosObject::CodeBinding(cb) = obj {
+                covered_constraints.insert(cb.bound_object.hex().to_string());
+            }
+        }
+
+        let mut created = 0;
+        for (id, obj) in &all_objects {
+            match obj {
+                TelosObject::CodeBinding(cb) => {
+                    if cb.resolution == BindingResolution::Unresolved
+                        || !root.join(&cb.path).exists()
+                    {
+                        if live.contains(&id.hex().to_string()) {
+                            continue;
+                        }
+                        self.enqueue(
+                            TaskKind::UnresolvedBinding,
+                            id,
+                            Vec::new(),
+                            format!("code binding to '{}' no longer resolves", cb.path),
+                        )?;
+                        created += 1;
+                    }
+                }
+                TelosObject::Constraint(c) => {
+                    if c.severity == ConstraintSeverity::Must
+                        && c.status == ConstraintStatus::Active
+                        && !covered_constraints.contains(&id.hex().to_string())
+                    {
+                        if live.contains(&id.hex().to_string()) {
+                            continue;
+                        }
+                        self.enqueue(
+                            TaskKind::UncoveredConstraint,
+                            id,
+                            c.impacts.clone(),
+                            format!("must constraint '{}' has no code binding", c.statement),
+                        )?;
+                        created += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(created)
+    }
+
+    /// Claim the oldest eligible task (`Open`, or `Claimed` past its lease)
+    /// via compare-and-swap on its state.
+    pub fn claim(&self, agent_id: &str) -> Result<Option<AgentTask>, StoreError> {
+        let tasks = self.tasks_tree()?;
+        let now = now_millis();
+
+        for entry in tasks.iter() {
+            let (key, value) = entry.map_err(sled_err)?;
+            let task: AgentTask = serde_json::from_slice(&value)?;
+
+            let eligible = match task.state {
+                TaskState::Open => true,
+                TaskState::Claimed => task.lease_expires_at.map(|exp| exp <= now).unwrap_or(false),
+                TaskState::Done => false,
+            };
+            if !eligible {
+                continue;
+            }
+
+            let mut claimed = task.clone();
+            claimed.state = TaskState::Claimed;
+            claimed.claimed_by = Some(agent_id.to_string());
+            claimed.lease_expires_at = Some(now + LEASE_DURATION.as_millis() as u64);
+
+            let cas = tasks
+                .compare_and_swap(
+                    key,
+                    Some(value.as_ref()),
+                    Some(serde_json::to_vec(&claimed)?),
+                )
+                .map_err(sled_err)?;
+            if cas.is_ok() {
+                return Ok(Some(claimed));
+            }
+            // Lost the race to another agent; move on to the next task.
+        }
+        Ok(None)
+    }
+
+    /// Mark a claimed task `Done`.
+    pub fn complete(&self, task_id: u64) -> Result<(), StoreError> {
+        let tasks = self.tasks_tree()?;
+        let key = task_id.to_be_bytes();
+        let Some(value) = tasks.get(key).map_err(sled_err)? else {
+            return Err(StoreError::IndexError(format!(
+                "no such agent task: {}",
+                task_id
+            )));
+        };
+        let mut task: AgentTask = serde_json::from_slice(&value)?;
+        task.state = TaskState::Done;
+        task.lease_expires_at = None;
+        tasks.insert(key, serde_json::to_vec(&task)?).map_err(sled_err)?;
+        Ok(())
+    }
+
+    /// Open or reclaimable tasks, oldest first, optionally filtered to those
+    /// whose `impacts` contain `impact`.
+    pub fn list_open(&self, impact: Option<&str>) -> Result<Vec<AgentTask>, StoreError> {
+        let tasks = self.tasks_tree()?;
+        let now = now_millis();
+        let mut open = Vec::new();
+        for entry in tasks.iter() {
+            let (_, value) = entry.map_err(sled_err)?;
+            let task: AgentTask = serde_json::from_slice(&value)?;
+            let pending = match task.state {
+                TaskState::Open => true,
+                TaskState::Claimed => task.lease_expires_at.map(|exp| exp <= now).unwrap_or(false),
+                TaskState::Done => false,
+            };
+            if !pending {
+                continue;
+            }
+            if let Some(impact) = impact {
+                if !task.impacts.iter().any(|i| i == impact) {
+                    continue;
+                }
+            }
+            open.push(task);
+        }
+        Ok(open)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use telos_core::object::code_binding::{BindingType, CodeBinding};
+    use telos_core::object::constraint::Constraint;
+    use telos_core::object::intent::Author;
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn make_queue() -> (tempfile::TempDir, AgentTaskQueue) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let queue = AgentTaskQueue::new(dir.path().join("agent_queue"));
+        (dir, queue)
+    }
+
+    fn uncovered_constraint() -> Constraint {
+        Constraint {
+            author: Author {
+                name: "Alice".into(),
+                email: "alice@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: "Must validate email format".into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Active,
+            source_intent: ObjectId::hash(b"intent1"),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec!["auth".into()],
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn materialize_enqueues_uncovered_must_constraint() {
+        let (dir, queue) = make_queue();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        odb.write(&TelosObject::Constraint(uncovered_constraint())).unwrap();
+
+        let created = queue.materialize(&odb, dir.path()).unwrap();
+        assert_eq!(created, 1);
+
+        let task = queue.claim("agent-1").unwrap().expect("task should be claimable");
+        assert_eq!(task.kind, TaskKind::UncoveredConstraint);
+        assert_eq!(task.impacts, vec!["auth".to_string()]);
+        assert_eq!(task.claimed_by.as_deref(), Some("agent-1"));
+    }
+
+    #[test]
+    fn materialize_skips_covered_constraint() {
+        let (dir, queue) = make_queue();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        let constraint_id = odb.write(&TelosObject::Constraint(uncovered_constraint())).unwrap();
+        odb.write(&TelosObject::CodeBinding(CodeBinding {
+            path: "src/auth/mod.rs".into(),
+            symbol: None,
+            span: None,
+            binding_type: BindingType::File,
+            resolution: BindingResolution::Resolved,
+            bound_object: constraint_id,
+            fingerprint: None,
+            metadata: HashMap::new(),
+        }))
+        .unwrap();
+
+        let created = queue.materialize(&odb, dir.path()).unwrap();
+        assert_eq!(created, 0);
+        assert!(queue.claim("agent-1").unwrap().is_none());
+    }
+
+    #[test]
+    fn materialize_is_idempotent_for_open_tasks() {
+        let (dir, queue) = make_queue();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        odb.write(&TelosObject::Constraint(uncovered_constraint())).unwrap();
+
+        assert_eq!(queue.materialize(&odb, dir.path()).unwrap(), 1);
+        assert_eq!(queue.materialize(&odb, dir.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn complete_marks_task_done_and_unclaimable() {
+        let (dir, queue) = make_queue();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        odb.write(&TelosObject::Constraint(uncovered_constraint())).unwrap();
+        queue.materialize(&odb, dir.path()).unwrap();
+
+        let task = queue.claim("agent-1").unwrap().unwrap();
+        queue.complete(task.task_id).unwrap();
+
+        assert!(queue.list_open(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn claim_reclaims_expired_lease() {
+        let (dir, queue) = make_queue();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        odb.write(&TelosObject::Constraint(uncovered_constraint())).unwrap();
+        queue.materialize(&odb, dir.path()).unwrap();
+
+        let task = queue.claim("agent-1").unwrap().unwrap();
+        let tasks = queue.tasks_tree().unwrap();
+        let mut stuck = task.clone();
+        stuck.lease_expires_at = Some(now_millis() - 1);
+        tasks
+            .insert(task.task_id.to_be_bytes(), serde_json::to_vec(&stuck).unwrap())
+            .unwrap();
+
+        let reclaimed = queue
+            .claim("agent-2")
+            .unwrap()
+            .expect("expired lease should be reclaimable");
+        assert_eq!(reclaimed.task_id, task.task_id);
+        assert_eq!(reclaimed.claimed_by.as_deref(), Some("agent-2"));
+    }
+}
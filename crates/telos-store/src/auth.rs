@@ -0,0 +1,318 @@
+//! Capability tokens: signed, self-describing grants that let a shared
+//! repository restrict *who* may `intent`/`decide`/`supersede`/`deprecate`,
+//! rather than letting any caller mutate the decision record.
+//!
+//! A [`CapabilityToken`] names a principal, a set of [`Verb`]s it may
+//! perform, an optional scope of `impacts` tags, and an expiry. It isn't a
+//! `TelosObject` — it's never stored in the object database — but it's
+//! content-addressed the same way via
+//! `telos_core::serialize::content_hash`, so a [`RevokedTokens`] list can
+//! key off a stable id without embedding the signature in the hash it
+//! signs. [`SignedToken`] is the on-disk `--token <file>` format: the token
+//! plus a detached signature from the repository's authority key (see
+//! `Repository::authority_key`), mirroring how [`crate::signing`] keeps
+//! signatures detached from the bytes they cover.
+//!
+//! [`authorize`] is the single choke point every mutating command runs
+//! through when `[auth] required = true`: it verifies the signature,
+//! rejects expired or revoked tokens, and checks the requested verb and
+//! impact scope against the grant — failing closed on every check.
+
+use crate::error::StoreError;
+use crate::signing::verify_bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use telos_core::serialize::canonical_serialize;
+
+/// An action a capability token may grant. Mirrors the CLI commands that
+/// mutate state; `log`/`show`/`query`/`context` never require a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verb {
+    Intent,
+    Decide,
+    Supersede,
+    Deprecate,
+}
+
+impl fmt::Display for Verb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verb::Intent => "intent",
+            Verb::Decide => "decide",
+            Verb::Supersede => "supersede",
+            Verb::Deprecate => "deprecate",
+        })
+    }
+}
+
+impl FromStr for Verb {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "intent" => Ok(Verb::Intent),
+            "decide" => Ok(Verb::Decide),
+            "supersede" => Ok(Verb::Supersede),
+            "deprecate" => Ok(Verb::Deprecate),
+            other => Err(StoreError::InvalidToken(format!("unknown verb '{}'", other))),
+        }
+    }
+}
+
+/// A capability grant: `principal` may perform `verbs`, optionally scoped
+/// to `impacts`, until `expires`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityToken {
+    pub principal: String,
+    pub verbs: BTreeSet<Verb>,
+    /// If `Some`, the token only grants verbs against objects that declare
+    /// at least one of these impact tags. `None` means unscoped (grants
+    /// against any object, including one with no impacts at all).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impacts: Option<Vec<String>>,
+    pub expires: DateTime<Utc>,
+}
+
+impl CapabilityToken {
+    /// Content-addressed id, for recording "this object was authorized by
+    /// token X" and for entries in [`RevokedTokens`].
+    pub fn id(&self) -> Result<String, StoreError> {
+        Ok(telos_core::serialize::content_hash("capability_token", self)
+            .map_err(StoreError::Core)?
+            .hex()
+            .to_string())
+    }
+
+    /// Whether this token grants `verb` against an object declaring
+    /// `object_impacts`. Fails closed: a scoped token with `impacts:
+    /// Some([...])` denies an object that declares no impacts at all,
+    /// since there's nothing in common to match against.
+    pub fn grants(&self, verb: Verb, object_impacts: &[String]) -> bool {
+        if !self.verbs.contains(&verb) {
+            return false;
+        }
+        match &self.impacts {
+            None => true,
+            Some(scoped) => object_impacts.iter().any(|i| scoped.contains(i)),
+        }
+    }
+}
+
+/// The `--token <file>` on-disk format: a [`CapabilityToken`] plus a
+/// detached signature from the repository's authority key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedToken {
+    pub token: CapabilityToken,
+    /// Ed25519 signature over the token's canonical bytes, base64-encoded.
+    pub signature: String,
+    /// Hex-encoded authority public key the signature was made with, so
+    /// `authorize` can check it against the repository's current
+    /// `.telos/keys/authority_ed25519` without a side channel.
+    pub authority_key: String,
+}
+
+impl SignedToken {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let contents = fs::read_to_string(path.as_ref())
+            .map_err(|e| StoreError::InvalidToken(format!("{}: {}", path.as_ref().display(), e)))?;
+        serde_json::from_str(&contents).map_err(|e| StoreError::InvalidToken(e.to_string()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StoreError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Check the signature against the token's own canonical bytes and
+    /// `authority_key`. Doesn't check expiry or revocation — that's
+    /// [`authorize`]'s job, since those checks need repository state this
+    /// type doesn't carry.
+    fn signature_valid(&self) -> Result<bool, StoreError> {
+        let bytes = canonical_serialize("capability_token", &self.token).map_err(StoreError::Core)?;
+        verify_bytes(&self.authority_key, &bytes, &self.signature)
+    }
+}
+
+/// Ids of tokens that have been revoked via `telos auth revoke`, stored at
+/// `.telos/revoked_tokens.json` — same shape as
+/// `crate::signing::AuthorityList`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RevokedTokens {
+    #[serde(default)]
+    ids: BTreeSet<String>,
+}
+
+impl RevokedTokens {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StoreError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, token_id: impl Into<String>) {
+        self.ids.insert(token_id.into());
+    }
+
+    pub fn is_revoked(&self, token_id: &str) -> bool {
+        self.ids.contains(token_id)
+    }
+}
+
+/// Verify `signed` against `authority_key_hex`, reject it if expired or
+/// revoked, and check it grants `verb` against `impacts`. Returns the
+/// token's id on success, for recording into the created object's
+/// `metadata`. Every failure mode — bad signature, wrong authority key,
+/// expired, revoked, wrong verb, out-of-scope impacts — fails closed with
+/// `StoreError::Unauthorized` (malformed input is `InvalidToken` instead,
+/// surfaced separately so callers can tell "no permission" from "broken
+/// token").
+pub fn authorize(
+    signed: &SignedToken,
+    authority_key_hex: &str,
+    revoked: &RevokedTokens,
+    verb: Verb,
+    impacts: &[String],
+    now: DateTime<Utc>,
+) -> Result<String, StoreError> {
+    if signed.authority_key != authority_key_hex {
+        return Err(StoreError::Unauthorized(
+            "token was not signed by this repository's authority key".into(),
+        ));
+    }
+    if !signed.signature_valid()? {
+        return Err(StoreError::Unauthorized("token signature is invalid".into()));
+    }
+
+    let token_id = signed.token.id()?;
+    if revoked.is_revoked(&token_id) {
+        return Err(StoreError::Unauthorized(format!("token {} has been revoked", token_id)));
+    }
+    if now >= signed.token.expires {
+        return Err(StoreError::Unauthorized(format!("token {} expired at {}", token_id, signed.token.expires)));
+    }
+    if !signed.token.grants(verb, impacts) {
+        return Err(StoreError::Unauthorized(format!(
+            "token {} does not grant '{}' for the given impacts",
+            token_id, verb
+        )));
+    }
+
+    Ok(token_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::AuthorKey;
+
+    fn sign(token: &CapabilityToken, key: &AuthorKey) -> SignedToken {
+        let bytes = canonical_serialize("capability_token", token).unwrap();
+        SignedToken {
+            token: token.clone(),
+            signature: key.sign_bytes(&bytes).unwrap(),
+            authority_key: key.public_key_hex(),
+        }
+    }
+
+    fn sample_token(verbs: &[Verb], impacts: Option<Vec<String>>, expires: DateTime<Utc>) -> CapabilityToken {
+        CapabilityToken {
+            principal: "alice".into(),
+            verbs: verbs.iter().copied().collect(),
+            impacts,
+            expires,
+        }
+    }
+
+    #[test]
+    fn grants_checks_verb_and_unscoped_impacts() {
+        let token = sample_token(&[Verb::Decide], None, Utc::now());
+        assert!(token.grants(Verb::Decide, &[]));
+        assert!(token.grants(Verb::Decide, &["billing".into()]));
+        assert!(!token.grants(Verb::Supersede, &[]));
+    }
+
+    #[test]
+    fn grants_fails_closed_when_scoped_and_object_has_no_impacts() {
+        let token = sample_token(&[Verb::Decide], Some(vec!["billing".into()]), Utc::now());
+        assert!(!token.grants(Verb::Decide, &[]));
+        assert!(!token.grants(Verb::Decide, &["checkout".into()]));
+        assert!(token.grants(Verb::Decide, &["billing".into()]));
+    }
+
+    #[test]
+    fn authorize_accepts_valid_unexpired_unrevoked_token() {
+        let key = AuthorKey::generate();
+        let token = sample_token(&[Verb::Decide], None, Utc::now() + chrono::Duration::days(1));
+        let signed = sign(&token, &key);
+        let revoked = RevokedTokens::default();
+
+        let id = authorize(&signed, &key.public_key_hex(), &revoked, Verb::Decide, &[], Utc::now()).unwrap();
+        assert_eq!(id, token.id().unwrap());
+    }
+
+    #[test]
+    fn authorize_rejects_wrong_authority_key() {
+        let key = AuthorKey::generate();
+        let other_key = AuthorKey::generate();
+        let token = sample_token(&[Verb::Decide], None, Utc::now() + chrono::Duration::days(1));
+        let signed = sign(&token, &key);
+        let revoked = RevokedTokens::default();
+
+        let err = authorize(&signed, &other_key.public_key_hex(), &revoked, Verb::Decide, &[], Utc::now()).unwrap_err();
+        assert!(matches!(err, StoreError::Unauthorized(_)));
+    }
+
+    #[test]
+    fn authorize_rejects_expired_token() {
+        let key = AuthorKey::generate();
+        let token = sample_token(&[Verb::Decide], None, Utc::now() - chrono::Duration::days(1));
+        let signed = sign(&token, &key);
+        let revoked = RevokedTokens::default();
+
+        let err = authorize(&signed, &key.public_key_hex(), &revoked, Verb::Decide, &[], Utc::now()).unwrap_err();
+        assert!(matches!(err, StoreError::Unauthorized(_)));
+    }
+
+    #[test]
+    fn authorize_rejects_revoked_token() {
+        let key = AuthorKey::generate();
+        let token = sample_token(&[Verb::Decide], None, Utc::now() + chrono::Duration::days(1));
+        let signed = sign(&token, &key);
+        let mut revoked = RevokedTokens::default();
+        revoked.revoke(token.id().unwrap());
+
+        let err = authorize(&signed, &key.public_key_hex(), &revoked, Verb::Decide, &[], Utc::now()).unwrap_err();
+        assert!(matches!(err, StoreError::Unauthorized(_)));
+    }
+
+    #[test]
+    fn authorize_rejects_verb_outside_grant() {
+        let key = AuthorKey::generate();
+        let token = sample_token(&[Verb::Decide], None, Utc::now() + chrono::Duration::days(1));
+        let signed = sign(&token, &key);
+        let revoked = RevokedTokens::default();
+
+        let err = authorize(&signed, &key.public_key_hex(), &revoked, Verb::Supersede, &[], Utc::now()).unwrap_err();
+        assert!(matches!(err, StoreError::Unauthorized(_)));
+    }
+
+    #[test]
+    fn verb_parses_and_displays_snake_case() {
+        assert_eq!("decide".parse::<Verb>().unwrap(), Verb::Decide);
+        assert_eq!(Verb::Deprecate.to_string(), "deprecate");
+        assert!("bogus".parse::<Verb>().is_err());
+    }
+}
@@ -0,0 +1,480 @@
+//! Pluggable storage backends for refs and objects, selected by address URI
+//! (`file:///abs/path`, `memory://`, `sled:///abs/path`), in the spirit of
+//! tvix-castore's `from_addr`.
+//!
+//! [`RefStore`] and [`ObjectDatabase`] remain the concrete, file-backed types
+//! [`crate::repository::Repository`] embeds directly — this module doesn't
+//! change that wiring. What it adds is [`RefBackend`]/[`ObjectBackend`],
+//! trait-object-safe interfaces both already satisfy, plus two more
+//! implementations behind them: an in-memory one for tests that shouldn't
+//! touch disk, and a single-file `sled`-backed one for an embedded store
+//! that isn't a directory of loose files. A `grpc://` or
+//! `objectstore+s3://` backend would need a client library this tree
+//! doesn't vendor (no `Cargo.toml` pulls in `tonic` or `object_store`), so
+//! `from_addr` reports those schemes as unsupported rather than faking a
+//! client; adding one later only means writing a new impl of these traits.
+//!
+//! Wiring `Repository::discover` to resolve its backend from an address in
+//! config (rather than always constructing [`RefStore`]/[`ObjectDatabase`]
+//! directly) is follow-up work — every other module reaches into
+//! `repo.refs`/`repo.odb`'s full inherent APIs (remote-tracking refs, pack
+//! compaction, prefix resolution with ambiguity errors), which is more
+//! surface than these two traits cover today.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use crate::refs::RefStore;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+use telos_core::hash::ObjectId;
+use telos_core::object::intent_stream::IntentStreamRef;
+use telos_core::object::TelosObject;
+
+/// Stream ref storage: HEAD plus named `IntentStreamRef`s.
+pub trait RefBackend: Send + Sync {
+    fn read_head(&self) -> Result<String, StoreError>;
+    fn set_head(&self, stream_name: &str) -> Result<(), StoreError>;
+    fn read_stream(&self, name: &str) -> Result<IntentStreamRef, StoreError>;
+    fn write_stream(&self, stream: &IntentStreamRef) -> Result<(), StoreError>;
+    fn list_streams(&self) -> Result<Vec<String>, StoreError>;
+    fn delete_stream(&self, name: &str) -> Result<(), StoreError>;
+}
+
+/// Content-addressed object storage.
+pub trait ObjectBackend: Send + Sync {
+    fn has(&self, id: &ObjectId) -> Result<bool, StoreError>;
+    fn read(&self, id: &ObjectId) -> Result<TelosObject, StoreError>;
+    fn write(&self, object: &TelosObject) -> Result<ObjectId, StoreError>;
+    fn iter_all(&self) -> Result<Vec<(ObjectId, TelosObject)>, StoreError>;
+    fn resolve_prefix(&self, prefix: &str) -> Result<ObjectId, StoreError>;
+}
+
+// --- file:// (today's on-disk layout, delegating to the existing types) ---
+
+impl RefBackend for RefStore {
+    fn read_head(&self) -> Result<String, StoreError> {
+        RefStore::read_head(self)
+    }
+    fn set_head(&self, stream_name: &str) -> Result<(), StoreError> {
+        RefStore::set_head(self, stream_name)
+    }
+    fn read_stream(&self, name: &str) -> Result<IntentStreamRef, StoreError> {
+        RefStore::read_stream(self, name)
+    }
+    fn write_stream(&self, stream: &IntentStreamRef) -> Result<(), StoreError> {
+        RefStore::write_stream(self, stream)
+    }
+    fn list_streams(&self) -> Result<Vec<String>, StoreError> {
+        RefStore::list_streams(self)
+    }
+    fn delete_stream(&self, name: &str) -> Result<(), StoreError> {
+        RefStore::delete_stream(self, name)
+    }
+}
+
+impl ObjectBackend for ObjectDatabase {
+    fn has(&self, id: &ObjectId) -> Result<bool, StoreError> {
+        Ok(ObjectDatabase::exists(self, id))
+    }
+    fn read(&self, id: &ObjectId) -> Result<TelosObject, StoreError> {
+        ObjectDatabase::read(self, id)
+    }
+    fn write(&self, object: &TelosObject) -> Result<ObjectId, StoreError> {
+        ObjectDatabase::write(self, object)
+    }
+    fn iter_all(&self) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+        ObjectDatabase::iter_all(self)
+    }
+    fn resolve_prefix(&self, prefix: &str) -> Result<ObjectId, StoreError> {
+        ObjectDatabase::resolve_prefix(self, prefix)
+    }
+}
+
+// --- memory:// (process-local, for tests that shouldn't touch disk) ---
+
+/// An in-memory [`RefBackend`], for tests. Nothing is persisted; a new
+/// instance starts with no HEAD and no streams.
+#[derive(Default)]
+pub struct MemoryRefBackend {
+    head: RwLock<Option<String>>,
+    streams: RwLock<HashMap<String, IntentStreamRef>>,
+}
+
+impl MemoryRefBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl RefBackend for MemoryRefBackend {
+    fn read_head(&self) -> Result<String, StoreError> {
+        self.head
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| StoreError::InvalidHead("HEAD not set".into()))
+    }
+
+    fn set_head(&self, stream_name: &str) -> Result<(), StoreError> {
+        *self.head.write().unwrap() = Some(stream_name.to_string());
+        Ok(())
+    }
+
+    fn read_stream(&self, name: &str) -> Result<IntentStreamRef, StoreError> {
+        self.streams
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| StoreError::StreamNotFound(name.to_string()))
+    }
+
+    fn write_stream(&self, stream: &IntentStreamRef) -> Result<(), StoreError> {
+        self.streams
+            .write()
+            .unwrap()
+            .insert(stream.name.clone(), stream.clone());
+        Ok(())
+    }
+
+    fn list_streams(&self) -> Result<Vec<String>, StoreError> {
+        let mut names: Vec<_> = self.streams.read().unwrap().keys().cloned().collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete_stream(&self, name: &str) -> Result<(), StoreError> {
+        if self.streams.write().unwrap().remove(name).is_none() {
+            return Err(StoreError::StreamNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// An in-memory [`ObjectBackend`], for tests.
+#[derive(Default)]
+pub struct MemoryObjectBackend {
+    objects: RwLock<HashMap<ObjectId, TelosObject>>,
+}
+
+impl MemoryObjectBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ObjectBackend for MemoryObjectBackend {
+    fn has(&self, id: &ObjectId) -> Result<bool, StoreError> {
+        Ok(self.objects.read().unwrap().contains_key(id))
+    }
+
+    fn read(&self, id: &ObjectId) -> Result<TelosObject, StoreError> {
+        self.objects
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| StoreError::ObjectNotFound(id.hex().to_string()))
+    }
+
+    fn write(&self, object: &TelosObject) -> Result<ObjectId, StoreError> {
+        let id = object.content_id()?;
+        self.objects.write().unwrap().insert(id.clone(), object.clone());
+        Ok(id)
+    }
+
+    fn iter_all(&self) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+        Ok(self
+            .objects
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, obj)| (id.clone(), obj.clone()))
+            .collect())
+    }
+
+    fn resolve_prefix(&self, prefix: &str) -> Result<ObjectId, StoreError> {
+        let objects = self.objects.read().unwrap();
+        let matches: Vec<_> = objects.keys().filter(|id| id.hex().starts_with(prefix)).collect();
+        match matches.as_slice() {
+            [id] => Ok((*id).clone()),
+            [] => Err(StoreError::ObjectNotFound(prefix.to_string())),
+            _ => Err(StoreError::AmbiguousPrefix {
+                prefix: prefix.to_string(),
+                count: matches.len(),
+            }),
+        }
+    }
+}
+
+// --- sled:// (single embedded file, for a store that isn't a directory of
+// loose files) ---
+
+fn sled_err(e: sled::Error) -> StoreError {
+    StoreError::IndexError(e.to_string())
+}
+
+/// A [`RefBackend`] backed by a single `sled` database instead of
+/// `HEAD`/`refs/streams/*` files.
+pub struct SledRefBackend {
+    db: sled::Db,
+}
+
+impl SledRefBackend {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Ok(Self {
+            db: sled::open(path).map_err(sled_err)?,
+        })
+    }
+
+    fn streams_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("streams").map_err(sled_err)
+    }
+}
+
+impl RefBackend for SledRefBackend {
+    fn read_head(&self) -> Result<String, StoreError> {
+        self.db
+            .get(b"HEAD")
+            .map_err(sled_err)?
+            .map(|v| String::from_utf8_lossy(&v).to_string())
+            .ok_or_else(|| StoreError::InvalidHead("HEAD not set".into()))
+    }
+
+    fn set_head(&self, stream_name: &str) -> Result<(), StoreError> {
+        self.db.insert(b"HEAD", stream_name.as_bytes()).map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn read_stream(&self, name: &str) -> Result<IntentStreamRef, StoreError> {
+        let streams = self.streams_tree()?;
+        let value = streams
+            .get(name)
+            .map_err(sled_err)?
+            .ok_or_else(|| StoreError::StreamNotFound(name.to_string()))?;
+        Ok(serde_json::from_slice(&value)?)
+    }
+
+    fn write_stream(&self, stream: &IntentStreamRef) -> Result<(), StoreError> {
+        let streams = self.streams_tree()?;
+        streams
+            .insert(stream.name.as_bytes(), serde_json::to_vec(stream)?)
+            .map_err(sled_err)?;
+        Ok(())
+    }
+
+    fn list_streams(&self) -> Result<Vec<String>, StoreError> {
+        let streams = self.streams_tree()?;
+        let mut names = Vec::new();
+        for entry in streams.iter() {
+            let (key, _) = entry.map_err(sled_err)?;
+            names.push(String::from_utf8_lossy(&key).to_string());
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete_stream(&self, name: &str) -> Result<(), StoreError> {
+        let streams = self.streams_tree()?;
+        if streams.remove(name).map_err(sled_err)?.is_none() {
+            return Err(StoreError::StreamNotFound(name.to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// An [`ObjectBackend`] backed by a single `sled` database instead of
+/// `objects/<fan-out>/*` loose files.
+pub struct SledObjectBackend {
+    db: sled::Db,
+}
+
+impl SledObjectBackend {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Ok(Self {
+            db: sled::open(path).map_err(sled_err)?,
+        })
+    }
+
+    fn objects_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("objects").map_err(sled_err)
+    }
+}
+
+impl ObjectBackend for SledObjectBackend {
+    fn has(&self, id: &ObjectId) -> Result<bool, StoreError> {
+        Ok(self.objects_tree()?.contains_key(id.hex()).map_err(sled_err)?)
+    }
+
+    fn read(&self, id: &ObjectId) -> Result<TelosObject, StoreError> {
+        let value = self
+            .objects_tree()?
+            .get(id.hex())
+            .map_err(sled_err)?
+            .ok_or_else(|| StoreError::ObjectNotFound(id.hex().to_string()))?;
+        Ok(TelosObject::from_canonical_bytes(&value)?)
+    }
+
+    fn write(&self, object: &TelosObject) -> Result<ObjectId, StoreError> {
+        let id = object.content_id()?;
+        self.objects_tree()?
+            .insert(id.hex(), object.canonical_bytes()?)
+            .map_err(sled_err)?;
+        Ok(id)
+    }
+
+    fn iter_all(&self) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+        let mut out = Vec::new();
+        for entry in self.objects_tree()?.iter() {
+            let (key, value) = entry.map_err(sled_err)?;
+            let id = ObjectId::parse(&String::from_utf8_lossy(&key)).map_err(StoreError::Core)?;
+            out.push((id, TelosObject::from_canonical_bytes(&value)?));
+        }
+        Ok(out)
+    }
+
+    fn resolve_prefix(&self, prefix: &str) -> Result<ObjectId, StoreError> {
+        let tree = self.objects_tree()?;
+        let mut matches = Vec::new();
+        for entry in tree.scan_prefix(prefix) {
+            let (key, _) = entry.map_err(sled_err)?;
+            matches.push(ObjectId::parse(&String::from_utf8_lossy(&key)).map_err(StoreError::Core)?);
+        }
+        match matches.len() {
+            1 => Ok(matches.remove(0)),
+            0 => Err(StoreError::ObjectNotFound(prefix.to_string())),
+            count => Err(StoreError::AmbiguousPrefix {
+                prefix: prefix.to_string(),
+                count,
+            }),
+        }
+    }
+}
+
+// --- address dispatch ---
+
+/// Construct a [`RefBackend`] from an address URI: `file://<path>` (today's
+/// on-disk layout), `memory://` (ignores any path, fresh per call), or
+/// `sled://<path>` (a single embedded database file). Any other scheme —
+/// e.g. `grpc://` or `objectstore+s3://` — is reported as unsupported
+/// rather than attempting a client this tree has no library for.
+pub fn ref_backend_from_addr(addr: &str) -> Result<Box<dyn RefBackend>, StoreError> {
+    if let Some(path) = addr.strip_prefix("file://") {
+        return Ok(Box::new(RefStore::new(Path::new(path))));
+    }
+    if addr == "memory://" || addr.starts_with("memory://") {
+        return Ok(Box::new(MemoryRefBackend::new()));
+    }
+    if let Some(path) = addr.strip_prefix("sled://") {
+        return Ok(Box::new(SledRefBackend::open(path)?));
+    }
+    Err(StoreError::ConfigError(format!(
+        "unsupported ref backend address '{}' (supported schemes: file://, memory://, sled://)",
+        addr
+    )))
+}
+
+/// Construct an [`ObjectBackend`] from an address URI. See
+/// [`ref_backend_from_addr`] for the supported schemes.
+pub fn object_backend_from_addr(addr: &str) -> Result<Box<dyn ObjectBackend>, StoreError> {
+    if let Some(path) = addr.strip_prefix("file://") {
+        return Ok(Box::new(ObjectDatabase::new(Path::new(path))));
+    }
+    if addr == "memory://" || addr.starts_with("memory://") {
+        return Ok(Box::new(MemoryObjectBackend::new()));
+    }
+    if let Some(path) = addr.strip_prefix("sled://") {
+        return Ok(Box::new(SledObjectBackend::open(path)?));
+    }
+    Err(StoreError::ConfigError(format!(
+        "unsupported object backend address '{}' (supported schemes: file://, memory://, sled://)",
+        addr
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn sample_stream(name: &str) -> IntentStreamRef {
+        IntentStreamRef {
+            name: name.into(),
+            tip: None,
+            created_at: Utc::now(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn memory_ref_backend_round_trips_head_and_streams() {
+        let backend = MemoryRefBackend::new();
+        assert!(backend.read_head().is_err());
+
+        backend.set_head("main").unwrap();
+        assert_eq!(backend.read_head().unwrap(), "main");
+
+        backend.write_stream(&sample_stream("main")).unwrap();
+        assert_eq!(backend.read_stream("main").unwrap().name, "main");
+        assert_eq!(backend.list_streams().unwrap(), vec!["main".to_string()]);
+
+        backend.delete_stream("main").unwrap();
+        assert!(backend.read_stream("main").is_err());
+    }
+
+    #[test]
+    fn memory_object_backend_round_trips_and_resolves_prefix() {
+        let backend = MemoryObjectBackend::new();
+        let intent = TelosObject::Constraint(telos_core::object::constraint::Constraint {
+            author: telos_core::object::intent::Author {
+                name: "Alice".into(),
+                email: "alice@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: "Must do the thing".into(),
+            severity: telos_core::object::constraint::ConstraintSeverity::Must,
+            status: telos_core::object::constraint::ConstraintStatus::Active,
+            source_intent: ObjectId::hash(b"intent1"),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: Default::default(),
+        });
+        let id = backend.write(&intent).unwrap();
+        assert!(backend.has(&id).unwrap());
+        assert_eq!(backend.read(&id).unwrap(), intent);
+        assert_eq!(backend.resolve_prefix(&id.hex()[..8]).unwrap(), id);
+    }
+
+    #[test]
+    fn sled_backends_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let refs = SledRefBackend::open(dir.path().join("refs")).unwrap();
+        refs.set_head("main").unwrap();
+        refs.write_stream(&sample_stream("main")).unwrap();
+        assert_eq!(refs.read_head().unwrap(), "main");
+        assert_eq!(refs.read_stream("main").unwrap().name, "main");
+
+        let objects = SledObjectBackend::open(dir.path().join("objects")).unwrap();
+        let stream_snapshot = TelosObject::IntentStreamSnapshot(
+            telos_core::object::intent_stream::IntentStreamSnapshot {
+                name: "main".into(),
+                tip: ObjectId::hash(b"tip"),
+                created_at: Utc::now(),
+                description: None,
+                parent_stream: None,
+            },
+        );
+        let id = objects.write(&stream_snapshot).unwrap();
+        assert!(objects.has(&id).unwrap());
+        assert_eq!(objects.read(&id).unwrap(), stream_snapshot);
+    }
+
+    #[test]
+    fn from_addr_rejects_unsupported_scheme() {
+        assert!(ref_backend_from_addr("grpc://localhost:1234").is_err());
+        assert!(object_backend_from_addr("objectstore+s3://bucket/prefix").is_err());
+    }
+}
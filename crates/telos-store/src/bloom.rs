@@ -0,0 +1,182 @@
+//! Probabilistic "have" summary for sync negotiation.
+//!
+//! A [`HaveFilter`] is a bloom filter over every `ObjectId` in a repository's
+//! object database. Exchanging it instead of a full id list lets a sync peer
+//! answer "do you already have this object?" locally, shrinking the
+//! have/want round trips that [`crate::repository::Repository::missing_objects`]
+//! would otherwise require. Bloom filters never produce false negatives —
+//! if the filter says an id is absent, it is — so only a reported "present"
+//! needs re-verification, which happens naturally when a transferred object
+//! is rehashed by [`crate::odb::ObjectDatabase::write`].
+//!
+//! [`crate::sync::push`] is the actual negotiation path this shrinks: when
+//! [`crate::remote::RemoteBackend::have_filter`] returns one, pushing tests
+//! every local id against it directly instead of shipping the whole local
+//! id list to the remote and waiting on an exact [`crate::remote::RemoteBackend::has`]
+//! response.
+
+use serde::{Deserialize, Serialize};
+use telos_core::hash::ObjectId;
+
+/// Target false-positive rate used when sizing a filter from an expected
+/// element count.
+const TARGET_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// A fixed-size bloom filter over `ObjectId`s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HaveFilter {
+    num_bits: usize,
+    num_hashes: u32,
+    words: Vec<u64>,
+}
+
+impl HaveFilter {
+    /// Build an empty filter sized for `expected_items` elements at a ~1%
+    /// target false-positive rate.
+    pub fn with_capacity(expected_items: usize) -> Self {
+        let n = expected_items.max(1);
+        let num_bits = optimal_num_bits(n, TARGET_FALSE_POSITIVE_RATE);
+        let num_hashes = optimal_num_hashes(num_bits, n);
+        let num_words = ((num_bits + 63) / 64).max(1);
+        Self {
+            num_bits: num_words * 64,
+            num_hashes,
+            words: vec![0u64; num_words],
+        }
+    }
+
+    /// Build a filter containing every id yielded by `ids`.
+    pub fn build(ids: &[ObjectId]) -> Self {
+        let mut filter = Self::with_capacity(ids.len());
+        for id in ids {
+            filter.insert(id);
+        }
+        filter
+    }
+
+    /// Insert an id into the filter.
+    pub fn insert(&mut self, id: &ObjectId) {
+        for lane in self.lanes(id) {
+            let bit = (lane % self.num_bits as u64) as usize;
+            self.words[bit / 64] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Check whether `id` might be present. May return a false positive;
+    /// never returns a false negative.
+    pub fn contains(&self, id: &ObjectId) -> bool {
+        self.lanes(id)
+            .into_iter()
+            .all(|lane| {
+                let bit = (lane % self.num_bits as u64) as usize;
+                self.words[bit / 64] & (1 << (bit % 64)) != 0
+            })
+    }
+
+    /// Derive `num_hashes` independent 64-bit lanes from `id`'s string
+    /// representation via FNV-1a, re-seeded per lane and mixed with the
+    /// hash index, rather than running `num_hashes` separate hash
+    /// functions.
+    ///
+    /// Deliberately doesn't assume anything about `id.hex()`'s length or
+    /// charset — it used to slice a presumed bare 64-char hex string into
+    /// four fixed 16-char chunks, which silently broke (every lane parsed
+    /// to 0, collapsing the filter to its false-positive floor) once
+    /// `ObjectId` started encoding as a variable-length multibase string.
+    fn lanes(&self, id: &ObjectId) -> Vec<u64> {
+        let bytes = id.hex().as_bytes();
+        (0..self.num_hashes).map(|k| fnv1a_seeded(bytes, k as u64)).collect()
+    }
+}
+
+/// FNV-1a over `bytes`, with `seed` folded into the initial basis so
+/// different seeds produce independent lanes from the same input.
+fn fnv1a_seeded(bytes: &[u8], seed: u64) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash = FNV_OFFSET_BASIS ^ seed.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// `m = ceil(-(n * ln(p)) / ln(2)^2)`
+fn optimal_num_bits(expected_items: usize, false_positive_rate: f64) -> usize {
+    let n = expected_items as f64;
+    let m = -(n * false_positive_rate.ln()) / std::f64::consts::LN_2.powi(2);
+    m.ceil().max(64.0) as usize
+}
+
+/// `k = round((m / n) * ln(2))`, clamped to a sane range so tiny inputs
+/// don't produce pathological hash counts.
+fn optimal_num_hashes(num_bits: usize, expected_items: usize) -> u32 {
+    let k = (num_bits as f64 / expected_items as f64) * std::f64::consts::LN_2;
+    (k.round() as i64).clamp(1, 16) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_all_inserted_ids() {
+        let ids: Vec<ObjectId> = (0..200)
+            .map(|i| ObjectId::hash(format!("object-{}", i).as_bytes()))
+            .collect();
+        let filter = HaveFilter::build(&ids);
+        for id in &ids {
+            assert!(filter.contains(id), "false negative for {}", id);
+        }
+    }
+
+    #[test]
+    fn contains_all_inserted_ids_for_a_non_default_hash_algo() {
+        // lanes() used to assume id.hex() was a bare 64-char hex string and
+        // slice it into four fixed 16-char chunks; a multihash-encoded
+        // Blake3 id doesn't fit that shape and every lane silently parsed
+        // to 0.
+        use telos_core::hash::HashAlgo;
+        let ids: Vec<ObjectId> = (0..200)
+            .map(|i| ObjectId::hash_with(HashAlgo::Blake3, format!("object-{}", i).as_bytes()))
+            .collect();
+        let filter = HaveFilter::build(&ids);
+        for id in &ids {
+            assert!(filter.contains(id), "false negative for {}", id);
+        }
+    }
+
+    #[test]
+    fn false_positive_rate_is_reasonably_low() {
+        let ids: Vec<ObjectId> = (0..1000)
+            .map(|i| ObjectId::hash(format!("present-{}", i).as_bytes()))
+            .collect();
+        let filter = HaveFilter::build(&ids);
+
+        let absent: Vec<ObjectId> = (0..1000)
+            .map(|i| ObjectId::hash(format!("absent-{}", i).as_bytes()))
+            .collect();
+        let false_positives = absent.iter().filter(|id| filter.contains(id)).count();
+
+        // Sized for ~1% FP rate; allow generous headroom to avoid flakiness.
+        assert!(
+            false_positives < 50,
+            "unexpectedly high false-positive count: {}",
+            false_positives
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let ids: Vec<ObjectId> = (0..10)
+            .map(|i| ObjectId::hash(format!("obj-{}", i).as_bytes()))
+            .collect();
+        let filter = HaveFilter::build(&ids);
+        let json = serde_json::to_string(&filter).unwrap();
+        let restored: HaveFilter = serde_json::from_str(&json).unwrap();
+        for id in &ids {
+            assert!(restored.contains(id));
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Release-note generation from [`ChangeSet`] history.
+//!
+//! Git commit messages only tell half the story; the reasoning behind a
+//! change lives in the intents, decisions, and constraints a [`ChangeSet`]
+//! links to. This walks the change sets whose intents fall within a stream
+//! range — an optional `--since` point up to an `--until` point, both
+//! resolvable from either an [`IntentStreamSnapshot`] or a bare intent id —
+//! and resolves each one's references into the underlying objects, so a
+//! changelog can be rendered straight from Telos history.
+//!
+//! The range itself reuses [`crate::sync::ancestors`]'s intent-DAG walk:
+//! "in range" means reachable from `until` but not from `since`, exactly
+//! the same new-intents-on-a-side computation [`crate::stream_merge`] uses.
+
+use crate::error::StoreError;
+use crate::repository::Repository;
+use crate::sync::ancestors;
+use std::collections::HashSet;
+use telos_core::hash::ObjectId;
+use telos_core::object::{ChangeSet, Constraint, DecisionRecord, Intent, TelosObject};
+
+/// One change set's resolved contribution to a changelog.
+#[derive(Debug, Clone)]
+pub struct ChangelogEntry {
+    pub change_set_id: ObjectId,
+    pub change_set: ChangeSet,
+    pub intents: Vec<(ObjectId, Intent)>,
+    pub decisions: Vec<(ObjectId, DecisionRecord)>,
+    pub constraints: Vec<(ObjectId, Constraint)>,
+}
+
+/// Resolve a `--since`/`--until` argument to the intent it denotes: an
+/// [`telos_core::object::IntentStreamSnapshot`]'s `tip`, or — for anything
+/// else, e.g. a stream's current tip — the id itself. Accepts full hex or
+/// an unambiguous prefix.
+pub fn resolve_range_point(repo: &Repository, reference: &str) -> Result<ObjectId, StoreError> {
+    let (id, obj) = repo.read_object(reference)?;
+    match obj {
+        TelosObject::IntentStreamSnapshot(snapshot) => Ok(snapshot.tip),
+        _ => Ok(id),
+    }
+}
+
+fn resolve_intent(repo: &Repository, id: &ObjectId) -> Option<(ObjectId, Intent)> {
+    match repo.odb.read(id).ok()? {
+        TelosObject::Intent(intent) => Some((id.clone(), intent)),
+        _ => None,
+    }
+}
+
+fn resolve_decision(repo: &Repository, id: &ObjectId) -> Option<(ObjectId, DecisionRecord)> {
+    match repo.odb.read(id).ok()? {
+        TelosObject::DecisionRecord(record) => Some((id.clone(), record)),
+        _ => None,
+    }
+}
+
+fn resolve_constraint(repo: &Repository, id: &ObjectId) -> Option<(ObjectId, Constraint)> {
+    match repo.odb.read(id).ok()? {
+        TelosObject::Constraint(constraint) => Some((id.clone(), constraint)),
+        _ => None,
+    }
+}
+
+/// Build a changelog covering every change set that references at least one
+/// intent reachable from `until` but not from `since` (or, with `since`
+/// omitted, every intent reachable from `until`). Entries are sorted by the
+/// change set's own timestamp, oldest first.
+pub fn build_changelog(
+    repo: &Repository,
+    until: &ObjectId,
+    since: Option<&ObjectId>,
+) -> Result<Vec<ChangelogEntry>, StoreError> {
+    let until_ancestors = ancestors(repo, until)?;
+    let in_range: HashSet<ObjectId> = match since {
+        Some(since_id) => {
+            let since_ancestors = ancestors(repo, since_id)?;
+            until_ancestors.difference(&since_ancestors).cloned().collect()
+        }
+        None => until_ancestors,
+    };
+
+    let mut entries = Vec::new();
+    for (id, obj) in repo.odb.iter_all()? {
+        let TelosObject::ChangeSet(cs) = obj else { continue };
+        if !cs.intents.iter().any(|i| in_range.contains(i)) {
+            continue;
+        }
+        let intents = cs.intents.iter().filter_map(|i| resolve_intent(repo, i)).collect();
+        let decisions = cs.decisions.iter().filter_map(|i| resolve_decision(repo, i)).collect();
+        let constraints = cs.constraints.iter().filter_map(|i| resolve_constraint(repo, i)).collect();
+        entries.push(ChangelogEntry {
+            change_set_id: id,
+            change_set: cs,
+            intents,
+            decisions,
+            constraints,
+        });
+    }
+    entries.sort_by_key(|e| e.change_set.timestamp);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use telos_core::object::intent::Author;
+
+    fn make_intent(statement: &str, parents: Vec<ObjectId>) -> Intent {
+        Intent {
+            author: Author { name: "Test".into(), email: "test@test.com".into() },
+            timestamp: chrono::Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents,
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    fn make_change_set(author_intent: &ObjectId, git_commit: &str) -> ChangeSet {
+        ChangeSet {
+            author: Author { name: "Test".into(), email: "test@test.com".into() },
+            timestamp: chrono::Utc::now(),
+            git_commit: git_commit.into(),
+            parents: vec![],
+            intents: vec![author_intent.clone()],
+            constraints: vec![],
+            decisions: vec![],
+            code_bindings: vec![],
+            agent_operations: vec![],
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn includes_only_change_sets_in_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let cs_root_id = repo.create_change_set(make_change_set(&root, "aaa111")).unwrap();
+
+        let child = repo.create_intent(make_intent("Child", vec![root.clone()])).unwrap();
+        let cs_child_id = repo.create_change_set(make_change_set(&child, "bbb222")).unwrap();
+
+        let entries = build_changelog(&repo, &child, Some(&root)).unwrap();
+        let ids: Vec<_> = entries.iter().map(|e| &e.change_set_id).collect();
+        assert_eq!(ids, vec![&cs_child_id]);
+
+        let full = build_changelog(&repo, &child, None).unwrap();
+        let full_ids: HashSet<_> = full.iter().map(|e| e.change_set_id.clone()).collect();
+        assert!(full_ids.contains(&cs_root_id));
+        assert!(full_ids.contains(&cs_child_id));
+    }
+
+    #[test]
+    fn resolve_range_point_follows_snapshot_tip() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let snapshot = telos_core::object::IntentStreamSnapshot {
+            name: "main".into(),
+            tip: root.clone(),
+            created_at: chrono::Utc::now(),
+            description: None,
+            parent_stream: None,
+        };
+        let snapshot_id = repo.odb.write(&TelosObject::IntentStreamSnapshot(snapshot)).unwrap();
+
+        let resolved = resolve_range_point(&repo, snapshot_id.hex()).unwrap();
+        assert_eq!(resolved, root);
+    }
+}
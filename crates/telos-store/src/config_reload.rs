@@ -0,0 +1,187 @@
+//! Hot-reloadable `.telos/config.toml`, for long-lived processes (`telos
+//! watch`, future daemons) that shouldn't need a restart to pick up a
+//! changed author identity or signing policy.
+//!
+//! Readers go through [`ConfigHandle::current`], which returns a snapshot
+//! `Arc<TelosConfig>` — a reload only ever *replaces* that `Arc`, never
+//! mutates one in place, so a reader never observes a half-applied config.
+//! [`ConfigWatcher`] polls the file's mtime (the same plain-polling idiom
+//! `telos_store::watch` uses, since both run inside the same kind of
+//! long-lived loop) and, on a change, re-parses and swaps in the new
+//! config. On a parse error the previous config is left in place and the
+//! error is returned to the caller instead of panicking.
+
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use telos_core::config::{ConfigError, TelosConfig};
+
+/// A shared, swappable reference to the current config. Cloning is cheap
+/// (an `Arc` clone); every clone observes the same reloads.
+#[derive(Clone)]
+pub struct ConfigHandle {
+    inner: Arc<RwLock<Arc<TelosConfig>>>,
+}
+
+impl ConfigHandle {
+    pub fn new(config: TelosConfig) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Arc::new(config))),
+        }
+    }
+
+    /// A consistent snapshot of the config as of the most recent reload.
+    pub fn current(&self) -> Arc<TelosConfig> {
+        self.inner.read().unwrap().clone()
+    }
+
+    fn swap(&self, config: TelosConfig) {
+        *self.inner.write().unwrap() = Arc::new(config);
+    }
+}
+
+/// Which top-level sections differ between two config snapshots, for a
+/// reload log line that says what actually changed rather than just "the
+/// file changed".
+fn changed_sections(old: &TelosConfig, new: &TelosConfig) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if old.author != new.author {
+        changed.push("author");
+    }
+    if old.codex != new.codex {
+        changed.push("codex");
+    }
+    if old.sign != new.sign {
+        changed.push("sign");
+    }
+    if old.profiles != new.profiles {
+        changed.push("profile");
+    }
+    changed
+}
+
+fn config_mtime(telos_dir: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(telos_dir.join("config.toml"))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+/// Polls `.telos/config.toml` for changes and republishes a [`ConfigHandle`]
+/// when it does. Call [`ConfigWatcher::poll`] periodically from a long-lived
+/// loop (e.g. alongside `telos_store::watch::Watcher::poll`).
+pub struct ConfigWatcher {
+    telos_dir: PathBuf,
+    handle: ConfigHandle,
+    last_mtime: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Load `telos_dir/config.toml` (defaults if absent) and return a handle
+    /// plus the watcher that keeps it fresh.
+    pub fn open(telos_dir: impl Into<PathBuf>) -> Result<(ConfigHandle, Self), ConfigError> {
+        let telos_dir = telos_dir.into();
+        let config = TelosConfig::load(&telos_dir)?;
+        let last_mtime = config_mtime(&telos_dir);
+        let handle = ConfigHandle::new(config);
+        Ok((
+            handle.clone(),
+            Self {
+                telos_dir,
+                handle,
+                last_mtime,
+            },
+        ))
+    }
+
+    /// Check whether the config file's mtime changed since the last poll
+    /// and, if so, try to reload it.
+    ///
+    /// Returns `Ok(None)` if nothing changed, `Ok(Some(sections))` if a new
+    /// config was published (`sections` lists what differed, possibly
+    /// empty if the file was rewritten with identical content), or `Err` if
+    /// the file changed but failed to parse — in which case the previous
+    /// config is still being served by the handle.
+    pub fn poll(&mut self) -> Result<Option<Vec<&'static str>>, ConfigError> {
+        let mtime = config_mtime(&self.telos_dir);
+        if mtime == self.last_mtime {
+            return Ok(None);
+        }
+        self.last_mtime = mtime;
+
+        match TelosConfig::load(&self.telos_dir) {
+            Ok(new_config) => {
+                let old_config = self.handle.current();
+                let changed = changed_sections(&old_config, &new_config);
+                self.handle.swap(new_config);
+                tracing::info!(sections = ?changed, "config.toml reloaded");
+                Ok(Some(changed))
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "config.toml changed but failed to parse; keeping previous settings");
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_config(telos_dir: &std::path::Path, contents: &str) {
+        let mut f = std::fs::File::create(telos_dir.join("config.toml")).unwrap();
+        f.write_all(contents.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn handle_reflects_swapped_config() {
+        let handle = ConfigHandle::new(TelosConfig::default());
+        assert_eq!(handle.current().author.name, None);
+
+        let mut updated = TelosConfig::default();
+        updated.author.name = Some("New Author".into());
+        handle.swap(updated);
+
+        assert_eq!(handle.current().author.name.as_deref(), Some("New Author"));
+    }
+
+    #[test]
+    fn watcher_reports_no_change_until_file_is_touched() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path(), "[author]\nname = \"Original\"\n");
+
+        let (handle, mut watcher) = ConfigWatcher::open(dir.path()).unwrap();
+        assert_eq!(handle.current().author.name.as_deref(), Some("Original"));
+        assert_eq!(watcher.poll().unwrap(), None);
+    }
+
+    #[test]
+    fn watcher_reloads_and_reports_changed_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path(), "[author]\nname = \"Original\"\n");
+        let (handle, mut watcher) = ConfigWatcher::open(dir.path()).unwrap();
+
+        // Ensure the new mtime is observably different on coarse-grained filesystems.
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        write_config(dir.path(), "[author]\nname = \"Updated\"\n\n[sign]\ndefault = true\n");
+
+        let changed = watcher.poll().unwrap().expect("expected a reload");
+        assert!(changed.contains(&"author"));
+        assert!(changed.contains(&"sign"));
+        assert_eq!(handle.current().author.name.as_deref(), Some("Updated"));
+    }
+
+    #[test]
+    fn watcher_keeps_previous_config_on_parse_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path(), "[author]\nname = \"Original\"\n");
+        let (handle, mut watcher) = ConfigWatcher::open(dir.path()).unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        write_config(dir.path(), "this is not valid toml [[[");
+
+        assert!(watcher.poll().is_err());
+        assert_eq!(handle.current().author.name.as_deref(), Some("Original"));
+    }
+}
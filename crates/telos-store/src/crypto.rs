@@ -0,0 +1,97 @@
+//! AEAD primitives backing at-rest encryption of object bytes and wrapped
+//! data keys. XChaCha20-Poly1305 is used throughout for its 24-byte nonce,
+//! which makes random nonce generation safe without a counter.
+
+use crate::error::StoreError;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+/// A raw 32-byte symmetric key (either the ODB data key or a key-encryption key).
+pub type DataKey = [u8; 32];
+
+/// Fill a fixed-size array with cryptographically random bytes.
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut buf = [0u8; N];
+    OsRng.fill_bytes(&mut buf);
+    buf
+}
+
+/// Encrypt `plaintext` under `key`, returning a freshly generated nonce and the ciphertext+tag.
+pub fn encrypt(key: &DataKey, plaintext: &[u8]) -> Result<([u8; 24], Vec<u8>), StoreError> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce_bytes: [u8; 24] = random_bytes();
+    let nonce = XNonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| StoreError::EncryptionError(e.to_string()))?;
+    Ok((nonce_bytes, ciphertext))
+}
+
+/// Decrypt `ciphertext` (as produced by [`encrypt`]) under `key` and `nonce`.
+pub fn decrypt(key: &DataKey, nonce: &[u8; 24], ciphertext: &[u8]) -> Result<Vec<u8>, StoreError> {
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XNonce::from_slice(nonce);
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| StoreError::EncryptionError(e.to_string()))
+}
+
+/// Encode an object's on-disk payload as `nonce || ciphertext`.
+pub fn seal(key: &DataKey, plaintext: &[u8]) -> Result<Vec<u8>, StoreError> {
+    let (nonce, ciphertext) = encrypt(key, plaintext)?;
+    let mut out = Vec::with_capacity(24 + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decode a payload produced by [`seal`] back into plaintext.
+pub fn open(key: &DataKey, sealed: &[u8]) -> Result<Vec<u8>, StoreError> {
+    if sealed.len() < 24 {
+        return Err(StoreError::EncryptionError(
+            "sealed object is shorter than the nonce".into(),
+        ));
+    }
+    let (nonce_bytes, ciphertext) = sealed.split_at(24);
+    let nonce: [u8; 24] = nonce_bytes
+        .try_into()
+        .map_err(|_| StoreError::EncryptionError("malformed nonce".into()))?;
+    decrypt(key, &nonce, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_open_round_trip() {
+        let key: DataKey = random_bytes();
+        let plaintext = b"intent statement goes here";
+
+        let sealed = seal(&key, plaintext).unwrap();
+        assert_ne!(&sealed[24..], &plaintext[..]);
+
+        let opened = open(&key, &sealed).unwrap();
+        assert_eq!(opened, plaintext);
+    }
+
+    #[test]
+    fn open_rejects_tampered_ciphertext() {
+        let key: DataKey = random_bytes();
+        let mut sealed = seal(&key, b"payload").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0xFF;
+
+        assert!(open(&key, &sealed).is_err());
+    }
+
+    #[test]
+    fn open_rejects_wrong_key() {
+        let key: DataKey = random_bytes();
+        let other_key: DataKey = random_bytes();
+        let sealed = seal(&key, b"payload").unwrap();
+
+        assert!(open(&other_key, &sealed).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+//! Opaque pagination cursors for the `query_*` functions.
+//!
+//! A cursor encodes the sort key of the last item returned by a page —
+//! `(timestamp, ObjectId)`, since every `query_*` result is sorted by
+//! timestamp descending and the id is folded in to break ties and
+//! guarantee a total order (otherwise two objects sharing a timestamp
+//! could be skipped or duplicated across pages). The encoded form is
+//! base64url (no padding) over a small JSON payload, so it's a single
+//! URL-safe opaque string a caller can round-trip without parsing it.
+
+use crate::error::StoreError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use telos_core::hash::ObjectId;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CursorKey {
+    pub timestamp: DateTime<Utc>,
+    pub id: String,
+}
+
+impl CursorKey {
+    pub fn new(timestamp: DateTime<Utc>, id: &ObjectId) -> Self {
+        Self {
+            timestamp,
+            id: id.hex().to_string(),
+        }
+    }
+
+    /// `true` if `self` is at or after `other` in `(timestamp, id)` value
+    /// order — i.e. at or ahead of `other` in the descending order the
+    /// `query_*` functions use, so an item at `self` should be skipped when
+    /// resuming from a cursor at `other`.
+    fn at_or_after(&self, other: &CursorKey) -> bool {
+        (&self.timestamp, &self.id) >= (&other.timestamp, &other.id)
+    }
+}
+
+/// Encode `key` as an opaque, URL-safe cursor string.
+pub fn encode(key: &CursorKey) -> String {
+    let json = serde_json::to_vec(key).expect("CursorKey always serializes");
+    base64url_encode(&json)
+}
+
+/// Decode a cursor produced by [`encode`]. Tolerates both padded and
+/// unpadded base64url input for client compatibility.
+pub fn decode(cursor: &str) -> Result<CursorKey, StoreError> {
+    let bytes = base64url_decode(cursor)
+        .ok_or_else(|| StoreError::InvalidCursor(cursor.to_string()))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Skip every `(timestamp, id)`-ordered item at or after `cursor`, then
+/// take up to `limit` of what remains (all remaining items when `limit`
+/// is `None`).
+pub fn paginate<T>(
+    items: Vec<T>,
+    key_of: impl Fn(&T) -> CursorKey,
+    after: Option<&CursorKey>,
+    limit: Option<usize>,
+) -> (Vec<T>, Option<CursorKey>) {
+    let mut items = items;
+    if let Some(after) = after {
+        items.retain(|item| !key_of(item).at_or_after(after));
+    }
+
+    let next_cursor = limit
+        .filter(|&n| items.len() > n)
+        .map(|n| key_of(&items[n - 1]));
+
+    if let Some(n) = limit {
+        items.truncate(n);
+    }
+    (items, next_cursor)
+}
+
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+fn base64url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        if chunk.len() > 1 {
+            out.push(ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char);
+        }
+        if chunk.len() > 2 {
+            out.push(ALPHABET[(b2 & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    // Tolerate trailing '=' padding even though `encode` never emits it.
+    let input = input.trim_end_matches('=');
+
+    let value_of = |c: u8| -> Option<u8> {
+        ALPHABET.iter().position(|&a| a == c).map(|p| p as u8)
+    };
+
+    let mut out = Vec::with_capacity(input.len() / 4 * 3 + 3);
+    let bytes = input.as_bytes();
+    for chunk in bytes.chunks(4) {
+        if chunk.len() == 1 {
+            return None;
+        }
+        let v0 = value_of(chunk[0])?;
+        let v1 = value_of(chunk[1])?;
+        out.push((v0 << 2) | (v1 >> 4));
+
+        if chunk.len() > 2 {
+            let v2 = value_of(chunk[2])?;
+            out.push((v1 << 4) | (v2 >> 2));
+            if chunk.len() > 3 {
+                let v3 = value_of(chunk[3])?;
+                out.push((v2 << 6) | v3);
+            }
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_encode_decode() {
+        let key = CursorKey::new(Utc::now(), &ObjectId::hash(b"test"));
+        let encoded = encode(&key);
+        assert!(!encoded.contains('='));
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, key);
+    }
+
+    #[test]
+    fn decode_tolerates_padding() {
+        let key = CursorKey::new(Utc::now(), &ObjectId::hash(b"padded"));
+        let encoded = encode(&key);
+        let mut padded = encoded.clone();
+        while padded.len() % 4 != 0 {
+            padded.push('=');
+        }
+        assert_eq!(decode(&padded).unwrap(), key);
+        assert_eq!(decode(&encoded).unwrap(), key);
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert!(decode("not valid base64url json!!").is_err());
+    }
+
+    #[test]
+    fn paginate_breaks_ties_on_id() {
+        let t = Utc::now();
+        let a = ObjectId::hash(b"a");
+        let b = ObjectId::hash(b"b");
+        let c = ObjectId::hash(b"c");
+        // Same timestamp, descending by id as the query_* sort would
+        // produce under a stable (timestamp, id) tie-break.
+        let mut ids = vec![a.clone(), b.clone(), c.clone()];
+        ids.sort_by(|x, y| y.hex().cmp(x.hex()));
+        let items: Vec<(ObjectId, ())> = ids.into_iter().map(|id| (id, ())).collect();
+
+        let (page1, next) = paginate(
+            items.clone(),
+            |(id, _)| CursorKey::new(t, id),
+            None,
+            Some(2),
+        );
+        assert_eq!(page1.len(), 2);
+        let next = next.unwrap();
+
+        let (page2, next2) = paginate(items, |(id, _)| CursorKey::new(t, id), Some(&next), Some(2));
+        assert_eq!(page2.len(), 1);
+        assert!(next2.is_none());
+        assert_ne!(page1[1].0, page2[0].0);
+    }
+}
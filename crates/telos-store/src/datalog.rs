@@ -0,0 +1,503 @@
+//! A small Datalog engine over the object graph.
+//!
+//! The typed `query_*` functions in [`crate::query`] only express fixed
+//! single-type filters; they can't express a join across object kinds or a
+//! transitive relationship (e.g. "every ancestor of intent X"). This module
+//! models each object kind as a relation — `intent(id, author, statement)`,
+//! `constraint(id, source_intent, status, severity)`,
+//! `binding(id, path, symbol, bound_object)`, `parent(child, parent)`,
+//! `impacts(id, target)`, `agent_operation(id, agent_id, summary)`,
+//! `context_ref(op_id, ref_id)` — seeded once from a single [`ObjectDatabase::iter_all`]
+//! scan, then lets a caller layer user-written rules (including recursive
+//! ones, for transitive closures like ancestry) on top and evaluate a goal
+//! against the result.
+//!
+//! Evaluation is semi-naive bottom-up fixpoint: each round, every rule is
+//! re-joined once per body atom with that atom sourced from the *previous
+//! round's delta* (newly derived tuples) and every other atom sourced from
+//! the full relation so far, so a round only does the work of the tuples
+//! that actually changed. The round's newly-derived tuples (deduplicated
+//! against everything already known) become the next round's delta; the
+//! fixpoint is reached when a round derives nothing new.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use std::collections::{HashMap, HashSet};
+use telos_core::object::TelosObject;
+
+/// A single value a [`Term`] can bind to or a fact field can hold. Every
+/// relation is untyped text under the hood (ids are hex strings) so joins
+/// across kinds — e.g. an intent id appearing in both `intent` and
+/// `parent` — compare equal without a cast.
+pub type Value = String;
+
+/// One row of a relation.
+pub type Tuple = Vec<Value>;
+
+/// Every relation's full set of known tuples, keyed by relation name.
+pub type Facts = HashMap<String, HashSet<Tuple>>;
+
+/// A term in an atom: either a variable (binds to whatever it matches) or a
+/// constant (must match exactly).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Var(String),
+    Const(Value),
+}
+
+/// One relation reference inside a rule body or head: `name(term, term, ...)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Atom {
+    pub relation: String,
+    pub terms: Vec<Term>,
+}
+
+/// `head :- body1, body2, ...`. A body of zero atoms isn't supported by the
+/// parser (facts come only from [`base_facts`]); every rule is a derivation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub head: Atom,
+    pub body: Vec<Atom>,
+}
+
+/// A parsed program: its rules plus the goal atom from the trailing `?-` line.
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub rules: Vec<Rule>,
+    pub goal: Atom,
+}
+
+/// Scan the object database once and build the base relations every
+/// program can query:
+///
+/// - `intent(id, author_email, statement)`
+/// - `constraint(id, source_intent, status, severity)`
+/// - `binding(id, path, symbol, bound_object)` (`symbol` is `""` when unset)
+/// - `parent(child, parent)`, one row per `Intent.parents` entry
+/// - `impacts(id, target)`, one row per `Intent.impacts`/`Constraint.impacts` entry
+/// - `agent_operation(id, agent_id, summary)`
+/// - `context_ref(op_id, ref_id)`, one row per `AgentOperation.context_refs` entry
+pub fn base_facts(odb: &ObjectDatabase) -> Result<Facts, StoreError> {
+    let mut facts: Facts = Facts::new();
+    let mut add = |relation: &str, tuple: Tuple| {
+        facts.entry(relation.to_string()).or_default().insert(tuple);
+    };
+
+    for (id, obj) in odb.iter_all()? {
+        let hex = id.hex().to_string();
+        match &obj {
+            TelosObject::Intent(intent) => {
+                add(
+                    "intent",
+                    vec![hex.clone(), intent.author.email.clone(), intent.statement.clone()],
+                );
+                for parent in &intent.parents {
+                    add("parent", vec![hex.clone(), parent.hex().to_string()]);
+                }
+                for target in &intent.impacts {
+                    add("impacts", vec![hex.clone(), target.clone()]);
+                }
+            }
+            TelosObject::Constraint(constraint) => {
+                add(
+                    "constraint",
+                    vec![
+                        hex.clone(),
+                        constraint.source_intent.hex().to_string(),
+                        format!("{:?}", constraint.status),
+                        format!("{:?}", constraint.severity),
+                    ],
+                );
+                for target in &constraint.impacts {
+                    add("impacts", vec![hex.clone(), target.clone()]);
+                }
+            }
+            TelosObject::CodeBinding(binding) => {
+                add(
+                    "binding",
+                    vec![
+                        hex.clone(),
+                        binding.path.clone(),
+                        binding.symbol.clone().unwrap_or_default(),
+                        binding.bound_object.hex().to_string(),
+                    ],
+                );
+            }
+            TelosObject::AgentOperation(op) => {
+                add("agent_operation", vec![hex.clone(), op.agent_id.clone(), op.summary.clone()]);
+                for ctx in &op.context_refs {
+                    add("context_ref", vec![hex.clone(), ctx.hex().to_string()]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(facts)
+}
+
+/// Bind `atom`'s terms against each tuple of `source[atom.relation]`,
+/// extending `binding` and recursing into the remaining atoms. Collects one
+/// completed binding per satisfying assignment into `out`.
+fn join_atom(
+    atoms: &[Atom],
+    i: usize,
+    delta_index: usize,
+    all: &Facts,
+    delta: &Facts,
+    binding: HashMap<String, Value>,
+    out: &mut Vec<HashMap<String, Value>>,
+) {
+    if i == atoms.len() {
+        out.push(binding);
+        return;
+    }
+    let atom = &atoms[i];
+    let source = if i == delta_index { delta } else { all };
+    let empty = HashSet::new();
+    let tuples = source.get(&atom.relation).unwrap_or(&empty);
+
+    for tuple in tuples {
+        if tuple.len() != atom.terms.len() {
+            continue;
+        }
+        let mut extended = binding.clone();
+        let mut ok = true;
+        for (term, value) in atom.terms.iter().zip(tuple.iter()) {
+            match term {
+                Term::Var(name) => match extended.get(name) {
+                    Some(existing) if existing != value => {
+                        ok = false;
+                        break;
+                    }
+                    Some(_) => {}
+                    None => {
+                        extended.insert(name.clone(), value.clone());
+                    }
+                },
+                Term::Const(c) => {
+                    if c != value {
+                        ok = false;
+                        break;
+                    }
+                }
+            }
+        }
+        if ok {
+            join_atom(atoms, i + 1, delta_index, all, delta, extended, out);
+        }
+    }
+}
+
+fn instantiate(head: &Atom, binding: &HashMap<String, Value>) -> Option<Tuple> {
+    head.terms
+        .iter()
+        .map(|term| match term {
+            Term::Var(name) => binding.get(name).cloned(),
+            Term::Const(c) => Some(c.clone()),
+        })
+        .collect()
+}
+
+/// Run `rules` to a semi-naive bottom-up fixpoint over `base`, returning the
+/// full set of derived facts (base facts included).
+pub fn evaluate(base: Facts, rules: &[Rule]) -> Facts {
+    let mut all = base.clone();
+    let mut delta = base;
+
+    loop {
+        let mut new_delta: Facts = Facts::new();
+
+        for rule in rules {
+            for atom_index in 0..rule.body.len() {
+                let mut bindings = Vec::new();
+                join_atom(&rule.body, 0, atom_index, &all, &delta, HashMap::new(), &mut bindings);
+                for binding in bindings {
+                    let Some(tuple) = instantiate(&rule.head, &binding) else {
+                        continue;
+                    };
+                    let already_known = all.get(&rule.head.relation).is_some_and(|s| s.contains(&tuple));
+                    if !already_known {
+                        new_delta.entry(rule.head.relation.clone()).or_default().insert(tuple);
+                    }
+                }
+            }
+        }
+
+        let mut next_delta: Facts = Facts::new();
+        for (relation, tuples) in new_delta {
+            for tuple in tuples {
+                if all.entry(relation.clone()).or_default().insert(tuple.clone()) {
+                    next_delta.entry(relation.clone()).or_default().insert(tuple);
+                }
+            }
+        }
+
+        if next_delta.is_empty() {
+            break;
+        }
+        delta = next_delta;
+    }
+
+    all
+}
+
+/// Evaluate `goal` against `facts`, returning the distinct variable names in
+/// the order they first appear (the answer's column headers) and one row
+/// per matching tuple, projected onto those variables. A goal with no
+/// variables (a ground query) returns no columns and one row per matching
+/// fact — i.e. existence is `!rows.is_empty()`.
+pub fn answer(goal: &Atom, facts: &Facts) -> (Vec<String>, Vec<Tuple>) {
+    let mut columns = Vec::new();
+    for term in &goal.terms {
+        if let Term::Var(name) = term {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    let empty = HashSet::new();
+    let tuples = facts.get(&goal.relation).unwrap_or(&empty);
+    for tuple in tuples {
+        if tuple.len() != goal.terms.len() {
+            continue;
+        }
+        let mut binding: HashMap<String, Value> = HashMap::new();
+        let mut ok = true;
+        for (term, value) in goal.terms.iter().zip(tuple.iter()) {
+            match term {
+                Term::Var(name) => match binding.get(name) {
+                    Some(existing) if existing != value => {
+                        ok = false;
+                        break;
+                    }
+                    Some(_) => {}
+                    None => {
+                        binding.insert(name.clone(), value.clone());
+                    }
+                },
+                Term::Const(c) => {
+                    if c != value {
+                        ok = false;
+                        break;
+                    }
+                }
+            }
+        }
+        if ok {
+            rows.push(columns.iter().map(|c| binding[c].clone()).collect());
+        }
+    }
+    (columns, rows)
+}
+
+/// Parse a program of the form:
+///
+/// ```text
+/// ancestor(X, Y) :- parent(X, Y).
+/// ancestor(X, Y) :- parent(X, Z), ancestor(Z, Y).
+/// ?- ancestor(X, "a1b2c3").
+/// ```
+///
+/// Identifiers starting with an uppercase letter or `_` are variables;
+/// everything else must be a double-quoted string constant. Exactly one
+/// `?-` goal line is required.
+pub fn parse(source: &str) -> Result<Program, StoreError> {
+    let mut rules = Vec::new();
+    let mut goal = None;
+
+    for raw_line in split_statements(source) {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(goal_src) = line.strip_prefix("?-") {
+            if goal.is_some() {
+                return Err(StoreError::InvalidDatalogProgram("query has more than one '?-' goal".into()));
+            }
+            goal = Some(parse_atom(goal_src.trim())?);
+            continue;
+        }
+        let Some((head_src, body_src)) = line.split_once(":-") else {
+            return Err(StoreError::InvalidDatalogProgram(format!("rule missing ':-': '{}'", line)));
+        };
+        let head = parse_atom(head_src.trim())?;
+        let body = split_top_level_commas(body_src.trim())
+            .into_iter()
+            .map(|atom_src| parse_atom(atom_src.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if body.is_empty() {
+            return Err(StoreError::InvalidDatalogProgram(format!("rule has an empty body: '{}'", line)));
+        }
+        rules.push(Rule { head, body });
+    }
+
+    let goal = goal.ok_or_else(|| StoreError::InvalidDatalogProgram("program has no '?-' goal".into()))?;
+    Ok(Program { rules, goal })
+}
+
+/// Split a program into its `.`-terminated statements, ignoring any `.`
+/// that falls inside a double-quoted string constant — e.g. the
+/// `author_email` field of `intent(id, author_email, statement)` routinely
+/// holds a literal email address like `"alice@example.com"`.
+fn split_statements(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    for c in s.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            '.' if !in_quotes => {
+                parts.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Split `relation(t1, t2), relation2(t3)` on the commas that separate
+/// atoms (not the ones inside an atom's own `(...)`).
+fn split_top_level_commas(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut current = String::new();
+    for c in s.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' => {
+                depth -= 1;
+                current.push(c);
+            }
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+fn parse_atom(s: &str) -> Result<Atom, StoreError> {
+    let open = s
+        .find('(')
+        .ok_or_else(|| StoreError::InvalidDatalogProgram(format!("atom missing '(': '{}'", s)))?;
+    let close = s
+        .rfind(')')
+        .ok_or_else(|| StoreError::InvalidDatalogProgram(format!("atom missing ')': '{}'", s)))?;
+    if close < open {
+        return Err(StoreError::InvalidDatalogProgram(format!("malformed atom: '{}'", s)));
+    }
+    let relation = s[..open].trim().to_string();
+    if relation.is_empty() {
+        return Err(StoreError::InvalidDatalogProgram(format!("atom missing a relation name: '{}'", s)));
+    }
+    let terms = s[open + 1..close]
+        .split(',')
+        .map(|t| parse_term(t.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Atom { relation, terms })
+}
+
+fn parse_term(s: &str) -> Result<Term, StoreError> {
+    if s.is_empty() {
+        return Err(StoreError::InvalidDatalogProgram("empty term".into()));
+    }
+    if let Some(inner) = s.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        return Ok(Term::Const(inner.to_string()));
+    }
+    let first = s.chars().next().unwrap();
+    if first.is_uppercase() || first == '_' {
+        Ok(Term::Var(s.to_string()))
+    } else {
+        Err(StoreError::InvalidDatalogProgram(format!(
+            "term '{}' must be a variable (starting uppercase/_) or a \"quoted\" constant",
+            s
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap as StdHashMap;
+    use telos_core::object::intent::Author;
+    use telos_core::object::Intent;
+
+    fn make_odb() -> (tempfile::TempDir, ObjectDatabase) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        (dir, odb)
+    }
+
+    fn make_intent(statement: &str, parents: Vec<telos_core::hash::ObjectId>) -> Intent {
+        Intent {
+            author: Author { name: "Test".into(), email: "test@test.com".into() },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents,
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: StdHashMap::new(),
+        }
+    }
+
+    #[test]
+    fn transitive_ancestor_rule_reaches_the_whole_chain() {
+        let (_dir, odb) = make_odb();
+        let root = odb.write(&TelosObject::Intent(make_intent("Root", vec![]))).unwrap();
+        let mid = odb
+            .write(&TelosObject::Intent(make_intent("Mid", vec![root.clone()])))
+            .unwrap();
+        let leaf = odb
+            .write(&TelosObject::Intent(make_intent("Leaf", vec![mid.clone()])))
+            .unwrap();
+
+        let program = parse(&format!(
+            "ancestor(X, Y) :- parent(X, Y).\nancestor(X, Y) :- parent(X, Z), ancestor(Z, Y).\n?- ancestor(\"{}\", Y).",
+            leaf.hex()
+        ))
+        .unwrap();
+
+        let facts = base_facts(&odb).unwrap();
+        let derived = evaluate(facts, &program.rules);
+        let (columns, rows) = answer(&program.goal, &derived);
+
+        assert_eq!(columns, vec!["Y".to_string()]);
+        let reached: HashSet<String> = rows.into_iter().map(|r| r[0].clone()).collect();
+        assert!(reached.contains(&mid.hex().to_string()));
+        assert!(reached.contains(&root.hex().to_string()));
+        assert_eq!(reached.len(), 2);
+    }
+
+    #[test]
+    fn parse_rejects_rule_without_arrow() {
+        assert!(parse("foo(X).\n?- foo(X).").is_err());
+    }
+
+    #[test]
+    fn parse_does_not_split_a_statement_on_a_period_inside_a_quoted_constant() {
+        // A literal email address in a quoted string constant has a '.' in
+        // it; the statement splitter must not treat that as a terminator.
+        let program = parse("?- intent(Id, \"alice@example.com\", Statement).").unwrap();
+        assert_eq!(program.goal.relation, "intent");
+        assert_eq!(program.goal.terms[1], Term::Const("alice@example.com".to_string()));
+    }
+}
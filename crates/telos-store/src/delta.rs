@@ -0,0 +1,180 @@
+//! Minimal copy/insert binary delta encoding, used by [`crate::pack`] to
+//! store near-duplicate objects (a revised `Constraint` against the one it
+//! supersedes, an `Intent` against a parent) as small diffs instead of
+//! full independent zstd frames.
+//!
+//! This isn't a general-purpose diff algorithm — objects here are small
+//! JSON blobs, so a single-pass greedy block match against the base (in
+//! the spirit of git's packfile deltas, minus the rolling checksum) is
+//! enough to catch the common case of "one field changed."
+
+use std::collections::HashMap;
+
+const BLOCK: usize = 16;
+
+fn write_varint(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn read_varint(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut value = 0u64;
+    let mut shift = 0u32;
+    for (i, &byte) in bytes.iter().enumerate() {
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+        shift += 7;
+        if shift >= 64 {
+            return None;
+        }
+    }
+    None
+}
+
+/// Encode `target` as a delta against `base`: a sequence of copy-from-base
+/// and literal-insert instructions. Always round-trips through
+/// [`apply_delta`] given the same `base`, regardless of how similar the
+/// two inputs actually are (an unrelated `base`/`target` pair just produces
+/// one big literal insert).
+pub fn encode_delta(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut index: HashMap<&[u8], usize> = HashMap::new();
+    if base.len() >= BLOCK {
+        for i in 0..=(base.len() - BLOCK) {
+            index.entry(&base[i..i + BLOCK]).or_insert(i);
+        }
+    }
+
+    let mut out = Vec::new();
+    write_varint(base.len() as u64, &mut out);
+    write_varint(target.len() as u64, &mut out);
+
+    let mut literal = Vec::new();
+    let mut pos = 0;
+    while pos < target.len() {
+        let matched = if pos + BLOCK <= target.len() {
+            index.get(&target[pos..pos + BLOCK]).copied()
+        } else {
+            None
+        };
+
+        if let Some(base_start) = matched {
+            let mut len = BLOCK;
+            while pos + len < target.len()
+                && base_start + len < base.len()
+                && target[pos + len] == base[base_start + len]
+            {
+                len += 1;
+            }
+            flush_literal(&mut out, &mut literal);
+            out.push(0x00); // Copy
+            write_varint(base_start as u64, &mut out);
+            write_varint(len as u64, &mut out);
+            pos += len;
+        } else {
+            literal.push(target[pos]);
+            pos += 1;
+        }
+    }
+    flush_literal(&mut out, &mut literal);
+
+    out
+}
+
+fn flush_literal(out: &mut Vec<u8>, literal: &mut Vec<u8>) {
+    if literal.is_empty() {
+        return;
+    }
+    out.push(0x01); // Insert
+    write_varint(literal.len() as u64, out);
+    out.extend_from_slice(literal);
+    literal.clear();
+}
+
+/// Reconstruct the target bytes a delta (produced by [`encode_delta`])
+/// encodes, given the same `base` bytes used to produce it. Returns `None`
+/// on a malformed delta or a `base` that doesn't match the one the delta
+/// was built against.
+pub fn apply_delta(base: &[u8], delta: &[u8]) -> Option<Vec<u8>> {
+    let (base_len, consumed) = read_varint(delta)?;
+    if base_len as usize != base.len() {
+        return None;
+    }
+    let mut cursor = consumed;
+    let (target_len, consumed) = read_varint(&delta[cursor..])?;
+    cursor += consumed;
+
+    let mut out = Vec::with_capacity(target_len as usize);
+    while cursor < delta.len() {
+        let tag = delta[cursor];
+        cursor += 1;
+        match tag {
+            0x00 => {
+                let (offset, consumed) = read_varint(&delta[cursor..])?;
+                cursor += consumed;
+                let (len, consumed) = read_varint(&delta[cursor..])?;
+                cursor += consumed;
+                out.extend_from_slice(base.get(offset as usize..(offset as usize + len as usize))?);
+            }
+            0x01 => {
+                let (len, consumed) = read_varint(&delta[cursor..])?;
+                cursor += consumed;
+                let len = len as usize;
+                out.extend_from_slice(delta.get(cursor..cursor + len)?);
+                cursor += len;
+            }
+            _ => return None,
+        }
+    }
+
+    if out.len() == target_len as usize {
+        Some(out)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta_round_trips_similar_blobs() {
+        let base = b"the quick brown fox jumps over the lazy dog, again and again and again";
+        let target = b"the quick brown FOX jumps over the lazy dog, again and again and again!!";
+        let delta = encode_delta(base, target);
+        assert!(delta.len() < target.len());
+        assert_eq!(apply_delta(base, &delta).unwrap(), target.to_vec());
+    }
+
+    #[test]
+    fn delta_round_trips_unrelated_blobs() {
+        let base = b"completely different content altogether, nothing alike here";
+        let target = b"not remotely similar to the base string above, zero overlap";
+        let delta = encode_delta(base, target);
+        assert_eq!(apply_delta(base, &delta).unwrap(), target.to_vec());
+    }
+
+    #[test]
+    fn delta_round_trips_empty_base() {
+        let target = b"brand new object with no base to diff against";
+        let delta = encode_delta(b"", target);
+        assert_eq!(apply_delta(b"", &delta).unwrap(), target.to_vec());
+    }
+
+    #[test]
+    fn apply_delta_rejects_mismatched_base() {
+        let base = b"original base bytes";
+        let target = b"original base bytes, extended a bit further";
+        let delta = encode_delta(base, target);
+        assert_eq!(apply_delta(b"a different base entirely", &delta), None);
+    }
+}
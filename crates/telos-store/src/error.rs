@@ -40,4 +40,52 @@ pub enum StoreError {
 
     #[error("invalid stream name '{0}': {1}")]
     InvalidStreamName(String, String),
+
+    #[error("encryption error: {0}")]
+    EncryptionError(String),
+
+    #[error("keystore error: {0}")]
+    KeystoreError(String),
+
+    #[error("remote not found: {0}")]
+    RemoteNotFound(String),
+
+    #[error("remote sync error: {0}")]
+    RemoteSyncError(String),
+
+    #[error("cycle detected in intent DAG: {0}")]
+    CycleDetected(String),
+
+    #[error("index store error: {0}")]
+    IndexError(String),
+
+    #[error("config error: {0}")]
+    ConfigError(String),
+
+    #[error("invalid pagination cursor: {0}")]
+    InvalidCursor(String),
+
+    #[error("object integrity check failed: expected {expected}, got {actual}")]
+    IntegrityError { expected: String, actual: String },
+
+    #[error("invalid dump archive: {0}")]
+    InvalidDump(String),
+
+    #[error("unsupported dump version: {0}")]
+    UnsupportedDumpVersion(u32),
+
+    #[error("signing error: {0}")]
+    SigningError(String),
+
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("invalid reference: {0}")]
+    InvalidReference(String),
+
+    #[error("invalid capability token: {0}")]
+    InvalidToken(String),
+
+    #[error("invalid datalog program: {0}")]
+    InvalidDatalogProgram(String),
 }
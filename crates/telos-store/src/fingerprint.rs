@@ -0,0 +1,90 @@
+//! Content fingerprints for [`CodeBinding`] targets.
+//!
+//! Turns a binding from a plain existence check into a change-aware one:
+//! `telos bind` captures a fingerprint of the bound file (or, when a line
+//! span is known, just the span) at bind time; `telos check --bindings`
+//! recomputes it later and reports whether the target drifted out from
+//! under the binding even though the file is still there.
+
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use telos_core::object::code_binding::CodeBinding;
+
+/// Compute the current fingerprint for `binding`'s target under `root`, or
+/// `None` if the file doesn't exist (an unresolved binding has nothing to
+/// fingerprint).
+///
+/// For a binding with a known line span, only those lines are hashed — a
+/// `function`/`symbol` binding's fingerprint is then stable against edits
+/// elsewhere in the file. Without a span (the common case today, since
+/// nothing yet extracts symbol spans automatically), the whole file is
+/// hashed.
+pub fn compute_fingerprint(root: &Path, binding: &CodeBinding) -> Option<String> {
+    let contents = std::fs::read_to_string(root.join(&binding.path)).ok()?;
+
+    let target = match binding.span {
+        Some((start, end)) => contents
+            .lines()
+            .skip(start.saturating_sub(1) as usize)
+            .take((end.saturating_sub(start) + 1) as usize)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => contents,
+    };
+
+    Some(hex::encode(Sha256::digest(target.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use telos_core::hash::ObjectId;
+    use telos_core::object::code_binding::{BindingResolution, BindingType};
+
+    fn make_binding(path: &str, span: Option<(u32, u32)>) -> CodeBinding {
+        CodeBinding {
+            path: path.into(),
+            symbol: None,
+            span,
+            binding_type: BindingType::File,
+            resolution: BindingResolution::Unchecked,
+            bound_object: ObjectId::hash(b"dummy"),
+            fingerprint: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn missing_file_has_no_fingerprint() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let binding = make_binding("does-not-exist.rs", None);
+        assert!(compute_fingerprint(dir.path(), &binding).is_none());
+    }
+
+    #[test]
+    fn whole_file_fingerprint_changes_with_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+        let binding = make_binding("a.rs", None);
+        let first = compute_fingerprint(dir.path(), &binding).unwrap();
+
+        std::fs::write(dir.path().join("a.rs"), "fn a() { /* changed */ }\n").unwrap();
+        let second = compute_fingerprint(dir.path(), &binding).unwrap();
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn span_fingerprint_ignores_edits_outside_the_span() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "line1\nline2\nline3\n").unwrap();
+        let binding = make_binding("a.rs", Some((2, 2)));
+        let first = compute_fingerprint(dir.path(), &binding).unwrap();
+
+        std::fs::write(dir.path().join("a.rs"), "line1 changed\nline2\nline3\n").unwrap();
+        let second = compute_fingerprint(dir.path(), &binding).unwrap();
+
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,247 @@
+//! Graphviz DOT / JSON adjacency export of the Telos knowledge graph.
+//!
+//! Every [`TelosObject`] is a node; [`TelosObject::links`] — the same edge
+//! set `sync` already walks for cross-repo transfer — doubles
+//! as the graph's edges here: an intent's `parents`, a constraint's
+//! `source_intent`, a decision's `intent_id`, a code binding's
+//! `bound_object`, and a change set's member references each draw one edge.
+//! [`reachable_from`] restricts that to the subgraph connected to a single
+//! seed object, for `telos graph --impact <id>`.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use telos_core::hash::ObjectId;
+use telos_core::object::TelosObject;
+
+/// One exported node: its id, kind, a short display label, and the ids it
+/// links to (by hex, so this serializes directly as the `--json` form).
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub kind: &'static str,
+    pub label: String,
+    pub links: Vec<String>,
+}
+
+/// Truncate `s` to `max_chars`, so a long statement doesn't blow up node
+/// labels in the rendered graph.
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        let head: String = s.chars().take(max_chars).collect();
+        format!("{}…", head)
+    }
+}
+
+fn label_for(obj: &TelosObject) -> String {
+    match obj {
+        TelosObject::Intent(o) => truncate(&o.statement, 40),
+        TelosObject::BehaviorDiff(o) => format!("diff of {}", o.intent_id.short()),
+        TelosObject::IntentStreamSnapshot(o) => format!("{}@{}", o.name, o.tip.short()),
+        TelosObject::DecisionRecord(o) => truncate(&o.question, 40),
+        TelosObject::Constraint(o) => truncate(&o.statement, 40),
+        TelosObject::CodeBinding(o) => truncate(o.symbol.as_deref().unwrap_or(&o.path), 40),
+        TelosObject::AgentOperation(o) => truncate(&o.summary, 40),
+        TelosObject::ChangeSet(o) => format!("change_set {}", truncate(&o.git_commit, 12)),
+    }
+}
+
+/// Graphviz shape/fill color per object kind, keyed by `type_tag()` so it
+/// stays in sync with [`TelosObject::type_tag`] without a second match on
+/// the enum itself.
+fn shape_and_color(kind: &str) -> (&'static str, &'static str) {
+    match kind {
+        "intent" => ("box", "lightblue"),
+        "behavior_diff" => ("note", "lavender"),
+        "intent_stream_snapshot" => ("folder", "khaki"),
+        "decision_record" => ("diamond", "lightyellow"),
+        "constraint" => ("hexagon", "lightpink"),
+        "code_binding" => ("component", "lightgray"),
+        "agent_operation" => ("ellipse", "palegreen"),
+        "change_set" => ("tab", "orange"),
+        _ => ("box", "white"),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Build [`GraphNode`]s for `objects`.
+pub fn build_nodes(objects: &[(ObjectId, TelosObject)]) -> Vec<GraphNode> {
+    objects
+        .iter()
+        .map(|(id, obj)| GraphNode {
+            id: id.hex().to_string(),
+            kind: obj.type_tag(),
+            label: label_for(obj),
+            links: obj.links().iter().map(|l| l.hex().to_string()).collect(),
+        })
+        .collect()
+}
+
+/// Render `nodes` as a Graphviz `digraph`. Edges to a node outside `nodes`
+/// (e.g. trimmed out of a `--impact` subgraph) are dropped rather than
+/// drawn to a node that was never declared.
+pub fn to_dot(nodes: &[GraphNode]) -> String {
+    let present: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+
+    let mut out = String::from("digraph telos {\n");
+    for node in nodes {
+        let (shape, color) = shape_and_color(node.kind);
+        out.push_str(&format!(
+            "  \"{}\" [label=\"{}\\n{} {}\", shape={}, style=filled, fillcolor={}];\n",
+            node.id,
+            escape(&node.label),
+            node.kind,
+            &node.id[..node.id.len().min(8)],
+            shape,
+            color,
+        ));
+    }
+    for node in nodes {
+        for target in &node.links {
+            if present.contains(target.as_str()) {
+                out.push_str(&format!("  \"{}\" -> \"{}\";\n", node.id, target));
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Breadth-first walk of the whole object database starting at `seed`,
+/// following [`TelosObject::links`] in both directions — a node's own
+/// links, and every other node that links to it — so the result is
+/// "everything connected to `seed`", not just what it points at.
+pub fn reachable_from(
+    odb: &ObjectDatabase,
+    seed: &ObjectId,
+) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+    let all = odb.iter_all()?;
+
+    let mut forward: HashMap<ObjectId, Vec<ObjectId>> = HashMap::new();
+    let mut reverse: HashMap<ObjectId, Vec<ObjectId>> = HashMap::new();
+    for (id, obj) in &all {
+        for link in obj.links() {
+            forward.entry(id.clone()).or_default().push(link.clone());
+            reverse.entry(link).or_default().push(id.clone());
+        }
+    }
+    let by_id: HashMap<ObjectId, TelosObject> = all.into_iter().collect();
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(seed.clone());
+    queue.push_back(seed.clone());
+
+    let mut result = Vec::new();
+    while let Some(id) = queue.pop_front() {
+        let Some(obj) = by_id.get(&id) else { continue };
+        result.push((id.clone(), obj.clone()));
+        let neighbors = forward
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .chain(reverse.get(&id).into_iter().flatten());
+        for next in neighbors {
+            if visited.insert(next.clone()) {
+                queue.push_back(next.clone());
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap as StdHashMap;
+    use telos_core::object::constraint::{Constraint, ConstraintStatus};
+    use telos_core::object::constraint::ConstraintSeverity;
+    use telos_core::object::intent::{Author, Intent};
+
+    fn make_odb() -> (tempfile::TempDir, ObjectDatabase) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        (dir, odb)
+    }
+
+    fn make_intent(statement: &str, parents: Vec<ObjectId>) -> Intent {
+        Intent {
+            author: Author {
+                name: "Test".into(),
+                email: "test@test.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents,
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: StdHashMap::new(),
+        }
+    }
+
+    #[test]
+    fn to_dot_draws_edge_per_link_and_drops_dangling_targets() {
+        let (_dir, odb) = make_odb();
+        let root = odb.write(&TelosObject::Intent(make_intent("Root", vec![]))).unwrap();
+        let child = odb
+            .write(&TelosObject::Intent(make_intent("Child", vec![root.clone()])))
+            .unwrap();
+
+        let nodes = build_nodes(&[
+            (root.clone(), TelosObject::Intent(make_intent("Root", vec![]))),
+            (child.clone(), TelosObject::Intent(make_intent("Child", vec![root.clone()]))),
+        ]);
+        let dot = to_dot(&nodes);
+
+        assert!(dot.starts_with("digraph telos {\n"));
+        assert!(dot.contains(&format!("\"{}\" -> \"{}\"", child.hex(), root.hex())));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn reachable_from_walks_links_in_both_directions() {
+        let (_dir, odb) = make_odb();
+        let root = odb.write(&TelosObject::Intent(make_intent("Root", vec![]))).unwrap();
+        let child = odb
+            .write(&TelosObject::Intent(make_intent("Child", vec![root.clone()])))
+            .unwrap();
+        let unrelated = odb.write(&TelosObject::Intent(make_intent("Unrelated", vec![]))).unwrap();
+
+        let constraint = Constraint {
+            author: Author {
+                name: "Test".into(),
+                email: "test@test.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: "Must hash passwords".into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Active,
+            source_intent: root.clone(),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: StdHashMap::new(),
+        };
+        let constraint_id = odb.write(&TelosObject::Constraint(constraint)).unwrap();
+
+        // Seeding from `root` should reach `child` (links to root) and
+        // `constraint` (links to root via source_intent, found through the
+        // reverse edge), but not `unrelated`.
+        let result = reachable_from(&odb, &root).unwrap();
+        let ids: HashSet<ObjectId> = result.into_iter().map(|(id, _)| id).collect();
+        assert!(ids.contains(&root));
+        assert!(ids.contains(&child));
+        assert!(ids.contains(&constraint_id));
+        assert!(!ids.contains(&unrelated));
+    }
+}
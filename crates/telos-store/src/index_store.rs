@@ -1,18 +1,93 @@
 //! Index layer for accelerating Telos queries.
 //!
-//! Indexes are caches stored in `.telos/indexes/`. They can be rebuilt
-//! from the object store at any time via `rebuild_all()`.
-
+//! Indexes live in an embedded transactional KV store (sled) under
+//! `.telos/indexes/kv` — one tree per index (`impact`, `codepath`,
+//! `symbols`, `text`), plus a `meta` tree holding per-index entry counters
+//! that are published as gauges without a full scan. Each entry in
+//! `impact`/`codepath`/`symbols` is stored under a composite key
+//! `b"{tag}\0{object_id}"` with the serialized [`IndexEntry`]/
+//! [`PathIndexEntry`] as the value; `by_impact`/`by_path`/`by_symbol` are
+//! `scan_prefix(tag)` range reads. `text` is keyed directly by normalized
+//! token (no object id suffix) with a sorted, deduped posting list of
+//! [`ObjectId`] hexes as the value — since sled trees are key-ordered,
+//! `scan_prefix` on a token's own bytes doubles as prefix search with no
+//! extra storage (see [`search_text`]). A write that touches more than one
+//! tree (a code binding updates both `codepath` and `symbols`) goes
+//! through a single sled transaction so it's atomic, and `rebuild_all`
+//! clears and repopulates every tree inside one transaction. Indexes can
+//! be rebuilt from the object store at any time via `rebuild_all()`.
+
+use crate::bloom::HaveFilter;
 use crate::error::StoreError;
 use crate::odb::ObjectDatabase;
+use metrics::gauge;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sled::transaction::{ConflictableTransactionError, TransactionalTree};
+use std::collections::BTreeSet;
 use std::fs;
-use std::io::Write;
 use std::path::PathBuf;
 use telos_core::hash::ObjectId;
 use telos_core::object::TelosObject;
 
+/// Minimum token length under which no separate prefix search is useful
+/// (the whole token already acts as the query).
+const MIN_TOKEN_LEN: usize = 2;
+
+/// Longest token actually indexed; longer words are truncated so a
+/// pathological field (e.g. a base64 blob pasted into a statement) can't
+/// blow up the text tree.
+const MAX_TOKEN_LEN: usize = 32;
+
+/// Lowercase `text` and split it into tokens on non-alphanumeric
+/// boundaries, dropping anything shorter than [`MIN_TOKEN_LEN`] and
+/// truncating anything longer than [`MAX_TOKEN_LEN`].
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| s.len() >= MIN_TOKEN_LEN)
+        .map(|s| s.chars().take(MAX_TOKEN_LEN).collect::<String>())
+        .collect()
+}
+
+/// Tokenize every field and dedup the result, so a field list such as an
+/// intent's `statement` plus its `constraints` contributes each distinct
+/// token to the `text` tree only once.
+fn searchable_tokens(fields: &[&str]) -> Vec<String> {
+    let mut tokens: BTreeSet<String> = BTreeSet::new();
+    for field in fields {
+        tokens.extend(tokenize(field));
+    }
+    tokens.into_iter().collect()
+}
+
+/// Per-tree distinct-key counts from an [`IndexStore::rebuild_all`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RebuildCounts {
+    pub impact: usize,
+    pub codepath: usize,
+    pub symbols: usize,
+    pub text: usize,
+}
+
+/// Which object kinds [`search_text`] can return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSearchKind {
+    Intent,
+    Constraint,
+    DecisionRecord,
+}
+
+impl TextSearchKind {
+    fn matches(self, obj: &TelosObject) -> bool {
+        matches!(
+            (self, obj),
+            (TextSearchKind::Intent, TelosObject::Intent(_))
+                | (TextSearchKind::Constraint, TelosObject::Constraint(_))
+                | (TextSearchKind::DecisionRecord, TelosObject::DecisionRecord(_))
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexEntry {
     pub id: String,
@@ -29,31 +104,25 @@ pub struct PathIndexEntry {
     pub binding_type: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct IndexFile<T> {
-    version: u32,
-    entries: HashMap<String, Vec<T>>,
+fn sled_err(e: sled::Error) -> StoreError {
+    StoreError::IndexError(e.to_string())
 }
 
-impl<T> Default for IndexFile<T> {
-    fn default() -> Self {
-        Self {
-            version: 2,
-            entries: HashMap::new(),
-        }
-    }
+fn sled_tx_err<E: std::fmt::Display>(e: sled::transaction::TransactionError<E>) -> StoreError {
+    StoreError::IndexError(e.to_string())
 }
 
 /// Manages query indexes stored in `.telos/indexes/`.
 pub struct IndexStore {
     indexes_dir: PathBuf,
+    db: sled::Db,
 }
 
 impl IndexStore {
     pub fn new(indexes_dir: impl Into<PathBuf>) -> Self {
-        Self {
-            indexes_dir: indexes_dir.into(),
-        }
+        let indexes_dir = indexes_dir.into();
+        let db = sled::open(indexes_dir.join("kv")).expect("failed to open index kv store");
+        Self { indexes_dir, db }
     }
 
     pub fn ensure_dir(&self) -> Result<(), StoreError> {
@@ -61,64 +130,98 @@ impl IndexStore {
         Ok(())
     }
 
-    fn impact_path(&self) -> PathBuf {
-        self.indexes_dir.join("impact.json")
+    fn impact_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("impact").map_err(sled_err)
     }
 
-    fn codepath_path(&self) -> PathBuf {
-        self.indexes_dir.join("codepath.json")
+    fn codepath_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("codepath").map_err(sled_err)
     }
 
-    fn symbols_path(&self) -> PathBuf {
-        self.indexes_dir.join("symbols.json")
+    fn symbols_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("symbols").map_err(sled_err)
     }
 
-    fn load_index<T: for<'de> Deserialize<'de>>(&self, path: &PathBuf) -> IndexFile<T> {
-        match fs::read_to_string(path) {
-            Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
-            Err(_) => IndexFile::default(),
-        }
+    fn text_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("text").map_err(sled_err)
     }
 
-    fn save_index<T: Serialize>(&self, path: &PathBuf, index: &IndexFile<T>) -> Result<(), StoreError> {
-        self.ensure_dir()?;
-        let json = serde_json::to_string_pretty(index)?;
-        let tmp_path = path.with_extension("json.tmp");
-        let mut f = fs::File::create(&tmp_path)?;
-        f.write_all(json.as_bytes())?;
-        f.flush()?;
-        fs::rename(&tmp_path, path)?;
+    fn meta_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("meta").map_err(sled_err)
+    }
+
+    fn have_filter_path(&self) -> PathBuf {
+        self.indexes_dir.join("have_filter.json")
+    }
+
+    /// Composite key: `{tag}\0{object_id}`, so `scan_prefix(tag)` returns
+    /// every entry under that tag without scanning the whole tree.
+    fn composite_key(tag: &str, id: &str) -> Vec<u8> {
+        let mut key = Vec::with_capacity(tag.len() + 1 + id.len());
+        key.extend_from_slice(tag.as_bytes());
+        key.push(0);
+        key.extend_from_slice(id.as_bytes());
+        key
+    }
+
+    fn meta_key(index_name: &str) -> Vec<u8> {
+        format!("{}_count", index_name).into_bytes()
+    }
+
+    fn tx_insert<T: Serialize>(
+        tree: &TransactionalTree,
+        meta: &TransactionalTree,
+        index_name: &str,
+        tag: &str,
+        entry: &T,
+    ) -> Result<(), ConflictableTransactionError<StoreError>> {
+        let key = Self::composite_key(tag, &entry_id(entry));
+        let value = serde_json::to_vec(entry)
+            .map_err(|e| ConflictableTransactionError::Abort(StoreError::Json(e)))?;
+        tree.insert(key, value)?;
+
+        let meta_key = Self::meta_key(index_name);
+        let count = meta
+            .get(&meta_key)?
+            .map(|v| u64::from_be_bytes(v.as_ref().try_into().unwrap_or([0; 8])))
+            .unwrap_or(0);
+        meta.insert(meta_key, &(count + 1).to_be_bytes())?;
         Ok(())
     }
 
     /// Update indexes for a newly written object.
+    #[tracing::instrument(skip(self, obj), fields(object.type = obj.type_tag()))]
     pub fn update_for_object(&self, id: &ObjectId, obj: &TelosObject) -> Result<(), StoreError> {
         match obj {
             TelosObject::Intent(intent) => {
                 if !intent.impacts.is_empty() {
-                    let mut index: IndexFile<IndexEntry> = self.load_index(&self.impact_path());
                     let entry = IndexEntry {
                         id: id.hex().to_string(),
                         object_type: "intent".into(),
                     };
-                    for tag in &intent.impacts {
-                        index.entries.entry(tag.clone()).or_default().push(entry.clone());
-                    }
-                    self.save_index(&self.impact_path(), &index)?;
+                    self.insert_into_impact(&intent.impacts, &entry)?;
                 }
+                let mut searchable = vec![intent.statement.as_str()];
+                searchable.extend(intent.constraints.iter().map(String::as_str));
+                self.index_text(id, &searchable)?;
             }
             TelosObject::Constraint(c) => {
                 if !c.impacts.is_empty() {
-                    let mut index: IndexFile<IndexEntry> = self.load_index(&self.impact_path());
                     let entry = IndexEntry {
                         id: id.hex().to_string(),
                         object_type: "constraint".into(),
                     };
-                    for tag in &c.impacts {
-                        index.entries.entry(tag.clone()).or_default().push(entry.clone());
-                    }
-                    self.save_index(&self.impact_path(), &index)?;
+                    self.insert_into_impact(&c.impacts, &entry)?;
+                }
+                self.index_text(id, &[c.statement.as_str()])?;
+            }
+            TelosObject::DecisionRecord(record) => {
+                let mut searchable = vec![record.question.as_str(), record.decision.as_str()];
+                if let Some(rationale) = &record.rationale {
+                    searchable.push(rationale.as_str());
                 }
+                searchable.extend(record.tags.iter().map(String::as_str));
+                self.index_text(id, &searchable)?;
             }
             TelosObject::CodeBinding(cb) => {
                 let entry = PathIndexEntry {
@@ -127,100 +230,338 @@ impl IndexStore {
                     symbol: cb.symbol.clone(),
                     binding_type: Some(format!("{:?}", cb.binding_type).to_lowercase()),
                 };
-                let mut codepath: IndexFile<PathIndexEntry> = self.load_index(&self.codepath_path());
-                codepath.entries.entry(cb.path.clone()).or_default().push(entry.clone());
-                self.save_index(&self.codepath_path(), &codepath)?;
-
-                if let Some(ref sym) = cb.symbol {
-                    let mut symbols: IndexFile<PathIndexEntry> = self.load_index(&self.symbols_path());
-                    symbols.entries.entry(sym.clone()).or_default().push(entry);
-                    self.save_index(&self.symbols_path(), &symbols)?;
-                }
+                let codepath = self.codepath_tree()?;
+                let symbols = self.symbols_tree()?;
+                let meta = self.meta_tree()?;
+                (&codepath, &symbols, &meta)
+                    .transaction(|(codepath, symbols, meta)| {
+                        Self::tx_insert(codepath, meta, "codepath", &cb.path, &entry)?;
+                        if let Some(ref sym) = entry.symbol {
+                            Self::tx_insert(symbols, meta, "symbols", sym, &entry)?;
+                        }
+                        Ok(())
+                    })
+                    .map_err(sled_tx_err)?;
             }
             _ => {}
         }
+        self.record_entry_count_gauges();
         Ok(())
     }
 
-    /// Rebuild all indexes from the object store.
-    pub fn rebuild_all(&self, odb: &ObjectDatabase) -> Result<(usize, usize, usize), StoreError> {
+    /// Tokenize `fields` and append `id` to each resulting token's posting
+    /// list in the `text` tree, so `search_text` can find this object by
+    /// any of those tokens (or a prefix of one, via `scan_prefix`).
+    fn index_text(&self, id: &ObjectId, fields: &[&str]) -> Result<(), StoreError> {
+        let tokens = searchable_tokens(fields);
+        if tokens.is_empty() {
+            return Ok(());
+        }
+
+        let text = self.text_tree()?;
+        let meta = self.meta_tree()?;
+        let hex = id.hex().to_string();
+        (&text, &meta)
+            .transaction(|(text, meta)| {
+                for token in &tokens {
+                    Self::tx_append_posting(text, meta, token, &hex)?;
+                }
+                Ok(())
+            })
+            .map_err(sled_tx_err)
+    }
+
+    /// Append `id` to `token`'s posting list, keeping it sorted and
+    /// deduped (append-only: an id already present is a no-op) so the
+    /// same object being reindexed doesn't grow the list unboundedly.
+    fn tx_append_posting(
+        tree: &TransactionalTree,
+        meta: &TransactionalTree,
+        token: &str,
+        id: &str,
+    ) -> Result<(), ConflictableTransactionError<StoreError>> {
+        let key = token.as_bytes();
+        let mut ids: Vec<String> = tree
+            .get(key)?
+            .map(|v| serde_json::from_slice(&v).unwrap_or_default())
+            .unwrap_or_default();
+
+        if let Err(pos) = ids.binary_search(&id.to_string()) {
+            ids.insert(pos, id.to_string());
+            let value = serde_json::to_vec(&ids)
+                .map_err(|e| ConflictableTransactionError::Abort(StoreError::Json(e)))?;
+            tree.insert(key, value)?;
+
+            let meta_key = Self::meta_key("text");
+            let count = meta
+                .get(&meta_key)?
+                .map(|v| u64::from_be_bytes(v.as_ref().try_into().unwrap_or([0; 8])))
+                .unwrap_or(0);
+            meta.insert(meta_key, &(count + 1).to_be_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Publish the `meta` tree's per-index entry counters as gauges so a
+    /// dashboard can watch index growth without a full scan.
+    fn record_entry_count_gauges(&self) {
+        let Ok(meta) = self.meta_tree() else { return };
+        for index_name in ["impact", "codepath", "symbols", "text"] {
+            if let Ok(Some(value)) = meta.get(Self::meta_key(index_name)) {
+                let count = u64::from_be_bytes(value.as_ref().try_into().unwrap_or([0; 8]));
+                gauge!("telos.index_store.entry_count", "index" => index_name).set(count as f64);
+            }
+        }
+    }
+
+    fn insert_into_impact(&self, tags: &[String], entry: &IndexEntry) -> Result<(), StoreError> {
+        let impact = self.impact_tree()?;
+        let meta = self.meta_tree()?;
+        (&impact, &meta)
+            .transaction(|(impact, meta)| {
+                for tag in tags {
+                    Self::tx_insert(impact, meta, "impact", tag, entry)?;
+                }
+                Ok(())
+            })
+            .map_err(sled_tx_err)
+    }
+
+    /// Rebuild all indexes from the object store. Clears and repopulates
+    /// every tree inside a single transaction.
+    #[tracing::instrument(skip(self, odb))]
+    pub fn rebuild_all(&self, odb: &ObjectDatabase) -> Result<RebuildCounts, StoreError> {
         self.ensure_dir()?;
-        let mut impact: IndexFile<IndexEntry> = IndexFile::default();
-        let mut codepath: IndexFile<PathIndexEntry> = IndexFile::default();
-        let mut symbols: IndexFile<PathIndexEntry> = IndexFile::default();
+
+        struct Pending {
+            impact: Vec<(String, IndexEntry)>,
+            codepath: Vec<(String, PathIndexEntry)>,
+            symbols: Vec<(String, PathIndexEntry)>,
+            text: Vec<(String, String)>,
+        }
+        let mut pending = Pending {
+            impact: Vec::new(),
+            codepath: Vec::new(),
+            symbols: Vec::new(),
+            text: Vec::new(),
+        };
 
         for (id, obj) in odb.iter_all()? {
+            let hex = id.hex().to_string();
             match &obj {
                 TelosObject::Intent(intent) => {
                     let entry = IndexEntry {
-                        id: id.hex().to_string(),
+                        id: hex.clone(),
                         object_type: "intent".into(),
                     };
                     for tag in &intent.impacts {
-                        impact.entries.entry(tag.clone()).or_default().push(entry.clone());
+                        pending.impact.push((tag.clone(), entry.clone()));
+                    }
+                    let mut searchable = vec![intent.statement.as_str()];
+                    searchable.extend(intent.constraints.iter().map(String::as_str));
+                    for token in searchable_tokens(&searchable) {
+                        pending.text.push((token, hex.clone()));
                     }
                 }
                 TelosObject::Constraint(c) => {
                     let entry = IndexEntry {
-                        id: id.hex().to_string(),
+                        id: hex.clone(),
                         object_type: "constraint".into(),
                     };
                     for tag in &c.impacts {
-                        impact.entries.entry(tag.clone()).or_default().push(entry.clone());
+                        pending.impact.push((tag.clone(), entry.clone()));
+                    }
+                    for token in searchable_tokens(&[c.statement.as_str()]) {
+                        pending.text.push((token, hex.clone()));
+                    }
+                }
+                TelosObject::DecisionRecord(record) => {
+                    let mut searchable = vec![record.question.as_str(), record.decision.as_str()];
+                    if let Some(rationale) = &record.rationale {
+                        searchable.push(rationale.as_str());
+                    }
+                    searchable.extend(record.tags.iter().map(String::as_str));
+                    for token in searchable_tokens(&searchable) {
+                        pending.text.push((token, hex.clone()));
                     }
                 }
                 TelosObject::CodeBinding(cb) => {
                     let entry = PathIndexEntry {
-                        id: id.hex().to_string(),
+                        id: hex.clone(),
                         object_type: "code_binding".into(),
                         symbol: cb.symbol.clone(),
                         binding_type: Some(format!("{:?}", cb.binding_type).to_lowercase()),
                     };
-                    codepath.entries.entry(cb.path.clone()).or_default().push(entry.clone());
+                    pending.codepath.push((cb.path.clone(), entry.clone()));
                     if let Some(ref sym) = cb.symbol {
-                        symbols.entries.entry(sym.clone()).or_default().push(entry);
+                        pending.symbols.push((sym.clone(), entry));
                     }
                 }
                 _ => {}
             }
         }
 
-        let impact_count = impact.entries.len();
-        let path_count = codepath.entries.len();
-        let sym_count = symbols.entries.len();
+        let impact_count = pending.impact.iter().map(|(tag, _)| tag).collect::<std::collections::HashSet<_>>().len();
+        let path_count = pending.codepath.iter().map(|(p, _)| p).collect::<std::collections::HashSet<_>>().len();
+        let sym_count = pending.symbols.iter().map(|(s, _)| s).collect::<std::collections::HashSet<_>>().len();
+        let text_count = pending.text.iter().map(|(tok, _)| tok).collect::<std::collections::HashSet<_>>().len();
+
+        let impact = self.impact_tree()?;
+        let codepath = self.codepath_tree()?;
+        let symbols = self.symbols_tree()?;
+        let text = self.text_tree()?;
+        let meta = self.meta_tree()?;
+
+        (&impact, &codepath, &symbols, &text, &meta)
+            .transaction(|(impact, codepath, symbols, text, meta)| {
+                impact.clear()?;
+                codepath.clear()?;
+                symbols.clear()?;
+                text.clear()?;
+                meta.clear()?;
+
+                for (tag, entry) in &pending.impact {
+                    Self::tx_insert(impact, meta, "impact", tag, entry)?;
+                }
+                for (path, entry) in &pending.codepath {
+                    Self::tx_insert(codepath, meta, "codepath", path, entry)?;
+                }
+                for (sym, entry) in &pending.symbols {
+                    Self::tx_insert(symbols, meta, "symbols", sym, entry)?;
+                }
+                for (token, hex) in &pending.text {
+                    Self::tx_append_posting(text, meta, token, hex)?;
+                }
+                Ok(())
+            })
+            .map_err(sled_tx_err)?;
+
+        self.record_entry_count_gauges();
+        Ok(RebuildCounts {
+            impact: impact_count,
+            codepath: path_count,
+            symbols: sym_count,
+            text: text_count,
+        })
+    }
 
-        self.save_index(&self.impact_path(), &impact)?;
-        self.save_index(&self.codepath_path(), &codepath)?;
-        self.save_index(&self.symbols_path(), &symbols)?;
+    /// Tokenize `query` and intersect the posting lists of its tokens
+    /// (each token matched via `scan_prefix` rather than exact lookup, so
+    /// a query token also matches any indexed token it's a prefix of),
+    /// then read and return only the matched objects whose kind is in
+    /// `kind_filter` — structured filters (impact/status/tag) are left to
+    /// the caller as a post-filter over this result, same as the
+    /// `query_*` functions already do.
+    pub fn search_text(
+        &self,
+        odb: &ObjectDatabase,
+        query: &str,
+        kind_filter: &[TextSearchKind],
+    ) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+        let tokens = tokenize(query);
+        if tokens.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let text = self.text_tree()?;
+        let mut matched: Option<BTreeSet<String>> = None;
+        for token in &tokens {
+            let mut hits: BTreeSet<String> = BTreeSet::new();
+            let prefix = token.as_bytes();
+            for result in text.scan_prefix(prefix) {
+                let (_, value) = result.map_err(sled_err)?;
+                let ids: Vec<String> = serde_json::from_slice(&value)?;
+                hits.extend(ids);
+            }
+            matched = Some(match matched {
+                Some(acc) => acc.intersection(&hits).cloned().collect(),
+                None => hits,
+            });
+        }
 
-        Ok((impact_count, path_count, sym_count))
+        let mut results = Vec::new();
+        for hex in matched.unwrap_or_default() {
+            let Ok(id) = ObjectId::parse(&hex) else {
+                continue;
+            };
+            let Ok(obj) = odb.read(&id) else {
+                continue;
+            };
+            if kind_filter.is_empty() || kind_filter.iter().any(|k| k.matches(&obj)) {
+                results.push((id, obj));
+            }
+        }
+        Ok(results)
     }
 
     /// Lookup entries by impact tag.
     pub fn by_impact(&self, tag: &str) -> Vec<IndexEntry> {
-        let index: IndexFile<IndexEntry> = self.load_index(&self.impact_path());
-        index.entries.get(tag).cloned().unwrap_or_default()
+        self.scan_prefix(&self.impact_tree().ok(), tag)
     }
 
     /// Lookup entries by file path.
     pub fn by_path(&self, path: &str) -> Vec<PathIndexEntry> {
-        let index: IndexFile<PathIndexEntry> = self.load_index(&self.codepath_path());
-        index.entries.get(path).cloned().unwrap_or_default()
+        self.scan_prefix(&self.codepath_tree().ok(), path)
     }
 
     /// Lookup entries by symbol name.
     pub fn by_symbol(&self, name: &str) -> Vec<PathIndexEntry> {
-        let index: IndexFile<PathIndexEntry> = self.load_index(&self.symbols_path());
-        index.entries.get(name).cloned().unwrap_or_default()
+        self.scan_prefix(&self.symbols_tree().ok(), name)
+    }
+
+    fn scan_prefix<T: for<'de> Deserialize<'de>>(&self, tree: &Option<sled::Tree>, tag: &str) -> Vec<T> {
+        let Some(tree) = tree else {
+            return Vec::new();
+        };
+        let prefix = {
+            let mut p = tag.as_bytes().to_vec();
+            p.push(0);
+            p
+        };
+        tree.scan_prefix(prefix)
+            .filter_map(|result| result.ok())
+            .filter_map(|(_, value)| serde_json::from_slice(&value).ok())
+            .collect()
+    }
+
+    /// Load the persisted "have" bloom filter, if one has been built.
+    pub fn load_have_filter(&self) -> Option<HaveFilter> {
+        let data = fs::read_to_string(self.have_filter_path()).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+
+    /// Rebuild the "have" bloom filter from every id in `odb` and persist it.
+    /// Returns the number of ids the filter was built over.
+    pub fn rebuild_have_filter(&self, odb: &ObjectDatabase) -> Result<usize, StoreError> {
+        let ids: Vec<ObjectId> = odb.iter_all()?.into_iter().map(|(id, _)| id).collect();
+        let filter = HaveFilter::build(&ids);
+
+        self.ensure_dir()?;
+        let json = serde_json::to_string(&filter)?;
+        let path = self.have_filter_path();
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json.as_bytes())?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(ids.len())
     }
 }
 
+fn entry_id<T: Serialize>(entry: &T) -> String {
+    // Every index entry type carries an `id` field; round-trip through JSON
+    // to pull it out generically rather than requiring a trait per entry type.
+    serde_json::to_value(entry)
+        .ok()
+        .and_then(|v| v.get("id").and_then(|id| id.as_str().map(str::to_string)))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use telos_core::object::code_binding::{BindingResolution, BindingType, CodeBinding};
     use telos_core::object::constraint::{Constraint, ConstraintSeverity, ConstraintStatus};
-    use telos_core::object::code_binding::{CodeBinding, BindingType, BindingResolution};
     use telos_core::object::intent::{Author, Intent};
 
     fn make_odb_and_index() -> (tempfile::TempDir, ObjectDatabase, IndexStore) {
@@ -262,6 +603,7 @@ mod tests {
             binding_type: BindingType::Function,
             resolution: BindingResolution::Unchecked,
             bound_object: ObjectId::hash(b"test"),
+            fingerprint: None,
             metadata: std::collections::HashMap::new(),
         });
         let id = odb.write(&cb).unwrap();
@@ -312,11 +654,93 @@ mod tests {
         assert!(index.by_impact("payments").is_empty());
 
         // Rebuild
-        let (impact_count, _path_count, _sym_count) = index.rebuild_all(&odb).unwrap();
-        assert!(impact_count > 0);
+        let counts = index.rebuild_all(&odb).unwrap();
+        assert!(counts.impact > 0);
+        assert!(counts.text > 0);
 
         // Now index should have entries
         let results = index.by_impact("payments");
         assert_eq!(results.len(), 2);
     }
+
+    #[test]
+    fn rebuild_all_indexes_text() {
+        let (_dir, odb, index) = make_odb_and_index();
+
+        let intent = TelosObject::Intent(Intent {
+            author: Author { name: "T".into(), email: "t@t".into() },
+            timestamp: Utc::now(),
+            statement: "rotate database credentials".into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents: vec![],
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: std::collections::HashMap::new(),
+        });
+        let id = odb.write(&intent).unwrap();
+
+        index.rebuild_all(&odb).unwrap();
+
+        let results = index.search_text(&odb, "credentials", &[]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, id);
+
+        let filtered = index
+            .search_text(&odb, "credentials", &[TextSearchKind::Constraint])
+            .unwrap();
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn search_text_is_live_after_update_for_object() {
+        let (_dir, odb, index) = make_odb_and_index();
+
+        let intent = TelosObject::Intent(Intent {
+            author: Author { name: "T".into(), email: "t@t".into() },
+            timestamp: Utc::now(),
+            statement: "rate limit the login endpoint".into(),
+            constraints: vec!["must not exceed 5 req/s".into()],
+            behavior_spec: vec![],
+            parents: vec![],
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: std::collections::HashMap::new(),
+        });
+        let id = odb.write(&intent).unwrap();
+        index.update_for_object(&id, &intent).unwrap();
+
+        let by_statement = index.search_text(&odb, "login", &[]).unwrap();
+        assert_eq!(by_statement.len(), 1);
+
+        let by_constraint = index.search_text(&odb, "exceed", &[]).unwrap();
+        assert_eq!(by_constraint.len(), 1);
+
+        assert!(index.search_text(&odb, "nonexistent", &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rebuild_and_load_have_filter() {
+        let (_dir, odb, index) = make_odb_and_index();
+        assert!(index.load_have_filter().is_none());
+
+        let intent = TelosObject::Intent(Intent {
+            author: Author { name: "T".into(), email: "t@t".into() },
+            timestamp: Utc::now(),
+            statement: "test".into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents: vec![],
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: std::collections::HashMap::new(),
+        });
+        let id = odb.write(&intent).unwrap();
+
+        let count = index.rebuild_have_filter(&odb).unwrap();
+        assert_eq!(count, 1);
+
+        let filter = index.load_have_filter().unwrap();
+        assert!(filter.contains(&id));
+    }
 }
@@ -0,0 +1,115 @@
+//! Wrapping/unwrapping of the repository's object-database data key.
+//!
+//! The data key itself is a random 32-byte XChaCha20-Poly1305 key used to
+//! encrypt object bytes at rest (see [`crate::crypto`]). It is never stored
+//! in the clear: a `Keystore` wraps it before it's written to
+//! `.telos/keystore.json` and unwraps it on open. [`PassphraseKeystore`] is
+//! the only implementation today; the trait exists so a future external-KMS
+//! backed keystore can be swapped in without touching `ObjectDatabase`.
+
+use crate::crypto::{self, DataKey};
+use crate::error::StoreError;
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+
+/// Derives and wraps/unwraps a [`DataKey`].
+pub trait Keystore {
+    /// Wrap (encrypt) `data_key` for storage on disk.
+    fn wrap(&self, data_key: &DataKey) -> Result<WrappedKey, StoreError>;
+
+    /// Unwrap a previously wrapped key.
+    fn unwrap(&self, wrapped: &WrappedKey) -> Result<DataKey, StoreError>;
+}
+
+/// On-disk representation of a wrapped data key, as stored in
+/// `.telos/keystore.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedKey {
+    /// KDF salt (argon2id), hex-encoded.
+    pub salt: String,
+    /// AEAD nonce used to encrypt the data key, hex-encoded.
+    pub nonce: String,
+    /// Encrypted data key (ciphertext + tag), hex-encoded.
+    pub ciphertext: String,
+}
+
+/// Derives a key-encryption key from a user passphrase via Argon2id, then
+/// uses it to wrap/unwrap the data key with XChaCha20-Poly1305.
+pub struct PassphraseKeystore {
+    passphrase: String,
+}
+
+impl PassphraseKeystore {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self {
+            passphrase: passphrase.into(),
+        }
+    }
+
+    fn derive_kek(&self, salt: &[u8; 16]) -> Result<DataKey, StoreError> {
+        let mut kek = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(self.passphrase.as_bytes(), salt, &mut kek)
+            .map_err(|e| StoreError::KeystoreError(format!("key derivation failed: {e}")))?;
+        Ok(kek)
+    }
+}
+
+impl Keystore for PassphraseKeystore {
+    fn wrap(&self, data_key: &DataKey) -> Result<WrappedKey, StoreError> {
+        let salt: [u8; 16] = crypto::random_bytes();
+        let kek = self.derive_kek(&salt)?;
+        let (nonce, ciphertext) = crypto::encrypt(&kek, data_key)?;
+        Ok(WrappedKey {
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    fn unwrap(&self, wrapped: &WrappedKey) -> Result<DataKey, StoreError> {
+        let salt: [u8; 16] = decode_fixed(&wrapped.salt, "salt")?;
+        let nonce: [u8; 24] = decode_fixed(&wrapped.nonce, "nonce")?;
+        let ciphertext =
+            hex::decode(&wrapped.ciphertext).map_err(|e| StoreError::KeystoreError(e.to_string()))?;
+        let kek = self.derive_kek(&salt)?;
+        let plaintext = crypto::decrypt(&kek, &nonce, &ciphertext)?;
+        let key: DataKey = plaintext
+            .try_into()
+            .map_err(|_| StoreError::KeystoreError("unwrapped key has wrong length".into()))?;
+        Ok(key)
+    }
+}
+
+fn decode_fixed<const N: usize>(hex_str: &str, field: &str) -> Result<[u8; N], StoreError> {
+    let bytes = hex::decode(hex_str).map_err(|e| StoreError::KeystoreError(e.to_string()))?;
+    bytes
+        .try_into()
+        .map_err(|_| StoreError::KeystoreError(format!("{field} has wrong length")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_unwrap_round_trip() {
+        let keystore = PassphraseKeystore::new("correct horse battery staple");
+        let data_key: DataKey = crypto::random_bytes();
+
+        let wrapped = keystore.wrap(&data_key).unwrap();
+        let unwrapped = keystore.unwrap(&wrapped).unwrap();
+
+        assert_eq!(data_key, unwrapped);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails_to_unwrap() {
+        let keystore = PassphraseKeystore::new("correct horse battery staple");
+        let data_key: DataKey = crypto::random_bytes();
+        let wrapped = keystore.wrap(&data_key).unwrap();
+
+        let wrong = PassphraseKeystore::new("incorrect horse");
+        assert!(wrong.unwrap(&wrapped).is_err());
+    }
+}
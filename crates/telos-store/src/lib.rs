@@ -0,0 +1,36 @@
+pub mod agent_queue;
+pub mod auth;
+pub mod backend;
+pub mod bloom;
+pub mod changelog;
+pub mod config_reload;
+pub mod crypto;
+pub mod cursor;
+pub mod datalog;
+pub mod delta;
+pub mod dump;
+pub mod error;
+pub mod export;
+pub mod fingerprint;
+pub mod graph;
+pub mod index_store;
+pub mod keystore;
+pub mod lockfile;
+pub mod migrate;
+pub mod odb;
+pub mod pack;
+pub mod provenance;
+pub mod queue;
+pub mod query;
+pub mod query_arrow;
+pub mod refs;
+pub mod remote;
+pub mod repository;
+pub mod serve;
+pub mod signing;
+pub mod sigv4;
+pub mod ssh_agent;
+pub mod status_ref;
+pub mod stream_merge;
+pub mod sync;
+pub mod watch;
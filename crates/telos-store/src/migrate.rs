@@ -0,0 +1,147 @@
+//! Copy a repository's objects and stream refs from one [`crate::backend`]
+//! pair to another, so a repo can move between storage backends (e.g. a
+//! local `file://` repo into `sled://`) without losing intent history.
+//!
+//! Mirrors `telos reindex`-style commands in reporting counts rather than
+//! progress bars, and is resumable/idempotent by construction: objects
+//! already present at the destination (by [`ObjectId`]) are skipped, stream
+//! writes are plain overwrites of the same content, and HEAD is written last
+//! so a migration interrupted partway leaves the destination's HEAD either
+//! absent or pointing at a destination that already has everything HEAD
+//! needs — never half-populated.
+
+use crate::backend::{ObjectBackend, RefBackend};
+use crate::error::StoreError;
+use telos_core::hash::ObjectId;
+
+/// Counts from a completed (or partially completed, if it errored) migration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationCounts {
+    pub objects_copied: usize,
+    pub objects_skipped: usize,
+    pub streams_copied: usize,
+}
+
+/// Copy every object and stream ref from `src` to `dst`, verifying each
+/// object's hash on the way so corruption is caught rather than silently
+/// propagated, then point `dst`'s HEAD at the same stream `src`'s HEAD
+/// names.
+pub fn migrate(
+    src_objects: &dyn ObjectBackend,
+    dst_objects: &dyn ObjectBackend,
+    src_refs: &dyn RefBackend,
+    dst_refs: &dyn RefBackend,
+) -> Result<MigrationCounts, StoreError> {
+    let mut counts = MigrationCounts::default();
+
+    for (id, object) in src_objects.iter_all()? {
+        if dst_objects.has(&id)? {
+            counts.objects_skipped += 1;
+            continue;
+        }
+        // Verify with whatever algorithm `id` itself declares — ids are
+        // self-describing multihashes, so a store can mix algorithms
+        // across its history and this still catches real corruption.
+        let recomputed = object.content_id_with(id.algo())?;
+        if recomputed != id {
+            return Err(StoreError::IntegrityError {
+                expected: id.hex().to_string(),
+                actual: recomputed.hex().to_string(),
+            });
+        }
+        dst_objects.write(&object)?;
+        counts.objects_copied += 1;
+    }
+
+    for name in src_refs.list_streams()? {
+        let stream = src_refs.read_stream(&name)?;
+        dst_refs.write_stream(&stream)?;
+        counts.streams_copied += 1;
+    }
+
+    let head = src_refs.read_head()?;
+    dst_refs.set_head(&head)?;
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{MemoryObjectBackend, MemoryRefBackend};
+    use chrono::Utc;
+    use telos_core::object::intent::{Author, Intent};
+    use telos_core::object::intent_stream::IntentStreamRef;
+    use telos_core::object::TelosObject;
+
+    fn sample_intent(statement: &str) -> TelosObject {
+        TelosObject::Intent(Intent {
+            author: Author {
+                name: "Alice".into(),
+                email: "alice@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents: vec![],
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: Default::default(),
+        })
+    }
+
+    #[test]
+    fn migrate_copies_objects_streams_and_head_last() {
+        let src_objects = MemoryObjectBackend::new();
+        let src_refs = MemoryRefBackend::new();
+
+        let obj = sample_intent("do the thing");
+        let id = src_objects.write(&obj).unwrap();
+        src_refs
+            .write_stream(&IntentStreamRef {
+                name: "main".into(),
+                tip: Some(id.clone()),
+                created_at: Utc::now(),
+                description: None,
+            })
+            .unwrap();
+        src_refs.set_head("main").unwrap();
+
+        let dst_objects = MemoryObjectBackend::new();
+        let dst_refs = MemoryRefBackend::new();
+
+        let counts = migrate(&src_objects, &dst_objects, &src_refs, &dst_refs).unwrap();
+        assert_eq!(counts.objects_copied, 1);
+        assert_eq!(counts.objects_skipped, 0);
+        assert_eq!(counts.streams_copied, 1);
+
+        assert!(dst_objects.has(&id).unwrap());
+        assert_eq!(dst_refs.read_head().unwrap(), "main");
+        assert_eq!(dst_refs.read_stream("main").unwrap().tip, Some(id));
+    }
+
+    #[test]
+    fn migrate_is_idempotent_and_skips_existing_objects() {
+        let src_objects = MemoryObjectBackend::new();
+        let src_refs = MemoryRefBackend::new();
+        let id = src_objects.write(&sample_intent("idempotent")).unwrap();
+        src_refs
+            .write_stream(&IntentStreamRef {
+                name: "main".into(),
+                tip: Some(id.clone()),
+                created_at: Utc::now(),
+                description: None,
+            })
+            .unwrap();
+        src_refs.set_head("main").unwrap();
+
+        let dst_objects = MemoryObjectBackend::new();
+        let dst_refs = MemoryRefBackend::new();
+
+        migrate(&src_objects, &dst_objects, &src_refs, &dst_refs).unwrap();
+        let second = migrate(&src_objects, &dst_objects, &src_refs, &dst_refs).unwrap();
+        assert_eq!(second.objects_copied, 0);
+        assert_eq!(second.objects_skipped, 1);
+    }
+}
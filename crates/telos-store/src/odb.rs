@@ -1,25 +1,120 @@
+use crate::crypto::{self, DataKey};
 use crate::error::StoreError;
+use crate::pack::{self, PackStore};
+use metrics::{counter, histogram};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
-use telos_core::hash::ObjectId;
+use telos_core::hash::{HashAlgo, ObjectId};
 use telos_core::object::TelosObject;
+use telos_core::serialize::ContentFormat;
 
 /// Content-addressable object database.
 ///
-/// Objects are stored as `objects/<2-char fan-out>/<remaining 62 chars>`.
-/// Writes are atomic (temp file + rename).
+/// Objects are stored as `objects/<first 2 chars of the id>/<rest of the
+/// id>` — the fan-out split is on the id's full canonical representation
+/// ([`ObjectId::hex`]), so its width varies with the id's encoding (64
+/// hex chars for a legacy id, or a `b`-prefixed multihash string for a
+/// self-describing one) rather than being fixed. Writes are atomic (temp
+/// file + rename).
+///
+/// When `data_key` is set, on-disk bytes are sealed with XChaCha20-Poly1305
+/// (see [`crate::crypto`]) before being written, and opened again on read.
+/// The `ObjectId` is always computed over the *plaintext* canonical bytes,
+/// so content-addressing and dedup behave identically whether or not
+/// encryption is enabled.
+///
+/// Reads and existence checks consult [`PackStore`] before falling back to
+/// the loose path, so `gc --pack` (see `telos-cli`) can consolidate loose
+/// objects into packfiles transparently.
 pub struct ObjectDatabase {
     objects_dir: PathBuf,
+    data_key: Option<DataKey>,
+    packs: PackStore,
+    hash_algo: HashAlgo,
+    content_format: ContentFormat,
 }
 
 impl ObjectDatabase {
     pub fn new(objects_dir: impl Into<PathBuf>) -> Self {
+        let objects_dir = objects_dir.into();
+        let packs = PackStore::load(&objects_dir).unwrap_or_else(|_| PackStore::empty_for(&objects_dir));
         Self {
-            objects_dir: objects_dir.into(),
+            objects_dir,
+            data_key: None,
+            packs,
+            hash_algo: HashAlgo::default(),
+            content_format: ContentFormat::default(),
         }
     }
 
+    /// Construct an `ObjectDatabase` that encrypts object bytes at rest
+    /// under `data_key`.
+    pub fn new_encrypted(objects_dir: impl Into<PathBuf>, data_key: DataKey) -> Self {
+        let objects_dir = objects_dir.into();
+        let packs = PackStore::load(&objects_dir).unwrap_or_else(|_| PackStore::empty_for(&objects_dir));
+        Self {
+            objects_dir,
+            data_key: Some(data_key),
+            packs,
+            hash_algo: HashAlgo::default(),
+            content_format: ContentFormat::default(),
+        }
+    }
+
+    /// Use `algo` to hash objects written through this database from now
+    /// on, instead of the default (SHA-256). Ids are self-describing
+    /// multihashes, so existing objects hashed under a different algorithm
+    /// keep reading and comparing correctly — this only changes what new
+    /// writes use.
+    pub fn with_hash_algo(mut self, algo: HashAlgo) -> Self {
+        self.hash_algo = algo;
+        self
+    }
+
+    /// The algorithm new writes through this database use.
+    pub fn hash_algo(&self) -> HashAlgo {
+        self.hash_algo
+    }
+
+    /// Use `format` to canonicalize objects written through this database
+    /// from now on, instead of the default (legacy `type_tag\0sorted_json`
+    /// with UTF-8-byte-ordered keys). `TelosObject::from_canonical_bytes` is
+    /// format-agnostic on read, so existing objects written under a
+    /// different format keep reading correctly — this only changes what new
+    /// writes produce (and therefore hash).
+    pub fn with_content_format(mut self, format: ContentFormat) -> Self {
+        self.content_format = format;
+        self
+    }
+
+    /// The canonicalization new writes through this database use.
+    pub fn content_format(&self) -> ContentFormat {
+        self.content_format
+    }
+
+    /// Reload the pack index from disk, picking up packs written by a
+    /// concurrent or prior `gc --pack` run.
+    pub fn refresh_packs(&mut self) -> Result<(), StoreError> {
+        self.packs = PackStore::load(&self.objects_dir)?;
+        Ok(())
+    }
+
+    /// Directory this database stores loose objects and packs under.
+    pub fn objects_dir(&self) -> &std::path::Path {
+        &self.objects_dir
+    }
+
+    /// The repo's data key, if object bytes are encrypted at rest.
+    pub fn data_key(&self) -> Option<&DataKey> {
+        self.data_key.as_ref()
+    }
+
+    /// Whether this database encrypts object bytes at rest.
+    pub fn is_encrypted(&self) -> bool {
+        self.data_key.is_some()
+    }
+
     /// Compute the file path for a given ObjectId.
     fn object_path(&self, id: &ObjectId) -> PathBuf {
         let (dir, file) = id.fan_out();
@@ -29,12 +124,18 @@ impl ObjectDatabase {
     /// Write an object to the store. Returns the ObjectId.
     ///
     /// If the object already exists (same hash), this is a no-op.
+    #[tracing::instrument(skip(self, object), fields(object.type = object.type_tag(), object.id, object.bytes, object.hit_miss))]
     pub fn write(&self, object: &TelosObject) -> Result<ObjectId, StoreError> {
-        let bytes = object.canonical_bytes()?;
-        let id = ObjectId::hash(&bytes);
+        let bytes = object.canonical_bytes_with(self.content_format)?;
+        let id = ObjectId::hash_with(self.hash_algo, &bytes);
         let path = self.object_path(&id);
+        let span = tracing::Span::current();
+        span.record("object.id", id.hex());
+        span.record("object.bytes", bytes.len());
 
         if path.exists() {
+            span.record("object.hit_miss", "idempotent-skip");
+            counter!("telos.odb.write.idempotent_skip", "type" => object.type_tag()).increment(1);
             return Ok(id); // idempotent
         }
 
@@ -43,35 +144,80 @@ impl ObjectDatabase {
             fs::create_dir_all(parent)?;
         }
 
+        let on_disk = match &self.data_key {
+            Some(key) => crypto::seal(key, &bytes)?,
+            None => bytes.clone(),
+        };
+
         // Atomic write: temp file in same directory + rename
         let parent = path.parent().unwrap();
         let mut tmp = tempfile::NamedTempFile::new_in(parent)?;
-        tmp.write_all(&bytes)?;
+        tmp.write_all(&on_disk)?;
         tmp.flush()?;
         tmp.persist(&path).map_err(|e| StoreError::Io(e.error))?;
 
+        span.record("object.hit_miss", "fresh-write");
+        counter!("telos.odb.objects_written", "type" => object.type_tag()).increment(1);
+        histogram!("telos.odb.bytes_written", "type" => object.type_tag()).record(bytes.len() as f64);
+
         Ok(id)
     }
 
     /// Read an object by its exact ObjectId.
+    ///
+    /// Loose files take precedence, then the pack index. Packed bytes are
+    /// stored decompressed-plaintext (see [`crate::pack`]) since packing
+    /// doesn't yet compose with at-rest encryption.
+    #[tracing::instrument(skip(self), fields(object.id = %id))]
     pub fn read(&self, id: &ObjectId) -> Result<TelosObject, StoreError> {
+        let bytes = self.read_verified_bytes(id)?;
+        let obj = TelosObject::from_canonical_bytes(&bytes)?;
+        tracing::Span::current().record("object.type", obj.type_tag());
+        Ok(obj)
+    }
+
+    /// Read and integrity-check an object's exact on-disk canonical bytes,
+    /// without deserializing them.
+    ///
+    /// [`Self::pack_loose`] and [`crate::sync::push`] use this instead of
+    /// `TelosObject::canonical_bytes[_with]` so neither repacking nor
+    /// pushing ever re-canonicalizes an object: re-serializing a parsed
+    /// object can produce different bytes than what actually hashes to its
+    /// id (e.g. if [`Self::content_format`] has since changed, or differs
+    /// from the receiving side's), silently corrupting the pack or tripping
+    /// the post-upload rehash check on the other end.
+    pub(crate) fn read_verified_bytes(&self, id: &ObjectId) -> Result<Vec<u8>, StoreError> {
         let path = self.object_path(id);
-        let bytes = fs::read(&path)
-            .map_err(|_| StoreError::ObjectNotFound(id.hex().to_string()))?;
+        let bytes = if path.exists() {
+            let on_disk = fs::read(&path)?;
+            match &self.data_key {
+                Some(key) => crypto::open(key, &on_disk)?,
+                None => on_disk,
+            }
+        } else if let Some(packed) = self.packs.read(id)? {
+            packed
+        } else {
+            return Err(StoreError::ObjectNotFound(id.hex().to_string()));
+        };
 
-        // Verify integrity: recompute hash and compare to expected ID
-        let actual_id = ObjectId::hash(&bytes);
+        // Verify integrity: recompute hash and compare to expected ID, using
+        // whatever algorithm `id` itself is encoded with — not the repo
+        // default, since a repo configured for a non-default algorithm
+        // writes ids that the default algorithm can never reproduce.
+        let actual_id = ObjectId::hash_with(id.algo(), &bytes);
         if &actual_id != id {
+            counter!("telos.odb.integrity_failures").increment(1);
             return Err(StoreError::IntegrityError {
                 expected: id.hex().to_string(),
                 actual: actual_id.hex().to_string(),
             });
         }
 
-        Ok(TelosObject::from_canonical_bytes(&bytes)?)
+        Ok(bytes)
     }
 
     /// Iterate over all objects stored in the database.
+    #[tracing::instrument(skip(self))]
     pub fn iter_all(&self) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
         let mut results = Vec::new();
         // Walk 00-ff fan-out directories
@@ -101,17 +247,34 @@ impl ObjectDatabase {
                 }
             }
         }
+
+        let loose_ids: std::collections::HashSet<String> =
+            results.iter().map(|(id, _)| id.hex().to_string()).collect();
+        for hex in self.packs.all_ids() {
+            if loose_ids.contains(&hex) {
+                continue;
+            }
+            if let Ok(id) = ObjectId::parse(&hex) {
+                if let Ok(obj) = self.read(&id) {
+                    results.push((id, obj));
+                }
+            }
+        }
+
         Ok(results)
     }
 
-    /// Check if an object exists.
+    /// Check if an object exists, loose or packed.
     pub fn exists(&self, id: &ObjectId) -> bool {
-        self.object_path(id).exists()
+        self.object_path(id).exists() || self.packs.contains(id)
     }
 
-    /// Resolve a hex prefix to a full ObjectId.
+    /// Resolve a prefix of an id's canonical representation ([`ObjectId::hex`])
+    /// to a full ObjectId.
     ///
-    /// Scans the fan-out directory for matching objects.
+    /// Scans the fan-out directory for loose matches and the pack index for
+    /// packed matches, then requires the combined set to be unambiguous.
+    #[tracing::instrument(skip(self))]
     pub fn resolve_prefix(&self, prefix: &str) -> Result<ObjectId, StoreError> {
         if prefix.len() < 4 {
             return Err(StoreError::AmbiguousPrefix {
@@ -124,30 +287,140 @@ impl ObjectDatabase {
         let rest_prefix = &prefix[2..];
         let fan_dir = self.objects_dir.join(fan_out);
 
-        if !fan_dir.exists() {
-            return Err(StoreError::ObjectNotFound(prefix.to_string()));
-        }
-
-        let mut matches = Vec::new();
-        for entry in fs::read_dir(&fan_dir)? {
-            let entry = entry?;
-            let name = entry.file_name();
-            let name = name.to_string_lossy();
-            if name.starts_with(rest_prefix) {
-                let full_hex = format!("{}{}", fan_out, name);
-                matches.push(full_hex);
+        let mut matches = std::collections::BTreeSet::new();
+        if fan_dir.exists() {
+            for entry in fs::read_dir(&fan_dir)? {
+                let entry = entry?;
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name.starts_with(rest_prefix) {
+                    matches.insert(format!("{}{}", fan_out, name));
+                }
             }
         }
+        matches.extend(self.packs.resolve_prefix(prefix));
 
         match matches.len() {
             0 => Err(StoreError::ObjectNotFound(prefix.to_string())),
-            1 => Ok(ObjectId::parse(&matches[0])?),
-            n => Err(StoreError::AmbiguousPrefix {
-                prefix: prefix.to_string(),
-                count: n,
-            }),
+            1 => Ok(ObjectId::parse(matches.iter().next().unwrap())?),
+            n => {
+                counter!("telos.odb.prefix_ambiguous").increment(1);
+                Err(StoreError::AmbiguousPrefix {
+                    prefix: prefix.to_string(),
+                    count: n,
+                })
+            }
         }
     }
+
+    /// ObjectIds of every loose (unpacked) object.
+    fn loose_object_ids(&self) -> Result<Vec<ObjectId>, StoreError> {
+        let mut ids = Vec::new();
+        let entries = match fs::read_dir(&self.objects_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ids),
+            Err(e) => return Err(StoreError::Io(e)),
+        };
+        for fan_entry in entries {
+            let fan_entry = fan_entry.map_err(StoreError::Io)?;
+            let fan_name = fan_entry.file_name().to_string_lossy().to_string();
+            if fan_name.len() != 2 || fan_name == "pack" || !fan_entry.path().is_dir() {
+                continue;
+            }
+            for obj_entry in fs::read_dir(fan_entry.path()).map_err(StoreError::Io)? {
+                let obj_entry = obj_entry.map_err(StoreError::Io)?;
+                let hex = format!("{}{}", fan_name, obj_entry.file_name().to_string_lossy());
+                if let Ok(id) = ObjectId::parse(&hex) {
+                    ids.push(id);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Consolidate every loose object into a new packfile, then delete the
+    /// loose copies. Returns the number of objects packed.
+    ///
+    /// Because objects are content-addressed and immutable, this is always
+    /// safe: the loose file is only removed once the pack (and its index)
+    /// has been fsync'd and renamed into place. Objects that are plainly a
+    /// small revision of another object in this same batch (a `Constraint`
+    /// against its `superseded_by`, an `Intent` against its first parent)
+    /// are stored as a delta against it — see [`crate::pack`] and
+    /// [`delta_base_hint`].
+    pub fn pack_loose(&mut self) -> Result<usize, StoreError> {
+        let ids = self.loose_object_ids()?;
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let mut objects = Vec::with_capacity(ids.len());
+        for id in &ids {
+            let bytes = self.read_verified_bytes(id)?;
+            let obj = TelosObject::from_canonical_bytes(&bytes)?;
+            let hint = delta_base_hint(&obj);
+            objects.push((id.clone(), bytes, hint));
+        }
+
+        let packed = pack::create_pack(&self.objects_dir, &objects)?;
+
+        for id in &ids {
+            fs::remove_file(self.object_path(id))?;
+        }
+        self.refresh_packs()?;
+
+        Ok(packed)
+    }
+
+    /// Delete every loose object not reachable from `roots` (e.g. every
+    /// stream's current tip), then [`Self::pack_loose`] what's left.
+    /// Returns `(garbage_collected, packed)`.
+    ///
+    /// `roots` is caller-supplied and not limited to local stream tips — a
+    /// caller that's also tracking remote state (see
+    /// [`crate::refs::RefStore::read_remote_head`] /
+    /// [`crate::refs::RefStore::read_remote_objects`]) should fold those in
+    /// too, or objects fetched but not yet merged look unreferenced here
+    /// and get collected before the merge that needs them runs.
+    ///
+    /// Packed objects are never removed here — only loose ones, since those
+    /// are what drive inode pressure; an unreferenced object already
+    /// consolidated into a pack is cheap to keep around until the next full
+    /// repack.
+    pub fn gc(&mut self, roots: &[ObjectId]) -> Result<(usize, usize), StoreError> {
+        let mut reachable: std::collections::HashSet<ObjectId> = std::collections::HashSet::new();
+        for root in roots {
+            if !self.exists(root) {
+                continue;
+            }
+            reachable.insert(root.clone());
+            for (id, _) in crate::graph::reachable_from(self, root)? {
+                reachable.insert(id);
+            }
+        }
+
+        let mut collected = 0;
+        for id in self.loose_object_ids()? {
+            if !reachable.contains(&id) {
+                fs::remove_file(self.object_path(&id))?;
+                collected += 1;
+            }
+        }
+
+        let packed = self.pack_loose()?;
+        Ok((collected, packed))
+    }
+}
+
+/// The other object (if any) a [`pack::create_pack`] delta-base hint should
+/// point `obj` at, based on the field a maintainer would actually expect a
+/// revision to sit next to.
+fn delta_base_hint(obj: &TelosObject) -> Option<ObjectId> {
+    match obj {
+        TelosObject::Constraint(c) => c.superseded_by.clone(),
+        TelosObject::Intent(i) => i.parents.first().cloned(),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -188,6 +461,34 @@ mod tests {
         assert_eq!(restored, obj);
     }
 
+    #[test]
+    fn content_format_changes_what_new_writes_hash_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy_odb = ObjectDatabase::new(dir.path().join("legacy"));
+        let jcs_odb = ObjectDatabase::new(dir.path().join("jcs")).with_content_format(ContentFormat::Jcs);
+        let obj = sample_intent();
+
+        let legacy_id = legacy_odb.write(&obj).unwrap();
+        let jcs_id = jcs_odb.write(&obj).unwrap();
+        assert_ne!(legacy_id, jcs_id);
+
+        assert_eq!(legacy_odb.read(&legacy_id).unwrap(), obj);
+        assert_eq!(jcs_odb.read(&jcs_id).unwrap(), obj);
+    }
+
+    #[test]
+    fn pack_loose_preserves_a_non_default_content_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut odb =
+            ObjectDatabase::new(dir.path().join("objects")).with_content_format(ContentFormat::Jcs);
+        let obj = sample_intent();
+        let id = odb.write(&obj).unwrap();
+
+        odb.pack_loose().unwrap();
+
+        assert_eq!(odb.read(&id).unwrap(), obj);
+    }
+
     #[test]
     fn write_idempotent() {
         let dir = tempfile::tempdir().unwrap();
@@ -274,10 +575,103 @@ mod tests {
             rationale: None,
             alternatives: vec![],
             tags: vec![],
+            metadata: HashMap::new(),
         });
         let _id2 = odb.write(&record).unwrap();
 
         let all = odb.iter_all().unwrap();
         assert_eq!(all.len(), 2);
     }
+
+    #[test]
+    fn pack_loose_objects_stay_readable_and_loose_files_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut odb = ObjectDatabase::new(dir.path().join("objects"));
+        let obj = sample_intent();
+        let id = odb.write(&obj).unwrap();
+
+        let loose_path = dir.path().join("objects").join(id.fan_out().0).join(id.fan_out().1);
+        assert!(loose_path.exists());
+
+        let packed = odb.pack_loose().unwrap();
+        assert_eq!(packed, 1);
+        assert!(!loose_path.exists());
+
+        assert!(odb.exists(&id));
+        let restored = odb.read(&id).unwrap();
+        assert_eq!(restored, obj);
+    }
+
+    #[test]
+    fn pack_loose_delta_encodes_a_revised_constraint_against_its_successor() {
+        use telos_core::object::constraint::{Constraint, ConstraintSeverity, ConstraintStatus};
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut odb = ObjectDatabase::new(dir.path().join("objects"));
+        let author = Author { name: "Test".into(), email: "test@test.com".into() };
+        let intent_id = odb.write(&sample_intent()).unwrap();
+
+        let successor = Constraint {
+            author: author.clone(),
+            timestamp: Utc::now(),
+            statement: "Must not log raw passwords or tokens".into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Active,
+            source_intent: intent_id.clone(),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: HashMap::new(),
+        };
+        let successor_id = odb.write(&TelosObject::Constraint(successor)).unwrap();
+
+        let original = Constraint {
+            author,
+            timestamp: Utc::now(),
+            statement: "Must not log raw passwords".into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Superseded,
+            source_intent: intent_id,
+            superseded_by: Some(successor_id.clone()),
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: HashMap::new(),
+        };
+        let original_id = odb.write(&TelosObject::Constraint(original.clone())).unwrap();
+
+        odb.pack_loose().unwrap();
+
+        assert_eq!(odb.read(&original_id).unwrap(), TelosObject::Constraint(original));
+        assert_eq!(odb.read(&successor_id).unwrap().type_tag(), "constraint");
+    }
+
+    #[test]
+    fn gc_removes_unreferenced_loose_objects_but_keeps_reachable_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut odb = ObjectDatabase::new(dir.path().join("objects"));
+
+        let root = odb.write(&sample_intent()).unwrap();
+        let orphan = odb
+            .write(&TelosObject::Intent(Intent {
+                author: Author { name: "Test".into(), email: "test@test.com".into() },
+                timestamp: Utc::now(),
+                statement: "Orphaned intent".into(),
+                constraints: vec![],
+                behavior_spec: vec![],
+                parents: vec![],
+                impacts: vec![],
+                behavior_diff: None,
+                metadata: HashMap::new(),
+            }))
+            .unwrap();
+
+        let (collected, packed) = odb.gc(&[root.clone()]).unwrap();
+        assert_eq!(collected, 1);
+        assert_eq!(packed, 1);
+
+        assert!(odb.exists(&root));
+        assert!(!odb.exists(&orphan));
+    }
 }
@@ -0,0 +1,354 @@
+//! Packed storage for loose objects.
+//!
+//! Each loose object is one file under `objects/<fanout>/<rest>`, which is
+//! simple but wastes an inode and a directory entry per object as
+//! agent-operation logs grow into the tens of thousands. A pack is a single
+//! append-only file of zstd-compressed object blobs (`objects/pack/pack-<id>.pack`)
+//! plus a companion index (`objects/pack/pack-<id>.idx`) mapping `ObjectId` to
+//! `(offset, length)` within that file. [`PackStore`] loads every index under
+//! `objects/pack/` and is consulted by [`crate::odb::ObjectDatabase`] before
+//! falling back to the loose path, so packing is transparent to callers.
+//!
+//! Index keys are kept in a `BTreeMap`, so prefix resolution across packed
+//! objects is a cheap range scan rather than a linear one.
+//!
+//! Objects that are plainly revisions of one another — a `Constraint`
+//! against the one that supersedes it, an `Intent` against a parent — are
+//! stored as a [`crate::delta`] diff against that base instead of an
+//! independent zstd frame, cutting pack size for repos with many small
+//! edits to a handful of large objects. A delta's base is always itself
+//! stored in full (never another delta), so reconstruction is at most one
+//! hop.
+
+use crate::delta;
+use crate::error::StoreError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use telos_core::hash::ObjectId;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackIndexEntry {
+    id: String,
+    offset: u64,
+    length: u64,
+    /// Hex id of the full object this entry is a delta against, if it's
+    /// stored as a diff rather than standalone compressed bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delta_base: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackIndexFile {
+    pack_file: String,
+    entries: Vec<PackIndexEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct PackLocation {
+    pack_file: String,
+    offset: u64,
+    length: u64,
+    delta_base: Option<String>,
+}
+
+/// Read-only view over every pack under `objects/pack/`.
+pub struct PackStore {
+    pack_dir: PathBuf,
+    /// ObjectId hex -> location, sorted so prefix scans are a range query.
+    index: BTreeMap<String, PackLocation>,
+}
+
+impl PackStore {
+    /// An empty pack store, as if `objects_dir/pack/` didn't exist.
+    pub(crate) fn empty_for(objects_dir: &Path) -> Self {
+        Self {
+            pack_dir: objects_dir.join("pack"),
+            index: BTreeMap::new(),
+        }
+    }
+
+    /// Load all `pack-*.idx` files under `objects_dir/pack/`. Missing
+    /// directory is treated as "no packs yet".
+    pub fn load(objects_dir: &Path) -> Result<Self, StoreError> {
+        let pack_dir = objects_dir.join("pack");
+        let mut index = BTreeMap::new();
+
+        let entries = match fs::read_dir(&pack_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self { pack_dir, index });
+            }
+            Err(e) => return Err(StoreError::Io(e)),
+        };
+
+        for entry in entries {
+            let entry = entry.map_err(StoreError::Io)?;
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("idx") {
+                continue;
+            }
+            let data = fs::read_to_string(&path)?;
+            let idx: PackIndexFile = serde_json::from_str(&data)?;
+            for e in idx.entries {
+                index.insert(
+                    e.id,
+                    PackLocation {
+                        pack_file: idx.pack_file.clone(),
+                        offset: e.offset,
+                        length: e.length,
+                        delta_base: e.delta_base,
+                    },
+                );
+            }
+        }
+
+        Ok(Self { pack_dir, index })
+    }
+
+    pub fn contains(&self, id: &ObjectId) -> bool {
+        self.index.contains_key(id.hex())
+    }
+
+    /// Read and decompress an object's plaintext canonical bytes, if packed,
+    /// reassembling it against its delta base first if it's stored as one.
+    pub fn read(&self, id: &ObjectId) -> Result<Option<Vec<u8>>, StoreError> {
+        self.read_with_depth(id, 0)
+    }
+
+    fn read_with_depth(&self, id: &ObjectId, depth: usize) -> Result<Option<Vec<u8>>, StoreError> {
+        // Deltas are never chained (see module docs), so depth should never
+        // exceed 1; this just guards against a corrupt or hand-edited index.
+        if depth > 4 {
+            return Err(StoreError::Io(std::io::Error::other(format!(
+                "pack delta chain too deep resolving {}",
+                id.hex()
+            ))));
+        }
+        let Some(loc) = self.index.get(id.hex()) else {
+            return Ok(None);
+        };
+        let mut file = File::open(self.pack_dir.join(&loc.pack_file))?;
+        file.seek(SeekFrom::Start(loc.offset))?;
+        let mut compressed = vec![0u8; loc.length as usize];
+        file.read_exact(&mut compressed)?;
+        let bytes = zstd::decode_all(&compressed[..])
+            .map_err(|e| StoreError::Io(std::io::Error::other(e.to_string())))?;
+
+        let Some(base_hex) = &loc.delta_base else {
+            return Ok(Some(bytes));
+        };
+        let base_id = ObjectId::parse(base_hex)
+            .map_err(|e| StoreError::Io(std::io::Error::other(e.to_string())))?;
+        let Some(base_bytes) = self.read_with_depth(&base_id, depth + 1)? else {
+            return Err(StoreError::ObjectNotFound(base_hex.clone()));
+        };
+        let plaintext = delta::apply_delta(&base_bytes, &bytes).ok_or_else(|| {
+            StoreError::Io(std::io::Error::other(format!(
+                "corrupt pack delta for {}",
+                id.hex()
+            )))
+        })?;
+        Ok(Some(plaintext))
+    }
+
+    /// Hex ids of packed objects whose id starts with `prefix`.
+    pub fn resolve_prefix(&self, prefix: &str) -> Vec<String> {
+        self.index
+            .range(prefix.to_string()..)
+            .take_while(|(id, _)| id.starts_with(prefix))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Hex ids of every packed object.
+    pub fn all_ids(&self) -> impl Iterator<Item = String> + '_ {
+        self.index.keys().cloned()
+    }
+}
+
+/// Pack every `(id, plaintext_bytes, delta_base_hint)` triple into a single
+/// new packfile + index under `objects_dir/pack/`, returning the number of
+/// objects packed.
+///
+/// `delta_base_hint` names another object in this same batch that the
+/// caller believes `id` is a small edit away from (e.g. a `Constraint`'s
+/// `superseded_by`, or an `Intent`'s first parent). When the hint points at
+/// an object actually present in `objects`, `id` is stored as a
+/// [`crate::delta`] diff against it rather than independently; every base a
+/// hint points at is always stored in full, so delta chains never form.
+/// Objects with no hint (or whose hint falls outside this batch) are
+/// compressed independently, as before.
+pub fn create_pack(
+    objects_dir: &Path,
+    objects: &[(ObjectId, Vec<u8>, Option<ObjectId>)],
+) -> Result<usize, StoreError> {
+    if objects.is_empty() {
+        return Ok(0);
+    }
+
+    let pack_dir = objects_dir.join("pack");
+    fs::create_dir_all(&pack_dir)?;
+
+    // Name the pack after the hash of its sorted member ids, so re-running
+    // gc on an unchanged object set is idempotent.
+    let mut ids: Vec<&str> = objects.iter().map(|(id, _, _)| id.hex()).collect();
+    ids.sort_unstable();
+    let pack_name = format!("pack-{}", ObjectId::hash(ids.join(",").as_bytes()).hex());
+    let pack_file = format!("{pack_name}.pack");
+    let idx_file = format!("{pack_name}.idx");
+
+    let present: HashSet<&str> = objects.iter().map(|(id, _, _)| id.hex()).collect();
+    // Anything named as a delta base must itself be stored in full, so no
+    // entry ever needs more than one hop to reconstruct.
+    let forced_full: HashSet<&str> = objects
+        .iter()
+        .filter_map(|(_, _, hint)| hint.as_ref())
+        .map(|base| base.hex())
+        .filter(|base| present.contains(base))
+        .collect();
+
+    let mut tmp_pack = tempfile::NamedTempFile::new_in(&pack_dir)?;
+    let mut entries = Vec::with_capacity(objects.len());
+    let mut offset = 0u64;
+
+    for (id, plaintext, hint) in objects {
+        let base = hint
+            .as_ref()
+            .filter(|_| !forced_full.contains(id.hex()))
+            .filter(|base| base.hex() != id.hex())
+            .and_then(|base| objects.iter().find(|(bid, _, _)| bid.hex() == base.hex()))
+            .map(|(base_id, base_plaintext, _)| (base_id, base_plaintext));
+
+        let (payload, delta_base) = match base {
+            Some((base_id, base_plaintext)) => {
+                let diff = delta::encode_delta(base_plaintext, plaintext);
+                (diff, Some(base_id.hex().to_string()))
+            }
+            None => (plaintext.clone(), None),
+        };
+
+        let compressed = zstd::encode_all(&payload[..], 0)
+            .map_err(|e| StoreError::Io(std::io::Error::other(e.to_string())))?;
+        tmp_pack.write_all(&compressed)?;
+        entries.push(PackIndexEntry {
+            id: id.hex().to_string(),
+            offset,
+            length: compressed.len() as u64,
+            delta_base,
+        });
+        offset += compressed.len() as u64;
+    }
+    tmp_pack.flush()?;
+    tmp_pack
+        .persist(pack_dir.join(&pack_file))
+        .map_err(|e| StoreError::Io(e.error))?;
+
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    let idx = PackIndexFile {
+        pack_file,
+        entries,
+    };
+    fs::write(pack_dir.join(idx_file), serde_json::to_string_pretty(&idx)?)?;
+
+    Ok(objects.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_and_read_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let objects_dir = dir.path().join("objects");
+        fs::create_dir_all(&objects_dir).unwrap();
+
+        let id1 = ObjectId::hash(b"first object");
+        let id2 = ObjectId::hash(b"second object");
+        let packed = create_pack(
+            &objects_dir,
+            &[
+                (id1.clone(), b"first object".to_vec(), None),
+                (id2.clone(), b"second object".to_vec(), None),
+            ],
+        )
+        .unwrap();
+        assert_eq!(packed, 2);
+
+        let store = PackStore::load(&objects_dir).unwrap();
+        assert!(store.contains(&id1));
+        assert!(store.contains(&id2));
+        assert_eq!(store.read(&id1).unwrap().unwrap(), b"first object");
+        assert_eq!(store.read(&id2).unwrap().unwrap(), b"second object");
+    }
+
+    #[test]
+    fn resolve_prefix_across_packed_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let objects_dir = dir.path().join("objects");
+        fs::create_dir_all(&objects_dir).unwrap();
+
+        let id = ObjectId::hash(b"unique payload");
+        create_pack(&objects_dir, &[(id.clone(), b"unique payload".to_vec(), None)]).unwrap();
+
+        let store = PackStore::load(&objects_dir).unwrap();
+        let prefix = &id.hex()[..8];
+        let matches = store.resolve_prefix(prefix);
+        assert_eq!(matches, vec![id.hex().to_string()]);
+    }
+
+    #[test]
+    fn load_with_no_pack_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PackStore::load(&dir.path().join("objects")).unwrap();
+        assert!(!store.contains(&ObjectId::hash(b"anything")));
+    }
+
+    #[test]
+    fn delta_encoded_object_round_trips_against_its_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let objects_dir = dir.path().join("objects");
+        fs::create_dir_all(&objects_dir).unwrap();
+
+        let base_bytes = b"{\"statement\":\"must not log raw passwords\",\"severity\":\"must\"}".to_vec();
+        let revised_bytes =
+            b"{\"statement\":\"must not log raw passwords or tokens\",\"severity\":\"must\"}".to_vec();
+        let base_id = ObjectId::hash(&base_bytes);
+        let revised_id = ObjectId::hash(&revised_bytes);
+
+        let packed = create_pack(
+            &objects_dir,
+            &[
+                (revised_id.clone(), revised_bytes.clone(), Some(base_id.clone())),
+                (base_id.clone(), base_bytes.clone(), None),
+            ],
+        )
+        .unwrap();
+        assert_eq!(packed, 2);
+
+        let store = PackStore::load(&objects_dir).unwrap();
+        assert_eq!(store.read(&base_id).unwrap().unwrap(), base_bytes);
+        assert_eq!(store.read(&revised_id).unwrap().unwrap(), revised_bytes);
+    }
+
+    #[test]
+    fn delta_base_hint_outside_the_batch_falls_back_to_full_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let objects_dir = dir.path().join("objects");
+        fs::create_dir_all(&objects_dir).unwrap();
+
+        let missing_base = ObjectId::hash(b"never packed");
+        let id = ObjectId::hash(b"standalone object");
+        create_pack(
+            &objects_dir,
+            &[(id.clone(), b"standalone object".to_vec(), Some(missing_base))],
+        )
+        .unwrap();
+
+        let store = PackStore::load(&objects_dir).unwrap();
+        assert_eq!(store.read(&id).unwrap().unwrap(), b"standalone object");
+    }
+}
@@ -0,0 +1,278 @@
+//! Provenance lineage graph over the object store.
+//!
+//! Lineage edges are read from `Intent.parents`, `DecisionRecord.intent_id`,
+//! `Constraint.superseded_by`, `AgentOperation.parent_op`/`context_refs` and
+//! `CodeBinding.bound_object`. This module scans [`ObjectDatabase::iter_all`] once, builds adjacency lists in
+//! both directions, and answers ancestor/descendant/reverse-lookup queries in
+//! O(edges) rather than rescanning the store per query.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use std::collections::{HashMap, HashSet, VecDeque};
+use telos_core::hash::ObjectId;
+use telos_core::object::TelosObject;
+
+/// The kind of provenance edge between two objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// `Intent.parents` — this intent builds on a prior intent.
+    Parent,
+    /// `AgentOperation.context_refs` — the agent consulted this object.
+    Context,
+    /// `Constraint.superseded_by` — the live constraint that replaced this one.
+    Supersedes,
+    /// `DecisionRecord.intent_id` — the decision answers a question about this intent.
+    Decides,
+    /// `CodeBinding.bound_object` — the object this code binding is bound to.
+    Impacts,
+}
+
+/// An in-memory lineage graph built from the object store.
+///
+/// `forward` maps a node to the nodes it points *to* (e.g. an intent to its
+/// parents); `reverse` is the inverse, used for descendant queries.
+pub struct ProvenanceGraph {
+    forward: HashMap<ObjectId, Vec<(Relation, ObjectId)>>,
+    reverse: HashMap<ObjectId, Vec<(Relation, ObjectId)>>,
+    code_bindings_by_path: HashMap<String, Vec<ObjectId>>,
+}
+
+impl ProvenanceGraph {
+    /// Scan the whole object store and build the lineage graph.
+    pub fn build(odb: &ObjectDatabase) -> Result<Self, StoreError> {
+        let mut forward: HashMap<ObjectId, Vec<(Relation, ObjectId)>> = HashMap::new();
+        let mut reverse: HashMap<ObjectId, Vec<(Relation, ObjectId)>> = HashMap::new();
+        let mut code_bindings_by_path: HashMap<String, Vec<ObjectId>> = HashMap::new();
+
+        let mut add_edge = |from: ObjectId, rel: Relation, to: ObjectId| {
+            forward.entry(from.clone()).or_default().push((rel, to.clone()));
+            reverse.entry(to).or_default().push((rel, from));
+        };
+
+        for (id, obj) in odb.iter_all()? {
+            match &obj {
+                TelosObject::Intent(intent) => {
+                    for parent in &intent.parents {
+                        add_edge(id.clone(), Relation::Parent, parent.clone());
+                    }
+                }
+                TelosObject::DecisionRecord(dr) => {
+                    add_edge(id.clone(), Relation::Decides, dr.intent_id.clone());
+                }
+                TelosObject::Constraint(c) => {
+                    if let Some(new_id) = &c.superseded_by {
+                        add_edge(id.clone(), Relation::Supersedes, new_id.clone());
+                    }
+                }
+                TelosObject::AgentOperation(op) => {
+                    if let Some(parent_op) = &op.parent_op {
+                        add_edge(id.clone(), Relation::Parent, parent_op.clone());
+                    }
+                    for ctx in &op.context_refs {
+                        add_edge(id.clone(), Relation::Context, ctx.clone());
+                    }
+                }
+                TelosObject::CodeBinding(cb) => {
+                    code_bindings_by_path
+                        .entry(cb.path.clone())
+                        .or_default()
+                        .push(id.clone());
+                    add_edge(id.clone(), Relation::Impacts, cb.bound_object.clone());
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            forward,
+            reverse,
+            code_bindings_by_path,
+        })
+    }
+
+    /// BFS over `edges(node)` starting at `start` (exclusive of `start` itself).
+    /// Guards against cycles with a visited set.
+    fn bfs(
+        start: &ObjectId,
+        edges: &HashMap<ObjectId, Vec<(Relation, ObjectId)>>,
+    ) -> Vec<(Relation, ObjectId)> {
+        let mut visited: HashSet<ObjectId> = HashSet::new();
+        visited.insert(start.clone());
+        let mut queue: VecDeque<ObjectId> = VecDeque::from([start.clone()]);
+        let mut results = Vec::new();
+
+        while let Some(node) = queue.pop_front() {
+            for (rel, next) in edges.get(&node).into_iter().flatten() {
+                if visited.insert(next.clone()) {
+                    results.push((*rel, next.clone()));
+                    queue.push_back(next.clone());
+                }
+            }
+        }
+        results
+    }
+
+    /// All objects reachable by following outgoing edges from `id` (its ancestors:
+    /// parents, the intents its decisions answer, the objects its agent ops cite, ...).
+    pub fn ancestors(&self, id: &ObjectId) -> Vec<(Relation, ObjectId)> {
+        Self::bfs(id, &self.forward)
+    }
+
+    /// All objects reachable by following incoming edges into `id` (its descendants:
+    /// children that named it as a parent, decisions made about it, ...).
+    pub fn descendants(&self, id: &ObjectId) -> Vec<(Relation, ObjectId)> {
+        Self::bfs(id, &self.reverse)
+    }
+
+    /// Follow a constraint's `superseded_by` chain forward to the live (non-superseded) head.
+    pub fn supersession_head(&self, id: &ObjectId) -> ObjectId {
+        let mut current = id.clone();
+        let mut seen = HashSet::new();
+        seen.insert(current.clone());
+        loop {
+            let next = self
+                .forward
+                .get(&current)
+                .into_iter()
+                .flatten()
+                .find(|(rel, _)| *rel == Relation::Supersedes)
+                .map(|(_, to)| to.clone());
+            match next {
+                Some(next) if seen.insert(next.clone()) => current = next,
+                _ => return current,
+            }
+        }
+    }
+
+    /// For each `CodeBinding` on `path`: the binding itself, then its
+    /// [`Self::ancestors`] — the bound object and, from there, its
+    /// `superseded_by` chain or (for a bound intent) its parents. A
+    /// constraint's `source_intent` is not an edge in this graph, and
+    /// decisions (which point at an intent, not from it) are not included.
+    pub fn why_constrained(&self, path: &str) -> Vec<ObjectId> {
+        let mut chain = Vec::new();
+        for binding_id in self.code_bindings_by_path.get(path).into_iter().flatten() {
+            chain.push(binding_id.clone());
+            chain.extend(self.ancestors(binding_id).into_iter().map(|(_, id)| id));
+        }
+        chain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap as StdHashMap;
+    use telos_core::object::constraint::{Constraint, ConstraintSeverity, ConstraintStatus};
+    use telos_core::object::intent::{Author, Intent};
+
+    fn make_odb() -> (tempfile::TempDir, ObjectDatabase) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        (dir, odb)
+    }
+
+    fn make_intent(statement: &str, parents: Vec<ObjectId>) -> Intent {
+        Intent {
+            author: Author { name: "T".into(), email: "t@t".into() },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents,
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: StdHashMap::new(),
+        }
+    }
+
+    #[test]
+    fn ancestors_follow_intent_parents() {
+        let (_dir, odb) = make_odb();
+        let root = odb.write(&TelosObject::Intent(make_intent("root", vec![]))).unwrap();
+        let child = odb
+            .write(&TelosObject::Intent(make_intent("child", vec![root.clone()])))
+            .unwrap();
+
+        let graph = ProvenanceGraph::build(&odb).unwrap();
+        let ancestors = graph.ancestors(&child);
+        assert_eq!(ancestors, vec![(Relation::Parent, root)]);
+    }
+
+    #[test]
+    fn descendants_are_reverse_of_ancestors() {
+        let (_dir, odb) = make_odb();
+        let root = odb.write(&TelosObject::Intent(make_intent("root", vec![]))).unwrap();
+        let child = odb
+            .write(&TelosObject::Intent(make_intent("child", vec![root.clone()])))
+            .unwrap();
+
+        let graph = ProvenanceGraph::build(&odb).unwrap();
+        assert_eq!(graph.descendants(&root), vec![(Relation::Parent, child)]);
+    }
+
+    #[test]
+    fn supersession_chain_follows_to_live_head() {
+        let (_dir, odb) = make_odb();
+        let make_constraint = |statement: &str, status, superseded_by| Constraint {
+            author: Author { name: "T".into(), email: "t@t".into() },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            severity: ConstraintSeverity::Must,
+            status,
+            source_intent: ObjectId::hash(b"intent"),
+            superseded_by,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: StdHashMap::new(),
+        };
+
+        let head = odb
+            .write(&TelosObject::Constraint(make_constraint(
+                "v3",
+                ConstraintStatus::Active,
+                None,
+            )))
+            .unwrap();
+        let v2 = odb
+            .write(&TelosObject::Constraint(make_constraint(
+                "v2",
+                ConstraintStatus::Superseded,
+                Some(head.clone()),
+            )))
+            .unwrap();
+        let v1 = odb
+            .write(&TelosObject::Constraint(make_constraint(
+                "v1",
+                ConstraintStatus::Superseded,
+                Some(v2.clone()),
+            )))
+            .unwrap();
+
+        let graph = ProvenanceGraph::build(&odb).unwrap();
+        assert_eq!(graph.supersession_head(&v1), head);
+    }
+
+    #[test]
+    fn cycles_do_not_infinite_loop() {
+        // Two nodes that (abnormally) point at each other as parents. Content
+        // addressing keeps the object store itself acyclic, so the graph is
+        // built directly.
+        let a = ObjectId::hash(b"a");
+        let b = ObjectId::hash(b"b");
+        let edges = HashMap::from([
+            (a.clone(), vec![(Relation::Parent, b.clone())]),
+            (b.clone(), vec![(Relation::Parent, a.clone())]),
+        ]);
+        let graph = ProvenanceGraph {
+            forward: edges.clone(),
+            reverse: edges,
+            code_bindings_by_path: HashMap::new(),
+        };
+        // Should terminate rather than loop forever.
+        assert_eq!(graph.ancestors(&a), vec![(Relation::Parent, b.clone())]);
+        assert_eq!(graph.descendants(&a), vec![(Relation::Parent, b)]);
+    }
+}
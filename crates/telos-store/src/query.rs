@@ -1,4 +1,13 @@
-//! Query functions for filtering Telos objects
+//! Query functions for filtering Telos objects.
+//!
+//! Each `query_*` function opens a `tracing` span (recording its filter
+//! parameters plus `scanned`/`matched` counts) and emits `telos.query.*`
+//! counters/histograms via the `metrics` crate; the
+//! [`ObjectDatabase::iter_all`]/[`ObjectDatabase::read`] spans nest
+//! naturally under it since both call paths run inside the query's span. None
+//! of this pulls in OpenTelemetry directly — that stays isolated to
+//! `telos-telemetry`, the one crate that talks OTLP, so `telos-core` and a
+//! caller that never installs a subscriber pay nothing for it.
 
 use telos_core::hash::ObjectId;
 use telos_core::object::agent_operation::AgentOperation;
@@ -7,19 +16,57 @@ use telos_core::object::decision_record::DecisionRecord;
 use telos_core::object::intent::Intent;
 use telos_core::object::TelosObject;
 
+use crate::cursor;
 use crate::error::StoreError;
-use crate::index_store::IndexStore;
+use crate::index_store::{IndexStore, TextSearchKind};
 use crate::odb::ObjectDatabase;
+use metrics::{counter, histogram};
+use serde::Serialize;
+use std::time::Instant;
+
+/// Candidate `(id, object)` pairs for a query: every object in the ODB
+/// when there's no text term, or only the `text` index's posting-list
+/// hits (already narrowed to `kind`) when there is one — so a text term
+/// turns an O(total objects) scan into an O(matches) one.
+fn text_candidates(
+    odb: &ObjectDatabase,
+    index: &IndexStore,
+    text: Option<&str>,
+    kind: TextSearchKind,
+) -> Result<Vec<(ObjectId, TelosObject)>, StoreError> {
+    match text {
+        Some(q) => index.search_text(odb, q, &[kind]),
+        None => odb.iter_all(),
+    }
+}
+
+/// Records the `telos.query.*` metrics shared by every `query_*` function:
+/// a counter of queries by kind plus histograms of how many candidates were
+/// scanned and how many matched the filters. Latency is not recorded here:
+/// each function records its own `telos.query.latency_ms` histogram, and
+/// its `#[tracing::instrument]` span (exported, via
+/// [`telos_telemetry::init_from_env`], as an OTLP span duration) captures
+/// it as well.
+fn record_query_metrics(kind: &'static str, scanned: usize, matched: usize) {
+    counter!("telos.query.count", "kind" => kind).increment(1);
+    histogram!("telos.query.scanned", "kind" => kind).record(scanned as f64);
+    histogram!("telos.query.matched", "kind" => kind).record(matched as f64);
+}
 
 /// Query intents with optional filters.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
 pub fn query_intents(
     odb: &ObjectDatabase,
+    index: &IndexStore,
     impact: Option<&str>,
     constraint_contains: Option<&str>,
+    text: Option<&str>,
 ) -> Result<Vec<(ObjectId, Intent)>, StoreError> {
-    let all = odb.iter_all()?;
+    let started = Instant::now();
+    let candidates = text_candidates(odb, index, text, TextSearchKind::Intent)?;
+    let scanned = candidates.len();
     let mut results = Vec::new();
-    for (id, obj) in all {
+    for (id, obj) in candidates {
         if let TelosObject::Intent(intent) = obj {
             let mut matches = true;
             if let Some(impact_filter) = impact {
@@ -43,18 +90,29 @@ pub fn query_intents(
     }
     // Sort by timestamp descending (most recent first)
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("intent", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "intent").record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
 /// Query decision records with optional filters.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
 pub fn query_decisions(
     odb: &ObjectDatabase,
+    index: &IndexStore,
     intent_id: Option<&ObjectId>,
     tag: Option<&str>,
+    text: Option<&str>,
 ) -> Result<Vec<(ObjectId, DecisionRecord)>, StoreError> {
-    let all = odb.iter_all()?;
+    let started = Instant::now();
+    let candidates = text_candidates(odb, index, text, TextSearchKind::DecisionRecord)?;
+    let scanned = candidates.len();
     let mut results = Vec::new();
-    for (id, obj) in all {
+    for (id, obj) in candidates {
         if let TelosObject::DecisionRecord(record) = obj {
             let mut matches = true;
             if let Some(filter_id) = intent_id {
@@ -74,15 +132,26 @@ pub fn query_decisions(
     }
     // Sort by timestamp descending
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("decision_record", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "decision_record")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
 /// Query constraints with optional filters.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
 pub fn query_constraints(
     odb: &ObjectDatabase,
+    index: &IndexStore,
     impact: Option<&str>,
     status: Option<&str>,
+    text: Option<&str>,
 ) -> Result<Vec<(ObjectId, Constraint)>, StoreError> {
+    let started = Instant::now();
     let status_filter = status.unwrap_or("active");
     let target_status = match status_filter {
         "active" => ConstraintStatus::Active,
@@ -91,9 +160,10 @@ pub fn query_constraints(
         _ => ConstraintStatus::Active,
     };
 
-    let all = odb.iter_all()?;
+    let candidates = text_candidates(odb, index, text, TextSearchKind::Constraint)?;
+    let scanned = candidates.len();
     let mut results = Vec::new();
-    for (id, obj) in all {
+    for (id, obj) in candidates {
         if let TelosObject::Constraint(c) = obj {
             let mut matches = c.status == target_status;
             if let Some(impact_filter) = impact {
@@ -107,16 +177,145 @@ pub fn query_constraints(
         }
     }
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("constraint", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "constraint")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
+/// Read `id` as a [`Constraint`], erroring if it's some other object kind.
+fn read_constraint(odb: &ObjectDatabase, id: &ObjectId) -> Result<Constraint, StoreError> {
+    match odb.read(id)? {
+        TelosObject::Constraint(c) => Ok(c),
+        other => Err(StoreError::IndexError(format!(
+            "{} is a {}, not a constraint",
+            id.hex(),
+            other.type_tag()
+        ))),
+    }
+}
+
+/// Walk `id`'s `superseded_by` pointers to the version that hasn't itself
+/// been superseded — the constraint that is currently authoritative.
+///
+/// Errors with [`StoreError::CycleDetected`] if the chain loops back on
+/// itself, and with [`StoreError::ObjectNotFound`] (propagated from
+/// [`ObjectDatabase::read`]) if a `superseded_by` pointer is dangling.
+#[tracing::instrument(skip(odb))]
+pub fn resolve_effective(
+    odb: &ObjectDatabase,
+    id: &ObjectId,
+) -> Result<(ObjectId, Constraint), StoreError> {
+    let mut current_id = id.clone();
+    let mut current = read_constraint(odb, &current_id)?;
+    let mut seen = std::collections::HashSet::new();
+    seen.insert(current_id.hex().to_string());
+    while let Some(next_id) = current.superseded_by.clone() {
+        if !seen.insert(next_id.hex().to_string()) {
+            return Err(StoreError::CycleDetected(format!(
+                "constraint {} transitively supersedes itself",
+                id.hex()
+            )));
+        }
+        current = read_constraint(odb, &next_id)?;
+        current_id = next_id;
+    }
+    Ok((current_id, current))
+}
+
+/// Query constraints for `impact`, returning each supersession chain in
+/// full (oldest first) instead of collapsing it down to the active tip.
+///
+/// Candidates are matched against `impact` regardless of `status`, since an
+/// older entry in a chain has by definition been superseded or deprecated;
+/// they're then grouped by following `superseded_by` edges. A candidate
+/// that no other candidate's `superseded_by` points to is a chain root.
+/// Errors the same way [`resolve_effective`] does.
+#[tracing::instrument(skip(odb, index), fields(scanned, chains))]
+pub fn query_constraints_history(
+    odb: &ObjectDatabase,
+    index: &IndexStore,
+    impact: Option<&str>,
+) -> Result<Vec<Vec<(ObjectId, Constraint)>>, StoreError> {
+    let started = Instant::now();
+    let candidates = text_candidates(odb, index, None, TextSearchKind::Constraint)?;
+    let scanned = candidates.len();
+
+    let mut pool: std::collections::HashMap<String, (ObjectId, Constraint)> =
+        std::collections::HashMap::new();
+    for (id, obj) in candidates {
+        if let TelosObject::Constraint(c) = obj {
+            let keep = match impact {
+                Some(filter) => c.impacts.iter().any(|i| i == filter),
+                None => true,
+            };
+            if keep {
+                pool.insert(id.hex().to_string(), (id, c));
+            }
+        }
+    }
+
+    let superseded_targets: std::collections::HashSet<&str> = pool
+        .values()
+        .filter_map(|(_, c)| c.superseded_by.as_ref().map(|id| id.hex()))
+        .collect();
+    let mut root_hexes: Vec<&String> = pool
+        .keys()
+        .filter(|hex| !superseded_targets.contains(hex.as_str()))
+        .collect();
+    root_hexes.sort();
+
+    let mut chains = Vec::new();
+    for root_hex in root_hexes {
+        let (root_id, root_c) = pool.get(root_hex).expect("key came from pool.keys()");
+        let mut seen = std::collections::HashSet::new();
+        seen.insert(root_hex.clone());
+        let mut chain = vec![(root_id.clone(), root_c.clone())];
+        let mut current = root_c.clone();
+        while let Some(next_id) = current.superseded_by.clone() {
+            let next_hex = next_id.hex().to_string();
+            if !seen.insert(next_hex.clone()) {
+                return Err(StoreError::CycleDetected(format!(
+                    "constraint {} transitively supersedes itself",
+                    root_hex
+                )));
+            }
+            // Prefer the in-pool copy (it already matched the impact
+            // filter); fall back to the store directly since a chain's
+            // later entries may have since dropped that impact.
+            let next_c = match pool.get(&next_hex) {
+                Some((_, c)) => c.clone(),
+                None => read_constraint(odb, &next_id)?,
+            };
+            chain.push((next_id, next_c.clone()));
+            current = next_c;
+        }
+        chains.push(chain);
+    }
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("chains", chains.len());
+    record_query_metrics("constraint_history", scanned, chains.len());
+    histogram!("telos.query.latency_ms", "kind" => "constraint_history")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
+    Ok(chains)
+}
+
 /// Query constraints bound to a specific file path.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
 pub fn query_constraints_by_file(
     odb: &ObjectDatabase,
     index: &IndexStore,
     file_path: &str,
 ) -> Result<Vec<(ObjectId, Constraint)>, StoreError> {
+    let started = Instant::now();
     let bindings = index.by_path(file_path);
+    let scanned = bindings.len();
     let mut results = Vec::new();
     for binding_entry in bindings {
         if let Ok(binding_id) = ObjectId::parse(&binding_entry.id) {
@@ -129,16 +328,26 @@ pub fn query_constraints_by_file(
         }
     }
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("constraint_by_file", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "constraint_by_file")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
 /// Query constraints bound to a specific symbol name.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
 pub fn query_constraints_by_symbol(
     odb: &ObjectDatabase,
     index: &IndexStore,
     symbol: &str,
 ) -> Result<Vec<(ObjectId, Constraint)>, StoreError> {
+    let started = Instant::now();
     let bindings = index.by_symbol(symbol);
+    let scanned = bindings.len();
     let mut results = Vec::new();
     for binding_entry in bindings {
         if let Ok(binding_id) = ObjectId::parse(&binding_entry.id) {
@@ -151,16 +360,141 @@ pub fn query_constraints_by_symbol(
         }
     }
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("constraint_by_symbol", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "constraint_by_symbol")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
+    Ok(results)
+}
+
+/// Query intents tagged with a specific impact, reading the impact index's
+/// posting list directly instead of `query_intents`'s full-candidate scan —
+/// used by `log --impact` so "every intent that touched this tag" doesn't
+/// cost an O(history) walk.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
+pub fn query_intents_by_impact(
+    odb: &ObjectDatabase,
+    index: &IndexStore,
+    tag: &str,
+) -> Result<Vec<(ObjectId, Intent)>, StoreError> {
+    let started = Instant::now();
+    let entries = index.by_impact(tag);
+    let scanned = entries.len();
+    let mut results = Vec::new();
+    for entry in entries {
+        if entry.object_type != "intent" {
+            continue;
+        }
+        if let Ok(id) = ObjectId::parse(&entry.id) {
+            if let Ok(TelosObject::Intent(intent)) = odb.read(&id) {
+                results.push((id, intent));
+            }
+        }
+    }
+    results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("intent_by_impact", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "intent_by_impact")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
+    Ok(results)
+}
+
+/// Query intents bound to a specific file path (via `CodeBinding`s whose
+/// `bound_object` is the intent), the intent analogue of
+/// [`query_constraints_by_file`]. Used by `log --path`.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
+pub fn query_intents_by_file(
+    odb: &ObjectDatabase,
+    index: &IndexStore,
+    file_path: &str,
+) -> Result<Vec<(ObjectId, Intent)>, StoreError> {
+    let started = Instant::now();
+    let bindings = index.by_path(file_path);
+    let scanned = bindings.len();
+    let mut results = Vec::new();
+    for binding_entry in bindings {
+        if let Ok(binding_id) = ObjectId::parse(&binding_entry.id) {
+            if let Ok(TelosObject::CodeBinding(cb)) = odb.read(&binding_id) {
+                let bound_id = &cb.bound_object;
+                if let Ok(TelosObject::Intent(intent)) = odb.read(bound_id) {
+                    results.push((bound_id.clone(), intent));
+                }
+            }
+        }
+    }
+    results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("intent_by_file", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "intent_by_file")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
+    Ok(results)
+}
+
+/// Query intents bound to a specific symbol name, the intent analogue of
+/// [`query_constraints_by_symbol`]. Used by `log --symbol`.
+#[tracing::instrument(skip(odb, index), fields(scanned, matched))]
+pub fn query_intents_by_symbol(
+    odb: &ObjectDatabase,
+    index: &IndexStore,
+    symbol: &str,
+) -> Result<Vec<(ObjectId, Intent)>, StoreError> {
+    let started = Instant::now();
+    let bindings = index.by_symbol(symbol);
+    let scanned = bindings.len();
+    let mut results = Vec::new();
+    for binding_entry in bindings {
+        if let Ok(binding_id) = ObjectId::parse(&binding_entry.id) {
+            if let Ok(TelosObject::CodeBinding(cb)) = odb.read(&binding_id) {
+                let bound_id = &cb.bound_object;
+                if let Ok(TelosObject::Intent(intent)) = odb.read(bound_id) {
+                    results.push((bound_id.clone(), intent));
+                }
+            }
+        }
+    }
+    results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    let span = tracing::Span::current();
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("intent_by_symbol", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "intent_by_symbol")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
 /// Query agent operations with optional filters.
+///
+/// `agent_id`/`session_id` are recorded as span attributes (not just
+/// `skip`ped args) so an agent's activity can be correlated end-to-end
+/// across traces in the OTLP backend, even when the filter itself is
+/// `None` — the attribute is still present, just unset.
+#[tracing::instrument(skip(odb), fields(agent_id, session_id, scanned, matched))]
 pub fn query_agent_operations(
     odb: &ObjectDatabase,
     agent_id: Option<&str>,
     session_id: Option<&str>,
 ) -> Result<Vec<(ObjectId, AgentOperation)>, StoreError> {
+    let started = Instant::now();
+    let span = tracing::Span::current();
+    if let Some(aid) = agent_id {
+        span.record("agent_id", aid);
+    }
+    if let Some(sid) = session_id {
+        span.record("session_id", sid);
+    }
+
     let all = odb.iter_all()?;
+    let scanned = all.len();
     let mut results = Vec::new();
     for (id, obj) in all {
         if let TelosObject::AgentOperation(op) = obj {
@@ -181,9 +515,179 @@ pub fn query_agent_operations(
         }
     }
     results.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
+
+    span.record("scanned", scanned);
+    span.record("matched", results.len());
+    record_query_metrics("agent_operation", scanned, results.len());
+    histogram!("telos.query.latency_ms", "kind" => "agent_operation")
+        .record(started.elapsed().as_secs_f64() * 1000.0);
     Ok(results)
 }
 
+/// One page of results plus an opaque cursor for fetching the next one,
+/// `None` once there's nothing left.
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Accumulates filters for one of the `query_*` functions and applies
+/// cursor-based pagination over the (already timestamp-descending-sorted)
+/// result, so a caller doesn't have to re-scan the whole result set to
+/// fetch page two. Each `run_*` method dispatches to the matching
+/// `query_*` function; filters that function doesn't take are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    impact: Option<String>,
+    constraint_contains: Option<String>,
+    tag: Option<String>,
+    status: Option<String>,
+    intent_id: Option<ObjectId>,
+    agent_id: Option<String>,
+    session_id: Option<String>,
+    text: Option<String>,
+    limit: Option<usize>,
+    after: Option<String>,
+}
+
+impl QueryBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn impact(mut self, impact: impl Into<String>) -> Self {
+        self.impact = Some(impact.into());
+        self
+    }
+
+    pub fn constraint_contains(mut self, substr: impl Into<String>) -> Self {
+        self.constraint_contains = Some(substr.into());
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    pub fn status(mut self, status: impl Into<String>) -> Self {
+        self.status = Some(status.into());
+        self
+    }
+
+    pub fn intent_id(mut self, id: ObjectId) -> Self {
+        self.intent_id = Some(id);
+        self
+    }
+
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn session_id(mut self, session_id: impl Into<String>) -> Self {
+        self.session_id = Some(session_id.into());
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Cap the page at `n` items.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Resume from an opaque cursor returned as a previous page's
+    /// `next_cursor`.
+    pub fn after(mut self, cursor: impl Into<String>) -> Self {
+        self.after = Some(cursor.into());
+        self
+    }
+
+    fn after_key(&self) -> Result<Option<cursor::CursorKey>, StoreError> {
+        self.after.as_deref().map(cursor::decode).transpose()
+    }
+
+    pub fn run_intents(
+        &self,
+        odb: &ObjectDatabase,
+        index: &IndexStore,
+    ) -> Result<Page<(ObjectId, Intent)>, StoreError> {
+        let results = query_intents(
+            odb,
+            index,
+            self.impact.as_deref(),
+            self.constraint_contains.as_deref(),
+            self.text.as_deref(),
+        )?;
+        self.paginate(results, |(id, intent)| cursor::CursorKey::new(intent.timestamp, id))
+    }
+
+    pub fn run_decisions(
+        &self,
+        odb: &ObjectDatabase,
+        index: &IndexStore,
+    ) -> Result<Page<(ObjectId, DecisionRecord)>, StoreError> {
+        let results = query_decisions(
+            odb,
+            index,
+            self.intent_id.as_ref(),
+            self.tag.as_deref(),
+            self.text.as_deref(),
+        )?;
+        self.paginate(results, |(id, record)| cursor::CursorKey::new(record.timestamp, id))
+    }
+
+    pub fn run_constraints(
+        &self,
+        odb: &ObjectDatabase,
+        index: &IndexStore,
+    ) -> Result<Page<(ObjectId, Constraint)>, StoreError> {
+        let results = query_constraints(
+            odb,
+            index,
+            self.impact.as_deref(),
+            self.status.as_deref(),
+            self.text.as_deref(),
+        )?;
+        self.paginate(results, |(id, c)| cursor::CursorKey::new(c.timestamp, id))
+    }
+
+    pub fn run_agent_operations(
+        &self,
+        odb: &ObjectDatabase,
+    ) -> Result<Page<(ObjectId, AgentOperation)>, StoreError> {
+        let results =
+            query_agent_operations(odb, self.agent_id.as_deref(), self.session_id.as_deref())?;
+        self.paginate(results, |(id, op)| cursor::CursorKey::new(op.timestamp, id))
+    }
+
+    fn paginate<T>(
+        &self,
+        items: Vec<T>,
+        key_of: impl Fn(&T) -> cursor::CursorKey,
+    ) -> Result<Page<T>, StoreError> {
+        let after = self.after_key()?;
+        // The `query_*` functions sort by timestamp alone; order ties by id
+        // too, so the cursor's `(timestamp, id)` comparison sees a total order.
+        let mut items = items;
+        items.sort_by_cached_key(|item| {
+            let key = key_of(item);
+            std::cmp::Reverse((key.timestamp, key.id))
+        });
+        let (items, next_key) = cursor::paginate(items, key_of, after.as_ref(), self.limit);
+        Ok(Page {
+            items,
+            next_cursor: next_key.as_ref().map(cursor::encode),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,10 +695,11 @@ mod tests {
     use std::collections::HashMap;
     use telos_core::object::intent::Author;
 
-    fn make_odb() -> (tempfile::TempDir, ObjectDatabase) {
+    fn make_odb() -> (tempfile::TempDir, ObjectDatabase, IndexStore) {
         let dir = tempfile::TempDir::new().unwrap();
         let odb = ObjectDatabase::new(dir.path().join("objects"));
-        (dir, odb)
+        let index = IndexStore::new(dir.path().join("indexes"));
+        (dir, odb, index)
     }
 
     fn make_intent(statement: &str, impacts: Vec<&str>, constraints: Vec<&str>) -> Intent {
@@ -216,7 +721,7 @@ mod tests {
 
     #[test]
     fn query_intents_by_impact() {
-        let (_dir, odb) = make_odb();
+        let (_dir, odb, index) = make_odb();
         let i1 = make_intent("Auth setup", vec!["auth"], vec![]);
         let i2 = make_intent("Task CRUD", vec!["tasks"], vec![]);
         let i3 = make_intent("Auth tokens", vec!["auth", "security"], vec![]);
@@ -225,7 +730,7 @@ mod tests {
         odb.write(&TelosObject::Intent(i2)).unwrap();
         odb.write(&TelosObject::Intent(i3)).unwrap();
 
-        let results = query_intents(&odb, Some("auth"), None).unwrap();
+        let results = query_intents(&odb, &index, Some("auth"), None, None).unwrap();
         assert_eq!(results.len(), 2);
         assert!(results
             .iter()
@@ -234,21 +739,41 @@ mod tests {
 
     #[test]
     fn query_intents_by_constraint() {
-        let (_dir, odb) = make_odb();
+        let (_dir, odb, index) = make_odb();
         let i1 = make_intent("Auth setup", vec!["auth"], vec!["Token expiry <= 1 hour"]);
         let i2 = make_intent("Task CRUD", vec!["tasks"], vec!["Must validate input"]);
 
         odb.write(&TelosObject::Intent(i1)).unwrap();
         odb.write(&TelosObject::Intent(i2)).unwrap();
 
-        let results = query_intents(&odb, None, Some("token")).unwrap();
+        let results = query_intents(&odb, &index, None, Some("token"), None).unwrap();
         assert_eq!(results.len(), 1);
         assert!(results[0].1.constraints[0].contains("Token"));
     }
 
+    #[test]
+    fn query_intents_by_text() {
+        let (_dir, odb, index) = make_odb();
+        let i1 = make_intent("Rotate database credentials", vec!["auth"], vec![]);
+        let i2 = make_intent("Task CRUD", vec!["tasks"], vec![]);
+
+        let id1 = odb.write(&TelosObject::Intent(i1.clone())).unwrap();
+        index
+            .update_for_object(&id1, &TelosObject::Intent(i1))
+            .unwrap();
+        let id2 = odb.write(&TelosObject::Intent(i2.clone())).unwrap();
+        index
+            .update_for_object(&id2, &TelosObject::Intent(i2))
+            .unwrap();
+
+        let results = query_intents(&odb, &index, None, None, Some("credentials")).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.statement.contains("credentials"));
+    }
+
     #[test]
     fn query_decisions_by_intent() {
-        let (_dir, odb) = make_odb();
+        let (_dir, odb, index) = make_odb();
         let intent = make_intent("Auth setup", vec!["auth"], vec![]);
         let intent_id = odb.write(&TelosObject::Intent(intent)).unwrap();
 
@@ -264,17 +789,18 @@ mod tests {
             rationale: Some("Industry standard".into()),
             alternatives: vec![],
             tags: vec!["auth".into()],
+            metadata: HashMap::new(),
         };
         odb.write(&TelosObject::DecisionRecord(record)).unwrap();
 
-        let results = query_decisions(&odb, Some(&intent_id), None).unwrap();
+        let results = query_decisions(&odb, &index, Some(&intent_id), None, None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].1.decision, "JWT");
     }
 
     #[test]
     fn query_decisions_by_tag() {
-        let (_dir, odb) = make_odb();
+        let (_dir, odb, index) = make_odb();
         let intent = make_intent("Auth setup", vec!["auth"], vec![]);
         let intent_id = odb.write(&TelosObject::Intent(intent)).unwrap();
 
@@ -290,6 +816,7 @@ mod tests {
             rationale: None,
             alternatives: vec![],
             tags: vec!["auth".into(), "security".into()],
+            metadata: HashMap::new(),
         };
         let r2 = DecisionRecord {
             intent_id: intent_id.clone(),
@@ -303,12 +830,208 @@ mod tests {
             rationale: None,
             alternatives: vec![],
             tags: vec!["infra".into()],
+            metadata: HashMap::new(),
         };
         odb.write(&TelosObject::DecisionRecord(r1)).unwrap();
         odb.write(&TelosObject::DecisionRecord(r2)).unwrap();
 
-        let results = query_decisions(&odb, None, Some("auth")).unwrap();
+        let results = query_decisions(&odb, &index, None, Some("auth"), None).unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].1.decision, "JWT");
     }
+
+    #[test]
+    fn query_builder_paginates_intents() {
+        let (_dir, odb, index) = make_odb();
+        for i in 0..5 {
+            let intent = make_intent(&format!("Intent {}", i), vec!["auth"], vec![]);
+            odb.write(&TelosObject::Intent(intent)).unwrap();
+        }
+
+        let page1 = QueryBuilder::new()
+            .impact("auth")
+            .limit(2)
+            .run_intents(&odb, &index)
+            .unwrap();
+        assert_eq!(page1.items.len(), 2);
+        let cursor = page1.next_cursor.clone().expect("more pages remain");
+
+        let page2 = QueryBuilder::new()
+            .impact("auth")
+            .limit(2)
+            .after(cursor)
+            .run_intents(&odb, &index)
+            .unwrap();
+        assert_eq!(page2.items.len(), 2);
+
+        let ids1: Vec<_> = page1.items.iter().map(|(id, _)| id.clone()).collect();
+        assert!(page2.items.iter().all(|(id, _)| !ids1.contains(id)));
+    }
+
+    #[test]
+    fn query_builder_breaks_ties_on_identical_timestamps() {
+        let (_dir, odb, index) = make_odb();
+        let t = Utc::now();
+        for i in 0..4 {
+            let mut intent = make_intent(&format!("Intent {}", i), vec!["auth"], vec![]);
+            intent.timestamp = t;
+            odb.write(&TelosObject::Intent(intent)).unwrap();
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        let mut cursor_token: Option<String> = None;
+        loop {
+            let mut builder = QueryBuilder::new().impact("auth").limit(1);
+            if let Some(c) = &cursor_token {
+                builder = builder.after(c.clone());
+            }
+            let page = builder.run_intents(&odb, &index).unwrap();
+            if page.items.is_empty() {
+                break;
+            }
+            for (id, _) in &page.items {
+                assert!(seen.insert(id.clone()), "duplicate id returned across pages");
+            }
+            match page.next_cursor {
+                Some(c) => cursor_token = Some(c),
+                None => break,
+            }
+        }
+        assert_eq!(seen.len(), 4);
+    }
+
+    fn make_constraint(
+        statement: &str,
+        impacts: Vec<&str>,
+        status: ConstraintStatus,
+        superseded_by: Option<ObjectId>,
+    ) -> Constraint {
+        Constraint {
+            author: Author {
+                name: "Test".into(),
+                email: "test@test.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            severity: telos_core::object::constraint::ConstraintSeverity::Should,
+            status,
+            source_intent: ObjectId::hash(b"dummy-intent"),
+            superseded_by,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: impacts.into_iter().map(String::from).collect(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn resolve_effective_follows_chain_to_the_live_tip() {
+        let (_dir, odb, _index) = make_odb();
+        let v2 = make_constraint("v2", vec!["auth"], ConstraintStatus::Active, None);
+        let v2_id = odb.write(&TelosObject::Constraint(v2)).unwrap();
+        let v1 = make_constraint(
+            "v1",
+            vec!["auth"],
+            ConstraintStatus::Superseded,
+            Some(v2_id.clone()),
+        );
+        let v1_id = odb.write(&TelosObject::Constraint(v1)).unwrap();
+
+        let (effective_id, effective) = resolve_effective(&odb, &v1_id).unwrap();
+        assert_eq!(effective_id, v2_id);
+        assert_eq!(effective.statement, "v2");
+    }
+
+    #[test]
+    fn resolve_effective_errors_on_dangling_pointer() {
+        let (_dir, odb, _index) = make_odb();
+        let dangling_target = ObjectId::hash(b"never-written");
+        let c = make_constraint(
+            "a",
+            vec![],
+            ConstraintStatus::Superseded,
+            Some(dangling_target),
+        );
+        let id = odb.write(&TelosObject::Constraint(c)).unwrap();
+
+        let err = resolve_effective(&odb, &id).unwrap_err();
+        assert!(matches!(err, StoreError::ObjectNotFound(_)));
+    }
+
+    #[test]
+    fn query_constraints_history_returns_chain_oldest_to_newest() {
+        let (_dir, odb, index) = make_odb();
+        let v2 = make_constraint("v2", vec!["auth"], ConstraintStatus::Active, None);
+        let v2_id = odb.write(&TelosObject::Constraint(v2)).unwrap();
+        let v1 = make_constraint(
+            "v1",
+            vec!["auth"],
+            ConstraintStatus::Superseded,
+            Some(v2_id.clone()),
+        );
+        let v1_id = odb.write(&TelosObject::Constraint(v1)).unwrap();
+        // Unrelated constraint in a different impact area shouldn't show up.
+        odb.write(&TelosObject::Constraint(make_constraint(
+            "unrelated",
+            vec!["billing"],
+            ConstraintStatus::Active,
+            None,
+        )))
+        .unwrap();
+
+        let chains = query_constraints_history(&odb, &index, Some("auth")).unwrap();
+        assert_eq!(chains.len(), 1);
+        let chain = &chains[0];
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].0, v1_id);
+        assert_eq!(chain[1].0, v2_id);
+        assert_eq!(chain[0].1.statement, "v1");
+        assert_eq!(chain[1].1.statement, "v2");
+    }
+
+    #[test]
+    fn query_intents_by_impact_uses_index_not_full_scan() {
+        let (_dir, odb, index) = make_odb();
+        let i1 = make_intent("Auth setup", vec!["auth"], vec![]);
+        let i2 = make_intent("Task CRUD", vec!["tasks"], vec![]);
+
+        let id1 = odb.write(&TelosObject::Intent(i1.clone())).unwrap();
+        index.update_for_object(&id1, &TelosObject::Intent(i1)).unwrap();
+        let id2 = odb.write(&TelosObject::Intent(i2.clone())).unwrap();
+        index.update_for_object(&id2, &TelosObject::Intent(i2)).unwrap();
+
+        let results = super::query_intents_by_impact(&odb, &index, "auth").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, id1);
+    }
+
+    #[test]
+    fn query_intents_by_file_resolves_through_code_binding() {
+        use telos_core::object::code_binding::{BindingResolution, BindingType, CodeBinding};
+
+        let (_dir, odb, index) = make_odb();
+        let intent = make_intent("Refactor token refresh", vec![], vec![]);
+        let intent_id = odb.write(&TelosObject::Intent(intent)).unwrap();
+
+        let binding = TelosObject::CodeBinding(CodeBinding {
+            path: "src/auth/mod.rs".into(),
+            symbol: Some("refresh_token".into()),
+            span: None,
+            binding_type: BindingType::Function,
+            resolution: BindingResolution::Unchecked,
+            bound_object: intent_id.clone(),
+            fingerprint: None,
+            metadata: HashMap::new(),
+        });
+        let binding_id = odb.write(&binding).unwrap();
+        index.update_for_object(&binding_id, &binding).unwrap();
+
+        let by_file = query_intents_by_file(&odb, &index, "src/auth/mod.rs").unwrap();
+        assert_eq!(by_file.len(), 1);
+        assert_eq!(by_file[0].0, intent_id);
+
+        let by_symbol = query_intents_by_symbol(&odb, &index, "refresh_token").unwrap();
+        assert_eq!(by_symbol.len(), 1);
+        assert_eq!(by_symbol[0].0, intent_id);
+    }
 }
@@ -0,0 +1,328 @@
+//! Arrow `RecordBatch` export of `query_*` results.
+//!
+//! `export::write_object_tables_parquet` dumps the *whole* store to Parquet
+//! files; this module instead maps a single `query_*` call's
+//! `Vec<(ObjectId, T)>` result onto a fixed per-kind Arrow schema, so an
+//! analytics query ("active `Must` constraints per impact area over time")
+//! can run over typed columns without a round trip through a file or
+//! per-object JSON parsing. Results are chunked into [`ROW_GROUP_SIZE`]-row
+//! batches the same way `export` chunks Parquet row groups, so a large
+//! result set streams as several batches instead of one unbounded
+//! allocation. Low-cardinality enum fields (`ConstraintSeverity`,
+//! `ConstraintStatus`) are dictionary-encoded; repeated fields
+//! (`impacts`, `tags`) become Arrow list columns — the repo already uses exactly
+//! both in `export::write_object_tables_parquet`.
+//!
+//! [`to_flight_data`] encodes a schema plus batches as Arrow Flight
+//! `FlightData` messages, the wire format an external notebook would pull
+//! over gRPC via `pyarrow.flight`. This crate doesn't host a Flight
+//! server (same boundary as [`crate::remote::RemoteBackend`]: a client-side
+//! trait with no server hosted here) — `to_flight_data` is the encoding
+//! step a caller wires into one.
+
+use crate::error::StoreError;
+use crate::export;
+use arrow::array::{
+    ArrayRef, ListBuilder, StringArray, StringBuilder, StringDictionaryBuilder,
+    TimestampMicrosecondArray,
+};
+use arrow::datatypes::{DataType, Field, Int8Type, Schema, TimeUnit};
+use arrow::ipc::writer::IpcWriteOptions;
+use arrow::record_batch::RecordBatch;
+use arrow_flight::utils::flight_data_from_arrow_batch;
+use arrow_flight::{FlightData, SchemaAsIpc};
+use std::sync::Arc;
+use telos_core::hash::ObjectId;
+use telos_core::object::agent_operation::AgentOperation;
+use telos_core::object::constraint::{Constraint, ConstraintSeverity, ConstraintStatus};
+use telos_core::object::decision_record::DecisionRecord;
+use telos_core::object::intent::Intent;
+
+/// Query results are batched at this size, mirroring `export::ROW_GROUP_SIZE`.
+const ROW_GROUP_SIZE: usize = 4096;
+
+fn to_batch(schema: &Arc<Schema>, columns: Vec<ArrayRef>) -> Result<RecordBatch, StoreError> {
+    RecordBatch::try_new(schema.clone(), columns)
+        .map_err(|e| StoreError::Io(std::io::Error::other(e.to_string())))
+}
+
+pub fn intents_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+        Field::new("statement", DataType::Utf8, false),
+        Field::new(
+            "impacts",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+    ])
+}
+
+fn intents_batch(schema: &Arc<Schema>, rows: &[(ObjectId, Intent)]) -> Result<RecordBatch, StoreError> {
+    let ids: StringArray = rows.iter().map(|(id, _)| Some(id.hex())).collect();
+    let timestamps: TimestampMicrosecondArray = rows
+        .iter()
+        .map(|(_, i)| Some(i.timestamp.timestamp_micros()))
+        .collect();
+    let statements: StringArray = rows.iter().map(|(_, i)| Some(i.statement.as_str())).collect();
+    let mut impacts = ListBuilder::new(StringBuilder::new());
+    for (_, i) in rows {
+        for tag in &i.impacts {
+            impacts.values().append_value(tag);
+        }
+        impacts.append(true);
+    }
+
+    to_batch(
+        schema,
+        vec![
+            Arc::new(ids),
+            Arc::new(timestamps),
+            Arc::new(statements),
+            Arc::new(impacts.finish()),
+        ],
+    )
+}
+
+/// Batch a `query_intents` result into one or more [`RecordBatch`]es.
+pub fn intent_batches(rows: &[(ObjectId, Intent)]) -> Result<Vec<RecordBatch>, StoreError> {
+    let schema = Arc::new(intents_schema());
+    rows.chunks(ROW_GROUP_SIZE)
+        .map(|chunk| intents_batch(&schema, chunk))
+        .collect()
+}
+
+pub fn constraints_schema() -> Schema {
+    let dict_type = DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8));
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+        Field::new("severity", dict_type.clone(), false),
+        Field::new("status", dict_type, false),
+        Field::new("statement", DataType::Utf8, false),
+        Field::new(
+            "impacts",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+        Field::new("source_intent", DataType::Utf8, false),
+    ])
+}
+
+fn constraints_batch(
+    schema: &Arc<Schema>,
+    rows: &[(ObjectId, Constraint)],
+) -> Result<RecordBatch, StoreError> {
+    let ids: StringArray = rows.iter().map(|(id, _)| Some(id.hex())).collect();
+    let timestamps: TimestampMicrosecondArray = rows
+        .iter()
+        .map(|(_, c)| Some(c.timestamp.timestamp_micros()))
+        .collect();
+
+    let mut severities = StringDictionaryBuilder::<Int8Type>::new();
+    for (_, c) in rows {
+        severities.append_value(match c.severity {
+            ConstraintSeverity::Must => "must",
+            ConstraintSeverity::Should => "should",
+            ConstraintSeverity::Prefer => "prefer",
+        });
+    }
+    let mut statuses = StringDictionaryBuilder::<Int8Type>::new();
+    for (_, c) in rows {
+        statuses.append_value(match c.status {
+            ConstraintStatus::Active => "active",
+            ConstraintStatus::Superseded => "superseded",
+            ConstraintStatus::Deprecated => "deprecated",
+        });
+    }
+
+    let statements: StringArray = rows.iter().map(|(_, c)| Some(c.statement.as_str())).collect();
+    let mut impacts = ListBuilder::new(StringBuilder::new());
+    for (_, c) in rows {
+        for tag in &c.impacts {
+            impacts.values().append_value(tag);
+        }
+        impacts.append(true);
+    }
+    let source_intents: StringArray = rows
+        .iter()
+        .map(|(_, c)| Some(c.source_intent.hex()))
+        .collect();
+
+    to_batch(
+        schema,
+        vec![
+            Arc::new(ids),
+            Arc::new(timestamps),
+            Arc::new(severities.finish()),
+            Arc::new(statuses.finish()),
+            Arc::new(statements),
+            Arc::new(impacts.finish()),
+            Arc::new(source_intents),
+        ],
+    )
+}
+
+/// Batch a `query_constraints` result into one or more [`RecordBatch`]es.
+pub fn constraint_batches(rows: &[(ObjectId, Constraint)]) -> Result<Vec<RecordBatch>, StoreError> {
+    let schema = Arc::new(constraints_schema());
+    rows.chunks(ROW_GROUP_SIZE)
+        .map(|chunk| constraints_batch(&schema, chunk))
+        .collect()
+}
+
+pub fn decision_records_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("intent_id", DataType::Utf8, false),
+        Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+        Field::new("question", DataType::Utf8, false),
+        Field::new("decision", DataType::Utf8, false),
+        Field::new("rationale", DataType::Utf8, true),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            false,
+        ),
+    ])
+}
+
+fn decision_records_batch(
+    schema: &Arc<Schema>,
+    rows: &[(ObjectId, DecisionRecord)],
+) -> Result<RecordBatch, StoreError> {
+    let ids: StringArray = rows.iter().map(|(id, _)| Some(id.hex())).collect();
+    let intent_ids: StringArray = rows.iter().map(|(_, r)| Some(r.intent_id.hex())).collect();
+    let timestamps: TimestampMicrosecondArray = rows
+        .iter()
+        .map(|(_, r)| Some(r.timestamp.timestamp_micros()))
+        .collect();
+    let questions: StringArray = rows.iter().map(|(_, r)| Some(r.question.as_str())).collect();
+    let decisions: StringArray = rows.iter().map(|(_, r)| Some(r.decision.as_str())).collect();
+    let rationales: StringArray = rows.iter().map(|(_, r)| r.rationale.as_deref()).collect();
+    let mut tags = ListBuilder::new(StringBuilder::new());
+    for (_, r) in rows {
+        for tag in &r.tags {
+            tags.values().append_value(tag);
+        }
+        tags.append(true);
+    }
+
+    to_batch(
+        schema,
+        vec![
+            Arc::new(ids),
+            Arc::new(intent_ids),
+            Arc::new(timestamps),
+            Arc::new(questions),
+            Arc::new(decisions),
+            Arc::new(rationales),
+            Arc::new(tags.finish()),
+        ],
+    )
+}
+
+/// Batch a `query_decisions` result into one or more [`RecordBatch`]es.
+pub fn decision_record_batches(
+    rows: &[(ObjectId, DecisionRecord)],
+) -> Result<Vec<RecordBatch>, StoreError> {
+    let schema = Arc::new(decision_records_schema());
+    rows.chunks(ROW_GROUP_SIZE)
+        .map(|chunk| decision_records_batch(&schema, chunk))
+        .collect()
+}
+
+/// Batch a `query_agent_operations` result, reusing `export`'s existing
+/// schema and column-builder so the two stay in sync.
+pub fn agent_operation_batches(
+    rows: &[(ObjectId, AgentOperation)],
+) -> Result<Vec<RecordBatch>, StoreError> {
+    let schema = Arc::new(export::agent_operations_schema());
+    let hex_rows: Vec<(String, AgentOperation)> = rows
+        .iter()
+        .map(|(id, op)| (id.hex().to_string(), op.clone()))
+        .collect();
+    hex_rows
+        .chunks(ROW_GROUP_SIZE)
+        .map(|chunk| to_batch(&schema, export::agent_operation_row_to_columns(chunk)))
+        .collect()
+}
+
+/// Encode `schema` and `batches` as Arrow Flight `FlightData` messages — a
+/// schema message followed by one message per batch — ready to hand to a
+/// `FlightService::do_get` implementation.
+pub fn to_flight_data(schema: &Schema, batches: &[RecordBatch]) -> Vec<FlightData> {
+    let options = IpcWriteOptions::default();
+    let mut messages = vec![FlightData::from(SchemaAsIpc::new(schema, &options))];
+    for batch in batches {
+        let (dictionaries, batch_data) = flight_data_from_arrow_batch(batch, &options);
+        messages.extend(dictionaries);
+        messages.push(batch_data);
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use telos_core::object::intent::Author;
+
+    fn sample_constraint(severity: ConstraintSeverity, impacts: Vec<&str>) -> Constraint {
+        Constraint {
+            author: Author {
+                name: "Test".into(),
+                email: "test@test.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: "must be documented".into(),
+            severity,
+            status: ConstraintStatus::Active,
+            source_intent: ObjectId::hash(b"dummy"),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: impacts.into_iter().map(String::from).collect(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn constraint_batches_one_row_per_result() {
+        let rows = vec![
+            (ObjectId::hash(b"a"), sample_constraint(ConstraintSeverity::Must, vec!["payments"])),
+            (ObjectId::hash(b"b"), sample_constraint(ConstraintSeverity::Should, vec![])),
+        ];
+        let batches = constraint_batches(&rows).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].num_rows(), 2);
+        assert_eq!(batches[0].schema().fields().len(), constraints_schema().fields().len());
+    }
+
+    #[test]
+    fn constraint_batches_split_into_row_groups() {
+        let rows: Vec<_> = (0..ROW_GROUP_SIZE + 1)
+            .map(|i| {
+                (
+                    ObjectId::hash(format!("c{i}").as_bytes()),
+                    sample_constraint(ConstraintSeverity::Should, vec![]),
+                )
+            })
+            .collect();
+        let batches = constraint_batches(&rows).unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].num_rows(), ROW_GROUP_SIZE);
+        assert_eq!(batches[1].num_rows(), 1);
+    }
+
+    #[test]
+    fn flight_data_includes_schema_and_batch_messages() {
+        let rows = vec![(ObjectId::hash(b"a"), sample_constraint(ConstraintSeverity::Must, vec![]))];
+        let batches = constraint_batches(&rows).unwrap();
+        let flight_data = to_flight_data(&constraints_schema(), &batches);
+        // At least a schema message plus one per batch.
+        assert!(flight_data.len() >= 1 + batches.len());
+    }
+}
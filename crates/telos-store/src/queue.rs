@@ -0,0 +1,439 @@
+//! Persistent verification job queue.
+//!
+//! Modeled on pict-rs' `QueueRepo`: jobs live in a `jobs` sled tree keyed by
+//! a monotonic job id (tracked in a `meta` tree counter) under
+//! `.telos/queue/`. A [`VerificationWorker`] claims the oldest queued job
+//! via a compare-and-swap on its state (`Queued` -> `Claimed`), builds a
+//! prompt from the job's `BehaviorDiff`, runs it through `CodexRunner`, and
+//! writes a new `BehaviorDiff` object back to the ODB carrying the
+//! resulting `Verification` (objects are content-addressed and immutable,
+//! so "writing back" means a fresh object rather than an in-place edit).
+//!
+//! `enqueue` notifies a `Condvar` so a sleeping worker wakes immediately
+//! instead of polling; the worker still wakes periodically on its own to
+//! reclaim jobs stuck in `Claimed` past their lease, in case the worker
+//! that claimed them crashed before calling `complete`.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use telos_core::hash::ObjectId;
+use telos_core::object::behavior_diff::{Verification, VerificationStatus};
+use telos_core::object::TelosObject;
+use telos_experiment::codex::CodexRunner;
+
+/// How long a `Claimed` job may go without a `complete()` call before it's
+/// considered abandoned and eligible for another worker to reclaim.
+const LEASE_DURATION: Duration = Duration::from_secs(300);
+/// Base of the exponential backoff applied to a job's `not_before` after a
+/// `Failed` verification (`BASE * 2^retries`, capped at `MAX_BACKOFF`).
+const BASE_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_BACKOFF: Duration = Duration::from_secs(3600);
+/// How often the worker wakes on its own to check for lease-expired jobs,
+/// even with no `enqueue` notification.
+const LEASE_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+fn sled_err(e: sled::Error) -> StoreError {
+    StoreError::IndexError(e.to_string())
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Claimed,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationJob {
+    pub job_id: u64,
+    /// Hex id of the `BehaviorDiff` object this job verifies.
+    pub behavior_diff_id: String,
+    pub state: JobState,
+    pub retries: u32,
+    /// Unix millis after which a `Claimed` job is considered abandoned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<u64>,
+    /// Unix millis before which a retried job should not be reclaimed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<u64>,
+}
+
+/// A `Condvar`-backed wakeup so `enqueue` can rouse a sleeping worker
+/// immediately instead of the worker polling on a fixed interval.
+#[derive(Clone)]
+struct Notify {
+    inner: Arc<(Mutex<()>, Condvar)>,
+}
+
+impl Notify {
+    fn new() -> Self {
+        Self {
+            inner: Arc::new((Mutex::new(()), Condvar::new())),
+        }
+    }
+
+    fn notify_one(&self) {
+        self.inner.1.notify_one();
+    }
+
+    fn wait_timeout(&self, timeout: Duration) {
+        let (lock, cvar) = &*self.inner;
+        let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = cvar.wait_timeout(guard, timeout);
+    }
+}
+
+pub struct VerificationQueue {
+    dir: PathBuf,
+    db: sled::Db,
+    notify: Notify,
+}
+
+impl VerificationQueue {
+    pub fn new(queue_dir: impl Into<PathBuf>) -> Self {
+        let dir = queue_dir.into();
+        let db = sled::open(dir.join("kv")).expect("failed to open queue kv store");
+        Self {
+            dir,
+            db,
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn ensure_dir(&self) -> Result<(), StoreError> {
+        std::fs::create_dir_all(&self.dir)?;
+        Ok(())
+    }
+
+    fn jobs_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("jobs").map_err(sled_err)
+    }
+
+    fn meta_tree(&self) -> Result<sled::Tree, StoreError> {
+        self.db.open_tree("meta").map_err(sled_err)
+    }
+
+    fn next_job_id(&self) -> Result<u64, StoreError> {
+        let meta = self.meta_tree()?;
+        let next = meta
+            .update_and_fetch(b"next_job_id", |old| {
+                let id = old
+                    .map(|v| u64::from_be_bytes(v.try_into().unwrap_or([0; 8])))
+                    .unwrap_or(0)
+                    + 1;
+                Some(id.to_be_bytes().to_vec())
+            })
+            .map_err(sled_err)?
+            .expect("update_and_fetch always returns Some here");
+        Ok(u64::from_be_bytes(next.as_ref().try_into().unwrap()))
+    }
+
+    /// Enqueue a verification job for a `BehaviorDiff` object and wake a
+    /// sleeping worker, if one is waiting.
+    pub fn enqueue(&self, behavior_diff_id: &ObjectId) -> Result<u64, StoreError> {
+        self.ensure_dir()?;
+        let job_id = self.next_job_id()?;
+        let job = VerificationJob {
+            job_id,
+            behavior_diff_id: behavior_diff_id.hex().to_string(),
+            state: JobState::Queued,
+            retries: 0,
+            lease_expires_at: None,
+            not_before: None,
+        };
+        let jobs = self.jobs_tree()?;
+        jobs.insert(job_id.to_be_bytes(), serde_json::to_vec(&job)?)
+            .map_err(sled_err)?;
+        self.notify.notify_one();
+        Ok(job_id)
+    }
+
+    /// Claim the oldest eligible job (`Queued`, or `Claimed` past its
+    /// lease) via compare-and-swap on its state.
+    pub fn claim(&self) -> Result<Option<VerificationJob>, StoreError> {
+        let jobs = self.jobs_tree()?;
+        let now = now_millis();
+
+        for entry in jobs.iter() {
+            let (key, value) = entry.map_err(sled_err)?;
+            let job: VerificationJob = serde_json::from_slice(&value)?;
+
+            let eligible = match job.state {
+                JobState::Queued => job.not_before.map(|nb| nb <= now).unwrap_or(true),
+                JobState::Claimed => job
+                    .lease_expires_at
+                    .map(|exp| exp <= now)
+                    .unwrap_or(false),
+                JobState::Done => false,
+            };
+            if !eligible {
+                continue;
+            }
+
+            let mut claimed = job.clone();
+            claimed.state = JobState::Claimed;
+            claimed.lease_expires_at = Some(now + LEASE_DURATION.as_millis() as u64);
+
+            let cas = jobs
+                .compare_and_swap(
+                    key,
+                    Some(value.as_ref()),
+                    Some(serde_json::to_vec(&claimed)?),
+                )
+                .map_err(sled_err)?;
+            if cas.is_ok() {
+                return Ok(Some(claimed));
+            }
+            // Lost the race to another worker; move on to the next job.
+        }
+        Ok(None)
+    }
+
+    /// Record the outcome of a claimed job. `Passed` marks it `Done`;
+    /// `Failed` bumps `retries` and requeues with exponential backoff.
+    pub fn complete(
+        &self,
+        job_id: u64,
+        status: VerificationStatus,
+    ) -> Result<(), StoreError> {
+        let jobs = self.jobs_tree()?;
+        let key = job_id.to_be_bytes();
+        let Some(value) = jobs.get(key).map_err(sled_err)? else {
+            return Err(StoreError::IndexError(format!(
+                "no such verification job: {}",
+                job_id
+            )));
+        };
+        let mut job: VerificationJob = serde_json::from_slice(&value)?;
+        job.lease_expires_at = None;
+
+        match status {
+            VerificationStatus::Passed => {
+                job.state = JobState::Done;
+            }
+            VerificationStatus::Failed => {
+                job.retries += 1;
+                let backoff = BASE_BACKOFF
+                    .saturating_mul(1u32.checked_shl(job.retries).unwrap_or(u32::MAX))
+                    .min(MAX_BACKOFF);
+                job.state = JobState::Queued;
+                job.not_before = Some(now_millis() + backoff.as_millis() as u64);
+            }
+            VerificationStatus::Pending => {
+                job.state = JobState::Queued;
+            }
+        }
+
+        jobs.insert(key, serde_json::to_vec(&job)?).map_err(sled_err)?;
+        Ok(())
+    }
+
+    /// Number of jobs still waiting to be claimed (queued, or claimed but
+    /// past their lease).
+    pub fn pending_count(&self) -> Result<usize, StoreError> {
+        let jobs = self.jobs_tree()?;
+        let now = now_millis();
+        let mut count = 0;
+        for entry in jobs.iter() {
+            let (_, value) = entry.map_err(sled_err)?;
+            let job: VerificationJob = serde_json::from_slice(&value)?;
+            let pending = match job.state {
+                JobState::Queued => true,
+                JobState::Claimed => job.lease_expires_at.map(|exp| exp <= now).unwrap_or(false),
+                JobState::Done => false,
+            };
+            if pending {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// Drives claimed jobs through `CodexRunner` and writes the resulting
+/// `Verification` back as a new `BehaviorDiff` object.
+pub struct VerificationWorker<'a> {
+    pub queue: &'a VerificationQueue,
+    pub odb: &'a ObjectDatabase,
+    pub codex: CodexRunner,
+}
+
+impl<'a> VerificationWorker<'a> {
+    pub fn new(queue: &'a VerificationQueue, odb: &'a ObjectDatabase) -> Self {
+        Self {
+            queue,
+            odb,
+            codex: CodexRunner::default(),
+        }
+    }
+
+    /// Build a worker whose `CodexRunner` is resolved from `config`
+    /// (`[profile.<name>].codex` if `profile` is selected, else top-level
+    /// `[codex]`, else `CodexRunner::default()`'s values) instead of always
+    /// using the hardcoded defaults.
+    pub fn with_config(
+        queue: &'a VerificationQueue,
+        odb: &'a ObjectDatabase,
+        config: &telos_core::config::TelosConfig,
+        profile: Option<&str>,
+    ) -> Self {
+        Self {
+            queue,
+            odb,
+            codex: CodexRunner::from_config(&config.resolve_codex(profile)),
+        }
+    }
+
+    /// Claim and process a single job, if one is available. Returns
+    /// `false` when the queue had nothing eligible to claim.
+    pub fn run_once(&self) -> Result<bool, StoreError> {
+        let Some(job) = self.queue.claim()? else {
+            return Ok(false);
+        };
+
+        let diff_id = ObjectId::parse(&job.behavior_diff_id)
+            .map_err(|e| StoreError::IndexError(e.to_string()))?;
+        let TelosObject::BehaviorDiff(mut diff) = self.odb.read(&diff_id)? else {
+            self.queue.complete(job.job_id, VerificationStatus::Failed)?;
+            return Ok(true);
+        };
+
+        let prompt = Self::build_prompt(&diff);
+        let (status, details) = match self.codex.run(&prompt) {
+            Ok(response) if !response.timed_out => {
+                (VerificationStatus::Passed, Some(response.output))
+            }
+            Ok(response) => (VerificationStatus::Failed, Some(response.output)),
+            Err(e) => (VerificationStatus::Failed, Some(e.to_string())),
+        };
+
+        diff.verification = Some(Verification {
+            status: status.clone(),
+            details,
+        });
+        self.odb.write(&TelosObject::BehaviorDiff(diff))?;
+
+        self.queue.complete(job.job_id, status)?;
+        Ok(true)
+    }
+
+    fn build_prompt(diff: &telos_core::object::BehaviorDiff) -> String {
+        let mut prompt = String::from(
+            "Verify that the following behavior changes hold given their impact radius:\n\n",
+        );
+        for change in &diff.changes {
+            prompt.push_str(&format!("- {}\n", change.description));
+            if let Some(before) = &change.before {
+                prompt.push_str(&format!("  before: {}\n", before));
+            }
+            prompt.push_str(&format!("  after: {}\n", change.after));
+        }
+        prompt.push_str("\nImpact radius:\n");
+        prompt.push_str(&format!("  direct: {}\n", diff.impact.direct.join(", ")));
+        if !diff.impact.indirect.is_empty() {
+            prompt.push_str(&format!("  indirect: {}\n", diff.impact.indirect.join(", ")));
+        }
+        prompt
+    }
+
+    /// Run until `queue.notify` wakes us (on `enqueue`) or the periodic
+    /// lease sweep interval elapses, processing every eligible job each
+    /// time. Intended to run on a dedicated thread.
+    pub fn run_forever(&self) -> ! {
+        loop {
+            while self.run_once().unwrap_or(false) {}
+            self.queue.notify.wait_timeout(LEASE_SWEEP_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_queue() -> (tempfile::TempDir, VerificationQueue) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let queue = VerificationQueue::new(dir.path().join("queue"));
+        (dir, queue)
+    }
+
+    #[test]
+    fn enqueue_and_claim_round_trips() {
+        let (_dir, queue) = make_queue();
+        let diff_id = ObjectId::hash(b"diff1");
+
+        let job_id = queue.enqueue(&diff_id).unwrap();
+        assert_eq!(queue.pending_count().unwrap(), 1);
+
+        let job = queue.claim().unwrap().expect("job should be claimable");
+        assert_eq!(job.job_id, job_id);
+        assert_eq!(job.state, JobState::Claimed);
+        assert_eq!(job.behavior_diff_id, diff_id.hex());
+
+        // Claimed within its lease, so it's no longer eligible for reclaim.
+        assert_eq!(queue.pending_count().unwrap(), 0);
+        assert!(queue.claim().unwrap().is_none());
+    }
+
+    #[test]
+    fn complete_passed_marks_job_done() {
+        let (_dir, queue) = make_queue();
+        let diff_id = ObjectId::hash(b"diff1");
+        queue.enqueue(&diff_id).unwrap();
+        let job = queue.claim().unwrap().unwrap();
+
+        queue.complete(job.job_id, VerificationStatus::Passed).unwrap();
+        assert_eq!(queue.pending_count().unwrap(), 0);
+        assert!(queue.claim().unwrap().is_none());
+    }
+
+    #[test]
+    fn complete_failed_requeues_with_backoff() {
+        let (_dir, queue) = make_queue();
+        let diff_id = ObjectId::hash(b"diff1");
+        queue.enqueue(&diff_id).unwrap();
+        let job = queue.claim().unwrap().unwrap();
+
+        queue.complete(job.job_id, VerificationStatus::Failed).unwrap();
+
+        // Backoff delay means the job isn't immediately reclaimable.
+        assert!(queue.claim().unwrap().is_none());
+
+        let jobs = queue.jobs_tree().unwrap();
+        let stored: VerificationJob =
+            serde_json::from_slice(&jobs.get(job.job_id.to_be_bytes()).unwrap().unwrap()).unwrap();
+        assert_eq!(stored.retries, 1);
+        assert_eq!(stored.state, JobState::Queued);
+        assert!(stored.not_before.unwrap() > now_millis());
+    }
+
+    #[test]
+    fn claim_reclaims_expired_lease() {
+        let (_dir, queue) = make_queue();
+        let diff_id = ObjectId::hash(b"diff1");
+        queue.enqueue(&diff_id).unwrap();
+        let job = queue.claim().unwrap().unwrap();
+
+        // Simulate a crashed worker: force the lease into the past.
+        let jobs = queue.jobs_tree().unwrap();
+        let mut stuck = job.clone();
+        stuck.lease_expires_at = Some(now_millis() - 1);
+        jobs.insert(job.job_id.to_be_bytes(), serde_json::to_vec(&stuck).unwrap())
+            .unwrap();
+
+        let reclaimed = queue.claim().unwrap().expect("expired lease should be reclaimable");
+        assert_eq!(reclaimed.job_id, job.job_id);
+    }
+}
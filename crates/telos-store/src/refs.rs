@@ -1,7 +1,9 @@
 use crate::error::StoreError;
 use crate::lockfile::Lockfile;
+use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
+use telos_core::hash::ObjectId;
 use telos_core::object::intent_stream::IntentStreamRef;
 
 /// Manages HEAD and stream references on disk.
@@ -210,6 +212,114 @@ impl RefStore {
         stream.tip = Some(tip);
         self.write_stream(&stream)
     }
+
+    /// Compare-and-swap update of the current stream's tip.
+    ///
+    /// Re-reads the stream under the same lock the write commits through, so
+    /// the check and the write are atomic with respect to other processes:
+    /// unlike [`Self::update_current_tip`]'s read-modify-write (which can let
+    /// two concurrent writers both read the same old tip and have one
+    /// silently clobber the other's advance), this fails with
+    /// [`StoreError::LockConflict`] if the on-disk tip no longer equals
+    /// `expected`. Callers building a new object on top of a known parent
+    /// pass that parent as `expected`, so a losing writer gets a clear
+    /// conflict to rebuild on the new tip instead of losing history.
+    pub fn update_current_tip_cas(
+        &self,
+        expected: Option<telos_core::hash::ObjectId>,
+        new: telos_core::hash::ObjectId,
+    ) -> Result<(), StoreError> {
+        let name = self.read_head()?;
+        let path = self.stream_path(&name);
+        let mut lock = Lockfile::acquire(&path)?;
+        let mut stream = self.read_stream(&name)?;
+        if stream.tip != expected {
+            return Err(StoreError::LockConflict(format!(
+                "stream '{}' tip changed concurrently: expected {}, found {}",
+                name,
+                expected.as_ref().map(|t| t.short()).unwrap_or("<none>"),
+                stream.tip.as_ref().map(|t| t.short()).unwrap_or("<none>"),
+            )));
+        }
+        stream.tip = Some(new);
+        let json = serde_json::to_string_pretty(&stream)?;
+        lock.write_all(json.as_bytes())?;
+        lock.commit()
+    }
+
+    // --- Remote-tracking refs ---
+    //
+    // `refs/remotes/<remote>/<stream>` records where a stream's tip was last
+    // seen on a given remote, analogous to git's `refs/remotes/<name>/*` —
+    // so a `fetch` can record remote state without touching the local
+    // stream tip, leaving the merge/fast-forward decision to `pull`.
+
+    fn remote_head_path(&self, remote: &str, stream: &str) -> PathBuf {
+        self.telos_dir.join("refs").join("remotes").join(remote).join(stream)
+    }
+
+    /// Read a remote-tracking stream tip, or `None` if never fetched.
+    pub fn read_remote_head(
+        &self,
+        remote: &str,
+        stream: &str,
+    ) -> Result<Option<telos_core::hash::ObjectId>, StoreError> {
+        let path = self.remote_head_path(remote, stream);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let hex = fs::read_to_string(path)?;
+        let hex = hex.trim();
+        if hex.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(telos_core::hash::ObjectId::parse(hex).map_err(StoreError::Core)?))
+    }
+
+    /// Record `remote`'s last-seen tip for `stream`.
+    pub fn write_remote_head(
+        &self,
+        remote: &str,
+        stream: &str,
+        tip: &telos_core::hash::ObjectId,
+    ) -> Result<(), StoreError> {
+        Self::validate_stream_name(stream)?;
+        let path = self.remote_head_path(remote, stream);
+        fs::create_dir_all(path.parent().unwrap())?;
+        let mut lock = Lockfile::acquire(&path)?;
+        lock.write_all(tip.hex().as_bytes())?;
+        lock.commit()
+    }
+
+    fn remote_objects_path(&self, remote: &str) -> PathBuf {
+        self.telos_dir.join("refs").join("remotes").join(remote).join("objects")
+    }
+
+    /// Every object id `remote` was known to hold as of the last [`crate::sync::fetch`].
+    /// Used to tell whether a locally-visible object originated here or was
+    /// pulled in, since objects themselves carry no branch/origin tag.
+    pub fn read_remote_objects(&self, remote: &str) -> Result<HashSet<ObjectId>, StoreError> {
+        let path = self.remote_objects_path(remote);
+        if !path.exists() {
+            return Ok(HashSet::new());
+        }
+        fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| ObjectId::parse(line).map_err(StoreError::Core))
+            .collect()
+    }
+
+    /// Overwrite the recorded set of object ids `remote` holds.
+    pub fn write_remote_objects(&self, remote: &str, ids: &HashSet<ObjectId>) -> Result<(), StoreError> {
+        let path = self.remote_objects_path(remote);
+        fs::create_dir_all(path.parent().unwrap())?;
+        let mut hexes: Vec<&str> = ids.iter().map(|id| id.hex()).collect();
+        hexes.sort_unstable();
+        let mut lock = Lockfile::acquire(&path)?;
+        lock.write_all(hexes.join("\n").as_bytes())?;
+        lock.commit()
+    }
 }
 
 #[cfg(test)]
@@ -335,4 +445,71 @@ mod tests {
         let names = store.list_streams().unwrap();
         assert_eq!(names, vec!["alpha", "beta", "main"]);
     }
+
+    #[test]
+    fn update_current_tip_cas_succeeds_when_expected_matches() {
+        let (_dir, store) = setup();
+        let stream = IntentStreamRef {
+            name: "main".into(),
+            tip: None,
+            created_at: Utc::now(),
+            description: None,
+        };
+        store.create_stream(&stream).unwrap();
+
+        let first = telos_core::hash::ObjectId::hash(b"first");
+        store.update_current_tip_cas(None, first.clone()).unwrap();
+        assert_eq!(store.current_stream().unwrap().tip, Some(first.clone()));
+
+        let second = telos_core::hash::ObjectId::hash(b"second");
+        store
+            .update_current_tip_cas(Some(first), second.clone())
+            .unwrap();
+        assert_eq!(store.current_stream().unwrap().tip, Some(second));
+    }
+
+    #[test]
+    fn update_current_tip_cas_rejects_stale_expected() {
+        let (_dir, store) = setup();
+        let stream = IntentStreamRef {
+            name: "main".into(),
+            tip: None,
+            created_at: Utc::now(),
+            description: None,
+        };
+        store.create_stream(&stream).unwrap();
+
+        let actual = telos_core::hash::ObjectId::hash(b"actual");
+        store.update_current_tip_cas(None, actual.clone()).unwrap();
+
+        let stale = telos_core::hash::ObjectId::hash(b"stale-guess");
+        let losing_write = telos_core::hash::ObjectId::hash(b"losing-write");
+        let result = store.update_current_tip_cas(Some(stale), losing_write);
+        assert!(matches!(result, Err(StoreError::LockConflict(_))));
+        // the conflicting write must not have taken effect
+        assert_eq!(store.current_stream().unwrap().tip, Some(actual));
+    }
+
+    #[test]
+    fn remote_head_round_trip() {
+        let (_dir, store) = setup();
+        assert_eq!(store.read_remote_head("origin", "main").unwrap(), None);
+
+        let tip = telos_core::hash::ObjectId::hash(b"tip");
+        store.write_remote_head("origin", "main", &tip).unwrap();
+        assert_eq!(store.read_remote_head("origin", "main").unwrap(), Some(tip));
+    }
+
+    #[test]
+    fn remote_objects_round_trip() {
+        let (_dir, store) = setup();
+        assert!(store.read_remote_objects("origin").unwrap().is_empty());
+
+        let ids: HashSet<_> = [b"a".as_slice(), b"b".as_slice()]
+            .into_iter()
+            .map(ObjectId::hash)
+            .collect();
+        store.write_remote_objects("origin", &ids).unwrap();
+        assert_eq!(store.read_remote_objects("origin").unwrap(), ids);
+    }
 }
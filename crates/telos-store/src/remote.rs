@@ -0,0 +1,629 @@
+//! Remote object store backends for syncing content-addressed objects
+//! between repositories.
+//!
+//! Because every object is immutable and named by its hash, sync is a
+//! set-difference problem: enumerate local ids, ask the remote which it
+//! already has, and transfer only the rest. [`RemoteBackend`] captures the
+//! operations that requires; [`HttpRemote`] implements it against a simple
+//! key-value HTTP endpoint (`GET`/`PUT` by hex id, plus a `have`
+//! negotiation endpoint), and [`S3Remote`] implements it directly against
+//! an S3-compatible bucket (real S3, or a self-hosted Garage cluster) with
+//! hand-rolled SigV4 request signing (see [`crate::sigv4`]) — no proxy
+//! required.
+
+use crate::bloom::HaveFilter;
+use crate::error::StoreError;
+use crate::sigv4::{authorization_header, SigningRequest};
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use telos_core::hash::ObjectId;
+
+/// A place objects can be pushed to or pulled from.
+pub trait RemoteBackend {
+    /// Of the given ids, return the subset the remote already has.
+    fn has(&self, ids: &[ObjectId]) -> Result<HashSet<ObjectId>, StoreError>;
+
+    /// Every ObjectId the remote currently holds.
+    fn list_ids(&self) -> Result<Vec<ObjectId>, StoreError>;
+
+    /// A compact membership summary of everything the remote holds, if it
+    /// can produce one cheaply. Lets [`crate::sync::push`] test candidate
+    /// ids against it locally instead of shipping the whole local id list
+    /// to [`Self::has`] and waiting on an exact response. `None` means the
+    /// remote doesn't support this; callers fall back to [`Self::has`].
+    fn have_filter(&self) -> Result<Option<HaveFilter>, StoreError>;
+
+    /// Upload an object's canonical plaintext bytes. Idempotent: uploading
+    /// an id the remote already has is a no-op, mirroring
+    /// [`crate::odb::ObjectDatabase::write`].
+    fn upload(&self, id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError>;
+
+    /// Download an object's canonical plaintext bytes by id.
+    fn download(&self, id: &ObjectId) -> Result<Vec<u8>, StoreError>;
+
+    /// Current tip of `stream` on the remote, if it has one.
+    fn get_stream_head(&self, stream: &str) -> Result<Option<ObjectId>, StoreError>;
+
+    /// Update the remote's tip for `stream`.
+    fn set_stream_head(&self, stream: &str, id: &ObjectId) -> Result<(), StoreError>;
+
+    /// The raw JSON bytes of the constraint status ref keyed by `base_id`
+    /// (see [`crate::status_ref`]), if the remote has one.
+    fn get_status_ref(&self, base_id: &ObjectId) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Overwrite the remote's status ref for `base_id` with `bytes` — a
+    /// genuinely mutable key, unlike every object `upload` ever touches.
+    fn set_status_ref(&self, base_id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError>;
+}
+
+/// Open a remote by URL: `http://`/`https://` dispatches to [`HttpRemote`],
+/// `s3://<bucket>[/<prefix>]` dispatches to [`S3Remote`] (configured via
+/// `TELOS_S3_*` env vars, see [`S3Remote::from_env`]), and anything else is
+/// treated as a filesystem path and dispatches to [`FsRemote`] — so
+/// `telos remote add origin /srv/shared-repo.telos` works without a
+/// server, the same way a bare git remote can be a local path.
+pub fn open(url: &str, token: Option<String>) -> Box<dyn RemoteBackend> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Box::new(HttpRemote::new(url.to_string(), token))
+    } else if let Some(rest) = url.strip_prefix("s3://") {
+        let (bucket, prefix) = match rest.split_once('/') {
+            Some((bucket, prefix)) => (bucket.to_string(), prefix.trim_end_matches('/').to_string()),
+            None => (rest.to_string(), String::new()),
+        };
+        Box::new(S3Remote::from_env(bucket, prefix))
+    } else {
+        Box::new(FsRemote::new(url))
+    }
+}
+
+/// A remote backed by a simple HTTP key-value endpoint:
+///
+/// - `POST  {base_url}/have`        body: `["<hex>", ...]` -> `["<hex>", ...]` (ids the remote has)
+/// - `GET   {base_url}/objects`                             -> `["<hex>", ...]` (every id the remote has)
+/// - `PUT   {base_url}/objects/<hex>` body: raw bytes       -> 204/200
+/// - `GET   {base_url}/objects/<hex>`                       -> raw bytes
+pub struct HttpRemote {
+    base_url: String,
+    token: Option<String>,
+    client: reqwest::blocking::Client,
+}
+
+impl HttpRemote {
+    pub fn new(base_url: impl Into<String>, token: Option<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            token,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    fn authed(&self, builder: reqwest::blocking::RequestBuilder) -> reqwest::blocking::RequestBuilder {
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+}
+
+impl RemoteBackend for HttpRemote {
+    fn has(&self, ids: &[ObjectId]) -> Result<HashSet<ObjectId>, StoreError> {
+        let hexes: Vec<String> = ids.iter().map(|id| id.hex().to_string()).collect();
+        let resp = self
+            .authed(self.client.post(format!("{}/have", self.base_url)))
+            .json(&hexes)
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let present: Vec<String> = resp.json().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        present
+            .into_iter()
+            .map(|hex| ObjectId::parse(&hex).map_err(StoreError::Core))
+            .collect()
+    }
+
+    fn list_ids(&self) -> Result<Vec<ObjectId>, StoreError> {
+        let resp = self
+            .authed(self.client.get(format!("{}/objects", self.base_url)))
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let hexes: Vec<String> = resp.json().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        hexes
+            .into_iter()
+            .map(|hex| ObjectId::parse(&hex).map_err(StoreError::Core))
+            .collect()
+    }
+
+    fn upload(&self, id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        self.authed(self.client.put(format!("{}/objects/{}", self.base_url, id.hex())))
+            .body(bytes.to_vec())
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn download(&self, id: &ObjectId) -> Result<Vec<u8>, StoreError> {
+        let resp = self
+            .authed(self.client.get(format!("{}/objects/{}", self.base_url, id.hex())))
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(resp.bytes().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?.to_vec())
+    }
+
+    fn get_stream_head(&self, stream: &str) -> Result<Option<ObjectId>, StoreError> {
+        let resp = self
+            .authed(self.client.get(format!("{}/refs/streams/{}", self.base_url, stream)))
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let hex: String = resp.text().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let hex = hex.trim();
+        if hex.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ObjectId::parse(hex).map_err(StoreError::Core)?))
+    }
+
+    fn set_stream_head(&self, stream: &str, id: &ObjectId) -> Result<(), StoreError> {
+        self.authed(self.client.put(format!("{}/refs/streams/{}", self.base_url, stream)))
+            .body(id.hex().to_string())
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn get_status_ref(&self, base_id: &ObjectId) -> Result<Option<Vec<u8>>, StoreError> {
+        let resp = self
+            .authed(self.client.get(format!("{}/refs/constraints/{}", self.base_url, base_id.hex())))
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(Some(resp.bytes().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?.to_vec()))
+    }
+
+    fn set_status_ref(&self, base_id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        self.authed(self.client.put(format!("{}/refs/constraints/{}", self.base_url, base_id.hex())))
+            .body(bytes.to_vec())
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn have_filter(&self) -> Result<Option<HaveFilter>, StoreError> {
+        let resp = self
+            .authed(self.client.get(format!("{}/have-filter", self.base_url)))
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let filter: HaveFilter = resp.json().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(Some(filter))
+    }
+}
+
+/// A remote backed by a plain directory on disk (local path or a mounted
+/// network share) — no server required, mirroring a bare git remote at a
+/// filesystem path. Layout:
+///
+/// - `<dir>/objects/<hex>`        — raw canonical object bytes
+/// - `<dir>/refs/streams/<name>`  — the stream's tip, as hex text
+pub struct FsRemote {
+    dir: PathBuf,
+}
+
+impl FsRemote {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn objects_dir(&self) -> PathBuf {
+        self.dir.join("objects")
+    }
+
+    fn object_path(&self, id: &ObjectId) -> PathBuf {
+        self.objects_dir().join(id.hex())
+    }
+
+    fn stream_head_path(&self, stream: &str) -> PathBuf {
+        self.dir.join("refs").join("streams").join(stream)
+    }
+
+    fn status_ref_path(&self, base_id: &ObjectId) -> PathBuf {
+        self.dir.join("refs").join("constraints").join(format!("{}.json", base_id.hex()))
+    }
+}
+
+impl RemoteBackend for FsRemote {
+    fn has(&self, ids: &[ObjectId]) -> Result<HashSet<ObjectId>, StoreError> {
+        Ok(ids.iter().filter(|id| self.object_path(id).exists()).cloned().collect())
+    }
+
+    fn list_ids(&self) -> Result<Vec<ObjectId>, StoreError> {
+        let dir = self.objects_dir();
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str() {
+                ids.push(ObjectId::parse(name).map_err(StoreError::Core)?);
+            }
+        }
+        Ok(ids)
+    }
+
+    fn upload(&self, id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        fs::create_dir_all(self.objects_dir())?;
+        let path = self.object_path(id);
+        if path.exists() {
+            return Ok(());
+        }
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn download(&self, id: &ObjectId) -> Result<Vec<u8>, StoreError> {
+        fs::read(self.object_path(id)).map_err(|_| StoreError::ObjectNotFound(id.hex().to_string()))
+    }
+
+    fn get_stream_head(&self, stream: &str) -> Result<Option<ObjectId>, StoreError> {
+        let path = self.stream_head_path(stream);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let hex = fs::read_to_string(path)?;
+        let hex = hex.trim();
+        if hex.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ObjectId::parse(hex).map_err(StoreError::Core)?))
+    }
+
+    fn set_stream_head(&self, stream: &str, id: &ObjectId) -> Result<(), StoreError> {
+        let path = self.stream_head_path(stream);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, id.hex())?;
+        Ok(())
+    }
+
+    fn get_status_ref(&self, base_id: &ObjectId) -> Result<Option<Vec<u8>>, StoreError> {
+        let path = self.status_ref_path(base_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(fs::read(path)?))
+    }
+
+    fn set_status_ref(&self, base_id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        let path = self.status_ref_path(base_id);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    fn have_filter(&self) -> Result<Option<HaveFilter>, StoreError> {
+        // A filesystem remote can always enumerate its own objects cheaply,
+        // so it always has one to offer.
+        Ok(Some(HaveFilter::build(&self.list_ids()?)))
+    }
+}
+
+/// A remote backed directly by an S3-compatible bucket — real AWS S3, or a
+/// self-hosted Garage cluster, since both speak the same REST API and
+/// SigV4 signing scheme. Every key lives under `<prefix>/`, laid out the
+/// same way as [`FsRemote`]'s directory (`objects/<hex>`,
+/// `refs/streams/<name>`, `refs/constraints/<hex>.json`) so the two
+/// backends are drop-in replacements for each other.
+pub struct S3Remote {
+    endpoint: String,
+    bucket: String,
+    prefix: String,
+    region: String,
+    access_key_id: String,
+    secret_access_key: String,
+    client: reqwest::blocking::Client,
+}
+
+impl S3Remote {
+    pub fn new(
+        endpoint: impl Into<String>,
+        bucket: impl Into<String>,
+        prefix: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            bucket: bucket.into(),
+            prefix: prefix.into(),
+            region: region.into(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Build an [`S3Remote`] for `bucket`/`prefix` from the standard Telos
+    /// S3 env vars, mirroring how `.telos/config.json` keeps a remote's
+    /// name/URL but never its secrets (see `TELOS_PASSPHRASE`,
+    /// `TELOS_AUTH_TOKEN`): `TELOS_S3_ENDPOINT`, `TELOS_S3_REGION`
+    /// (default `us-east-1`, which Garage also accepts), and the required
+    /// `TELOS_S3_ACCESS_KEY_ID` / `TELOS_S3_SECRET_ACCESS_KEY`.
+    pub fn from_env(bucket: String, prefix: String) -> Self {
+        let endpoint = env::var("TELOS_S3_ENDPOINT").unwrap_or_else(|_| "https://s3.amazonaws.com".into());
+        let region = env::var("TELOS_S3_REGION").unwrap_or_else(|_| "us-east-1".into());
+        let access_key_id = env::var("TELOS_S3_ACCESS_KEY_ID").unwrap_or_default();
+        let secret_access_key = env::var("TELOS_S3_SECRET_ACCESS_KEY").unwrap_or_default();
+        Self::new(endpoint, bucket, prefix, region, access_key_id, secret_access_key)
+    }
+
+    fn key(&self, suffix: &str) -> String {
+        if self.prefix.is_empty() {
+            suffix.to_string()
+        } else {
+            format!("{}/{}", self.prefix, suffix)
+        }
+    }
+
+    fn object_key(&self, id: &ObjectId) -> String {
+        self.key(&format!("objects/{}", id.hex()))
+    }
+
+    fn stream_head_key(&self, stream: &str) -> String {
+        self.key(&format!("refs/streams/{}", stream))
+    }
+
+    fn status_ref_key(&self, base_id: &ObjectId) -> String {
+        self.key(&format!("refs/constraints/{}.json", base_id.hex()))
+    }
+
+    /// Issue a signed request against `key` (path-style, so the request
+    /// always hits `<endpoint>/<bucket>/<key>` regardless of whether the
+    /// endpoint supports virtual-hosted addressing — Garage's default).
+    fn request(
+        &self,
+        method: reqwest::Method,
+        key: &str,
+        query: &str,
+        body: Vec<u8>,
+    ) -> Result<reqwest::blocking::Response, StoreError> {
+        let host = self
+            .endpoint
+            .splitn(2, "://")
+            .nth(1)
+            .ok_or_else(|| StoreError::RemoteSyncError(format!("invalid S3 endpoint '{}'", self.endpoint)))?;
+        let canonical_uri = format!("/{}/{}", self.bucket, key);
+        let amz_date = current_amz_date();
+
+        let payload_hash = {
+            use sha2::Digest;
+            hex::encode(sha2::Sha256::digest(&body))
+        };
+        let headers = [
+            ("host", host),
+            ("x-amz-content-sha256", payload_hash.as_str()),
+            ("x-amz-date", amz_date.as_str()),
+        ];
+        let authorization = authorization_header(&SigningRequest {
+            access_key_id: &self.access_key_id,
+            secret_access_key: &self.secret_access_key,
+            region: &self.region,
+            method: method.as_str(),
+            canonical_uri: &canonical_uri,
+            headers: &headers,
+            body: &body,
+            amz_date: &amz_date,
+        });
+
+        let url = if query.is_empty() {
+            format!("{}{}", self.endpoint, canonical_uri)
+        } else {
+            format!("{}{}?{}", self.endpoint, canonical_uri, query)
+        };
+
+        self.client
+            .request(method, url)
+            .header("host", host)
+            .header("x-amz-content-sha256", &payload_hash)
+            .header("x-amz-date", &amz_date)
+            .header("Authorization", authorization)
+            .body(body)
+            .send()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StoreError> {
+        let resp = self.request(reqwest::Method::GET, key, "", Vec::new())?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(Some(resp.bytes().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?.to_vec()))
+    }
+
+    fn put(&self, key: &str, bytes: Vec<u8>) -> Result<(), StoreError> {
+        self.request(reqwest::Method::PUT, key, "", bytes)?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Parse the `<Key>...</Key>` entries out of a `ListObjectsV2` XML
+    /// response body. Hand-rolled tag extraction rather than an XML
+    /// parser dependency — `ListObjectsV2`'s response shape is fixed and
+    /// this is the only field Telos ever reads from it.
+    fn parse_list_keys(xml: &str) -> Vec<String> {
+        let mut keys = Vec::new();
+        let mut rest = xml;
+        while let Some(start) = rest.find("<Key>") {
+            let after_start = &rest[start + "<Key>".len()..];
+            let Some(end) = after_start.find("</Key>") else {
+                break;
+            };
+            keys.push(after_start[..end].to_string());
+            rest = &after_start[end + "</Key>".len()..];
+        }
+        keys
+    }
+}
+
+impl RemoteBackend for S3Remote {
+    fn has(&self, ids: &[ObjectId]) -> Result<HashSet<ObjectId>, StoreError> {
+        let all = self.list_ids()?.into_iter().collect::<HashSet<_>>();
+        Ok(ids.iter().filter(|id| all.contains(id)).cloned().collect())
+    }
+
+    fn list_ids(&self) -> Result<Vec<ObjectId>, StoreError> {
+        let prefix = self.key("objects/");
+        let query = format!("list-type=2&prefix={}", prefix);
+        let resp = self
+            .request(reqwest::Method::GET, "", &query, Vec::new())?
+            .error_for_status()
+            .map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        let body = resp.text().map_err(|e| StoreError::RemoteSyncError(e.to_string()))?;
+        Self::parse_list_keys(&body)
+            .into_iter()
+            .filter_map(|k| k.rsplit('/').next().map(str::to_string))
+            .map(|hex| ObjectId::parse(&hex).map_err(StoreError::Core))
+            .collect()
+    }
+
+    fn upload(&self, id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        self.put(&self.object_key(id), bytes.to_vec())
+    }
+
+    fn download(&self, id: &ObjectId) -> Result<Vec<u8>, StoreError> {
+        self.get(&self.object_key(id))?
+            .ok_or_else(|| StoreError::ObjectNotFound(id.hex().to_string()))
+    }
+
+    fn get_stream_head(&self, stream: &str) -> Result<Option<ObjectId>, StoreError> {
+        match self.get(&self.stream_head_key(stream))? {
+            Some(bytes) => Ok(Some(ObjectId::parse(String::from_utf8_lossy(&bytes).trim()).map_err(StoreError::Core)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn set_stream_head(&self, stream: &str, id: &ObjectId) -> Result<(), StoreError> {
+        self.put(&self.stream_head_key(stream), id.hex().to_string().into_bytes())
+    }
+
+    fn get_status_ref(&self, base_id: &ObjectId) -> Result<Option<Vec<u8>>, StoreError> {
+        self.get(&self.status_ref_key(base_id))
+    }
+
+    fn set_status_ref(&self, base_id: &ObjectId, bytes: &[u8]) -> Result<(), StoreError> {
+        self.put(&self.status_ref_key(base_id), bytes.to_vec())
+    }
+
+    fn have_filter(&self) -> Result<Option<HaveFilter>, StoreError> {
+        // Same reasoning as FsRemote: listing is cheap (one ListObjectsV2
+        // call), so build the filter fresh rather than maintaining a
+        // persisted copy that could drift from the actual object set.
+        Ok(Some(HaveFilter::build(&self.list_ids()?)))
+    }
+}
+
+/// `YYYYMMDDTHHMMSSZ`, as SigV4 requires for `x-amz-date`.
+fn current_amz_date() -> String {
+    use chrono::Utc;
+    Utc::now().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fs_remote_round_trips_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(dir.path());
+        let id = ObjectId::hash(b"hello");
+
+        assert!(remote.has(&[id.clone()]).unwrap().is_empty());
+        remote.upload(&id, b"hello").unwrap();
+        assert!(remote.has(&[id.clone()]).unwrap().contains(&id));
+        assert_eq!(remote.download(&id).unwrap(), b"hello");
+        assert_eq!(remote.list_ids().unwrap(), vec![id]);
+    }
+
+    #[test]
+    fn fs_remote_have_filter_reports_what_it_holds() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(dir.path());
+        let present = ObjectId::hash(b"present");
+        let absent = ObjectId::hash(b"absent");
+        remote.upload(&present, b"present").unwrap();
+
+        let filter = remote.have_filter().unwrap().expect("fs remote always offers a filter");
+        assert!(filter.contains(&present));
+        assert!(!filter.contains(&absent));
+    }
+
+    #[test]
+    fn fs_remote_round_trips_stream_heads() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(dir.path());
+        let id = ObjectId::hash(b"tip");
+
+        assert_eq!(remote.get_stream_head("main").unwrap(), None);
+        remote.set_stream_head("main", &id).unwrap();
+        assert_eq!(remote.get_stream_head("main").unwrap(), Some(id));
+    }
+
+    #[test]
+    fn fs_remote_round_trips_status_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(dir.path());
+        let base_id = ObjectId::hash(b"constraint");
+
+        assert_eq!(remote.get_status_ref(&base_id).unwrap(), None);
+        remote.set_status_ref(&base_id, b"{}").unwrap();
+        assert_eq!(remote.get_status_ref(&base_id).unwrap(), Some(b"{}".to_vec()));
+    }
+
+    #[test]
+    fn open_dispatches_on_url_scheme() {
+        let dir = tempfile::tempdir().unwrap();
+        let _fs_remote = open(dir.path().to_str().unwrap(), None);
+        let _http_remote = open("https://example.com/telos", None);
+        let _s3_remote = open("s3://my-bucket/telos", None);
+    }
+
+    #[test]
+    fn parse_list_keys_extracts_every_key_tag() {
+        let xml = "<ListBucketResult><Contents><Key>telos/objects/aa</Key></Contents>\
+                   <Contents><Key>telos/objects/bb</Key></Contents></ListBucketResult>";
+        assert_eq!(S3Remote::parse_list_keys(xml), vec!["telos/objects/aa", "telos/objects/bb"]);
+    }
+}
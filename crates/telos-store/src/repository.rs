@@ -1,16 +1,27 @@
+use crate::agent_queue::AgentTaskQueue;
+use crate::auth::{RevokedTokens, SignedToken, Verb};
+use crate::crypto::{self, DataKey};
 use crate::error::StoreError;
 use crate::index_store::IndexStore;
+use crate::keystore::{Keystore, PassphraseKeystore, WrappedKey};
 use crate::odb::ObjectDatabase;
+use crate::queue::VerificationQueue;
 use crate::refs::RefStore;
+use crate::signing::{AllowedSigners, AuthorKey, AuthorityList, ObjectSignature, SignatureStatus, SignatureStore};
+use crate::status_ref::{StatusRef, StatusRefStore};
+use crate::sync::{ConstraintConflict, ResolvedConflicts};
 use chrono::Utc;
+use metrics::{counter, histogram};
 use std::collections::{HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
-use telos_core::hash::ObjectId;
+use telos_core::hash::{HashAlgo, ObjectId};
+use telos_core::object::constraint::ConstraintStatus;
 use telos_core::object::intent_stream::IntentStreamRef;
 use telos_core::object::{
     AgentOperation, ChangeSet, CodeBinding, Constraint, DecisionRecord, Intent, TelosObject,
 };
+use telos_core::serialize::ContentFormat;
 
 const TELOS_DIR: &str = ".telos";
 
@@ -23,11 +34,58 @@ pub struct Repository {
     pub odb: ObjectDatabase,
     pub refs: RefStore,
     pub indexes: IndexStore,
+    pub queue: VerificationQueue,
+    pub signatures: SignatureStore,
+    pub status_refs: StatusRefStore,
+    pub agent_tasks: AgentTaskQueue,
 }
 
 impl Repository {
     /// Initialize a new Telos repository at `path`.
     pub fn init(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::init_inner(path, None, HashAlgo::default(), ContentFormat::default())
+    }
+
+    /// Initialize a new Telos repository at `path` with object bytes encrypted
+    /// at rest under a key derived from `passphrase`. Content-addressing is
+    /// unaffected: hashes are still computed over plaintext (see
+    /// [`crate::odb::ObjectDatabase`]).
+    pub fn init_encrypted(path: impl AsRef<Path>, passphrase: &str) -> Result<Self, StoreError> {
+        Self::init_inner(path, Some(passphrase), HashAlgo::default(), ContentFormat::default())
+    }
+
+    /// Initialize a new repository whose objects are content-addressed with
+    /// `hash_algo` (e.g. [`HashAlgo::Blake3`]) instead of the default
+    /// SHA-256. The choice is recorded in `.telos/config.json` so `open`
+    /// picks it back up; since ids are self-describing multihashes,
+    /// changing this later (by editing that field) is also safe — it only
+    /// changes what new writes use, not how existing ids read or compare.
+    pub fn init_with_hash_algo(path: impl AsRef<Path>, hash_algo: HashAlgo) -> Result<Self, StoreError> {
+        Self::init_inner(path, None, hash_algo, ContentFormat::default())
+    }
+
+    /// Initialize a new repository whose objects are canonicalized with
+    /// `content_format` (e.g. [`ContentFormat::Jcs`]) instead of the legacy
+    /// `type_tag\0sorted_json` encoding. The choice is recorded in
+    /// `.telos/config.json` so `open` picks it back up. As with `hash_algo`,
+    /// changing it later only changes what new writes use: `gc --pack`
+    /// re-reads and repacks existing loose bytes verbatim rather than
+    /// re-canonicalizing them (see
+    /// [`crate::odb::ObjectDatabase::pack_loose`]), so existing content keeps
+    /// hashing under whatever format wrote it.
+    pub fn init_with_content_format(
+        path: impl AsRef<Path>,
+        content_format: ContentFormat,
+    ) -> Result<Self, StoreError> {
+        Self::init_inner(path, None, HashAlgo::default(), content_format)
+    }
+
+    fn init_inner(
+        path: impl AsRef<Path>,
+        passphrase: Option<&str>,
+        hash_algo: HashAlgo,
+        content_format: ContentFormat,
+    ) -> Result<Self, StoreError> {
         let root = path.as_ref().to_path_buf();
         let telos_dir = root.join(TELOS_DIR);
 
@@ -42,21 +100,51 @@ impl Repository {
         fs::create_dir_all(telos_dir.join("refs").join("streams"))?;
         fs::create_dir_all(telos_dir.join("logs").join("streams"))?;
         fs::create_dir_all(telos_dir.join("indexes"))?;
+        fs::create_dir_all(telos_dir.join("queue"))?;
+        fs::create_dir_all(telos_dir.join("agent_queue"))?;
+        fs::create_dir_all(telos_dir.join("signatures"))?;
+
+        let data_key = match passphrase {
+            Some(passphrase) => {
+                let data_key: DataKey = crypto::random_bytes();
+                let wrapped = PassphraseKeystore::new(passphrase).wrap(&data_key)?;
+                fs::write(
+                    telos_dir.join("keystore.json"),
+                    serde_json::to_string_pretty(&wrapped)?,
+                )?;
+                Some(data_key)
+            }
+            None => None,
+        };
 
         // Write default config
         let config = serde_json::json!({
             "version": 1,
             "created_at": Utc::now().to_rfc3339(),
+            "encrypted": data_key.is_some(),
+            "hash_algo": hash_algo.as_str(),
+            "content_format": content_format.as_str(),
         });
         fs::write(
             telos_dir.join("config.json"),
             serde_json::to_string_pretty(&config)?,
         )?;
 
+        let odb = match data_key {
+            Some(key) => ObjectDatabase::new_encrypted(telos_dir.join("objects"), key),
+            None => ObjectDatabase::new(telos_dir.join("objects")),
+        }
+        .with_hash_algo(hash_algo)
+        .with_content_format(content_format);
+
         let repo = Self {
-            odb: ObjectDatabase::new(telos_dir.join("objects")),
+            odb,
             refs: RefStore::new(&telos_dir),
             indexes: IndexStore::new(telos_dir.join("indexes")),
+            queue: VerificationQueue::new(telos_dir.join("queue")),
+            signatures: SignatureStore::new(telos_dir.join("signatures")),
+            status_refs: StatusRefStore::new(&telos_dir),
+            agent_tasks: AgentTaskQueue::new(telos_dir.join("agent_queue")),
             root,
         };
 
@@ -75,8 +163,20 @@ impl Repository {
         Ok(repo)
     }
 
-    /// Open an existing repository at `path`.
+    /// Open an existing repository at `path`. Fails with
+    /// [`StoreError::KeystoreError`] if the repository is encrypted; use
+    /// [`Repository::open_encrypted`] instead.
     pub fn open(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        Self::open_inner(path, None)
+    }
+
+    /// Open an existing encrypted repository at `path`, unwrapping the data
+    /// key with `passphrase`.
+    pub fn open_encrypted(path: impl AsRef<Path>, passphrase: &str) -> Result<Self, StoreError> {
+        Self::open_inner(path, Some(passphrase))
+    }
+
+    fn open_inner(path: impl AsRef<Path>, passphrase: Option<&str>) -> Result<Self, StoreError> {
         let root = path.as_ref().to_path_buf();
         let telos_dir = root.join(TELOS_DIR);
 
@@ -86,14 +186,362 @@ impl Repository {
             ));
         }
 
+        let encrypted = Self::is_encrypted(&telos_dir)?;
+        let hash_algo = Self::configured_hash_algo(&telos_dir)?;
+        let content_format = Self::configured_content_format(&telos_dir)?;
+        let odb = match (encrypted, passphrase) {
+            (false, _) => ObjectDatabase::new(telos_dir.join("objects")),
+            (true, None) => {
+                return Err(StoreError::KeystoreError(
+                    "repository is encrypted; open with Repository::open_encrypted".into(),
+                ))
+            }
+            (true, Some(passphrase)) => {
+                let wrapped: WrappedKey =
+                    serde_json::from_str(&fs::read_to_string(telos_dir.join("keystore.json"))?)?;
+                let data_key = PassphraseKeystore::new(passphrase).unwrap(&wrapped)?;
+                ObjectDatabase::new_encrypted(telos_dir.join("objects"), data_key)
+            }
+        }
+        .with_hash_algo(hash_algo)
+        .with_content_format(content_format);
+
         Ok(Self {
-            odb: ObjectDatabase::new(telos_dir.join("objects")),
+            odb,
             refs: RefStore::new(&telos_dir),
             indexes: IndexStore::new(telos_dir.join("indexes")),
+            queue: VerificationQueue::new(telos_dir.join("queue")),
+            signatures: SignatureStore::new(telos_dir.join("signatures")),
+            status_refs: StatusRefStore::new(&telos_dir),
+            agent_tasks: AgentTaskQueue::new(telos_dir.join("agent_queue")),
             root,
         })
     }
 
+    fn is_encrypted(telos_dir: &Path) -> Result<bool, StoreError> {
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(telos_dir.join("config.json"))?)?;
+        Ok(config.get("encrypted").and_then(|v| v.as_bool()).unwrap_or(false))
+    }
+
+    /// The `hash_algo` recorded in `.telos/config.json` at `init` time,
+    /// defaulting to SHA-256 for repositories created before this setting
+    /// existed (or with an unrecognized value).
+    fn configured_hash_algo(telos_dir: &Path) -> Result<HashAlgo, StoreError> {
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(telos_dir.join("config.json"))?)?;
+        Ok(config
+            .get("hash_algo")
+            .and_then(|v| v.as_str())
+            .and_then(HashAlgo::parse_name)
+            .unwrap_or_default())
+    }
+
+    /// The `content_format` recorded in `.telos/config.json` at `init` time,
+    /// defaulting to the legacy format for repositories created before this
+    /// setting existed (or with an unrecognized value).
+    fn configured_content_format(telos_dir: &Path) -> Result<ContentFormat, StoreError> {
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(telos_dir.join("config.json"))?)?;
+        Ok(config
+            .get("content_format")
+            .and_then(|v| v.as_str())
+            .and_then(ContentFormat::parse_name)
+            .unwrap_or_default())
+    }
+
+    fn config_path(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("config.json")
+    }
+
+    /// Load the repository's layered `.telos/config.toml` (author/codex
+    /// defaults plus named `[profile.<name>]` overrides). A missing file
+    /// resolves to an all-default config, since the file is optional.
+    pub fn telos_config(&self) -> Result<telos_core::config::TelosConfig, StoreError> {
+        telos_core::config::TelosConfig::load(&self.root.join(TELOS_DIR))
+            .map_err(|e| StoreError::ConfigError(e.to_string()))
+    }
+
+    fn read_config(&self) -> Result<serde_json::Value, StoreError> {
+        Ok(serde_json::from_str(&fs::read_to_string(self.config_path())?)?)
+    }
+
+    fn write_config(&self, config: &serde_json::Value) -> Result<(), StoreError> {
+        fs::write(self.config_path(), serde_json::to_string_pretty(config)?)?;
+        Ok(())
+    }
+
+    /// Record a remote's URL (and optional auth token) under `name` in
+    /// `.telos/config.json`, so `telos push <name>` / `telos pull <name>`
+    /// can look it up by name instead of requiring the URL every time.
+    pub fn set_remote(&self, name: &str, url: &str, token: Option<&str>) -> Result<(), StoreError> {
+        let mut config = self.read_config()?;
+        let remotes = config
+            .as_object_mut()
+            .unwrap()
+            .entry("remotes")
+            .or_insert_with(|| serde_json::json!({}));
+        remotes[name] = serde_json::json!({ "url": url, "token": token });
+        self.write_config(&config)
+    }
+
+    /// Look up a configured remote's URL and optional token by name.
+    pub fn remote(&self, name: &str) -> Result<(String, Option<String>), StoreError> {
+        let config = self.read_config()?;
+        let entry = config
+            .get("remotes")
+            .and_then(|r| r.get(name))
+            .ok_or_else(|| StoreError::RemoteNotFound(name.to_string()))?;
+        let url = entry
+            .get("url")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| StoreError::RemoteNotFound(name.to_string()))?
+            .to_string();
+        let token = entry
+            .get("token")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        Ok((url, token))
+    }
+
+    /// Names of every configured remote.
+    pub fn list_remotes(&self) -> Result<Vec<String>, StoreError> {
+        let config = self.read_config()?;
+        let Some(remotes) = config.get("remotes").and_then(|r| r.as_object()) else {
+            return Ok(vec![]);
+        };
+        let mut names: Vec<String> = remotes.keys().cloned().collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn authorities_path(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("authorities.json")
+    }
+
+    /// Load the set of public keys authorized to sign `Must`-severity
+    /// constraints from `.telos/authorities.json` (empty if the repo has
+    /// never set one).
+    pub fn authorities(&self) -> Result<AuthorityList, StoreError> {
+        AuthorityList::load(self.authorities_path())
+    }
+
+    /// Persist `authorities` to `.telos/authorities.json`.
+    pub fn save_authorities(&self, authorities: &AuthorityList) -> Result<(), StoreError> {
+        authorities.save(self.authorities_path())
+    }
+
+    fn keys_dir(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("keys")
+    }
+
+    /// Resolve the `AuthorKey` used to sign objects created in this
+    /// repository. If `ssh_key_path` is given, load it as an OpenSSH
+    /// `ssh-ed25519` private key. Otherwise lazily load (or, on first use,
+    /// generate and persist) the repository's own raw key at
+    /// `.telos/keys/id_ed25519` — mirroring `set_remote`'s
+    /// lazy-creation-of-`.telos/config.json` precedent.
+    pub fn signing_key(&self, ssh_key_path: Option<&Path>) -> Result<AuthorKey, StoreError> {
+        if let Some(path) = ssh_key_path {
+            return AuthorKey::from_openssh_file(path);
+        }
+
+        let keys_dir = self.keys_dir();
+        let key_path = keys_dir.join("id_ed25519");
+        if key_path.exists() {
+            let seed: [u8; 32] = fs::read(&key_path)?
+                .try_into()
+                .map_err(|_| StoreError::SigningError(format!("{} is corrupt", key_path.display())))?;
+            return Ok(AuthorKey::from_seed(&seed));
+        }
+
+        fs::create_dir_all(&keys_dir)?;
+        let key = AuthorKey::generate();
+        fs::write(&key_path, key.seed())?;
+        Ok(key)
+    }
+
+    /// Resolve the `AuthorKey` to a key already loaded in a running
+    /// `ssh-agent`, so a contributor can sign with an existing SSH
+    /// identity instead of a Telos-generated one. `public_key_hex` selects
+    /// which agent identity to use (`None` picks the first ed25519 one).
+    pub fn signing_key_from_agent(&self, public_key_hex: Option<&str>) -> Result<AuthorKey, StoreError> {
+        AuthorKey::from_ssh_agent(public_key_hex)
+    }
+
+    /// Sign `id`'s stored object with `key` and persist the resulting
+    /// signature. Re-reads the object rather than taking it by value, since
+    /// `create_intent`/`create_decision`/`create_constraint` already
+    /// consume their object and return just its `ObjectId`.
+    #[tracing::instrument(skip(self, key))]
+    pub fn sign_object(&self, id: &ObjectId, key: &AuthorKey) -> Result<ObjectSignature, StoreError> {
+        let object = self.odb.read(id)?;
+        let sig = key.sign_with_authority(&object, &self.authorities()?)?;
+        self.signatures.ensure_dir()?;
+        self.signatures.put(&sig)?;
+        Ok(sig)
+    }
+
+    fn allowed_signers_path(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("allowed_signers")
+    }
+
+    /// Load the trusted-keys file used to verify signatures
+    /// (`.telos/allowed_signers`), empty if the repo has never set one.
+    pub fn allowed_signers(&self) -> Result<AllowedSigners, StoreError> {
+        AllowedSigners::load(self.allowed_signers_path())
+    }
+
+    /// Persist `allowed` to `.telos/allowed_signers`.
+    pub fn save_allowed_signers(&self, allowed: &AllowedSigners) -> Result<(), StoreError> {
+        allowed.save(self.allowed_signers_path())
+    }
+
+    /// Verify `id`'s stored signature (if any) against its current
+    /// canonical bytes and the `.telos/allowed_signers` trust store.
+    pub fn signature_status(&self, id: &ObjectId) -> Result<SignatureStatus, StoreError> {
+        crate::signing::signature_status(&self.odb, &self.signatures, &self.allowed_signers()?, id)
+    }
+
+    fn resolved_conflicts_path(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("resolved_conflicts.json")
+    }
+
+    /// Load the set of constraint-supersession conflicts already settled by
+    /// `resolve_constraint_conflict`, empty if none have ever been resolved.
+    pub fn resolved_conflicts(&self) -> Result<ResolvedConflicts, StoreError> {
+        ResolvedConflicts::load(self.resolved_conflicts_path())
+    }
+
+    /// Settle a [`ConstraintConflict`] surfaced by a merge: the losing
+    /// branch's replacement is re-created as a `Deprecated` copy (objects
+    /// are immutable, so "resolving" means adding a new record rather than
+    /// mutating the old one) with a reason noting the merge conflict, and
+    /// the conflict is recorded as resolved so future merges don't
+    /// re-report it. Returns the id of the newly deprecated copy.
+    pub fn resolve_constraint_conflict(
+        &self,
+        conflict: &ConstraintConflict,
+        keep: crate::sync::Keep,
+    ) -> Result<ObjectId, StoreError> {
+        let losing_replacement = match keep {
+            crate::sync::Keep::Local => &conflict.remote_replacement,
+            crate::sync::Keep::Remote => &conflict.local_replacement,
+        };
+        let TelosObject::Constraint(mut losing) = self.odb.read(losing_replacement)? else {
+            return Err(StoreError::IndexError(format!(
+                "{} is not a constraint",
+                losing_replacement
+            )));
+        };
+        losing.status = ConstraintStatus::Deprecated;
+        losing.deprecation_reason = Some(format!(
+            "superseded by the competing branch's replacement during merge conflict resolution (kept {:?})",
+            keep
+        ));
+        let deprecated_id = self.create_constraint(losing)?;
+
+        let mut resolved = self.resolved_conflicts()?;
+        resolved.mark_resolved(conflict);
+        resolved.save(self.resolved_conflicts_path())?;
+
+        Ok(deprecated_id)
+    }
+
+    /// Record a status change (`supersede`/`deprecate`) keyed by `base_id`
+    /// — the id of the constraint copy being transitioned away from (what
+    /// `supersede`/`deprecate` were called with). That's the id two
+    /// concurrent calls agree on: if repository A and repository B both
+    /// deprecate (or supersede) the very same constraint copy before
+    /// syncing, they write to the same status ref, which is exactly the
+    /// conflict this layer exists to reconcile. `writer`
+    /// identifies the author making the change (e.g. their signing key's
+    /// fingerprint or configured author email) for the causal version
+    /// vector. See [`crate::status_ref`] for why this can't just be a field
+    /// on the new `Constraint` copy.
+    pub fn record_status_change(
+        &self,
+        base_id: &ObjectId,
+        current: ObjectId,
+        status: ConstraintStatus,
+        superseded_by: Option<ObjectId>,
+        deprecation_reason: Option<String>,
+        writer: &str,
+    ) -> Result<(), StoreError> {
+        let mut status_ref = match self.status_refs.load(base_id)? {
+            Some(mut existing) => {
+                existing.vector.bump(writer);
+                existing
+            }
+            None => StatusRef::new(current.clone(), status.clone(), writer),
+        };
+        status_ref.current = current;
+        status_ref.status = status;
+        status_ref.superseded_by = superseded_by;
+        status_ref.deprecation_reason = deprecation_reason;
+        self.status_refs.save(base_id, &status_ref)
+    }
+
+    /// Reconcile the local status ref for `base_id` with one fetched from a
+    /// remote, persisting whichever [`StatusRef`] the merge decides is
+    /// current (see [`StatusRef::merge`] for the causal-version-vector
+    /// tiebreak). Returns the merged ref so a caller can report any new
+    /// merge notes.
+    pub fn merge_status_ref(&self, base_id: &ObjectId, remote: &StatusRef) -> Result<StatusRef, StoreError> {
+        let merged = match self.status_refs.load(base_id)? {
+            Some(local) => local.merge(remote),
+            None => remote.clone(),
+        };
+        self.status_refs.save(base_id, &merged)?;
+        Ok(merged)
+    }
+
+    /// Resolve the repository-wide authority key used to sign capability
+    /// tokens (`telos auth issue`). Lazily loads (or, on first use,
+    /// generates and persists) the key at `.telos/keys/authority_ed25519`
+    /// — the same lazy-creation pattern as `signing_key`, but kept in its
+    /// own file since an authority key is a repo-wide policy key, distinct
+    /// from any individual contributor's per-object signing key.
+    pub fn authority_key(&self) -> Result<AuthorKey, StoreError> {
+        let key_path = self.keys_dir().join("authority_ed25519");
+        if key_path.exists() {
+            let seed: [u8; 32] = fs::read(&key_path)?
+                .try_into()
+                .map_err(|_| StoreError::SigningError(format!("{} is corrupt", key_path.display())))?;
+            return Ok(AuthorKey::from_seed(&seed));
+        }
+
+        fs::create_dir_all(self.keys_dir())?;
+        let key = AuthorKey::generate();
+        fs::write(&key_path, key.seed())?;
+        Ok(key)
+    }
+
+    fn revoked_tokens_path(&self) -> PathBuf {
+        self.root.join(TELOS_DIR).join("revoked_tokens.json")
+    }
+
+    /// Load the set of revoked capability-token ids from
+    /// `.telos/revoked_tokens.json`, empty if none have ever been revoked.
+    pub fn revoked_tokens(&self) -> Result<RevokedTokens, StoreError> {
+        RevokedTokens::load(self.revoked_tokens_path())
+    }
+
+    /// Persist `revoked` to `.telos/revoked_tokens.json`.
+    pub fn save_revoked_tokens(&self, revoked: &RevokedTokens) -> Result<(), StoreError> {
+        revoked.save(self.revoked_tokens_path())
+    }
+
+    /// Verify `signed` against this repository's authority key and
+    /// `revoked_tokens()`, and check it grants `verb` for `impacts`.
+    /// Returns the token's id on success, for recording into the created
+    /// object's `metadata`.
+    pub fn authorize(&self, signed: &SignedToken, verb: Verb, impacts: &[String]) -> Result<String, StoreError> {
+        let authority_key_hex = self.authority_key()?.public_key_hex();
+        let revoked = self.revoked_tokens()?;
+        crate::auth::authorize(signed, &authority_key_hex, &revoked, verb, impacts, Utc::now())
+    }
+
     /// Search upward from `start` for a `.telos/` directory and open that repo.
     pub fn discover(start: impl AsRef<Path>) -> Result<Self, StoreError> {
         let mut current = start.as_ref().to_path_buf();
@@ -115,12 +563,51 @@ impl Repository {
     }
 
     /// Create an intent, store it, and advance the current stream tip.
+    ///
+    /// For a single-parent (or root) intent, the expected CAS tip is
+    /// unambiguous — it's the one parent, or `None`. A merge intent
+    /// (`parents.len() == 2`) has no such unambiguous tip, so it goes
+    /// through [`Self::create_intent_advancing`] instead, which requires
+    /// the caller to say which side it's advancing.
+    #[tracing::instrument(skip(self, intent), fields(intent.parents = intent.parents.len()))]
     pub fn create_intent(&self, intent: Intent) -> Result<ObjectId, StoreError> {
+        let expected = match intent.parents.as_slice() {
+            [single] => Some(single.clone()),
+            [] => None,
+            _ => {
+                return Err(StoreError::InvalidReference(
+                    "merge intents must be created via create_intent_advancing, \
+                     which requires an explicit expected tip"
+                        .to_string(),
+                ));
+            }
+        };
+        self.create_intent_advancing(intent, expected)
+    }
+
+    /// Create an intent (including a multi-parent merge marker), storing it
+    /// and advancing the current stream tip with
+    /// [`crate::refs::RefStore::update_current_tip_cas`] against
+    /// `expected_tip`.
+    ///
+    /// Merge callers already hold the tip they're advancing from (the local
+    /// head a merge marker's parents include) — passing it here closes the
+    /// same lost-update race [`crate::refs::RefStore::update_current_tip_cas`]
+    /// closes for linear writes: a concurrent write that lands between the
+    /// merge logic deciding to merge and this call now fails with
+    /// [`StoreError::LockConflict`] instead of silently clobbering it.
+    #[tracing::instrument(skip(self, intent), fields(intent.parents = intent.parents.len()))]
+    pub fn create_intent_advancing(
+        &self,
+        intent: Intent,
+        expected_tip: Option<ObjectId>,
+    ) -> Result<ObjectId, StoreError> {
         // Validate parent references exist and are Intents
         for parent_id in &intent.parents {
             match self.odb.read(parent_id)? {
                 TelosObject::Intent(_) => {}
                 other => {
+                    counter!("telos.repository.invalid_reference", "op" => "create_intent").increment(1);
                     return Err(StoreError::InvalidReference(format!(
                         "parent {} is a {}, expected intent",
                         parent_id, other.type_tag()
@@ -128,19 +615,27 @@ impl Repository {
                 }
             }
         }
+        let behavior_diff_id = intent.behavior_diff.clone();
         let obj = TelosObject::Intent(intent);
         let id = self.odb.write(&obj)?;
         self.indexes.update_for_object(&id, &obj)?;
-        self.refs.update_current_tip(id.clone())?;
+        self.refs.update_current_tip_cas(expected_tip, id.clone())?;
+
+        if let Some(diff_id) = behavior_diff_id {
+            self.queue.enqueue(&diff_id)?;
+        }
+
         Ok(id)
     }
 
     /// Create a decision record and store it.
+    #[tracing::instrument(skip(self, record), fields(decision.intent_id = %record.intent_id))]
     pub fn create_decision(&self, record: DecisionRecord) -> Result<ObjectId, StoreError> {
         // Validate intent_id exists and is an Intent
         match self.odb.read(&record.intent_id)? {
             TelosObject::Intent(_) => {}
             other => {
+                counter!("telos.repository.invalid_reference", "op" => "create_decision").increment(1);
                 return Err(StoreError::InvalidReference(format!(
                     "intent_id {} is a {}, expected intent",
                     record.intent_id, other.type_tag()
@@ -154,6 +649,7 @@ impl Repository {
     }
 
     /// Create a constraint and store it.
+    #[tracing::instrument(skip(self, constraint), fields(constraint.severity = ?constraint.severity))]
     pub fn create_constraint(&self, constraint: Constraint) -> Result<ObjectId, StoreError> {
         let obj = TelosObject::Constraint(constraint);
         let id = self.odb.write(&obj)?;
@@ -162,6 +658,7 @@ impl Repository {
     }
 
     /// Create a code binding and store it.
+    #[tracing::instrument(skip(self, binding), fields(binding.path = %binding.path))]
     pub fn create_code_binding(&self, binding: CodeBinding) -> Result<ObjectId, StoreError> {
         let obj = TelosObject::CodeBinding(binding);
         let id = self.odb.write(&obj)?;
@@ -170,6 +667,15 @@ impl Repository {
     }
 
     /// Create an agent operation and store it.
+    ///
+    /// `op.parent_op`, when set, is recorded as a span field so agent-operation
+    /// chains can be correlated in a trace backend even though the parent op
+    /// was recorded in a (possibly) separate trace.
+    #[tracing::instrument(skip(self, op), fields(
+        operation.agent_id = %op.agent_id,
+        operation.session_id = %op.session_id,
+        operation.parent_op = op.parent_op.as_ref().map(|p| p.hex().to_string()),
+    ))]
     pub fn create_agent_operation(&self, op: AgentOperation) -> Result<ObjectId, StoreError> {
         let obj = TelosObject::AgentOperation(op);
         let id = self.odb.write(&obj)?;
@@ -178,6 +684,7 @@ impl Repository {
     }
 
     /// Create a change set and store it.
+    #[tracing::instrument(skip(self, cs), fields(change_set.git_commit = %cs.git_commit))]
     pub fn create_change_set(&self, cs: ChangeSet) -> Result<ObjectId, StoreError> {
         let obj = TelosObject::ChangeSet(cs);
         let id = self.odb.write(&obj)?;
@@ -186,6 +693,7 @@ impl Repository {
     }
 
     /// Read any object by ID (exact or prefix).
+    #[tracing::instrument(skip(self))]
     pub fn read_object(&self, id_or_prefix: &str) -> Result<(ObjectId, TelosObject), StoreError> {
         // Try exact parse first
         if let Ok(id) = ObjectId::parse(id_or_prefix) {
@@ -202,6 +710,288 @@ impl Repository {
     pub fn walk_intents(&self, start: &ObjectId) -> IntentWalker<'_> {
         IntentWalker::new(&self.odb, start.clone())
     }
+
+    /// Walk the intent DAG starting from `start` in reverse-topological order:
+    /// every intent appears before all of its ancestors, and intents not
+    /// ordered by the ancestor relation are broken by `timestamp` descending
+    /// so a branch's intents stay grouped together instead of interleaving
+    /// with a concurrent one.
+    ///
+    /// Implemented as a DFS post-order traversal (push node, recurse into
+    /// parents sorted by timestamp, emit node after all parents are emitted)
+    /// over the full reachable set, followed by reversing the post-order
+    /// list. Returns [`StoreError::CycleDetected`] if `parents` links form a
+    /// cycle.
+    pub fn walk_intents_topo(&self, start: &ObjectId) -> Result<Vec<(ObjectId, Intent)>, StoreError> {
+        // Pass 1: collect the full reachable set via DFS over `parents`.
+        let mut intents: std::collections::HashMap<String, Intent> = std::collections::HashMap::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![start.clone()];
+        while let Some(id) = stack.pop() {
+            let hex = id.hex().to_string();
+            if !seen.insert(hex.clone()) {
+                continue;
+            }
+            if let TelosObject::Intent(intent) = self.odb.read(&id)? {
+                for parent in &intent.parents {
+                    stack.push(parent.clone());
+                }
+                intents.insert(hex, intent);
+            }
+        }
+
+        // Pass 2: DFS post-order, visiting parents oldest first so that after
+        // the final reversal the newest branch comes first (ties broken by
+        // timestamp descending), with in-progress marking to detect cycles.
+        enum Mark {
+            InProgress,
+            Done,
+        }
+        fn visit(
+            id: &ObjectId,
+            intents: &std::collections::HashMap<String, Intent>,
+            marks: &mut std::collections::HashMap<String, Mark>,
+            post_order: &mut Vec<ObjectId>,
+        ) -> Result<(), StoreError> {
+            let hex = id.hex().to_string();
+            match marks.get(&hex) {
+                Some(Mark::Done) => return Ok(()),
+                Some(Mark::InProgress) => {
+                    return Err(StoreError::CycleDetected(format!(
+                        "cycle detected at intent {}",
+                        hex
+                    )));
+                }
+                None => {}
+            }
+            marks.insert(hex.clone(), Mark::InProgress);
+
+            if let Some(intent) = intents.get(&hex) {
+                let mut parents = intent.parents.clone();
+                parents.sort_by_key(|p| intents.get(p.hex()).map(|i| i.timestamp));
+                for parent in &parents {
+                    visit(parent, intents, marks, post_order)?;
+                }
+            }
+
+            marks.insert(hex, Mark::Done);
+            post_order.push(id.clone());
+            Ok(())
+        }
+
+        let mut marks = std::collections::HashMap::new();
+        let mut post_order = Vec::new();
+        visit(start, &intents, &mut marks, &mut post_order)?;
+        post_order.reverse();
+
+        Ok(post_order
+            .into_iter()
+            .filter_map(|id| intents.get(id.hex()).cloned().map(|intent| (id.clone(), intent)))
+            .collect())
+    }
+
+    /// Compute the transitive closure of objects reachable from `remote_heads`
+    /// by following `parents` and the object model's other cross-links
+    /// (`DecisionRecord::intent_id`, `CodeBinding::bound_object`, etc. — see
+    /// [`TelosObject::links`]), stopping a branch's descent as soon as it
+    /// reaches an object already recorded in `have`. This lets repo-to-repo
+    /// sync negotiate a minimal transfer set without either side needing a
+    /// full inventory of the other.
+    pub fn missing_objects(
+        &self,
+        remote_heads: &[ObjectId],
+        have: &crate::sync::HaveSet,
+    ) -> Result<Vec<ObjectId>, StoreError> {
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        let mut frontier: VecDeque<ObjectId> = remote_heads.iter().cloned().collect();
+
+        while let Some(id) = frontier.pop_front() {
+            if !seen.insert(id.hex().to_string()) {
+                continue;
+            }
+            if have.contains(&id) {
+                continue;
+            }
+            let obj = self.odb.read(&id)?;
+            frontier.extend(obj.links());
+            missing.push(id);
+        }
+
+        Ok(missing)
+    }
+
+    /// Return the local object database's "have" bloom filter, loading the
+    /// persisted copy under `.telos/indexes/` if one exists, or building
+    /// (without persisting) a fresh one from the current object store
+    /// otherwise. Used to shrink have/want negotiation round trips with a
+    /// sync peer: see [`crate::bloom::HaveFilter`].
+    pub fn have_filter(&self) -> Result<crate::bloom::HaveFilter, StoreError> {
+        if let Some(filter) = self.indexes.load_have_filter() {
+            return Ok(filter);
+        }
+        let ids: Vec<ObjectId> = self.odb.iter_all()?.into_iter().map(|(id, _)| id).collect();
+        Ok(crate::bloom::HaveFilter::build(&ids))
+    }
+
+    /// Like [`Repository::missing_objects`], but instead of an exact `have`
+    /// set, short-circuits a branch's descent as soon as the peer's
+    /// [`crate::bloom::HaveFilter`] reports the current object as present.
+    /// A false positive stops the walk at that object: it, and anything
+    /// reachable only through it, is left out of the result.
+    pub fn missing_objects_via_filter(
+        &self,
+        remote_heads: &[ObjectId],
+        filter: &crate::bloom::HaveFilter,
+    ) -> Result<Vec<ObjectId>, StoreError> {
+        let mut missing = Vec::new();
+        let mut seen = HashSet::new();
+        let mut frontier: VecDeque<ObjectId> = remote_heads.iter().cloned().collect();
+
+        while let Some(id) = frontier.pop_front() {
+            if !seen.insert(id.hex().to_string()) {
+                continue;
+            }
+            if filter.contains(&id) {
+                continue;
+            }
+            let obj = self.odb.read(&id)?;
+            frontier.extend(obj.links());
+            missing.push(id);
+        }
+
+        Ok(missing)
+    }
+
+    /// Export `ids` as an ordered pack: every object's links appear before
+    /// the object itself, so [`Repository::import_pack`] can write them in
+    /// order on the receiving side without hitting dangling references.
+    pub fn export_pack(&self, ids: &[ObjectId]) -> Result<Vec<TelosObject>, StoreError> {
+        let id_set: HashSet<String> = ids.iter().map(|id| id.hex().to_string()).collect();
+        let mut order = Vec::new();
+        let mut done = HashSet::new();
+
+        fn visit(
+            id: &ObjectId,
+            odb: &ObjectDatabase,
+            id_set: &HashSet<String>,
+            done: &mut HashSet<String>,
+            order: &mut Vec<TelosObject>,
+        ) -> Result<(), StoreError> {
+            if !done.insert(id.hex().to_string()) {
+                return Ok(());
+            }
+            let obj = odb.read(id)?;
+            for link in obj.links() {
+                if id_set.contains(link.hex()) {
+                    visit(&link, odb, id_set, done, order)?;
+                }
+            }
+            order.push(obj);
+            Ok(())
+        }
+
+        for id in ids {
+            visit(id, &self.odb, &id_set, &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Import a pack produced by [`Repository::export_pack`], writing each
+    /// object in order and re-validating its references (the same checks
+    /// [`Repository::create_intent`] and [`Repository::create_decision`]
+    /// perform) before advancing `stream_name`'s tip to `new_tip`.
+    pub fn import_pack(
+        &self,
+        pack: &[TelosObject],
+        stream_name: &str,
+        new_tip: &ObjectId,
+    ) -> Result<usize, StoreError> {
+        let mut imported = 0;
+        for obj in pack {
+            self.validate_pack_object(obj)?;
+            let id = self.odb.write(obj)?;
+            self.indexes.update_for_object(&id, obj)?;
+            imported += 1;
+        }
+
+        let mut stream = self.refs.read_stream(stream_name)?;
+        stream.tip = Some(new_tip.clone());
+        self.refs.write_stream(&stream)?;
+
+        Ok(imported)
+    }
+
+    /// Validate that an incoming pack object's references resolve, mirroring
+    /// the checks performed when creating the same object type directly.
+    fn validate_pack_object(&self, obj: &TelosObject) -> Result<(), StoreError> {
+        match obj {
+            TelosObject::Intent(intent) => {
+                for parent_id in &intent.parents {
+                    match self.odb.read(parent_id)? {
+                        TelosObject::Intent(_) => {}
+                        other => {
+                            counter!("telos.repository.invalid_reference", "op" => "import_pack").increment(1);
+                            return Err(StoreError::InvalidReference(format!(
+                                "parent {} is a {}, expected intent",
+                                parent_id, other.type_tag()
+                            )));
+                        }
+                    }
+                }
+            }
+            TelosObject::DecisionRecord(record) => match self.odb.read(&record.intent_id)? {
+                TelosObject::Intent(_) => {}
+                other => {
+                    counter!("telos.repository.invalid_reference", "op" => "import_pack").increment(1);
+                    return Err(StoreError::InvalidReference(format!(
+                        "intent_id {} is a {}, expected intent",
+                        record.intent_id, other.type_tag()
+                    )));
+                }
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Walk the intent DAG like [`Repository::walk_intents`], but tolerate a
+    /// shallow clone or partially-synced object store: when a parent can't be
+    /// read, its id is recorded into `missing` instead of aborting the walk,
+    /// and traversal continues with whatever else is reachable. Returns every
+    /// readable `(ObjectId, Intent)` pair reachable from `start`.
+    pub fn walk_intents_partial(
+        &self,
+        start: &ObjectId,
+        missing: &mut HashSet<ObjectId>,
+    ) -> Vec<(ObjectId, Intent)> {
+        let mut results = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(start.hex().to_string());
+        queue.push_back(start.clone());
+
+        while let Some(id) = queue.pop_front() {
+            let obj = match self.odb.read(&id) {
+                Ok(obj) => obj,
+                Err(_) => {
+                    missing.insert(id);
+                    continue;
+                }
+            };
+
+            if let TelosObject::Intent(intent) = obj {
+                for parent_id in &intent.parents {
+                    if visited.insert(parent_id.hex().to_string()) {
+                        queue.push_back(parent_id.clone());
+                    }
+                }
+                results.push((id, intent));
+            }
+        }
+
+        results
+    }
 }
 
 /// BFS walker over the intent DAG (follows `parents` links).
@@ -209,6 +999,8 @@ pub struct IntentWalker<'a> {
     odb: &'a ObjectDatabase,
     queue: VecDeque<ObjectId>,
     visited: HashSet<String>,
+    started_at: std::time::Instant,
+    depth: u64,
 }
 
 impl<'a> IntentWalker<'a> {
@@ -221,6 +1013,8 @@ impl<'a> IntentWalker<'a> {
             odb,
             queue,
             visited,
+            started_at: std::time::Instant::now(),
+            depth: 0,
         }
     }
 }
@@ -242,10 +1036,18 @@ impl<'a> Iterator for IntentWalker<'a> {
                         self.queue.push_back(parent_id.clone());
                     }
                 }
+                self.depth += 1;
                 return Some(Ok((id, intent)));
             }
             // Skip non-Intent objects in the walk
         }
+        // Queue exhausted: record how deep and how long this walk ran.
+        if self.depth > 0 {
+            histogram!("telos.intent_walker.depth").record(self.depth as f64);
+            histogram!("telos.intent_walker.duration_ms")
+                .record(self.started_at.elapsed().as_secs_f64() * 1000.0);
+            self.depth = 0;
+        }
         None
     }
 }
@@ -281,6 +1083,23 @@ mod tests {
         assert_eq!(repo.refs.read_head().unwrap(), "main");
     }
 
+    #[test]
+    fn init_encrypted_round_trips_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init_encrypted(dir.path(), "correct horse battery staple").unwrap();
+        assert!(repo.odb.is_encrypted());
+
+        let intent = make_intent("Encrypted intent", vec![]);
+        let id = repo.create_intent(intent).unwrap();
+        drop(repo);
+
+        let reopened = Repository::open_encrypted(dir.path(), "correct horse battery staple").unwrap();
+        let (_, obj) = reopened.read_object(id.hex()).unwrap();
+        assert!(matches!(obj, TelosObject::Intent(_)));
+
+        assert!(Repository::open(dir.path()).is_err());
+    }
+
     #[test]
     fn init_twice_fails() {
         let dir = tempfile::tempdir().unwrap();
@@ -288,6 +1107,95 @@ mod tests {
         assert!(Repository::init(dir.path()).is_err());
     }
 
+    #[test]
+    fn create_intent_rejects_merge_intents_without_an_explicit_expected_tip() {
+        // A two-parent (merge) intent has no single unambiguous expected
+        // tip, so create_intent refuses it rather than silently falling
+        // back to an unprotected read-modify-write tip update.
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let a = repo.create_intent(make_intent("A", vec![])).unwrap();
+        let b = repo.odb.write(&TelosObject::Intent(make_intent("B", vec![]))).unwrap();
+
+        let merge = make_intent("Merge A and B", vec![a, b]);
+        assert!(matches!(
+            repo.create_intent(merge),
+            Err(StoreError::InvalidReference(_))
+        ));
+    }
+
+    #[test]
+    fn create_intent_advancing_cas_protects_a_merge_marker_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let local_tip = repo.create_intent(make_intent("Local work", vec![root.clone()])).unwrap();
+        let remote_tip = repo
+            .odb
+            .write(&TelosObject::Intent(make_intent("Remote work", vec![root.clone()])))
+            .unwrap();
+
+        // Another writer slips a commit in after `local_tip` was read as
+        // the expected merge base.
+        let sneaky = repo.create_intent(make_intent("Sneaky local commit", vec![local_tip.clone()])).unwrap();
+
+        let merge = make_intent("Merge", vec![local_tip.clone(), remote_tip]);
+        let result = repo.create_intent_advancing(merge, Some(local_tip));
+        assert!(matches!(result, Err(StoreError::LockConflict(_))));
+        assert_eq!(repo.refs.current_stream().unwrap().tip, Some(sneaky));
+    }
+
+    #[test]
+    fn init_with_hash_algo_round_trips_through_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init_with_hash_algo(dir.path(), HashAlgo::Blake3).unwrap();
+        let id = repo.create_intent(make_intent("Blake3 intent", vec![])).unwrap();
+        assert_eq!(id.algo(), HashAlgo::Blake3);
+        // The read path must verify against the id's own algorithm, not the
+        // repo default (SHA-256) — otherwise every read on a non-default
+        // repo fails with a bogus IntegrityError.
+        assert!(repo.odb.read(&id).is_ok());
+        drop(repo);
+
+        let reopened = Repository::open(dir.path()).unwrap();
+        let new_id = reopened
+            .create_intent(make_intent("Another blake3 intent", vec![]))
+            .unwrap();
+        assert_eq!(new_id.algo(), HashAlgo::Blake3);
+        assert!(reopened.odb.read(&new_id).is_ok());
+        assert!(reopened.odb.read(&id).is_ok());
+    }
+
+    #[test]
+    fn reads_back_objects_from_a_sha512_configured_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init_with_hash_algo(dir.path(), HashAlgo::Sha512).unwrap();
+        let id = repo.create_intent(make_intent("Sha512 intent", vec![])).unwrap();
+        assert_eq!(id.algo(), HashAlgo::Sha512);
+
+        let (read_id, obj) = repo.read_object(id.hex()).unwrap();
+        assert_eq!(read_id, id);
+        assert_eq!(obj.type_tag(), "intent");
+    }
+
+    #[test]
+    fn init_with_content_format_round_trips_through_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init_with_content_format(dir.path(), ContentFormat::Jcs).unwrap();
+        assert_eq!(repo.odb.content_format(), ContentFormat::Jcs);
+        let id = repo.create_intent(make_intent("JCS intent", vec![])).unwrap();
+        assert!(repo.odb.read(&id).is_ok());
+        drop(repo);
+
+        let reopened = Repository::open(dir.path()).unwrap();
+        assert_eq!(reopened.odb.content_format(), ContentFormat::Jcs);
+        let new_id = reopened
+            .create_intent(make_intent("Another JCS intent", vec![]))
+            .unwrap();
+        assert!(reopened.odb.read(&new_id).is_ok());
+        assert!(reopened.odb.read(&id).is_ok());
+    }
+
     #[test]
     fn discover_from_subdirectory() {
         let dir = tempfile::tempdir().unwrap();
@@ -357,6 +1265,7 @@ mod tests {
             rationale: None,
             alternatives: vec![],
             tags: vec![],
+            metadata: HashMap::new(),
         };
         let result = repo.create_decision(record);
         assert!(result.is_err(), "should reject decision with nonexistent intent");
@@ -386,4 +1295,125 @@ mod tests {
         assert_eq!(walked[1].0, id2);
         assert_eq!(walked[2].0, id1);
     }
+
+    #[test]
+    fn walk_intents_topo_orders_ancestors_last() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let i1 = make_intent("Root", vec![]);
+        let id1 = repo.create_intent(i1).unwrap();
+
+        let i2 = make_intent("Child", vec![id1.clone()]);
+        let id2 = repo.create_intent(i2).unwrap();
+
+        let i3 = make_intent("Grandchild", vec![id2.clone()]);
+        let id3 = repo.create_intent(i3).unwrap();
+
+        let topo = repo.walk_intents_topo(&id3).unwrap();
+        assert_eq!(topo.len(), 3);
+        assert_eq!(topo[0].0, id3);
+        assert_eq!(topo[1].0, id2);
+        assert_eq!(topo[2].0, id1);
+    }
+
+    #[test]
+    fn export_and_import_pack_round_trips_between_repos() {
+        let src_dir = tempfile::tempdir().unwrap();
+        let src = Repository::init(src_dir.path()).unwrap();
+
+        let i1 = make_intent("Root", vec![]);
+        let id1 = src.create_intent(i1).unwrap();
+        let i2 = make_intent("Child", vec![id1.clone()]);
+        let id2 = src.create_intent(i2).unwrap();
+
+        let have = crate::sync::HaveSet::new();
+        let missing = src.missing_objects(&[id2.clone()], &have).unwrap();
+        assert_eq!(missing.len(), 2);
+
+        let pack = src.export_pack(&missing).unwrap();
+        assert_eq!(pack.len(), 2);
+        // Parent must precede child.
+        assert_eq!(pack[0].content_id().unwrap(), id1);
+        assert_eq!(pack[1].content_id().unwrap(), id2);
+
+        let dst_dir = tempfile::tempdir().unwrap();
+        let dst = Repository::init(dst_dir.path()).unwrap();
+        let imported = dst.import_pack(&pack, "main", &id2).unwrap();
+        assert_eq!(imported, 2);
+
+        let (_, obj) = dst.read_object(id2.hex()).unwrap();
+        assert!(matches!(obj, TelosObject::Intent(_)));
+        let stream = dst.refs.read_stream("main").unwrap();
+        assert_eq!(stream.tip.unwrap(), id2);
+    }
+
+    #[test]
+    fn missing_objects_stops_descent_at_have_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let i1 = make_intent("Root", vec![]);
+        let id1 = repo.create_intent(i1).unwrap();
+        let i2 = make_intent("Child", vec![id1.clone()]);
+        let id2 = repo.create_intent(i2).unwrap();
+
+        let mut have = crate::sync::HaveSet::new();
+        have.insert(id1.clone());
+        let missing = repo.missing_objects(&[id2.clone()], &have).unwrap();
+
+        assert_eq!(missing, vec![id2]);
+    }
+
+    #[test]
+    fn walk_intents_partial_records_unreadable_parents() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let i1 = make_intent("Root", vec![]);
+        let id1 = repo.create_intent(i1).unwrap();
+
+        // Simulate a shallow clone: child references a parent that was never
+        // fetched (and therefore fails the create-time validation, so we
+        // write it directly into the odb rather than through create_intent).
+        let fake_parent = ObjectId::hash(b"never-fetched");
+        let i2 = make_intent("Child", vec![fake_parent.clone()]);
+        let id2 = repo.odb.write(&TelosObject::Intent(i2)).unwrap();
+
+        let mut missing = HashSet::new();
+        let results = repo.walk_intents_partial(&id2, &mut missing);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, id2);
+        assert_eq!(missing, HashSet::from([fake_parent]));
+        assert!(!missing.contains(&id1));
+    }
+
+    #[test]
+    fn have_filter_builds_from_odb_when_not_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let id = repo.create_intent(make_intent("Root", vec![])).unwrap();
+
+        let filter = repo.have_filter().unwrap();
+        assert!(filter.contains(&id));
+    }
+
+    #[test]
+    fn missing_objects_via_filter_stops_descent_at_present_boundary() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let id1 = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let id2 = repo
+            .create_intent(make_intent("Child", vec![id1.clone()]))
+            .unwrap();
+
+        let filter = crate::bloom::HaveFilter::build(&[id1]);
+        let missing = repo
+            .missing_objects_via_filter(&[id2.clone()], &filter)
+            .unwrap();
+
+        assert_eq!(missing, vec![id2]);
+    }
 }
@@ -0,0 +1,472 @@
+//! `telos serve`: a persistent HTTP server exposing the read paths other
+//! commands already have (`query constraints`, `query agent-ops`,
+//! `context --impact`, `show <hash>`) plus a long-poll `/changes` route, so
+//! an editor plugin or a fleet of review agents can hit a live endpoint
+//! instead of forking the binary per call. Backed directly by
+//! [`Repository`]'s sled-backed [`crate::index_store::IndexStore`], the same
+//! as every other query path.
+//!
+//! Hand-rolled HTTP/1.1 over a plain [`TcpListener`] (the same tradeoff
+//! `ssh_agent.rs` and `sigv4.rs` make for their protocols): one connection
+//! per request, `Content-Length`-framed bodies, no keep-alive, no chunked
+//! transfer-encoding — everything this server needs to speak and nothing
+//! a full HTTP library would carry that it doesn't.
+//!
+//! Every other route here only reads; the one route that mutates state
+//! (`POST /agent-log`) is gated behind a bearer token (`Authorization:
+//! Bearer <token>`) resolved by [`telos_core::config::TelosConfig::resolve_serve_token`]
+//! — if no token is configured, write routes refuse every request rather
+//! than accepting unauthenticated mutations.
+
+use crate::error::StoreError;
+use crate::query;
+use crate::repository::Repository;
+use crate::watch::{ScopeFilter, WatchEvent, Watcher};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+use telos_core::hash::ObjectId;
+use telos_core::object::agent_operation::{AgentOperation, OperationResult, OperationType};
+use telos_core::object::TelosObject;
+
+/// How often the background poller checks the repository for newly
+/// committed objects to append to the change log. Mirrors
+/// `commands::watch::POLL_INTERVAL`.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// How long a `GET /changes` request blocks waiting for new events before
+/// returning an empty batch at the same `since` cursor, so a client's
+/// long-poll loop never hangs forever on an idle repository.
+const LONG_POLL_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// An in-memory, append-only log of every [`WatchEvent`] seen since this
+/// server started, indexed by position — `GET /changes?since=<n>` is just
+/// "give me everything appended after index `n`". Not persisted: a
+/// restarted server starts its log (and its `since` numbering) over from
+/// zero, the same way `telos watch` always starts from "now".
+#[derive(Default)]
+struct ChangeLog {
+    events: Mutex<Vec<WatchEvent>>,
+    condvar: Condvar,
+}
+
+impl ChangeLog {
+    fn push(&self, new_events: Vec<WatchEvent>) {
+        if new_events.is_empty() {
+            return;
+        }
+        let mut events = self.events.lock().unwrap();
+        events.extend(new_events);
+        self.condvar.notify_all();
+    }
+
+    /// Return every event after `since`, waiting up to `timeout` for at
+    /// least one to show up if the log hasn't grown past `since` yet.
+    fn wait_since(&self, since: usize, timeout: Duration) -> (Vec<WatchEvent>, usize) {
+        let events = self.events.lock().unwrap();
+        let events = self
+            .condvar
+            .wait_timeout_while(events, timeout, |events| events.len() <= since)
+            .unwrap()
+            .0;
+        (events.get(since..).unwrap_or_default().to_vec(), events.len())
+    }
+}
+
+/// Run the background poller that feeds `log` from `watcher`, forever.
+fn run_poller(repo: Arc<Repository>, mut watcher: Watcher, log: Arc<ChangeLog>) {
+    loop {
+        match watcher.poll(&repo, &ScopeFilter::default()) {
+            Ok(events) => log.push(events),
+            Err(e) => eprintln!("telos serve: change poll failed: {}", e),
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (url_decode(k), url_decode(v)))
+        .collect()
+}
+
+fn url_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => {
+                out.push(b' ');
+                i += 1;
+            }
+            b'%' if i + 2 < bytes.len() => {
+                // Slice `bytes`, not `s`: a `&str` slice by byte offset
+                // panics if the offset lands inside a multi-byte UTF-8
+                // codepoint, which a `%` followed by non-ASCII input (e.g.
+                // `%€`) does on every GET route before any auth check runs.
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|b| std::str::from_utf8(b).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                if let Some(byte) = hex {
+                    out.push(byte);
+                    i += 3;
+                } else {
+                    out.push(bytes[i]);
+                    i += 1;
+                }
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.trim_end().splitn(3, ' ');
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (target, HashMap::new()),
+    };
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let content_length: usize = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let mut body = vec![0u8; content_length];
+    if content_length > 0 {
+        reader.read_exact(&mut body)?;
+    }
+
+    Ok(Request { method, path, query, headers, body })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &[u8]) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        body.len()
+    )?;
+    stream.write_all(body)
+}
+
+fn json_response(stream: &mut TcpStream, status: u16, value: &serde_json::Value) -> std::io::Result<()> {
+    write_response(stream, status, serde_json::to_string(value).unwrap_or_default().as_bytes())
+}
+
+fn error_response(stream: &mut TcpStream, status: u16, message: impl AsRef<str>) -> std::io::Result<()> {
+    json_response(stream, status, &serde_json::json!({ "error": message.as_ref() }))
+}
+
+fn handle_query_constraints(repo: &Repository, req: &Request) -> Result<serde_json::Value, StoreError> {
+    let status = req.query.get("status").map(String::as_str);
+    let results = if let Some(file) = req.query.get("file") {
+        query::query_constraints_by_file(&repo.odb, &repo.indexes, file)?
+    } else if let Some(symbol) = req.query.get("symbol") {
+        query::query_constraints_by_symbol(&repo.odb, &repo.indexes, symbol)?
+    } else {
+        query::query_constraints(
+            &repo.odb,
+            &repo.indexes,
+            req.query.get("impact").map(String::as_str),
+            status,
+            req.query.get("text").map(String::as_str),
+        )?
+    };
+    Ok(constraints_json(&results))
+}
+
+fn constraints_json(results: &[(ObjectId, telos_core::object::constraint::Constraint)]) -> serde_json::Value {
+    serde_json::Value::Array(
+        results
+            .iter()
+            .map(|(id, c)| serde_json::json!({ "id": id.hex(), "object": c }))
+            .collect(),
+    )
+}
+
+fn handle_query_agent_ops(repo: &Repository, req: &Request) -> Result<serde_json::Value, StoreError> {
+    let results = query::query_agent_operations(
+        &repo.odb,
+        req.query.get("agent").map(String::as_str),
+        req.query.get("session").map(String::as_str),
+    )?;
+    Ok(serde_json::Value::Array(
+        results
+            .iter()
+            .map(|(id, op)| serde_json::json!({ "id": id.hex(), "object": op }))
+            .collect(),
+    ))
+}
+
+/// Same assembly `commands::context::run`'s `--json` branch produces.
+fn handle_context(repo: &Repository, req: &Request) -> Result<serde_json::Value, StoreError> {
+    let impact = req.query.get("impact").cloned().unwrap_or_default();
+    let intents = query::query_intents(&repo.odb, &repo.indexes, Some(&impact), None, None)?;
+
+    let mut entries = Vec::new();
+    for (intent_id, intent) in &intents {
+        let decisions = query::query_decisions(&repo.odb, &repo.indexes, Some(intent_id), None, None)?;
+        let decision_json: Vec<_> = decisions
+            .iter()
+            .map(|(did, dr)| serde_json::json!({ "id": did.hex(), "object": dr }))
+            .collect();
+        entries.push(serde_json::json!({
+            "intent_id": intent_id.hex(),
+            "intent": intent,
+            "decisions": decision_json,
+        }));
+    }
+    let tasks = repo.agent_tasks.list_open(Some(&impact))?;
+    Ok(serde_json::json!({ "impact": impact, "intents": entries, "outstanding_tasks": tasks }))
+}
+
+fn handle_show(repo: &Repository, id: &str) -> Result<serde_json::Value, StoreError> {
+    let (oid, obj) = repo.read_object(id)?;
+    let signature_status = repo.signature_status(&oid)?;
+    Ok(serde_json::json!({
+        "id": oid.hex(),
+        "object": obj,
+        "signature": signature_status.to_string(),
+    }))
+}
+
+fn handle_changes(log: &ChangeLog, req: &Request) -> serde_json::Value {
+    let since: usize = req.query.get("since").and_then(|v| v.parse().ok()).unwrap_or(0);
+    let (events, next_since) = log.wait_since(since, LONG_POLL_TIMEOUT);
+    serde_json::json!({ "since": next_since, "events": events })
+}
+
+/// Body accepted by `POST /agent-log`, mirroring `commands::agent_log::run`'s
+/// arguments minus signing (an HTTP caller has no local key file to sign
+/// with; sign the CLI way if a signed record is required).
+#[derive(serde::Deserialize)]
+struct AgentLogBody {
+    agent: String,
+    session: String,
+    operation: String,
+    summary: String,
+    #[serde(default)]
+    context_refs: Vec<String>,
+    #[serde(default)]
+    files_touched: Vec<String>,
+}
+
+fn handle_agent_log(repo: &Repository, req: &Request) -> Result<serde_json::Value, StoreError> {
+    let body: AgentLogBody = serde_json::from_slice(&req.body)?;
+
+    let op_type = match body.operation.to_lowercase().as_str() {
+        "review" => OperationType::Review,
+        "generate" => OperationType::Generate,
+        "decide" => OperationType::Decide,
+        "query" => OperationType::Query,
+        "violation" => OperationType::Violation,
+        other => OperationType::Custom(other.to_string()),
+    };
+    let refs: Vec<ObjectId> = body
+        .context_refs
+        .iter()
+        .map(|r| repo.read_object(r).map(|(oid, _)| oid))
+        .collect::<Result<_, _>>()?;
+
+    let agent_op = AgentOperation {
+        agent_id: body.agent,
+        session_id: body.session,
+        timestamp: Utc::now(),
+        operation: op_type,
+        result: OperationResult::Success,
+        summary: body.summary,
+        context_refs: refs,
+        files_touched: body.files_touched,
+        parent_op: None,
+        metadata: HashMap::new(),
+    };
+    let id = repo.create_agent_operation(agent_op)?;
+    Ok(serde_json::json!({ "id": id.hex() }))
+}
+
+/// `true` if `req` carries `Authorization: Bearer <token>` matching `token`.
+fn bearer_authorized(req: &Request, token: &str) -> bool {
+    req.headers
+        .get("authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|presented| presented == token)
+}
+
+fn handle_connection(mut stream: TcpStream, repo: &Repository, log: &ChangeLog, token: &Option<String>) {
+    let req = match read_request(&mut stream) {
+        Ok(req) => req,
+        Err(_) => return,
+    };
+
+    let result = match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/query/constraints") => handle_query_constraints(repo, &req),
+        ("GET", "/query/agent-ops") => handle_query_agent_ops(repo, &req),
+        ("GET", "/context") => handle_context(repo, &req),
+        ("GET", path) if path.starts_with("/show/") => handle_show(repo, &path["/show/".len()..]),
+        ("GET", "/changes") => Ok(handle_changes(log, &req)),
+        ("POST", "/agent-log") => {
+            let Some(token) = token else {
+                let _ = error_response(&mut stream, 401, "telos serve has no [serve].token configured; write routes are disabled");
+                return;
+            };
+            if !bearer_authorized(&req, token) {
+                let _ = error_response(&mut stream, 401, "missing or invalid bearer token");
+                return;
+            }
+            handle_agent_log(repo, &req)
+        }
+        _ => {
+            let _ = error_response(&mut stream, 404, "no such route");
+            return;
+        }
+    };
+
+    match result {
+        Ok(value) => {
+            let _ = json_response(&mut stream, 200, &value);
+        }
+        Err(e) => {
+            let _ = error_response(&mut stream, 400, e.to_string());
+        }
+    }
+}
+
+/// Serve `repo`'s read paths (plus the bearer-token-gated `POST
+/// /agent-log`) over HTTP on `bind_addr`. Runs forever, one thread per
+/// connection, fed in the background by a [`Watcher`]-driven change log for
+/// `GET /changes`.
+pub fn run(repo: Repository, bind_addr: &str, token: Option<String>) -> Result<(), StoreError> {
+    let repo = Arc::new(repo);
+    let listener = TcpListener::bind(bind_addr)?;
+    println!("telos serve listening on {}", bind_addr);
+    if token.is_none() {
+        eprintln!("warning: no [serve].token/TELOS_SERVE_TOKEN configured; POST /agent-log is disabled");
+    }
+
+    let stream_name = repo.refs.read_head()?;
+    let watcher = Watcher::new(&repo, stream_name)?;
+    let log = Arc::new(ChangeLog::default());
+    {
+        let repo = Arc::clone(&repo);
+        let log = Arc::clone(&log);
+        thread::spawn(move || run_poller(repo, watcher, log));
+    }
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+        let repo = Arc::clone(&repo);
+        let log = Arc::clone(&log);
+        let token = token.clone();
+        thread::spawn(move || handle_connection(stream, &repo, &log, &token));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_decode_handles_percent_and_plus() {
+        assert_eq!(url_decode("auth%2Fsession"), "auth/session");
+        assert_eq!(url_decode("a+b"), "a b");
+    }
+
+    #[test]
+    fn url_decode_does_not_panic_on_percent_before_multibyte_utf8() {
+        // `%` followed by a non-ASCII character used to slice `&str` by
+        // byte offset and panic mid-codepoint; it should just pass the
+        // unrecognized escape through literally.
+        assert_eq!(url_decode("a=%€x"), "a=%€x");
+    }
+
+    #[test]
+    fn parse_query_splits_pairs() {
+        let parsed = parse_query("impact=auth&text=hello+world");
+        assert_eq!(parsed.get("impact").map(String::as_str), Some("auth"));
+        assert_eq!(parsed.get("text").map(String::as_str), Some("hello world"));
+    }
+
+    #[test]
+    fn change_log_wait_since_returns_immediately_once_events_exist() {
+        let log = ChangeLog::default();
+        log.push(vec![WatchEvent {
+            event: "intent".into(),
+            stream: "main".into(),
+            id: "deadbeef".into(),
+            object: serde_json::json!({}),
+        }]);
+        let (events, next_since) = log.wait_since(0, Duration::from_millis(50));
+        assert_eq!(events.len(), 1);
+        assert_eq!(next_since, 1);
+    }
+
+    #[test]
+    fn change_log_wait_since_times_out_when_nothing_new() {
+        let log = ChangeLog::default();
+        let (events, next_since) = log.wait_since(0, Duration::from_millis(20));
+        assert!(events.is_empty());
+        assert_eq!(next_since, 0);
+    }
+
+    #[test]
+    fn bearer_authorized_requires_exact_match() {
+        let req = Request {
+            method: "POST".into(),
+            path: "/agent-log".into(),
+            query: HashMap::new(),
+            headers: HashMap::from([("authorization".to_string(), "Bearer secret".to_string())]),
+            body: Vec::new(),
+        };
+        assert!(bearer_authorized(&req, "secret"));
+        assert!(!bearer_authorized(&req, "other"));
+    }
+}
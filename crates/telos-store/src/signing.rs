@@ -0,0 +1,769 @@
+//! Detached Ed25519 signatures over canonical object bytes.
+//!
+//! `Constraint` and `ChangeSet` are content-addressed by
+//! `TelosObject::canonical_bytes` (sorted JSON keys, RFC 3339 timestamps —
+//! see [`telos_core::serialize::canonical_serialize`]), so a signature
+//! can't be embedded as a field on the object itself without changing the
+//! very bytes it's meant to sign. Instead a signature is a detached
+//! sidecar, [`ObjectSignature`], keyed by the signed object's `ObjectId`
+//! and kept in its own [`SignatureStore`] — never touched by
+//! `IndexStore::rebuild_all`, since unlike an index a signature can't be
+//! regenerated from object content alone (it requires the author's
+//! private key).
+//!
+//! [`verify`] recomputes an object's canonical bytes and checks a single
+//! signature against them. [`verify_chain`] walks a `ChangeSet`'s
+//! `parents`, verifying every link, so a tampered or unsigned ancestor
+//! invalidates every descendant that (transitively) points to it.
+
+use crate::error::StoreError;
+use crate::odb::ObjectDatabase;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use telos_core::hash::ObjectId;
+use telos_core::object::constraint::ConstraintSeverity;
+use telos_core::object::TelosObject;
+
+/// Signature algorithm identifier stored in [`ObjectSignature::algorithm`].
+pub const ALGORITHM_ED25519: &str = "ed25519";
+
+/// A detached signature over an object's canonical bytes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ObjectSignature {
+    pub object_id: String,
+    /// Signature algorithm, e.g. `"ed25519"`.
+    pub algorithm: String,
+    /// Ed25519 public key, hex-encoded (32 bytes) — kept alongside
+    /// `fingerprint` since `sign_with_authority`/`AuthorityList` key their
+    /// capability checks on the full public key, not its truncated digest.
+    pub public_key: String,
+    /// SHA-256 of the raw public key, base64-encoded and truncated to 16
+    /// characters, for short display in `telos verify`/`log` output.
+    pub fingerprint: String,
+    /// Ed25519 signature, base64-encoded (64 bytes).
+    pub signature: String,
+}
+
+fn sled_err(e: sled::Error) -> StoreError {
+    StoreError::IndexError(e.to_string())
+}
+
+fn decode_fixed_hex<const N: usize>(hex_str: &str) -> Option<[u8; N]> {
+    hex::decode(hex_str).ok()?.try_into().ok()
+}
+
+fn decode_fixed_base64<const N: usize>(s: &str) -> Option<[u8; N]> {
+    base64_decode(s)?.try_into().ok()
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Hand-rolled standard (padded) base64 encode, matching the approach
+/// `cursor.rs` takes for its own (unpadded, URL-safe) variant rather than
+/// pulling in an external `base64` crate for one format.
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim_end_matches('=');
+    let mut out = Vec::with_capacity(s.len() * 3 / 4 + 3);
+    let mut buf = 0u32;
+    let mut bits = 0u32;
+    for c in s.bytes() {
+        let val = BASE64_ALPHABET.iter().position(|&b| b == c)? as u32;
+        buf = (buf << 6) | val;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// SHA-256 of the raw public key, base64-encoded and truncated to 16
+/// characters. A truncation collision only risks an unknown signer
+/// mistakenly *displaying* as a known fingerprint — [`AllowedSigners`]
+/// always re-checks the full embedded public key, so it can't be used to
+/// forge trust.
+fn fingerprint_of(public_key: &VerifyingKey) -> String {
+    let digest = Sha256::digest(public_key.to_bytes());
+    let encoded = base64_encode(&digest);
+    encoded.chars().take(16).collect()
+}
+
+/// Where an `AuthorKey`'s private material actually lives. A `Local` key
+/// holds the raw signing scalar; an `Agent` key never does — signing is
+/// delegated to whatever process holds `SSH_AUTH_SOCK` over the
+/// `ssh-agent` wire protocol (see [`crate::ssh_agent`]), the same way `ssh`
+/// itself signs without ever reading the private key off disk.
+enum KeySource {
+    Local(SigningKey),
+    Agent(Vec<u8>),
+}
+
+/// An author's Ed25519 keypair, either held locally or delegated to a
+/// running `ssh-agent`.
+pub struct AuthorKey {
+    verifying_key: VerifyingKey,
+    source: KeySource,
+}
+
+impl AuthorKey {
+    /// Generate a fresh random keypair.
+    pub fn generate() -> Self {
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        Self {
+            verifying_key: signing_key.verifying_key(),
+            source: KeySource::Local(signing_key),
+        }
+    }
+
+    /// Restore a keypair from its 32-byte seed.
+    pub fn from_seed(seed: &[u8; 32]) -> Self {
+        let signing_key = SigningKey::from_bytes(seed);
+        Self {
+            verifying_key: signing_key.verifying_key(),
+            source: KeySource::Local(signing_key),
+        }
+    }
+
+    /// Load an `ssh-ed25519` OpenSSH private key, so an author can reuse an
+    /// existing agent key instead of generating a Telos-specific one.
+    pub fn from_openssh_file(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        let key = ssh_key::PrivateKey::read_openssh_file(path)
+            .map_err(|e| StoreError::SigningError(format!("{}: {}", path.display(), e)))?;
+        let keypair = key.key_data().ed25519().ok_or_else(|| {
+            StoreError::SigningError(format!("{} is not an ssh-ed25519 key", path.display()))
+        })?;
+        Ok(Self::from_seed(&keypair.private.to_bytes()))
+    }
+
+    /// Use a key already loaded in a running `ssh-agent` (via
+    /// `SSH_AUTH_SOCK`), so a team can sign with an existing SSH identity
+    /// without ever exporting its private key to a file. `public_key_hex`
+    /// selects which loaded identity to use; `None` picks the agent's
+    /// first ed25519 identity.
+    pub fn from_ssh_agent(public_key_hex: Option<&str>) -> Result<Self, StoreError> {
+        let identity = crate::ssh_agent::find_identity(public_key_hex)?;
+        let public_key = identity
+            .public_key
+            .ok_or_else(|| StoreError::SigningError("ssh-agent identity is not an ed25519 key".into()))?;
+        let verifying_key = VerifyingKey::from_bytes(&public_key)
+            .map_err(|e| StoreError::SigningError(format!("ssh-agent returned an invalid public key: {}", e)))?;
+        Ok(Self {
+            verifying_key,
+            source: KeySource::Agent(identity.key_blob),
+        })
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.verifying_key.to_bytes())
+    }
+
+    /// The raw 32-byte seed, for persisting a generated key to
+    /// `.telos/keys/id_ed25519`. Only meaningful for a locally held key —
+    /// never called on an agent-backed one, since `Repository` only ever
+    /// persists keys it generated itself.
+    pub fn seed(&self) -> [u8; 32] {
+        match &self.source {
+            KeySource::Local(k) => k.to_bytes(),
+            KeySource::Agent(_) => panic!("cannot export the seed of an ssh-agent-backed key"),
+        }
+    }
+
+    /// Short display fingerprint — see [`fingerprint_of`].
+    pub fn fingerprint(&self) -> String {
+        fingerprint_of(&self.verifying_key)
+    }
+
+    /// Sign `object`'s canonical bytes.
+    pub fn sign(&self, object: &TelosObject) -> Result<ObjectSignature, StoreError> {
+        let bytes = object.canonical_bytes().map_err(StoreError::Core)?;
+        let id = ObjectId::hash(&bytes);
+        Ok(ObjectSignature {
+            object_id: id.hex().to_string(),
+            algorithm: ALGORITHM_ED25519.to_string(),
+            public_key: self.public_key_hex(),
+            fingerprint: self.fingerprint(),
+            signature: self.sign_bytes(&bytes)?,
+        })
+    }
+
+    /// Sign `object`, first checking the capability required for its kind
+    /// against `authorities` — today that's just "`Must`-severity
+    /// constraints require an authorized key", but the check is centralized
+    /// here so a future capability gains a single place to plug in.
+    pub fn sign_with_authority(
+        &self,
+        object: &TelosObject,
+        authorities: &AuthorityList,
+    ) -> Result<ObjectSignature, StoreError> {
+        if let TelosObject::Constraint(c) = object {
+            if c.severity == ConstraintSeverity::Must
+                && !authorities.is_authorized_for_must(&self.public_key_hex())
+            {
+                return Err(StoreError::Unauthorized(format!(
+                    "key {} is not authorized to sign Must-severity constraints",
+                    self.public_key_hex()
+                )));
+            }
+        }
+        self.sign(object)
+    }
+
+    /// Sign raw bytes directly, for artifacts that aren't a `TelosObject` —
+    /// e.g. a capability token's canonical bytes in `auth.rs`, which has no
+    /// `ObjectId` of its own worth recomputing here. For an agent-backed
+    /// key this round-trips through `ssh-agent`, which is why it's
+    /// fallible (a local key can't fail to sign, but an agent can be
+    /// unreachable or refuse the key).
+    pub fn sign_bytes(&self, bytes: &[u8]) -> Result<String, StoreError> {
+        match &self.source {
+            KeySource::Local(k) => Ok(base64_encode(&k.sign(bytes).to_bytes())),
+            KeySource::Agent(key_blob) => {
+                let sig = crate::ssh_agent::sign(key_blob, bytes)?;
+                Ok(base64_encode(&sig))
+            }
+        }
+    }
+}
+
+/// Recompute `object`'s canonical bytes and check `sig` against them.
+/// Returns `Ok(false)` (rather than an error) for a mismatched object id
+/// or a cryptographically invalid signature — both are "not verified",
+/// not a failure to attempt verification.
+pub fn verify(object: &TelosObject, sig: &ObjectSignature) -> Result<bool, StoreError> {
+    let bytes = object.canonical_bytes().map_err(StoreError::Core)?;
+    let id = ObjectId::hash(&bytes);
+    if id.hex() != sig.object_id {
+        return Ok(false);
+    }
+
+    let Some(key_bytes) = decode_fixed_hex::<32>(&sig.public_key) else {
+        return Ok(false);
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return Ok(false);
+    };
+
+    let Some(sig_bytes) = decode_fixed_base64::<64>(&sig.signature) else {
+        return Ok(false);
+    };
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    Ok(verifying_key.verify(&bytes, &signature).is_ok())
+}
+
+/// Check a base64 signature over arbitrary bytes against a hex-encoded
+/// public key — the `sign_bytes` counterpart, for verifying artifacts like
+/// a capability token that aren't `TelosObject`s.
+pub fn verify_bytes(public_key_hex: &str, bytes: &[u8], signature_b64: &str) -> Result<bool, StoreError> {
+    let Some(key_bytes) = decode_fixed_hex::<32>(public_key_hex) else {
+        return Ok(false);
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return Ok(false);
+    };
+    let Some(sig_bytes) = decode_fixed_base64::<64>(signature_b64) else {
+        return Ok(false);
+    };
+    let signature = Signature::from_bytes(&sig_bytes);
+    Ok(verifying_key.verify(bytes, &signature).is_ok())
+}
+
+/// Walk `changeset_id`'s `ChangeSet::parents`, verifying every link against
+/// its stored signature in `signatures`. Returns `Ok(false)` as soon as
+/// any object in the chain is missing a signature or fails verification,
+/// so a modified (or unsigned) ancestor invalidates every descendant.
+pub fn verify_chain(
+    changeset_id: &ObjectId,
+    odb: &ObjectDatabase,
+    signatures: &SignatureStore,
+) -> Result<bool, StoreError> {
+    let obj = odb.read(changeset_id)?;
+    let TelosObject::ChangeSet(cs) = &obj else {
+        return Err(StoreError::IndexError(format!(
+            "{} is not a change_set",
+            changeset_id.hex()
+        )));
+    };
+
+    let Some(sig) = signatures.get(changeset_id)? else {
+        return Ok(false);
+    };
+    if !verify(&obj, &sig)? {
+        return Ok(false);
+    }
+
+    for parent in &cs.parents {
+        if !verify_chain(parent, odb, signatures)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Persists [`ObjectSignature`]s keyed by the signed object's id, in its
+/// own sled tree under `.telos/signatures/` — deliberately separate from
+/// `IndexStore` so a future `rebuild_all` can never clear it.
+pub struct SignatureStore {
+    dir: PathBuf,
+    db: sled::Db,
+}
+
+impl SignatureStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let db = sled::open(dir.join("kv")).expect("failed to open signature kv store");
+        Self { dir, db }
+    }
+
+    pub fn ensure_dir(&self) -> Result<(), StoreError> {
+        fs::create_dir_all(&self.dir)?;
+        Ok(())
+    }
+
+    pub fn put(&self, sig: &ObjectSignature) -> Result<(), StoreError> {
+        let value = serde_json::to_vec(sig)?;
+        self.db.insert(sig.object_id.as_bytes(), value).map_err(sled_err)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &ObjectId) -> Result<Option<ObjectSignature>, StoreError> {
+        match self.db.get(id.hex().as_bytes()).map_err(sled_err)? {
+            Some(v) => Ok(Some(serde_json::from_slice(&v)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Which public keys are authorized for which capabilities. Today the
+/// only capability is signing `Must`-severity constraints; persisted as
+/// plain JSON (see `.telos/authorities.json` in
+/// [`crate::repository::Repository`]) since it's small and human-editable,
+/// the same tradeoff `Repository::set_remote` makes for remotes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthorityList {
+    #[serde(default)]
+    must_signers: BTreeSet<String>,
+}
+
+impl AuthorityList {
+    /// Load from `path`, or an empty list if it doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StoreError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn authorize_must_signer(&mut self, public_key_hex: impl Into<String>) {
+        self.must_signers.insert(public_key_hex.into());
+    }
+
+    pub fn revoke_must_signer(&mut self, public_key_hex: &str) {
+        self.must_signers.remove(public_key_hex);
+    }
+
+    pub fn is_authorized_for_must(&self, public_key_hex: &str) -> bool {
+        self.must_signers.contains(public_key_hex)
+    }
+}
+
+/// Trusted-keys file for verifying signatures (`.telos/allowed_signers`),
+/// distinct from [`AuthorityList`]: this is "is this key trusted at all",
+/// not "is this key allowed to sign `Must`-severity constraints". Format
+/// mirrors git's own `allowed_signers` convention — one `fingerprint
+/// public_key_hex` pair per line, blank lines and `#`-comments ignored.
+#[derive(Debug, Clone, Default)]
+pub struct AllowedSigners {
+    entries: std::collections::BTreeMap<String, String>,
+}
+
+impl AllowedSigners {
+    /// Load from `path`, or an empty trust store if it doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let mut entries = std::collections::BTreeMap::new();
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some((fingerprint, public_key)) = line.split_once(' ') {
+                entries.insert(fingerprint.to_string(), public_key.trim().to_string());
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StoreError> {
+        let mut contents = String::new();
+        for (fingerprint, public_key) in &self.entries {
+            contents.push_str(fingerprint);
+            contents.push(' ');
+            contents.push_str(public_key);
+            contents.push('\n');
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn trust(&mut self, fingerprint: impl Into<String>, public_key_hex: impl Into<String>) {
+        self.entries.insert(fingerprint.into(), public_key_hex.into());
+    }
+
+    pub fn revoke(&mut self, fingerprint: &str) {
+        self.entries.remove(fingerprint);
+    }
+
+    /// Whether `sig`'s fingerprint is listed AND its listed public key
+    /// matches the one embedded in the signature — guards against a
+    /// truncated-fingerprint collision being used to smuggle in trust for
+    /// the wrong key.
+    pub fn is_trusted(&self, sig: &ObjectSignature) -> bool {
+        self.entries
+            .get(&sig.fingerprint)
+            .is_some_and(|public_key| public_key == &sig.public_key)
+    }
+}
+
+/// Outcome of checking an object's signature against both cryptographic
+/// validity and the [`AllowedSigners`] trust store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// No signature stored for this object.
+    Unsigned,
+    /// Cryptographically valid and the signer is trusted.
+    Verified,
+    /// Cryptographically valid but the signer's fingerprint isn't in
+    /// `.telos/allowed_signers` — deliberately not a hard error, since an
+    /// unrecognized signer is a fact worth surfacing, not one that should
+    /// block `log`/`show` from working.
+    Untrusted,
+    /// Signature doesn't match the object's canonical bytes (tampered,
+    /// wrong key, or corrupt).
+    Invalid,
+}
+
+impl fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignatureStatus::Unsigned => "unsigned",
+            SignatureStatus::Verified => "verified",
+            SignatureStatus::Untrusted => "untrusted",
+            SignatureStatus::Invalid => "invalid",
+        })
+    }
+}
+
+/// Look up `id`'s stored signature (if any), verify it against the object's
+/// current canonical bytes, and classify the result against `allowed`.
+pub fn signature_status(
+    odb: &ObjectDatabase,
+    signatures: &SignatureStore,
+    allowed: &AllowedSigners,
+    id: &ObjectId,
+) -> Result<SignatureStatus, StoreError> {
+    let Some(sig) = signatures.get(id)? else {
+        return Ok(SignatureStatus::Unsigned);
+    };
+    let object = odb.read(id)?;
+    if !verify(&object, &sig)? {
+        return Ok(SignatureStatus::Invalid);
+    }
+    if !allowed.is_trusted(&sig) {
+        return Ok(SignatureStatus::Untrusted);
+    }
+    Ok(SignatureStatus::Verified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use telos_core::object::change_set::ChangeSet;
+    use telos_core::object::constraint::{Constraint, ConstraintStatus};
+    use telos_core::object::intent::Author;
+
+    fn author() -> Author {
+        Author {
+            name: "Test".into(),
+            email: "test@test.com".into(),
+        }
+    }
+
+    fn sample_constraint(severity: ConstraintSeverity) -> TelosObject {
+        TelosObject::Constraint(Constraint {
+            author: author(),
+            timestamp: Utc::now(),
+            statement: "all public APIs must be documented".into(),
+            severity,
+            status: ConstraintStatus::Active,
+            source_intent: ObjectId::hash(b"dummy"),
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn sample_change_set(parents: Vec<ObjectId>) -> TelosObject {
+        TelosObject::ChangeSet(ChangeSet {
+            author: author(),
+            timestamp: Utc::now(),
+            git_commit: "deadbeef".into(),
+            parents,
+            intents: vec![],
+            constraints: vec![],
+            decisions: vec![],
+            code_bindings: vec![],
+            agent_operations: vec![],
+            metadata: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn sign_then_verify_succeeds() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+        assert!(verify(&obj, &sig).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_object() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+
+        let tampered = sample_constraint(ConstraintSeverity::Must);
+        assert!(!verify(&tampered, &sig).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_wrong_key() {
+        let key = AuthorKey::generate();
+        let other = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+
+        let sig = key.sign(&obj).unwrap();
+        let mut wrong_sig = sig.clone();
+        wrong_sig.public_key = other.public_key_hex();
+        assert!(!verify(&obj, &wrong_sig).unwrap());
+    }
+
+    #[test]
+    fn must_constraint_requires_authority() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Must);
+        let authorities = AuthorityList::default();
+
+        assert!(key.sign_with_authority(&obj, &authorities).is_err());
+
+        let mut authorized = authorities;
+        authorized.authorize_must_signer(key.public_key_hex());
+        assert!(key.sign_with_authority(&obj, &authorized).is_ok());
+    }
+
+    #[test]
+    fn should_constraint_needs_no_authority() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let authorities = AuthorityList::default();
+        assert!(key.sign_with_authority(&obj, &authorities).is_ok());
+    }
+
+    #[test]
+    fn verify_chain_detects_tampered_ancestor() {
+        let dir = tempfile::tempdir().unwrap();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        let signatures = SignatureStore::new(dir.path().join("signatures"));
+        signatures.ensure_dir().unwrap();
+        let key = AuthorKey::generate();
+
+        let root = sample_change_set(vec![]);
+        let root_id = odb.write(&root).unwrap();
+        signatures.put(&key.sign(&root).unwrap()).unwrap();
+
+        let child = sample_change_set(vec![root_id.clone()]);
+        let child_id = odb.write(&child).unwrap();
+        signatures.put(&key.sign(&child).unwrap()).unwrap();
+
+        assert!(verify_chain(&child_id, &odb, &signatures).unwrap());
+
+        // Simulate a tampered ancestor: a new root-less change_set swapped
+        // in without ever being signed, then re-pointed to by a new child.
+        let forged_root = sample_change_set(vec![]);
+        let forged_root_id = odb.write(&forged_root).unwrap();
+        let forged_child = sample_change_set(vec![forged_root_id]);
+        let forged_child_id = odb.write(&forged_child).unwrap();
+        signatures.put(&key.sign(&forged_child).unwrap()).unwrap();
+
+        assert!(!verify_chain(&forged_child_id, &odb, &signatures).unwrap());
+    }
+
+    #[test]
+    fn base64_round_trips() {
+        for data in [b"".as_slice(), b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            let encoded = base64_encode(data);
+            assert_eq!(base64_decode(&encoded).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_short() {
+        let key = AuthorKey::generate();
+        assert_eq!(key.fingerprint(), key.fingerprint());
+        assert_eq!(key.fingerprint().len(), 16);
+
+        let other = AuthorKey::generate();
+        assert_ne!(key.fingerprint(), other.fingerprint());
+    }
+
+    #[test]
+    fn sign_then_verify_succeeds_with_base64_signature() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+        assert_eq!(sig.algorithm, ALGORITHM_ED25519);
+        assert_eq!(sig.fingerprint, key.fingerprint());
+        assert!(verify(&obj, &sig).unwrap());
+    }
+
+    #[test]
+    fn allowed_signers_trusts_matching_fingerprint_and_key() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+
+        let mut allowed = AllowedSigners::default();
+        assert!(!allowed.is_trusted(&sig));
+
+        allowed.trust(sig.fingerprint.clone(), sig.public_key.clone());
+        assert!(allowed.is_trusted(&sig));
+
+        allowed.revoke(&sig.fingerprint);
+        assert!(!allowed.is_trusted(&sig));
+    }
+
+    #[test]
+    fn allowed_signers_rejects_fingerprint_collision_with_wrong_key() {
+        let key = AuthorKey::generate();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+
+        let mut allowed = AllowedSigners::default();
+        // Same fingerprint listed, but a different (wrong) public key.
+        allowed.trust(sig.fingerprint.clone(), "0".repeat(64));
+        assert!(!allowed.is_trusted(&sig));
+    }
+
+    #[test]
+    fn allowed_signers_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("allowed_signers");
+        let key = AuthorKey::generate();
+
+        let mut allowed = AllowedSigners::default();
+        allowed.trust(key.fingerprint(), key.public_key_hex());
+        allowed.save(&path).unwrap();
+
+        let loaded = AllowedSigners::load(&path).unwrap();
+        let obj = sample_constraint(ConstraintSeverity::Should);
+        let sig = key.sign(&obj).unwrap();
+        assert!(loaded.is_trusted(&sig));
+    }
+
+    #[test]
+    fn signature_status_covers_every_outcome() {
+        let dir = tempfile::tempdir().unwrap();
+        let odb = ObjectDatabase::new(dir.path().join("objects"));
+        let signatures = SignatureStore::new(dir.path().join("signatures"));
+        signatures.ensure_dir().unwrap();
+        let key = AuthorKey::generate();
+
+        let unsigned = sample_constraint(ConstraintSeverity::Should);
+        let unsigned_id = odb.write(&unsigned).unwrap();
+        assert_eq!(
+            signature_status(&odb, &signatures, &AllowedSigners::default(), &unsigned_id).unwrap(),
+            SignatureStatus::Unsigned
+        );
+
+        let trusted = sample_constraint(ConstraintSeverity::Must);
+        let trusted_id = odb.write(&trusted).unwrap();
+        let sig = key.sign(&trusted).unwrap();
+        signatures.put(&sig).unwrap();
+        let mut allowed = AllowedSigners::default();
+        allowed.trust(sig.fingerprint.clone(), sig.public_key.clone());
+        assert_eq!(
+            signature_status(&odb, &signatures, &allowed, &trusted_id).unwrap(),
+            SignatureStatus::Verified
+        );
+        assert_eq!(
+            signature_status(&odb, &signatures, &AllowedSigners::default(), &trusted_id).unwrap(),
+            SignatureStatus::Untrusted
+        );
+
+        let other = AuthorKey::generate();
+        let mut wrong_sig = sig.clone();
+        wrong_sig.public_key = other.public_key_hex();
+        signatures.put(&wrong_sig).unwrap();
+        assert_eq!(
+            signature_status(&odb, &signatures, &allowed, &trusted_id).unwrap(),
+            SignatureStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn authority_list_round_trips_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("authorities.json");
+        let key = AuthorKey::generate();
+
+        let mut authorities = AuthorityList::default();
+        authorities.authorize_must_signer(key.public_key_hex());
+        authorities.save(&path).unwrap();
+
+        let loaded = AuthorityList::load(&path).unwrap();
+        assert!(loaded.is_authorized_for_must(&key.public_key_hex()));
+    }
+}
@@ -0,0 +1,254 @@
+//! A minimal `ssh-agent` protocol client (draft-miller-ssh-agent), so a
+//! team can sign Telos objects with a key that already lives in a running
+//! agent instead of exporting it to an OpenSSH private-key file on disk.
+//!
+//! Only the two message types signing needs are implemented:
+//! `SSH_AGENTC_REQUEST_IDENTITIES` (list the agent's public keys) and
+//! `SSH_AGENTC_SIGN_REQUEST` (sign with one of them). Everything else an
+//! agent can do (adding/removing keys, locking) is out of scope — Telos
+//! only ever asks an agent to sign, never to manage its keyring.
+
+use crate::error::StoreError;
+use std::env;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+const SSH_AGENTC_SIGN_REQUEST: u8 = 13;
+const SSH_AGENT_SIGN_RESPONSE: u8 = 14;
+
+/// One key the agent is holding, as reported by
+/// `SSH_AGENTC_REQUEST_IDENTITIES`.
+pub struct AgentIdentity {
+    /// The SSH wire-format public key blob (`string "ssh-ed25519", string
+    /// 32-byte key`) — opaque to us, just echoed back in a sign request.
+    pub key_blob: Vec<u8>,
+    /// The raw 32-byte Ed25519 public key, extracted from `key_blob`, for
+    /// matching against `AuthorKey::public_key_hex`.
+    pub public_key: Option<[u8; 32]>,
+    pub comment: String,
+}
+
+fn write_frame(stream: &mut UnixStream, payload: &[u8]) -> Result<(), StoreError> {
+    stream.write_all(&(payload.len() as u32).to_be_bytes())?;
+    stream.write_all(payload)?;
+    Ok(())
+}
+
+fn read_frame(stream: &mut UnixStream) -> Result<Vec<u8>, StoreError> {
+    let mut len_buf = [0u8; 4];
+    stream.read_exact(&mut len_buf)?;
+    let len = u32::from_be_bytes(len_buf) as usize;
+    let mut buf = vec![0u8; len];
+    stream.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn write_ssh_string(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(data);
+}
+
+/// Read one length-prefixed SSH string starting at `buf[*pos]`, advancing
+/// `*pos` past it.
+fn read_ssh_string<'a>(buf: &'a [u8], pos: &mut usize) -> Result<&'a [u8], StoreError> {
+    if buf.len() < *pos + 4 {
+        return Err(StoreError::SigningError("truncated ssh-agent response".into()));
+    }
+    let len = u32::from_be_bytes(buf[*pos..*pos + 4].try_into().unwrap()) as usize;
+    *pos += 4;
+    if buf.len() < *pos + len {
+        return Err(StoreError::SigningError("truncated ssh-agent response".into()));
+    }
+    let s = &buf[*pos..*pos + len];
+    *pos += len;
+    Ok(s)
+}
+
+fn extract_ed25519_public_key(key_blob: &[u8]) -> Option<[u8; 32]> {
+    let mut pos = 0;
+    let key_type = read_ssh_string(key_blob, &mut pos).ok()?;
+    if key_type != b"ssh-ed25519" {
+        return None;
+    }
+    read_ssh_string(key_blob, &mut pos).ok()?.try_into().ok()
+}
+
+fn connect() -> Result<UnixStream, StoreError> {
+    let socket_path = env::var("SSH_AUTH_SOCK")
+        .map_err(|_| StoreError::SigningError("SSH_AUTH_SOCK is not set; no ssh-agent to connect to".into()))?;
+    UnixStream::connect(&socket_path)
+        .map_err(|e| StoreError::SigningError(format!("failed to connect to ssh-agent at {}: {}", socket_path, e)))
+}
+
+/// List the agent's identities.
+pub fn list_identities() -> Result<Vec<AgentIdentity>, StoreError> {
+    let mut stream = connect()?;
+    write_frame(&mut stream, &[SSH_AGENTC_REQUEST_IDENTITIES])?;
+    let reply = read_frame(&mut stream)?;
+
+    if reply.first() != Some(&SSH_AGENT_IDENTITIES_ANSWER) {
+        return Err(StoreError::SigningError("ssh-agent did not answer the identities request".into()));
+    }
+
+    let mut pos = 1;
+    if reply.len() < pos + 4 {
+        return Err(StoreError::SigningError("truncated ssh-agent response".into()));
+    }
+    let count = u32::from_be_bytes(reply[pos..pos + 4].try_into().unwrap());
+    pos += 4;
+
+    let mut identities = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let key_blob = read_ssh_string(&reply, &mut pos)?.to_vec();
+        let comment = String::from_utf8_lossy(read_ssh_string(&reply, &mut pos)?).into_owned();
+        let public_key = extract_ed25519_public_key(&key_blob);
+        identities.push(AgentIdentity {
+            key_blob,
+            public_key,
+            comment,
+        });
+    }
+    Ok(identities)
+}
+
+/// Ask the agent to sign `data` with the identity whose blob is
+/// `key_blob`, returning the raw 64-byte Ed25519 signature.
+pub fn sign(key_blob: &[u8], data: &[u8]) -> Result<[u8; 64], StoreError> {
+    let mut stream = connect()?;
+
+    let mut payload = vec![SSH_AGENTC_SIGN_REQUEST];
+    write_ssh_string(&mut payload, key_blob);
+    write_ssh_string(&mut payload, data);
+    payload.extend_from_slice(&0u32.to_be_bytes()); // flags
+
+    write_frame(&mut stream, &payload)?;
+    let reply = read_frame(&mut stream)?;
+
+    if reply.first() != Some(&SSH_AGENT_SIGN_RESPONSE) {
+        return Err(StoreError::SigningError("ssh-agent refused to sign (key not loaded?)".into()));
+    }
+
+    let mut pos = 1;
+    let signature_blob = read_ssh_string(&reply, &mut pos)?;
+    let mut inner_pos = 0;
+    let sig_type = read_ssh_string(signature_blob, &mut inner_pos)?;
+    if sig_type != b"ssh-ed25519" {
+        return Err(StoreError::SigningError(format!(
+            "ssh-agent returned an unexpected signature type '{}'",
+            String::from_utf8_lossy(sig_type)
+        )));
+    }
+    let sig_bytes = read_ssh_string(signature_blob, &mut inner_pos)?;
+    sig_bytes
+        .try_into()
+        .map_err(|_| StoreError::SigningError("ssh-agent returned a malformed ed25519 signature".into()))
+}
+
+/// Find the first identity in the agent matching `public_key_hex` (an
+/// `AuthorKey::public_key_hex`-style hex string), or the first ed25519
+/// identity if `None`.
+pub fn find_identity(public_key_hex: Option<&str>) -> Result<AgentIdentity, StoreError> {
+    let identities = list_identities()?;
+    match public_key_hex {
+        Some(wanted) => identities
+            .into_iter()
+            .find(|id| id.public_key.map(hex::encode).as_deref() == Some(wanted))
+            .ok_or_else(|| StoreError::SigningError(format!("ssh-agent has no loaded key matching {}", wanted))),
+        None => identities
+            .into_iter()
+            .find(|id| id.public_key.is_some())
+            .ok_or_else(|| StoreError::SigningError("ssh-agent has no ed25519 identities loaded".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use std::thread;
+
+    /// Serve one `SSH_AGENTC_REQUEST_IDENTITIES` with a single fake
+    /// ed25519 identity, then one `SSH_AGENTC_SIGN_REQUEST` echoing back a
+    /// fixed signature — just enough of the protocol to exercise our
+    /// client without depending on a real ssh-agent being available.
+    fn spawn_fake_agent(key_blob: Vec<u8>, comment: &'static str, signature: [u8; 64]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("agent.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        unsafe {
+            env::set_var("SSH_AUTH_SOCK", &socket_path);
+        }
+
+        thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let request = read_frame(&mut stream).unwrap();
+                match request.first() {
+                    Some(&SSH_AGENTC_REQUEST_IDENTITIES) => {
+                        let mut reply = vec![SSH_AGENT_IDENTITIES_ANSWER];
+                        reply.extend_from_slice(&1u32.to_be_bytes());
+                        write_ssh_string(&mut reply, &key_blob);
+                        write_ssh_string(&mut reply, comment.as_bytes());
+                        write_frame(&mut stream, &reply).unwrap();
+                    }
+                    Some(&SSH_AGENTC_SIGN_REQUEST) => {
+                        let mut sig_blob = Vec::new();
+                        write_ssh_string(&mut sig_blob, b"ssh-ed25519");
+                        write_ssh_string(&mut sig_blob, &signature);
+                        let mut reply = vec![SSH_AGENT_SIGN_RESPONSE];
+                        write_ssh_string(&mut reply, &sig_blob);
+                        write_frame(&mut stream, &reply).unwrap();
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        dir
+    }
+
+    fn fake_key_blob(public_key: [u8; 32]) -> Vec<u8> {
+        let mut blob = Vec::new();
+        write_ssh_string(&mut blob, b"ssh-ed25519");
+        write_ssh_string(&mut blob, &public_key);
+        blob
+    }
+
+    #[test]
+    fn list_identities_parses_a_single_fake_identity() {
+        let public_key = [7u8; 32];
+        let key_blob = fake_key_blob(public_key);
+        let _dir = spawn_fake_agent(key_blob.clone(), "test@example.com", [0u8; 64]);
+
+        let identities = list_identities().unwrap();
+        assert_eq!(identities.len(), 1);
+        assert_eq!(identities[0].key_blob, key_blob);
+        assert_eq!(identities[0].public_key, Some(public_key));
+        assert_eq!(identities[0].comment, "test@example.com");
+    }
+
+    #[test]
+    fn find_identity_matches_by_public_key_hex() {
+        let public_key = [9u8; 32];
+        let key_blob = fake_key_blob(public_key);
+        let _dir = spawn_fake_agent(key_blob.clone(), "test@example.com", [0u8; 64]);
+
+        let found = find_identity(Some(&hex::encode(public_key))).unwrap();
+        assert_eq!(found.key_blob, key_blob);
+
+        let err = find_identity(Some(&hex::encode([1u8; 32]))).unwrap_err();
+        assert!(err.to_string().contains("no loaded key matching"));
+    }
+
+    #[test]
+    fn sign_returns_the_agents_signature() {
+        let public_key = [3u8; 32];
+        let key_blob = fake_key_blob(public_key);
+        let signature = [42u8; 64];
+        let _dir = spawn_fake_agent(key_blob.clone(), "test@example.com", signature);
+
+        let result = sign(&key_blob, b"hello").unwrap();
+        assert_eq!(result, signature);
+    }
+}
@@ -0,0 +1,276 @@
+//! A mutable "ref" layer for a constraint's lifecycle status, reconciled
+//! across repositories with a per-writer causal version vector.
+//!
+//! Every other piece of repository state a sync has to deal with is either
+//! content-addressed and immutable (objects) or a simple last-writer tip
+//! (`IntentStreamRef`, where the intent DAG's ancestor relationship already
+//! tells `sync::merge_stream` which tip is newer). A constraint's `status`
+//! has neither property: `supersede`/`deprecate` mint a brand new immutable
+//! `Constraint` copy for every transition, so two repositories that
+//! independently deprecate (or supersede) the same constraint produce two
+//! unrelated copies with no DAG between them to order by.
+//!
+//! [`StatusRef`] tracks "what does this constraint's status currently read
+//! as" per base constraint id, alongside a [`VersionVector`] counting how
+//! many times each writer has changed it. [`StatusRef::merge`] reconciles
+//! two copies: if one vector causally dominates the other, that side simply
+//! won (the other already saw it, or will on its next sync). If they're
+//! concurrent — both sides changed the status without having seen the
+//! other's change — the update with the higher vector total wins, but the
+//! loser is recorded as a [`MergeNote`] rather than silently dropped, so a
+//! reviewer can see that a conflicting change happened and what it was.
+
+use crate::error::StoreError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use telos_core::hash::ObjectId;
+use telos_core::object::constraint::ConstraintStatus;
+
+/// A per-writer count of status changes applied to one constraint, used to
+/// detect whether one [`StatusRef`] causally happened-after another.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionVector(BTreeMap<String, u64>);
+
+impl VersionVector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Increment `writer`'s entry, recording a status change it made.
+    pub fn bump(&mut self, writer: &str) {
+        *self.0.entry(writer.to_string()).or_insert(0) += 1;
+    }
+
+    fn get(&self, writer: &str) -> u64 {
+        self.0.get(writer).copied().unwrap_or(0)
+    }
+
+    /// True if `self` has seen every change `other` has (`self >= other`
+    /// entrywise). A vector dominates an equal one too, so `a.dominates(a)`
+    /// is `true`.
+    pub fn dominates(&self, other: &Self) -> bool {
+        other.0.keys().all(|writer| self.get(writer) >= other.get(writer))
+    }
+
+    /// True if neither vector dominates the other — both sides made a
+    /// change the other hasn't seen.
+    pub fn concurrent_with(&self, other: &Self) -> bool {
+        !self.dominates(other) && !other.dominates(self)
+    }
+
+    /// Sum of every writer's count, used as the concurrent-update
+    /// tiebreaker: the side that has been changed more times wins.
+    fn total(&self) -> u64 {
+        self.0.values().sum()
+    }
+
+    /// The union of both vectors, each entry taking the max of the two —
+    /// the vector a merged ref carries forward, since it now reflects both
+    /// sides' history.
+    pub fn merged_with(&self, other: &Self) -> Self {
+        let mut merged = self.0.clone();
+        for (writer, count) in &other.0 {
+            let entry = merged.entry(writer.clone()).or_insert(0);
+            *entry = (*entry).max(*count);
+        }
+        Self(merged)
+    }
+}
+
+/// The current reconciled status of one constraint (keyed by the id of its
+/// first, originally-`Active` copy — stable across however many
+/// supersede/deprecate copies follow it).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusRef {
+    /// The id of the constraint copy that currently holds this status
+    /// (a fresh `Active` replacement for supersede, or the `Deprecated`
+    /// copy for deprecate).
+    pub current: ObjectId,
+    pub status: ConstraintStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<ObjectId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation_reason: Option<String>,
+    pub vector: VersionVector,
+    /// Notes left behind by `merge` describing a concurrent update that
+    /// lost the tiebreak, oldest first — never cleared automatically, so a
+    /// reviewer can audit every conflicting change a sync has reconciled.
+    #[serde(default)]
+    pub merge_notes: Vec<MergeNote>,
+}
+
+/// A concurrent status change that lost a [`StatusRef::merge`] tiebreak,
+/// kept for visibility instead of being silently dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeNote {
+    pub losing_current: ObjectId,
+    pub losing_status: ConstraintStatus,
+    pub losing_vector: VersionVector,
+}
+
+impl StatusRef {
+    pub fn new(current: ObjectId, status: ConstraintStatus, writer: &str) -> Self {
+        let mut vector = VersionVector::new();
+        vector.bump(writer);
+        Self {
+            current,
+            status,
+            superseded_by: None,
+            deprecation_reason: None,
+            vector,
+            merge_notes: Vec::new(),
+        }
+    }
+
+    /// Reconcile `self` (local) with `remote`, returning the ref both sides
+    /// should end up holding. If one side's vector dominates, it wins
+    /// outright. Otherwise the two updates are concurrent: the one with the
+    /// higher vector total wins (ties broken by `current`'s hex id, for a
+    /// deterministic result regardless of which side runs the merge), and
+    /// the other is appended to `merge_notes`.
+    pub fn merge(&self, remote: &Self) -> Self {
+        if self.vector.dominates(&remote.vector) {
+            return self.clone();
+        }
+        if remote.vector.dominates(&self.vector) {
+            return remote.clone();
+        }
+
+        let self_wins = match self.vector.total().cmp(&remote.vector.total()) {
+            std::cmp::Ordering::Greater => true,
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => self.current.hex() > remote.current.hex(),
+        };
+        let (winner, loser) = if self_wins { (self, remote) } else { (remote, self) };
+
+        let mut merged = winner.clone();
+        merged.vector = self.vector.merged_with(&remote.vector);
+        merged.merge_notes = winner.merge_notes.clone();
+        merged.merge_notes.extend(loser.merge_notes.clone());
+        merged.merge_notes.push(MergeNote {
+            losing_current: loser.current.clone(),
+            losing_status: loser.status.clone(),
+            losing_vector: loser.vector.clone(),
+        });
+        merged
+    }
+}
+
+/// Persists one [`StatusRef`] per base constraint id under
+/// `.telos/refs/constraints/<hex>.json` — a genuinely mutable file,
+/// overwritten in place on every status change or merge, unlike everything
+/// else under `.telos/refs` which only ever moves forward along a DAG.
+pub struct StatusRefStore {
+    dir: PathBuf,
+}
+
+impl StatusRefStore {
+    pub fn new(telos_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: telos_dir.into().join("refs").join("constraints"),
+        }
+    }
+
+    fn path(&self, base_id: &ObjectId) -> PathBuf {
+        self.dir.join(format!("{}.json", base_id.hex()))
+    }
+
+    pub fn load(&self, base_id: &ObjectId) -> Result<Option<StatusRef>, StoreError> {
+        let path = self.path(base_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    pub fn save(&self, base_id: &ObjectId, status_ref: &StatusRef) -> Result<(), StoreError> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.path(base_id), serde_json::to_string_pretty(status_ref)?)?;
+        Ok(())
+    }
+
+    /// Every base constraint id with a status ref on disk.
+    pub fn list_ids(&self) -> Result<Vec<ObjectId>, StoreError> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(stem) = name.to_str().and_then(|n| n.strip_suffix(".json")) else {
+                continue;
+            };
+            ids.push(ObjectId::parse(stem).map_err(StoreError::Core)?);
+        }
+        Ok(ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oid(seed: &[u8]) -> ObjectId {
+        ObjectId::hash(seed)
+    }
+
+    #[test]
+    fn dominates_is_reflexive_and_entrywise() {
+        let mut a = VersionVector::new();
+        a.bump("alice");
+        assert!(a.dominates(&a));
+
+        let mut b = a.clone();
+        b.bump("bob");
+        assert!(b.dominates(&a));
+        assert!(!a.dominates(&b));
+    }
+
+    #[test]
+    fn concurrent_when_neither_side_has_seen_the_other() {
+        let mut a = VersionVector::new();
+        a.bump("alice");
+        let mut b = VersionVector::new();
+        b.bump("bob");
+        assert!(a.concurrent_with(&b));
+        assert!(b.concurrent_with(&a));
+    }
+
+    #[test]
+    fn merge_fast_forwards_when_one_side_dominates() {
+        let base = StatusRef::new(oid(b"c1"), ConstraintStatus::Active, "alice");
+        let mut ahead = base.clone();
+        ahead.status = ConstraintStatus::Deprecated;
+        ahead.vector.bump("alice");
+
+        let merged = base.merge(&ahead);
+        assert_eq!(merged.status, ConstraintStatus::Deprecated);
+        assert!(merged.merge_notes.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_higher_vector_and_notes_the_loser() {
+        let mut local = StatusRef::new(oid(b"local-copy"), ConstraintStatus::Deprecated, "alice");
+        local.vector.bump("alice");
+        let remote = StatusRef::new(oid(b"remote-copy"), ConstraintStatus::Active, "bob");
+
+        let merged = local.merge(&remote);
+        assert_eq!(merged.current, local.current);
+        assert_eq!(merged.merge_notes.len(), 1);
+        assert_eq!(merged.merge_notes[0].losing_current, remote.current);
+        assert!(merged.vector.dominates(&local.vector));
+        assert!(merged.vector.dominates(&remote.vector));
+    }
+
+    #[test]
+    fn merge_is_commutative_for_the_winner() {
+        let mut local = StatusRef::new(oid(b"local-copy"), ConstraintStatus::Deprecated, "alice");
+        local.vector.bump("alice");
+        let remote = StatusRef::new(oid(b"remote-copy"), ConstraintStatus::Active, "bob");
+
+        assert_eq!(local.merge(&remote).current, remote.merge(&local).current);
+    }
+}
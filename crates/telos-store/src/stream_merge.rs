@@ -0,0 +1,390 @@
+//! Three-way merge between two local intent streams.
+//!
+//! `stream::{create,switch,delete}` give branch-like pointers, but until now
+//! there was no way to bring two streams back together: [`StreamConflict`]
+//! sat unused as a placeholder. This finds the merge base by intersecting
+//! both tips' [`crate::sync::ancestors`] sets (the same ancestor walk
+//! `sync::merge_stream` uses for its fast-forward check), classifies what
+//! each side added since that base, and either fast-forwards, creates a
+//! two-parent merge marker intent (exactly like `sync::merge_stream`'s
+//! divergent case), or reports [`StreamConflict`]s and aborts.
+//!
+//! This is deliberately the *local* counterpart to [`crate::sync`]'s
+//! network merge: `sync::merge_stream` reconciles a remote-tracking tip
+//! fetched over a [`crate::remote::RemoteBackend`]; `merge_streams` here
+//! reconciles two refs already in the same repository (no fetch, no
+//! remote-tracking state).
+
+use crate::error::StoreError;
+use crate::repository::Repository;
+use crate::sync::ancestors;
+use std::collections::HashSet;
+use telos_core::hash::ObjectId;
+use telos_core::object::constraint::Constraint;
+use telos_core::object::intent_stream::StreamConflict;
+use telos_core::object::{Intent, TelosObject};
+
+/// The outcome of merging `source` into the current stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamMergeOutcome {
+    /// The current stream's tip already contains `source`'s tip.
+    AlreadyUpToDate,
+    /// `source`'s tip is a descendant of the current tip; the current
+    /// stream was fast-forwarded to it.
+    FastForward(ObjectId),
+    /// Histories diverged with no conflicts; a two-parent merge marker
+    /// intent was created, the current stream advanced to it, and an
+    /// [`telos_core::object::IntentStreamSnapshot`] recording the merge
+    /// (with `parent_stream` set to `source`) was written.
+    Merged(ObjectId),
+    /// Histories diverged and at least one conflict was found. Nothing was
+    /// written; resolve the conflicts (e.g. by superseding one side's
+    /// constraint) and merge again.
+    Conflict(Vec<StreamConflict>),
+}
+
+/// The merge base: the most recent intent common to both ancestor sets.
+/// Multiple maximal common ancestors are possible in a DAG with more than
+/// one fork; picking the one with the latest timestamp is a heuristic (not
+/// a guaranteed unique LCA) but matches the common case of a single fork
+/// point, and an empty intersection (unrelated histories) just means
+/// "everything on both sides counts as new".
+fn merge_base(
+    repo: &Repository,
+    local_ancestors: &HashSet<ObjectId>,
+    remote_ancestors: &HashSet<ObjectId>,
+) -> Result<Option<ObjectId>, StoreError> {
+    let mut best: Option<(chrono::DateTime<chrono::Utc>, ObjectId)> = None;
+    for id in local_ancestors.intersection(remote_ancestors) {
+        let TelosObject::Intent(intent) = repo.odb.read(id)? else {
+            continue;
+        };
+        let replace = match &best {
+            Some((ts, _)) => intent.timestamp > *ts,
+            None => true,
+        };
+        if replace {
+            best = Some((intent.timestamp, id.clone()));
+        }
+    }
+    Ok(best.map(|(_, id)| id))
+}
+
+/// Intents added on one side: everything in `side_ancestors` that isn't in
+/// `base_ancestors`.
+fn new_on_side(side_ancestors: &HashSet<ObjectId>, base_ancestors: &HashSet<ObjectId>) -> HashSet<ObjectId> {
+    side_ancestors.difference(base_ancestors).cloned().collect()
+}
+
+/// Constraints whose `source_intent` was added on this side.
+fn constraints_on_side(
+    repo: &Repository,
+    new_intents: &HashSet<ObjectId>,
+) -> Result<Vec<(ObjectId, Constraint)>, StoreError> {
+    let mut out = Vec::new();
+    for (id, obj) in repo.odb.iter_all()? {
+        if let TelosObject::Constraint(c) = obj {
+            if new_intents.contains(&c.source_intent) {
+                out.push((id, c));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Classify what changed on each side relative to the merge base and look
+/// for conflicts: intents that target overlapping `impacts` areas, or
+/// constraints on the same `source_intent` left in contradictory statuses.
+/// Only intents' `impacts` are compared; a constraint's own `impacts` are
+/// not.
+fn detect_conflicts(
+    repo: &Repository,
+    current_name: &str,
+    source_name: &str,
+    local_new_intents: &HashSet<ObjectId>,
+    remote_new_intents: &HashSet<ObjectId>,
+) -> Result<Vec<StreamConflict>, StoreError> {
+    let mut conflicts = Vec::new();
+
+    let mut local_impacts: Vec<(ObjectId, Vec<String>)> = Vec::new();
+    let mut remote_impacts: Vec<(ObjectId, Vec<String>)> = Vec::new();
+    for id in local_new_intents {
+        if let TelosObject::Intent(intent) = repo.odb.read(id)? {
+            local_impacts.push((id.clone(), intent.impacts));
+        }
+    }
+    for id in remote_new_intents {
+        if let TelosObject::Intent(intent) = repo.odb.read(id)? {
+            remote_impacts.push((id.clone(), intent.impacts));
+        }
+    }
+
+    for (local_id, local_tags) in &local_impacts {
+        for (remote_id, remote_tags) in &remote_impacts {
+            let overlap: Vec<&String> = local_tags.iter().filter(|t| remote_tags.contains(t)).collect();
+            if !overlap.is_empty() {
+                let tags = overlap.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", ");
+                conflicts.push(StreamConflict {
+                    stream_a: current_name.to_string(),
+                    stream_b: source_name.to_string(),
+                    conflicting_intents: vec![local_id.clone(), remote_id.clone()],
+                    description: format!(
+                        "intent {} ('{}') and intent {} ('{}') both target impact area(s): {}",
+                        local_id.short(),
+                        current_name,
+                        remote_id.short(),
+                        source_name,
+                        tags
+                    ),
+                });
+            }
+        }
+    }
+
+    let local_constraints = constraints_on_side(repo, local_new_intents)?;
+    let remote_constraints = constraints_on_side(repo, remote_new_intents)?;
+    for (local_id, local_c) in &local_constraints {
+        for (remote_id, remote_c) in &remote_constraints {
+            if local_c.source_intent == remote_c.source_intent && local_c.status != remote_c.status {
+                conflicts.push(StreamConflict {
+                    stream_a: current_name.to_string(),
+                    stream_b: source_name.to_string(),
+                    conflicting_intents: vec![local_id.clone(), remote_id.clone()],
+                    description: format!(
+                        "constraint {} left status {:?} on stream '{}' but constraint {} left status {:?} \
+                         on stream '{}' for the same source intent {}",
+                        local_id.short(),
+                        local_c.status,
+                        current_name,
+                        remote_id.short(),
+                        remote_c.status,
+                        source_name,
+                        local_c.source_intent.short(),
+                    ),
+                });
+            }
+        }
+    }
+
+    Ok(conflicts)
+}
+
+/// Merge `source` into the current stream.
+pub fn merge_streams(repo: &Repository, source: &str) -> Result<StreamMergeOutcome, StoreError> {
+    let current = repo.refs.current_stream()?;
+    let source_ref = repo.refs.read_stream(source)?;
+
+    let Some(source_head) = source_ref.tip else {
+        return Ok(StreamMergeOutcome::AlreadyUpToDate);
+    };
+    let Some(local_head) = current.tip else {
+        repo.refs.update_current_tip_cas(None, source_head.clone())?;
+        return Ok(StreamMergeOutcome::FastForward(source_head));
+    };
+    if local_head == source_head {
+        return Ok(StreamMergeOutcome::AlreadyUpToDate);
+    }
+
+    let local_ancestors = ancestors(repo, &local_head)?;
+    if local_ancestors.contains(&source_head) {
+        return Ok(StreamMergeOutcome::AlreadyUpToDate);
+    }
+    let remote_ancestors = ancestors(repo, &source_head)?;
+    if remote_ancestors.contains(&local_head) {
+        repo.refs
+            .update_current_tip_cas(Some(local_head.clone()), source_head.clone())?;
+        return Ok(StreamMergeOutcome::FastForward(source_head));
+    }
+
+    let base_ancestors = match merge_base(repo, &local_ancestors, &remote_ancestors)? {
+        Some(base_id) => ancestors(repo, &base_id)?,
+        None => HashSet::new(),
+    };
+    let local_new = new_on_side(&local_ancestors, &base_ancestors);
+    let remote_new = new_on_side(&remote_ancestors, &base_ancestors);
+
+    let conflicts = detect_conflicts(repo, &current.name, source, &local_new, &remote_new)?;
+    if !conflicts.is_empty() {
+        return Ok(StreamMergeOutcome::Conflict(conflicts));
+    }
+
+    let config = repo.telos_config()?;
+    let resolved_author = config.resolve_author(None, None, None);
+    let marker = Intent {
+        author: telos_core::object::intent::Author {
+            name: resolved_author.name,
+            email: resolved_author.email,
+        },
+        timestamp: chrono::Utc::now(),
+        statement: format!("Merge stream '{}' into '{}'", source, current.name),
+        constraints: vec![],
+        behavior_spec: vec![],
+        parents: vec![local_head.clone(), source_head],
+        impacts: vec![],
+        behavior_diff: None,
+        metadata: std::collections::HashMap::from([("merge".to_string(), serde_json::json!(true))]),
+    };
+    // Expected tip is the local side being advanced, same CAS protection as
+    // the fast-forward branch above — closes the race where a concurrent
+    // local write lands between the merge decision and this write.
+    let marker_id = repo.create_intent_advancing(marker, Some(local_head))?;
+
+    let snapshot = telos_core::object::IntentStreamSnapshot {
+        name: current.name.clone(),
+        tip: marker_id.clone(),
+        created_at: chrono::Utc::now(),
+        description: Some(format!("merge of '{}' into '{}'", source, current.name)),
+        parent_stream: Some(source.to_string()),
+    };
+    repo.odb.write(&TelosObject::IntentStreamSnapshot(snapshot))?;
+
+    Ok(StreamMergeOutcome::Merged(marker_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use telos_core::object::constraint::{ConstraintSeverity, ConstraintStatus};
+    use telos_core::object::intent::Author;
+
+    fn make_intent(statement: &str, impacts: Vec<&str>, parents: Vec<ObjectId>) -> Intent {
+        Intent {
+            author: Author { name: "Test".into(), email: "test@test.com".into() },
+            timestamp: chrono::Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: vec![],
+            parents,
+            impacts: impacts.into_iter().map(String::from).collect(),
+            behavior_diff: None,
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    fn make_constraint(source_intent: ObjectId, statement: &str, status: ConstraintStatus) -> Constraint {
+        Constraint {
+            author: Author { name: "Test".into(), email: "test@test.com".into() },
+            timestamp: chrono::Utc::now(),
+            statement: statement.into(),
+            severity: ConstraintSeverity::Must,
+            status,
+            source_intent,
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: std::collections::HashMap::new(),
+        }
+    }
+
+    fn new_stream(repo: &Repository, name: &str, tip: Option<ObjectId>) {
+        repo.refs
+            .create_stream(&telos_core::object::intent_stream::IntentStreamRef {
+                name: name.to_string(),
+                tip,
+                created_at: chrono::Utc::now(),
+                description: None,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn fast_forwards_when_source_is_strictly_ahead() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![], vec![])).unwrap();
+        new_stream(&repo, "feature", Some(root.clone()));
+        repo.refs.set_head("feature").unwrap();
+        let child = repo
+            .create_intent(make_intent("Child", vec![], vec![root.clone()]))
+            .unwrap();
+        repo.refs.set_head("main").unwrap();
+        repo.refs.write_stream(&telos_core::object::intent_stream::IntentStreamRef {
+            name: "main".into(),
+            tip: Some(root.clone()),
+            created_at: chrono::Utc::now(),
+            description: None,
+        }).unwrap();
+
+        let outcome = merge_streams(&repo, "feature").unwrap();
+        assert_eq!(outcome, StreamMergeOutcome::FastForward(child.clone()));
+        assert_eq!(repo.refs.current_stream().unwrap().tip, Some(child));
+    }
+
+    #[test]
+    fn merges_divergent_streams_without_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![], vec![])).unwrap();
+        repo.refs.write_stream(&telos_core::object::intent_stream::IntentStreamRef {
+            name: "main".into(),
+            tip: Some(root.clone()),
+            created_at: chrono::Utc::now(),
+            description: None,
+        }).unwrap();
+        new_stream(&repo, "feature", Some(root.clone()));
+
+        repo.refs.set_head("feature").unwrap();
+        repo.create_intent(make_intent("Feature work", vec!["ui"], vec![root.clone()])).unwrap();
+        repo.refs.set_head("main").unwrap();
+        repo.create_intent(make_intent("Main work", vec!["backend"], vec![root])).unwrap();
+
+        let outcome = merge_streams(&repo, "feature").unwrap();
+        assert!(matches!(outcome, StreamMergeOutcome::Merged(_)));
+    }
+
+    #[test]
+    fn reports_conflict_on_overlapping_impacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![], vec![])).unwrap();
+        repo.refs.write_stream(&telos_core::object::intent_stream::IntentStreamRef {
+            name: "main".into(),
+            tip: Some(root.clone()),
+            created_at: chrono::Utc::now(),
+            description: None,
+        }).unwrap();
+        new_stream(&repo, "feature", Some(root.clone()));
+
+        repo.refs.set_head("feature").unwrap();
+        repo.create_intent(make_intent("Feature auth work", vec!["auth"], vec![root.clone()])).unwrap();
+        repo.refs.set_head("main").unwrap();
+        repo.create_intent(make_intent("Main auth work", vec!["auth"], vec![root])).unwrap();
+
+        let outcome = merge_streams(&repo, "feature").unwrap();
+        assert!(matches!(outcome, StreamMergeOutcome::Conflict(ref c) if !c.is_empty()));
+    }
+
+    #[test]
+    fn reports_conflict_on_contradictory_constraint_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![], vec![])).unwrap();
+        repo.refs.write_stream(&telos_core::object::intent_stream::IntentStreamRef {
+            name: "main".into(),
+            tip: Some(root.clone()),
+            created_at: chrono::Utc::now(),
+            description: None,
+        }).unwrap();
+        new_stream(&repo, "feature", Some(root.clone()));
+
+        repo.refs.set_head("feature").unwrap();
+        let feature_intent = repo
+            .create_intent(make_intent("Shared target", vec![], vec![root.clone()]))
+            .unwrap();
+        repo.create_constraint(make_constraint(feature_intent.clone(), "Must validate input", ConstraintStatus::Active))
+            .unwrap();
+        repo.refs.set_head("main").unwrap();
+        let main_intent = repo
+            .create_intent(make_intent("Main side work", vec![], vec![root]))
+            .unwrap();
+        // Reuse the same source_intent across streams to trigger the
+        // "contradictory status on the same source intent" rule.
+        repo.create_constraint(make_constraint(feature_intent, "Must validate input", ConstraintStatus::Deprecated))
+            .unwrap();
+        let _ = main_intent;
+
+        let outcome = merge_streams(&repo, "feature").unwrap();
+        assert!(matches!(outcome, StreamMergeOutcome::Conflict(ref c) if !c.is_empty()));
+    }
+}
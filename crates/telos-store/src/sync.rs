@@ -0,0 +1,613 @@
+//! Push/pull sync between a local repository and a [`RemoteBackend`].
+//!
+//! Both directions are a set-difference followed by idempotent transfer:
+//! enumerate one side's ids, ask (or check) what the other side is missing,
+//! then copy only those objects. Integrity is verified exactly as it is for
+//! local reads — `ObjectDatabase::write` recomputes the hash of anything
+//! pulled in before accepting it.
+//!
+//! [`fetch`] additionally records the remote's stream tip, and its full set
+//! of known object ids, as remote-tracking state (`refs/remotes/<name>/*`)
+//! without touching the local stream — mirroring `git fetch`. [`merge_stream`]
+//! then decides what to do with that tip: fast-forward the stream if one
+//! head is a (transitive) ancestor of the other (walking `Intent::parents`,
+//! which already form a DAG), or create a two-parent merge marker `Intent` —
+//! a normal, append-only intent, since intents never conflict.
+//!
+//! The one real conflict class is two sides independently superseding the
+//! same constraint. Constraints aren't linked into the intent DAG (a
+//! superseded copy carries the same `source_intent` as the original, not the
+//! intent that superseded it), so ancestor-walking can't tell which side
+//! produced a given copy. Instead [`detect_constraint_conflicts`] uses the
+//! remote-known-object-ids recorded by `fetch`: a superseded copy the remote
+//! already knows about came from there; one it doesn't is ours. Pairs that
+//! share the same original statement but disagree on what replaced it are
+//! reported, and [`merge_stream`] refuses to create a merge marker until
+//! every such conflict has been settled via
+//! [`crate::repository::Repository::resolve_constraint_conflict`].
+
+use crate::error::StoreError;
+use crate::remote::RemoteBackend;
+use crate::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use telos_core::hash::ObjectId;
+use telos_core::object::constraint::{Constraint, ConstraintStatus};
+use telos_core::object::TelosObject;
+
+/// Object ids already known to be present on the other side of a repo-to-repo
+/// sync, as reported during have/want negotiation. See
+/// [`Repository::missing_objects`].
+pub type HaveSet = HashSet<ObjectId>;
+
+/// Upload every local object the remote doesn't already have.
+/// Returns the number of objects uploaded.
+pub fn push(repo: &Repository, remote: &dyn RemoteBackend) -> Result<usize, StoreError> {
+    let local = repo.odb.iter_all()?;
+    let local_ids: Vec<_> = local.iter().map(|(id, _)| id.clone()).collect();
+
+    // When the remote can offer a HaveFilter, test every candidate id
+    // against it locally instead of shipping the whole local id list to
+    // `has` and waiting on an exact response — the round-trip shrink
+    // `HaveFilter` exists for (see crate::bloom). Fall back to the exact
+    // negotiation for a remote that doesn't support one.
+    let remote_has: HaveSet = match remote.have_filter()? {
+        Some(filter) => local_ids.iter().filter(|id| filter.contains(id)).cloned().collect(),
+        None => remote.has(&local_ids)?,
+    };
+
+    let mut uploaded = 0;
+    for (id, _) in &local {
+        if remote_has.contains(id) {
+            continue;
+        }
+        // Upload the exact bytes that hash to `id`, not a fresh
+        // `canonical_bytes()` re-serialization — the two can differ if the
+        // object was written under a non-default `ContentFormat` (see
+        // `ObjectDatabase::content_format`), which would otherwise trip the
+        // receiving side's post-download rehash check in `pull`.
+        remote.upload(id, &repo.odb.read_verified_bytes(id)?)?;
+        uploaded += 1;
+    }
+    Ok(uploaded)
+}
+
+/// Download every remote object the local store doesn't already have.
+/// Returns the number of objects pulled.
+pub fn pull(repo: &Repository, remote: &dyn RemoteBackend) -> Result<usize, StoreError> {
+    let remote_ids = remote.list_ids()?;
+
+    let mut pulled = 0;
+    for id in &remote_ids {
+        if repo.odb.exists(id) {
+            continue;
+        }
+        let bytes = remote.download(id)?;
+        let obj = TelosObject::from_canonical_bytes(&bytes)?;
+        let written_id = repo.odb.write(&obj)?;
+        if &written_id != id {
+            return Err(StoreError::RemoteSyncError(format!(
+                "remote object {} rehashed to {} after download",
+                id, written_id
+            )));
+        }
+        pulled += 1;
+    }
+    Ok(pulled)
+}
+
+/// Reconcile every local constraint status ref (see [`crate::status_ref`])
+/// with its counterpart on `remote`, in both directions: each side's
+/// [`crate::status_ref::StatusRef`] is merged by causal version vector and
+/// the result written back to both. Returns the base constraint ids whose
+/// merge produced a new [`crate::status_ref::MergeNote`] (a concurrent,
+/// conflicting status change), so a caller can surface them instead of the
+/// reconciliation passing silently.
+pub fn sync_status_refs(repo: &Repository, remote: &dyn RemoteBackend) -> Result<Vec<ObjectId>, StoreError> {
+    let mut conflicted = Vec::new();
+    for base_id in repo.status_refs.list_ids()? {
+        let Some(local) = repo.status_refs.load(&base_id)? else {
+            continue;
+        };
+        let merged = match remote.get_status_ref(&base_id)? {
+            Some(bytes) => {
+                let remote_ref: crate::status_ref::StatusRef = serde_json::from_slice(&bytes)?;
+                let notes_before = local.merge_notes.len();
+                let merged = repo.merge_status_ref(&base_id, &remote_ref)?;
+                if merged.merge_notes.len() > notes_before {
+                    conflicted.push(base_id.clone());
+                }
+                merged
+            }
+            None => local,
+        };
+        remote.set_status_ref(&base_id, &serde_json::to_vec(&merged)?)?;
+    }
+    Ok(conflicted)
+}
+
+/// Download every object the remote has that the local store doesn't, and
+/// record the remote's current object set and stream tip as remote-tracking
+/// state (`refs/remotes/<name>/*`). Does not touch the local stream tip —
+/// that decision belongs to [`merge_stream`].
+pub fn fetch(
+    repo: &Repository,
+    remote_name: &str,
+    remote: &dyn RemoteBackend,
+    stream: &str,
+) -> Result<usize, StoreError> {
+    let pulled = self::pull(repo, remote)?;
+
+    let remote_ids: HashSet<ObjectId> = remote.list_ids()?.into_iter().collect();
+    repo.refs.write_remote_objects(remote_name, &remote_ids)?;
+
+    if let Some(head) = remote.get_stream_head(stream)? {
+        repo.refs.write_remote_head(remote_name, stream, &head)?;
+    }
+    Ok(pulled)
+}
+
+/// The full set of intents reachable from `head` (including itself), via
+/// `Intent::parents`. Used for the fast-forward check below, and (as
+/// [`crate::stream_merge`]'s merge-base search) for local stream-to-stream
+/// merges too.
+pub(crate) fn ancestors(repo: &Repository, head: &ObjectId) -> Result<HashSet<ObjectId>, StoreError> {
+    let mut seen = HashSet::new();
+    for result in repo.walk_intents(head) {
+        let (id, _) = result?;
+        seen.insert(id);
+    }
+    Ok(seen)
+}
+
+/// The outcome of merging a remote-tracking stream tip into the local
+/// stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeOutcome {
+    /// The local tip already contains the remote tip; nothing to do.
+    AlreadyUpToDate,
+    /// The remote tip is a descendant of the local tip; the local stream
+    /// was fast-forwarded to it.
+    FastForward(ObjectId),
+    /// Histories diverged with no conflicting constraints; a two-parent
+    /// merge marker intent was created and the local stream updated to it.
+    Merged(ObjectId),
+    /// Histories diverged and both sides superseded the same constraint
+    /// differently. The merge was not performed; resolve every conflict
+    /// with `Repository::resolve_constraint_conflict`, then merge again.
+    Conflict(Vec<ConstraintConflict>),
+}
+
+/// Which side's replacement to keep when resolving a [`ConstraintConflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    Local,
+    Remote,
+}
+
+/// Two independent, differing replacements of the same original constraint.
+/// `base_statement` identifies the constraint both sides superseded;
+/// `local_superseded_copy`/`remote_superseded_copy` are the two superseded
+/// copies each side created (see `commands::supersede` for why superseding
+/// writes a new immutable copy rather than mutating the original), and
+/// `local_replacement`/`remote_replacement` are what each copy says it was
+/// replaced by.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstraintConflict {
+    pub base_statement: String,
+    pub local_superseded_copy: ObjectId,
+    pub local_replacement: ObjectId,
+    pub remote_superseded_copy: ObjectId,
+    pub remote_replacement: ObjectId,
+}
+
+/// A key identifying "the same original constraint" across two superseded
+/// copies: everything about a superseded copy except its own identity
+/// (timestamp/author) and what it was replaced by, since supersede always
+/// clones the original's content before marking it superseded.
+fn conflict_key(c: &Constraint) -> (String, String, ObjectId, Vec<ObjectId>, Vec<String>) {
+    (
+        c.statement.clone(),
+        format!("{:?}", c.severity),
+        c.source_intent.clone(),
+        c.scope.clone(),
+        c.impacts.clone(),
+    )
+}
+
+/// Persisted record of which [`ConstraintConflict`]s have already been
+/// settled via `Repository::resolve_constraint_conflict`, keyed by the pair
+/// of superseded-copy ids involved — so re-running a merge after a
+/// resolution doesn't keep re-reporting the same conflict forever (the
+/// superseded copies that triggered it are immutable and never go away).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResolvedConflicts {
+    #[serde(default)]
+    resolved: BTreeSet<(String, String)>,
+}
+
+impl ResolvedConflicts {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StoreError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StoreError> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn key(conflict: &ConstraintConflict) -> (String, String) {
+        (
+            conflict.local_superseded_copy.hex().to_string(),
+            conflict.remote_superseded_copy.hex().to_string(),
+        )
+    }
+
+    pub fn mark_resolved(&mut self, conflict: &ConstraintConflict) {
+        self.resolved.insert(Self::key(conflict));
+    }
+
+    pub fn is_resolved(&self, conflict: &ConstraintConflict) -> bool {
+        self.resolved.contains(&Self::key(conflict))
+    }
+}
+
+/// Find pairs of superseded-constraint copies that share a [`conflict_key`]
+/// but point (`superseded_by`) at different replacements — i.e. both sides
+/// independently superseded the same original constraint. A copy counts as
+/// "remote" if it's in `remote_known` (the ids `fetch` last recorded the
+/// remote as holding), "local" otherwise. Already-[`ResolvedConflicts`]
+/// pairs are skipped.
+pub fn detect_constraint_conflicts(
+    repo: &Repository,
+    remote_known: &HashSet<ObjectId>,
+    resolved: &ResolvedConflicts,
+) -> Result<Vec<ConstraintConflict>, StoreError> {
+    let mut local_superseded: HashMap<_, (ObjectId, Constraint)> = HashMap::new();
+    let mut remote_superseded: HashMap<_, (ObjectId, Constraint)> = HashMap::new();
+
+    for (id, obj) in repo.odb.iter_all()? {
+        let TelosObject::Constraint(c) = obj else {
+            continue;
+        };
+        if c.status != ConstraintStatus::Superseded {
+            continue;
+        }
+        let key = conflict_key(&c);
+        if remote_known.contains(&id) {
+            remote_superseded.insert(key, (id, c));
+        } else {
+            local_superseded.insert(key, (id, c));
+        }
+    }
+
+    let mut conflicts = Vec::new();
+    for (key, (local_id, local_c)) in &local_superseded {
+        let Some((remote_id, remote_c)) = remote_superseded.get(key) else {
+            continue;
+        };
+        if local_c.superseded_by == remote_c.superseded_by {
+            continue;
+        }
+        let conflict = ConstraintConflict {
+            base_statement: key.0.clone(),
+            local_superseded_copy: local_id.clone(),
+            local_replacement: local_c
+                .superseded_by
+                .clone()
+                .unwrap_or_else(|| local_id.clone()),
+            remote_superseded_copy: remote_id.clone(),
+            remote_replacement: remote_c
+                .superseded_by
+                .clone()
+                .unwrap_or_else(|| remote_id.clone()),
+        };
+        if resolved.is_resolved(&conflict) {
+            continue;
+        }
+        conflicts.push(conflict);
+    }
+    Ok(conflicts)
+}
+
+/// Merge `stream`'s remote-tracking tip (as last recorded by [`fetch`]) into
+/// the local stream: fast-forward if possible, otherwise check for
+/// constraint conflicts and either report them or create a merge marker.
+///
+/// Both the fast-forward tip updates and the divergent-merge marker write
+/// go through [`crate::refs::RefStore::update_current_tip_cas`] (the latter
+/// via [`Repository::create_intent_advancing`]) with the tip just read from
+/// `local_ref` as the expected value, so a concurrent local write that
+/// lands between that read and this write is caught as a
+/// [`StoreError::LockConflict`] instead of being silently overwritten —
+/// the caller sees a plain error and can re-run the pull.
+pub fn merge_stream(
+    repo: &Repository,
+    remote_name: &str,
+    stream: &str,
+) -> Result<MergeOutcome, StoreError> {
+    let Some(remote_head) = repo.refs.read_remote_head(remote_name, stream)? else {
+        return Ok(MergeOutcome::AlreadyUpToDate);
+    };
+    let local_ref = repo.refs.read_stream(stream)?;
+    let Some(local_head) = local_ref.tip else {
+        repo.refs.update_current_tip_cas(None, remote_head.clone())?;
+        return Ok(MergeOutcome::FastForward(remote_head));
+    };
+    if local_head == remote_head {
+        return Ok(MergeOutcome::AlreadyUpToDate);
+    }
+
+    let local_ancestors = ancestors(repo, &local_head)?;
+    if local_ancestors.contains(&remote_head) {
+        return Ok(MergeOutcome::AlreadyUpToDate);
+    }
+    let remote_ancestors = ancestors(repo, &remote_head)?;
+    if remote_ancestors.contains(&local_head) {
+        repo.refs
+            .update_current_tip_cas(Some(local_head.clone()), remote_head.clone())?;
+        return Ok(MergeOutcome::FastForward(remote_head));
+    }
+
+    let remote_known = repo.refs.read_remote_objects(remote_name)?;
+    let resolved = repo.resolved_conflicts()?;
+    let conflicts = detect_constraint_conflicts(repo, &remote_known, &resolved)?;
+    if !conflicts.is_empty() {
+        return Ok(MergeOutcome::Conflict(conflicts));
+    }
+
+    let config = repo.telos_config()?;
+    let resolved_author = config.resolve_author(None, None, None);
+    let marker = telos_core::object::Intent {
+        author: telos_core::object::intent::Author {
+            name: resolved_author.name,
+            email: resolved_author.email,
+        },
+        timestamp: chrono::Utc::now(),
+        statement: format!("Merge stream '{}' from remote '{}'", stream, remote_name),
+        constraints: vec![],
+        behavior_spec: vec![],
+        parents: vec![local_head.clone(), remote_head],
+        impacts: vec![],
+        behavior_diff: None,
+        metadata: HashMap::from([("merge".to_string(), serde_json::json!(true))]),
+    };
+    // Same CAS protection as the fast-forward branches above: the expected
+    // tip is the local side this merge marker is advancing from, so a
+    // concurrent local write that lands first is caught as a LockConflict
+    // instead of being silently clobbered.
+    let marker_id = repo.create_intent_advancing(marker, Some(local_head))?;
+    Ok(MergeOutcome::Merged(marker_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::remote::FsRemote;
+    use chrono::Utc;
+    use telos_core::object::constraint::ConstraintSeverity;
+    use telos_core::object::intent::{Author, BehaviorClause};
+    use telos_core::object::{Constraint as ConstraintObj, Intent};
+
+    fn make_intent(statement: &str, parents: Vec<ObjectId>) -> Intent {
+        Intent {
+            author: Author {
+                name: "Test".into(),
+                email: "test@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: Vec::<BehaviorClause>::new(),
+            parents,
+            impacts: vec![],
+            behavior_diff: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn make_constraint(source_intent: ObjectId, statement: &str) -> ConstraintObj {
+        ConstraintObj {
+            author: Author {
+                name: "Test".into(),
+                email: "test@example.com".into(),
+            },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Active,
+            source_intent,
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Clone of `commands::supersede`'s behavior, so tests don't depend on
+    /// the CLI crate: write a new `Active` replacement, then a separate
+    /// `Superseded` copy of the original pointing at it.
+    fn supersede(repo: &Repository, original: &ConstraintObj, new_statement: &str) -> (ObjectId, ObjectId) {
+        let mut replacement = original.clone();
+        replacement.statement = new_statement.into();
+        let replacement_id = repo.create_constraint(replacement).unwrap();
+
+        let mut superseded = original.clone();
+        superseded.status = ConstraintStatus::Superseded;
+        superseded.superseded_by = Some(replacement_id.clone());
+        let superseded_id = repo.create_constraint(superseded).unwrap();
+
+        (superseded_id, replacement_id)
+    }
+
+    #[test]
+    fn fetch_and_merge_fast_forwards_when_remote_is_ahead() {
+        let local_dir = tempfile::tempdir().unwrap();
+        let local = Repository::init(local_dir.path()).unwrap();
+        let root = local.create_intent(make_intent("Root", vec![])).unwrap();
+
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(remote_dir.path());
+        push(&local, &remote).unwrap();
+
+        // Simulate another clone committing a child of root and publishing it.
+        let child_bytes = TelosObject::Intent(make_intent("Child", vec![root.clone()]))
+            .canonical_bytes()
+            .unwrap();
+        let child = ObjectId::hash(&child_bytes);
+        remote.upload(&child, &child_bytes).unwrap();
+        remote.set_stream_head("main", &child).unwrap();
+
+        fetch(&local, "origin", &remote, "main").unwrap();
+        let outcome = merge_stream(&local, "origin", "main").unwrap();
+        assert_eq!(outcome, MergeOutcome::FastForward(child.clone()));
+        assert_eq!(local.refs.current_stream().unwrap().tip, Some(child));
+    }
+
+    /// A `fetch` pulls an object in and records it as remote-tracking state
+    /// before it's part of any local stream. A `gc --prune` run in that
+    /// window must not collect it as unreferenced, or the `merge_stream`
+    /// that follows fails reading a parent that's simply gone. Mirrors
+    /// `telos-cli`'s `gc` command's root-gathering rather than calling
+    /// `repo.odb.gc` with only stream tips, since that's the bug this guards
+    /// against.
+    #[test]
+    fn gc_after_fetch_keeps_objects_a_pending_merge_still_needs() {
+        let local_dir = tempfile::tempdir().unwrap();
+        let local = Repository::init(local_dir.path()).unwrap();
+        local.set_remote("origin", "unused://", None).unwrap();
+        let root = local.create_intent(make_intent("Root", vec![])).unwrap();
+
+        let remote_dir = tempfile::tempdir().unwrap();
+        let remote = FsRemote::new(remote_dir.path());
+        push(&local, &remote).unwrap();
+
+        let child_bytes = TelosObject::Intent(make_intent("Child", vec![root.clone()]))
+            .canonical_bytes()
+            .unwrap();
+        let child = ObjectId::hash(&child_bytes);
+        remote.upload(&child, &child_bytes).unwrap();
+        remote.set_stream_head("main", &child).unwrap();
+
+        fetch(&local, "origin", &remote, "main").unwrap();
+        assert!(local.odb.exists(&child));
+
+        // Same root-gathering `telos-cli`'s `gc --prune` does: local stream
+        // tips, plus every remote-tracking head and known-object id.
+        let mut roots = vec![root.clone()];
+        for name in local.refs.list_streams().unwrap() {
+            if let Some(head) = local.refs.read_remote_head("origin", &name).unwrap() {
+                roots.push(head);
+            }
+        }
+        for remote_name in local.list_remotes().unwrap() {
+            roots.extend(local.refs.read_remote_objects(&remote_name).unwrap());
+        }
+        let mut odb = local.odb;
+        odb.gc(&roots).unwrap();
+
+        assert!(odb.exists(&child), "fetched-but-unmerged object was collected as unreferenced");
+        assert!(odb.exists(&root));
+    }
+
+    #[test]
+    fn merge_creates_marker_when_histories_diverge_without_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let local_tip = repo.create_intent(make_intent("Local work", vec![root.clone()])).unwrap();
+
+        // Simulate a remote branch that diverged from the same root.
+        let remote_tip = repo.odb.write(&TelosObject::Intent(make_intent("Remote work", vec![root]))).unwrap();
+        repo.refs.write_remote_head("origin", "main", &remote_tip).unwrap();
+        repo.refs.write_remote_objects("origin", &HashSet::from([remote_tip.clone()])).unwrap();
+        repo.refs.update_current_tip(local_tip.clone()).unwrap();
+
+        let outcome = merge_stream(&repo, "origin", "main").unwrap();
+        let MergeOutcome::Merged(marker_id) = outcome else {
+            panic!("expected Merged, got {:?}", outcome);
+        };
+        let TelosObject::Intent(marker) = repo.odb.read(&marker_id).unwrap() else {
+            panic!("expected marker to be an Intent");
+        };
+        assert_eq!(marker.parents, vec![local_tip, remote_tip]);
+        assert_eq!(repo.refs.current_stream().unwrap().tip, Some(marker_id));
+    }
+
+    #[test]
+    fn merge_reports_conflict_when_both_sides_supersede_the_same_constraint() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let local_tip = repo.create_intent(make_intent("Local work", vec![root.clone()])).unwrap();
+        let remote_tip = repo.odb.write(&TelosObject::Intent(make_intent("Remote work", vec![root.clone()]))).unwrap();
+
+        let original = make_constraint(root, "Passwords must be hashed");
+        let (local_superseded, local_replacement) = supersede(&repo, &original, "Passwords must be hashed with argon2");
+
+        // The "remote" branch superseded the same constraint differently;
+        // mark both its new objects as remote-known so they classify as such.
+        let (remote_superseded, remote_replacement) = supersede(&repo, &original, "Passwords must be hashed with bcrypt");
+
+        repo.refs.write_remote_head("origin", "main", &remote_tip).unwrap();
+        repo.refs
+            .write_remote_objects(
+                "origin",
+                &HashSet::from([remote_tip.clone(), remote_superseded.clone(), remote_replacement.clone()]),
+            )
+            .unwrap();
+        repo.refs.update_current_tip(local_tip.clone()).unwrap();
+
+        let outcome = merge_stream(&repo, "origin", "main").unwrap();
+        let MergeOutcome::Conflict(conflicts) = outcome else {
+            panic!("expected Conflict, got {:?}", outcome);
+        };
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.base_statement, "Passwords must be hashed");
+        assert_eq!(conflict.local_superseded_copy, local_superseded);
+        assert_eq!(conflict.local_replacement, local_replacement);
+        assert_eq!(conflict.remote_superseded_copy, remote_superseded);
+        assert_eq!(conflict.remote_replacement, remote_replacement);
+
+        // Stream tip must not move while a conflict is outstanding.
+        assert_eq!(repo.refs.current_stream().unwrap().tip, Some(local_tip.clone()));
+
+        // Resolving it lets the merge proceed.
+        let mut resolved = ResolvedConflicts::default();
+        resolved.mark_resolved(conflict);
+        assert!(detect_constraint_conflicts(&repo, &repo.refs.read_remote_objects("origin").unwrap(), &resolved)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn merge_fast_forward_rejects_stale_expected_tip() {
+        // merge_stream's fast-forward branches go through
+        // update_current_tip_cas with the tip merge_stream itself just read
+        // as the expected value. If the stream's actual tip no longer
+        // matches by the time that CAS write lands — e.g. another writer
+        // slipped a commit in between — the update must be refused rather
+        // than silently stomped.
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![])).unwrap();
+        let sneaky = repo.create_intent(make_intent("Sneaky local commit", vec![root.clone()])).unwrap();
+
+        let remote_tip = repo
+            .odb
+            .write(&TelosObject::Intent(make_intent("Remote work", vec![root.clone()])))
+            .unwrap();
+
+        let result = repo.refs.update_current_tip_cas(Some(root), remote_tip);
+        assert!(matches!(result, Err(StoreError::LockConflict(_))));
+        assert_eq!(repo.refs.current_stream().unwrap().tip, Some(sneaky));
+    }
+}
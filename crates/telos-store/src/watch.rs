@@ -0,0 +1,327 @@
+//! Computing newly-committed objects for `telos watch`.
+//!
+//! Intents form a DAG via `parents`, so new intents since a previously-seen
+//! stream tip are found the same way [`crate::sync::merge_stream`] finds
+//! them: walk backward from the new tip until the old tip (or a root) is
+//! reached.
+//!
+//! Constraints and decisions aren't linked into that DAG — a superseded
+//! copy carries its *original* `source_intent`, not the intent (if any)
+//! that was current when the supersession happened — so there's no tip to
+//! walk for them. Instead a [`Watcher`] keeps the set of constraint/decision
+//! ids it has already reported and, each poll, scans the odb for ones it
+//! hasn't seen yet. This is the same "scan and diff against a known set"
+//! idiom `crate::sync::detect_constraint_conflicts` uses for the same
+//! underlying reason.
+
+use crate::error::StoreError;
+use crate::repository::Repository;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use telos_core::hash::ObjectId;
+use telos_core::object::constraint::ConstraintStatus;
+use telos_core::object::TelosObject;
+
+/// Which object kinds a watcher reports on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectKind {
+    Intent,
+    Decision,
+    Constraint,
+}
+
+impl std::str::FromStr for ObjectKind {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "intent" => Ok(Self::Intent),
+            "decision" => Ok(Self::Decision),
+            "constraint" => Ok(Self::Constraint),
+            other => Err(StoreError::InvalidStreamName(
+                other.into(),
+                "kind must be 'intent', 'decision', or 'constraint'".into(),
+            )),
+        }
+    }
+}
+
+/// Narrows which newly-committed objects a watcher reports.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeFilter {
+    pub impact: Option<String>,
+    pub kind: Option<ObjectKind>,
+}
+
+impl ScopeFilter {
+    fn allows_kind(&self, kind: ObjectKind) -> bool {
+        match self.kind {
+            None => true,
+            Some(k) => k == kind,
+        }
+    }
+
+    fn allows_impacts(&self, impacts: &[String]) -> bool {
+        match &self.impact {
+            None => true,
+            Some(tag) => impacts.iter().any(|i| i == tag),
+        }
+    }
+}
+
+/// One newly-committed object, as reported to a watcher.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEvent {
+    pub event: String,
+    pub stream: String,
+    pub id: String,
+    pub object: serde_json::Value,
+}
+
+impl WatchEvent {
+    fn new(event: &str, stream: &str, id: &ObjectId, obj: &TelosObject) -> Result<Self, StoreError> {
+        Ok(Self {
+            event: event.to_string(),
+            stream: stream.to_string(),
+            id: id.hex().to_string(),
+            object: serde_json::to_value(obj)?,
+        })
+    }
+}
+
+/// Tracks what a `telos watch` session has already reported for one stream,
+/// so repeated polls emit only what's new. Not persisted: a fresh `Watcher`
+/// starts from the stream's current tip and an empty seen-set, mirroring
+/// `tail -f` rather than a durable subscription.
+pub struct Watcher {
+    stream: String,
+    last_head: Option<ObjectId>,
+    seen_constraints: HashSet<ObjectId>,
+    seen_decisions: HashSet<ObjectId>,
+}
+
+impl Watcher {
+    /// Start watching `stream`, treating everything already committed as a
+    /// baseline (not re-reported) — only objects committed after this call
+    /// will be emitted by [`Watcher::poll`].
+    pub fn new(repo: &Repository, stream: impl Into<String>) -> Result<Self, StoreError> {
+        let stream = stream.into();
+        let last_head = repo.refs.read_stream(&stream)?.tip;
+        let mut watcher = Self {
+            stream,
+            last_head,
+            seen_constraints: HashSet::new(),
+            seen_decisions: HashSet::new(),
+        };
+        // Prime the seen-sets so backlog objects aren't replayed as "new".
+        watcher.poll(repo, &ScopeFilter::default())?;
+        Ok(watcher)
+    }
+
+    /// Like `new`, but replays every backlog object matching `scope` as
+    /// events instead of treating it as a baseline — used for the initial
+    /// snapshot a `--listen` client gets on connect.
+    pub fn backlog(repo: &Repository, stream: impl Into<String>, scope: &ScopeFilter) -> Result<(Self, Vec<WatchEvent>), StoreError> {
+        let stream = stream.into();
+        let mut watcher = Self {
+            stream,
+            last_head: None,
+            seen_constraints: HashSet::new(),
+            seen_decisions: HashSet::new(),
+        };
+        let events = watcher.poll(repo, scope)?;
+        Ok((watcher, events))
+    }
+
+    /// Check for objects committed since the last poll, matching `scope`.
+    /// Advances internal state regardless of what `scope` filters out, so a
+    /// later poll with a wider scope won't replay anything this one saw.
+    pub fn poll(&mut self, repo: &Repository, scope: &ScopeFilter) -> Result<Vec<WatchEvent>, StoreError> {
+        let mut events = Vec::new();
+
+        let current_head = repo.refs.read_stream(&self.stream)?.tip;
+        if current_head != self.last_head {
+            if let Some(head) = &current_head {
+                for (id, intent) in self.new_intents(repo, head)? {
+                    if scope.allows_kind(ObjectKind::Intent) && scope.allows_impacts(&intent.impacts) {
+                        events.push(WatchEvent::new("intent", &self.stream, &id, &TelosObject::Intent(intent))?);
+                    }
+                }
+            }
+            self.last_head = current_head;
+        }
+
+        for (id, obj) in repo.odb.iter_all()? {
+            match &obj {
+                TelosObject::Constraint(c) => {
+                    if !self.seen_constraints.insert(id.clone()) {
+                        continue;
+                    }
+                    if !scope.allows_kind(ObjectKind::Constraint) || !scope.allows_impacts(&c.impacts) {
+                        continue;
+                    }
+                    let event = match c.status {
+                        ConstraintStatus::Active => "constraint",
+                        ConstraintStatus::Superseded => "supersede",
+                        ConstraintStatus::Deprecated => "deprecate",
+                    };
+                    events.push(WatchEvent::new(event, &self.stream, &id, &obj)?);
+                }
+                TelosObject::DecisionRecord(d) => {
+                    if !self.seen_decisions.insert(id.clone()) {
+                        continue;
+                    }
+                    if !scope.allows_kind(ObjectKind::Decision) || !scope.allows_impacts(&d.tags) {
+                        continue;
+                    }
+                    events.push(WatchEvent::new("decision", &self.stream, &id, &obj)?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Intents reachable from `head` but not from `self.last_head`, oldest
+    /// first (so events come out in commit order).
+    fn new_intents(
+        &self,
+        repo: &Repository,
+        head: &ObjectId,
+    ) -> Result<Vec<(ObjectId, telos_core::object::Intent)>, StoreError> {
+        let mut found = Vec::new();
+        let mut seen = HashSet::new();
+        let mut stack = vec![head.clone()];
+        while let Some(id) = stack.pop() {
+            if self.last_head.as_ref() == Some(&id) {
+                continue;
+            }
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            let TelosObject::Intent(intent) = repo.odb.read(&id)? else {
+                continue;
+            };
+            for parent in &intent.parents {
+                stack.push(parent.clone());
+            }
+            found.push((id, intent));
+        }
+        found.sort_by_key(|(_, intent)| intent.timestamp);
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use telos_core::object::constraint::{Constraint, ConstraintSeverity};
+    use telos_core::object::intent::{Author, BehaviorClause};
+    use telos_core::object::Intent;
+
+    fn make_intent(statement: &str, parents: Vec<ObjectId>, impacts: Vec<String>) -> Intent {
+        Intent {
+            author: Author { name: "Test".into(), email: "test@example.com".into() },
+            timestamp: Utc::now(),
+            statement: statement.into(),
+            constraints: vec![],
+            behavior_spec: Vec::<BehaviorClause>::new(),
+            parents,
+            impacts,
+            behavior_diff: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn new_watcher_does_not_replay_backlog() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        repo.create_intent(make_intent("Before watching", vec![], vec![])).unwrap();
+
+        let mut watcher = Watcher::new(&repo, "main").unwrap();
+        let events = watcher.poll(&repo, &ScopeFilter::default()).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn poll_reports_new_intent() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut watcher = Watcher::new(&repo, "main").unwrap();
+
+        let id = repo.create_intent(make_intent("New work", vec![], vec![])).unwrap();
+        let events = watcher.poll(&repo, &ScopeFilter::default()).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, "intent");
+        assert_eq!(events[0].id, id.hex());
+
+        // A second poll with nothing new reports nothing.
+        assert!(watcher.poll(&repo, &ScopeFilter::default()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn poll_reports_supersede_and_deprecate() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let root = repo.create_intent(make_intent("Root", vec![], vec![])).unwrap();
+        let mut watcher = Watcher::new(&repo, "main").unwrap();
+
+        let original = Constraint {
+            author: Author { name: "Test".into(), email: "test@example.com".into() },
+            timestamp: Utc::now(),
+            statement: "Must hash passwords".into(),
+            severity: ConstraintSeverity::Must,
+            status: ConstraintStatus::Active,
+            source_intent: root,
+            superseded_by: None,
+            deprecation_reason: None,
+            scope: vec![],
+            impacts: vec![],
+            metadata: HashMap::new(),
+        };
+        let original_id = repo.create_constraint(original.clone()).unwrap();
+
+        let mut replacement = original.clone();
+        replacement.statement = "Must hash passwords with argon2".into();
+        let replacement_id = repo.create_constraint(replacement).unwrap();
+
+        let mut superseded = original.clone();
+        superseded.status = ConstraintStatus::Superseded;
+        superseded.superseded_by = Some(replacement_id.clone());
+        let superseded_id = repo.create_constraint(superseded).unwrap();
+
+        let mut deprecated = original;
+        deprecated.status = ConstraintStatus::Deprecated;
+        deprecated.deprecation_reason = Some("No longer needed".into());
+        let deprecated_id = repo.create_constraint(deprecated).unwrap();
+
+        let events = watcher.poll(&repo, &ScopeFilter::default()).unwrap();
+        let by_id = |id: &ObjectId| events.iter().find(|e| e.id == id.hex()).unwrap();
+
+        assert_eq!(by_id(&original_id).event, "constraint");
+        assert_eq!(by_id(&replacement_id).event, "constraint");
+        assert_eq!(by_id(&superseded_id).event, "supersede");
+        assert_eq!(by_id(&deprecated_id).event, "deprecate");
+    }
+
+    #[test]
+    fn scope_filter_narrows_by_impact_and_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut watcher = Watcher::new(&repo, "main").unwrap();
+
+        repo.create_intent(make_intent("Unrelated", vec![], vec!["billing".into()])).unwrap();
+        let matching = repo.create_intent(make_intent("Auth work", vec![], vec!["auth".into()])).unwrap();
+
+        let scope = ScopeFilter { impact: Some("auth".into()), kind: Some(ObjectKind::Intent) };
+        let events = watcher.poll(&repo, &scope).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, matching.hex());
+    }
+}
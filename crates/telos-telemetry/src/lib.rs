@@ -0,0 +1,119 @@
+//! OpenTelemetry wiring for Telos binaries.
+//!
+//! Call [`init_from_env`] once at process start (typically the first line of
+//! `main()`). It configures a `tracing` subscriber and, when an OTLP endpoint
+//! is configured, exports spans and metrics to it. Everywhere else in the
+//! codebase just uses `tracing::instrument` and the `metrics` crate macros —
+//! this crate owns the only place that talks to OpenTelemetry directly.
+//!
+//! Env vars:
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT` — OTLP gRPC endpoint (e.g. `http://localhost:4317`).
+//!   If unset, telemetry is exported to stderr only (no OTLP).
+//! - `OTEL_SERVICE_NAME` — service name reported in spans/metrics. Defaults to
+//!   the `service_name` argument passed to [`init_from_env`].
+//! - `RUST_LOG` — standard `tracing-subscriber` filter directive.
+
+use std::env;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{EnvFilter, Registry};
+
+/// Holds the resources that must stay alive for the lifetime of the process
+/// and be flushed on shutdown. Dropping this guard flushes pending spans and
+/// metrics to the OTLP exporter (if one was configured).
+pub struct TelemetryGuard {
+    tracer_provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+    meter_provider: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.tracer_provider.take() {
+            let _ = provider.shutdown();
+        }
+        if let Some(provider) = self.meter_provider.take() {
+            let _ = provider.shutdown();
+        }
+    }
+}
+
+/// Initialize tracing/metrics for `service_name`, configured from the standard
+/// `OTEL_*` env vars. Always installs a `tracing` subscriber (OTLP layer is
+/// only added when `OTEL_EXPORTER_OTLP_ENDPOINT` is set), so callers can rely
+/// on `tracing::instrument`/`tracing::info!` working either way.
+///
+/// Returns `None` if a global subscriber is already installed (e.g. in tests
+/// that call this more than once).
+pub fn init_from_env(service_name: &str) -> Option<TelemetryGuard> {
+    let service_name = env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| service_name.to_string());
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_target(false);
+
+    let endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok();
+
+    let (tracer_provider, otel_layer) = match &endpoint {
+        Some(endpoint) => {
+            let provider = build_tracer_provider(endpoint, &service_name);
+            let tracer = opentelemetry::trace::TracerProvider::tracer(&provider, "telos");
+            (
+                Some(provider),
+                Some(tracing_opentelemetry::layer().with_tracer(tracer)),
+            )
+        }
+        None => (None, None),
+    };
+
+    let meter_provider = endpoint
+        .as_deref()
+        .map(|endpoint| build_meter_provider(endpoint, &service_name));
+    if let Some(provider) = &meter_provider {
+        opentelemetry::global::set_meter_provider(provider.clone());
+    }
+
+    let subscriber = Registry::default().with(filter).with(fmt_layer).with(otel_layer);
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        return None;
+    }
+
+    Some(TelemetryGuard {
+        tracer_provider,
+        meter_provider,
+    })
+}
+
+fn build_tracer_provider(endpoint: &str, service_name: &str) -> opentelemetry_sdk::trace::TracerProvider {
+    use opentelemetry_otlp::WithExportConfig;
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(opentelemetry_sdk::trace::config().with_resource(
+            opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+                "service.name",
+                service_name.to_string(),
+            )]),
+        ))
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .expect("failed to build OTLP trace pipeline")
+}
+
+fn build_meter_provider(endpoint: &str, service_name: &str) -> opentelemetry_sdk::metrics::SdkMeterProvider {
+    use opentelemetry_otlp::WithExportConfig;
+    opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry_sdk::runtime::Tokio)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_resource(opentelemetry_sdk::Resource::new(vec![opentelemetry::KeyValue::new(
+            "service.name",
+            service_name.to_string(),
+        )]))
+        .build()
+        .expect("failed to build OTLP metrics pipeline")
+}
@@ -1,11 +1,37 @@
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// JWT token configuration
 pub const TOKEN_EXPIRY_SECS: u64 = 3600; // 1 hour — CONSTRAINT: must be <= 1 hour
 
+/// Signing/verification algorithm. HS256 verifies and signs with the same
+/// shared `secret`; RS256 signs with `rsa_private_key_pem` and verifies
+/// with `rsa_public_key_pem` (a real keypair, not the same key reused).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SigningAlgorithm {
+    Hs256,
+    Rs256,
+}
+
+impl SigningAlgorithm {
+    fn jwt_algorithm(&self) -> Algorithm {
+        match self {
+            SigningAlgorithm::Hs256 => Algorithm::HS256,
+            SigningAlgorithm::Rs256 => Algorithm::RS256,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthConfig {
+    pub alg: SigningAlgorithm,
+    /// HMAC shared secret, used when `alg` is `Hs256`.
     pub secret: String,
+    /// RSA private key (PEM), used to sign when `alg` is `Rs256`.
+    pub rsa_private_key_pem: Option<String>,
+    /// RSA public key (PEM), used to verify when `alg` is `Rs256`.
+    pub rsa_public_key_pem: Option<String>,
     pub token_expiry_secs: u64,
     pub issuer: String,
 }
@@ -13,7 +39,10 @@ pub struct AuthConfig {
 impl Default for AuthConfig {
     fn default() -> Self {
         Self {
+            alg: SigningAlgorithm::Hs256,
             secret: "dev-secret-do-not-use-in-prod".into(),
+            rsa_private_key_pem: None,
+            rsa_public_key_pem: None,
             token_expiry_secs: TOKEN_EXPIRY_SECS,
             issuer: "taskboard".into(),
         }
@@ -35,22 +64,81 @@ pub enum UserRole {
     Viewer,
 }
 
-/// Validate a JWT token (stub — returns claims if format is valid)
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_secs()
+}
+
+fn decoding_key(config: &AuthConfig) -> Result<DecodingKey, AuthError> {
+    match config.alg {
+        SigningAlgorithm::Hs256 => Ok(DecodingKey::from_secret(config.secret.as_bytes())),
+        SigningAlgorithm::Rs256 => {
+            let pem = config
+                .rsa_public_key_pem
+                .as_deref()
+                .ok_or(AuthError::InvalidSignature)?;
+            DecodingKey::from_rsa_pem(pem.as_bytes()).map_err(|_| AuthError::InvalidSignature)
+        }
+    }
+}
+
+fn encoding_key(config: &AuthConfig) -> Result<EncodingKey, AuthError> {
+    match config.alg {
+        SigningAlgorithm::Hs256 => Ok(EncodingKey::from_secret(config.secret.as_bytes())),
+        SigningAlgorithm::Rs256 => {
+            let pem = config
+                .rsa_private_key_pem
+                .as_deref()
+                .ok_or(AuthError::InvalidSignature)?;
+            EncodingKey::from_rsa_pem(pem.as_bytes()).map_err(|_| AuthError::InvalidSignature)
+        }
+    }
+}
+
+/// Issue a signed token for `sub`/`role`, with `exp` set to
+/// `token_expiry_secs` from now — so `AuthConfig::token_expiry_secs` (which
+/// the `token_expiry_within_limit` test constrains to <= 1 hour) actually
+/// governs the lifetime of minted tokens.
+pub fn issue_token(sub: &str, role: UserRole, config: &AuthConfig) -> Result<String, AuthError> {
+    let claims = Claims {
+        sub: sub.to_string(),
+        exp: unix_now() + config.token_expiry_secs,
+        iss: config.issuer.clone(),
+        role,
+    };
+    let header = Header::new(config.alg.jwt_algorithm());
+    encode(&header, &claims, &encoding_key(config)?).map_err(|_| AuthError::InvalidSignature)
+}
+
+/// Validate a JWT: verifies the signature, checks `iss` against
+/// `config.issuer`, and rejects an `exp` that has already passed.
 pub fn validate_token(token: &str, config: &AuthConfig) -> Result<Claims, AuthError> {
-    // Simplified validation for demo purposes
     if token.is_empty() {
         return Err(AuthError::EmptyToken);
     }
-    if !token.starts_with("tb_") {
-        return Err(AuthError::InvalidFormat);
+
+    let mut validation = Validation::new(config.alg.jwt_algorithm());
+    validation.set_issuer(&[config.issuer.as_str()]);
+    // We check `exp` ourselves below so a lapsed token always comes back as
+    // `AuthError::Expired` rather than whatever shape jsonwebtoken's own
+    // expiry error takes.
+    validation.validate_exp = false;
+
+    let data = decode::<Claims>(token, &decoding_key(config)?, &validation).map_err(|e| {
+        use jsonwebtoken::errors::ErrorKind;
+        match e.kind() {
+            ErrorKind::InvalidSignature | ErrorKind::InvalidIssuer => AuthError::InvalidSignature,
+            _ => AuthError::InvalidFormat,
+        }
+    })?;
+
+    if data.claims.exp < unix_now() {
+        return Err(AuthError::Expired);
     }
-    // In real implementation, would decode JWT and verify signature
-    Ok(Claims {
-        sub: "user-1".into(),
-        exp: 0,
-        iss: config.issuer.clone(),
-        role: UserRole::Member,
-    })
+
+    Ok(data.claims)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -78,18 +166,62 @@ mod tests {
     #[test]
     fn validate_empty_token_fails() {
         let config = AuthConfig::default();
-        assert!(validate_token("", &config).is_err());
+        assert!(matches!(validate_token("", &config), Err(AuthError::EmptyToken)));
     }
 
     #[test]
     fn validate_invalid_format_fails() {
         let config = AuthConfig::default();
-        assert!(validate_token("bad-token", &config).is_err());
+        assert!(validate_token("not-a-jwt", &config).is_err());
     }
 
     #[test]
-    fn validate_valid_format_succeeds() {
+    fn issue_and_validate_round_trip() {
         let config = AuthConfig::default();
-        assert!(validate_token("tb_test123", &config).is_ok());
+        let token = issue_token("user-1", UserRole::Member, &config).unwrap();
+        let claims = validate_token(&token, &config).unwrap();
+        assert_eq!(claims.sub, "user-1");
+        assert_eq!(claims.role, UserRole::Member);
+        assert_eq!(claims.iss, config.issuer);
+    }
+
+    #[test]
+    fn expired_token_is_rejected() {
+        let config = AuthConfig::default();
+        let claims = Claims {
+            sub: "user-1".into(),
+            exp: unix_now() - 10,
+            iss: config.issuer.clone(),
+            role: UserRole::Member,
+        };
+        let header = Header::new(config.alg.jwt_algorithm());
+        let token = encode(&header, &claims, &encoding_key(&config).unwrap()).unwrap();
+
+        assert!(matches!(validate_token(&token, &config), Err(AuthError::Expired)));
+    }
+
+    #[test]
+    fn wrong_secret_is_rejected() {
+        let signing_config = AuthConfig::default();
+        let token = issue_token("user-1", UserRole::Viewer, &signing_config).unwrap();
+
+        let mut verifying_config = signing_config.clone();
+        verifying_config.secret = "a-completely-different-secret".into();
+
+        assert!(matches!(
+            validate_token(&token, &verifying_config),
+            Err(AuthError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn wrong_issuer_is_rejected() {
+        let signing_config = AuthConfig::default();
+        let token = issue_token("user-1", UserRole::Admin, &signing_config).unwrap();
+
+        let mut verifying_config = signing_config.clone();
+        verifying_config.issuer = "someone-else".into();
+
+        assert!(validate_token(&token, &verifying_config).is_err());
     }
 }